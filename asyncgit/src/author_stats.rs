@@ -0,0 +1,295 @@
+//! per author commit counts of a list of commits
+
+use crate::{
+    background_error::report_background_error,
+    error::Result,
+    shutdown::{is_shutting_down, send_notification},
+    sync::{self, CommitId, CommitInfo},
+    AsyncNotification,
+};
+use crossbeam_channel::Sender;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// commits looked at between two notifications
+const STATS_CHUNK: usize = 2000;
+
+/// commits of one author
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthorStats {
+    ///
+    pub name: String,
+    ///
+    pub count: usize,
+    /// time of the oldest commit (seconds since the epoch)
+    pub first: i64,
+    /// time of the newest commit
+    pub last: i64,
+}
+
+/// authors of the commits looked at so far
+#[derive(Debug, Default, Clone)]
+pub struct AuthorStatsProgress {
+    /// most commits first (then by name)
+    pub stats: Vec<AuthorStats>,
+    /// commits looked at
+    pub done: usize,
+    /// commits to look at
+    pub total: usize,
+}
+
+/// counts the commits per author of a list of commits in the
+/// background, notifies (`AsyncNotification::AuthorStats`) after
+/// every chunk
+pub struct AsyncAuthorStats {
+    repo_path: Arc<str>,
+    progress: Arc<Mutex<AuthorStatsProgress>>,
+    pending: Arc<AtomicBool>,
+    /// tells the running count it got replaced
+    generation: Arc<AtomicUsize>,
+    sender: Sender<AsyncNotification>,
+}
+
+impl AsyncAuthorStats {
+    ///
+    pub fn new(
+        repo_path: Arc<str>,
+        sender: &Sender<AsyncNotification>,
+    ) -> Self {
+        Self {
+            repo_path,
+            progress: Arc::new(Mutex::new(
+                AuthorStatsProgress::default(),
+            )),
+            pending: Arc::new(AtomicBool::new(false)),
+            generation: Arc::new(AtomicUsize::new(0)),
+            sender: sender.clone(),
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    /// state of the latest request
+    pub fn progress(&self) -> Result<AuthorStatsProgress> {
+        Ok(self.progress.lock()?.clone())
+    }
+
+    /// starts over counting `ids`, a count still running is dropped
+    pub fn request(&mut self, ids: Vec<CommitId>) -> Result<()> {
+        log::trace!("request");
+
+        let own = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        *self.progress.lock()? = AuthorStatsProgress {
+            total: ids.len(),
+            ..AuthorStatsProgress::default()
+        };
+        self.pending.store(true, Ordering::Relaxed);
+
+        let repo_path = Arc::clone(&self.repo_path);
+        let arc_progress = Arc::clone(&self.progress);
+        let arc_pending = Arc::clone(&self.pending);
+        let arc_generation = Arc::clone(&self.generation);
+        let sender = self.sender.clone();
+
+        rayon_core::spawn(move || {
+            let is_current = || {
+                arc_generation.load(Ordering::Relaxed) == own
+                    && !is_shutting_down()
+            };
+
+            if let Err(e) = Self::run(
+                &repo_path,
+                &ids,
+                &arc_progress,
+                &sender,
+                &is_current,
+            ) {
+                report_background_error(&sender, "author stats", &e);
+            }
+
+            if is_current() {
+                arc_pending.store(false, Ordering::Relaxed);
+
+                send_notification(
+                    &sender,
+                    AsyncNotification::AuthorStats,
+                );
+            }
+        });
+
+        Ok(())
+    }
+
+    fn run(
+        repo_path: &str,
+        ids: &[CommitId],
+        arc_progress: &Arc<Mutex<AuthorStatsProgress>>,
+        sender: &Sender<AsyncNotification>,
+        is_current: &dyn Fn() -> bool,
+    ) -> Result<()> {
+        let mut by_author = HashMap::new();
+
+        for (idx, chunk) in ids.chunks(STATS_CHUNK).enumerate() {
+            if !is_current() {
+                return Ok(());
+            }
+
+            let commits =
+                sync::get_commits_info(repo_path, chunk, 0)?;
+            add_commits(&mut by_author, &commits);
+
+            let mut progress = arc_progress.lock()?;
+            if !is_current() {
+                return Ok(());
+            }
+            progress.stats = sorted_stats(&by_author);
+            progress.done = idx * STATS_CHUNK + chunk.len();
+            drop(progress);
+
+            send_notification(sender, AsyncNotification::AuthorStats);
+        }
+
+        Ok(())
+    }
+}
+
+fn add_commits(
+    by_author: &mut HashMap<String, AuthorStats>,
+    commits: &[CommitInfo],
+) {
+    for c in commits {
+        let stats =
+            by_author.entry(c.author.clone()).or_insert_with(|| {
+                AuthorStats {
+                    name: c.author.clone(),
+                    count: 0,
+                    first: c.time,
+                    last: c.time,
+                }
+            });
+
+        stats.count += 1;
+        stats.first = stats.first.min(c.time);
+        stats.last = stats.last.max(c.time);
+    }
+}
+
+fn sorted_stats(
+    by_author: &HashMap<String, AuthorStats>,
+) -> Vec<AuthorStats> {
+    let mut stats: Vec<AuthorStats> =
+        by_author.values().cloned().collect();
+    stats.sort_by(|a, b| {
+        b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name))
+    });
+
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{commit, tests::repo_init_empty};
+    use crossbeam_channel::unbounded;
+    use git2::{Signature, Time};
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    #[test]
+    fn test_add_commits() {
+        let commits: Vec<CommitInfo> = [
+            ("bob", 30),
+            ("alice", 10),
+            ("bob", 10),
+            ("carol", 5),
+            ("alice", 20),
+            ("bob", 20),
+        ]
+        .iter()
+        .map(|(author, time)| CommitInfo {
+            message: String::new(),
+            time: *time,
+            author: (*author).to_string(),
+            id: CommitId::default(),
+        })
+        .collect();
+
+        let mut by_author = HashMap::new();
+        add_commits(&mut by_author, &commits[..2]);
+        add_commits(&mut by_author, &commits[2..]);
+
+        let stats = |name: &str, count, first, last| AuthorStats {
+            name: name.to_string(),
+            count,
+            first,
+            last,
+        };
+        assert_eq!(
+            sorted_stats(&by_author),
+            vec![
+                stats("bob", 3, 10, 30),
+                stats("alice", 2, 10, 20),
+                stats("carol", 1, 5, 5),
+            ]
+        );
+        assert_eq!(sorted_stats(&HashMap::new()), Vec::new());
+    }
+
+    #[test]
+    fn test_async_author_stats() {
+        let (td, repo) = repo_init_empty().unwrap();
+        let repo_path: Arc<str> =
+            Arc::from(td.path().to_str().unwrap());
+
+        let mut ids = vec![commit(&repo_path, "a").unwrap()];
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let sig =
+            Signature::new("other", "email", &Time::new(1000, 0))
+                .unwrap();
+        let parent = repo.find_commit(ids[0].into()).unwrap();
+        ids.push(
+            repo.commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "b",
+                &tree,
+                &[&parent],
+            )
+            .unwrap()
+            .into(),
+        );
+
+        let (sender, _receiver) = unbounded();
+        let mut stats = AsyncAuthorStats::new(repo_path, &sender);
+        stats.request(ids.clone()).unwrap();
+
+        let start = Instant::now();
+        while stats.is_pending() {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            thread::sleep(Duration::from_millis(5));
+        }
+
+        let progress = stats.progress().unwrap();
+        assert_eq!((progress.done, progress.total), (2, 2));
+        let names: Vec<(&str, usize)> = progress
+            .stats
+            .iter()
+            .map(|s| (s.name.as_str(), s.count))
+            .collect();
+        assert_eq!(names, vec![("name", 1), ("other", 1)]);
+        assert_eq!(progress.stats[1].first, 1000);
+    }
+}
@@ -0,0 +1,82 @@
+//! errors of background jobs that are not worth giving up for,
+//! kept until the ui gets to show them
+
+use crate::{shutdown::send_notification, AsyncNotification};
+use crossbeam_channel::Sender;
+use std::{fmt::Display, sync::Mutex};
+
+/// errors kept at most, the oldest get dropped first
+const MAX_PENDING: usize = 100;
+
+/// failure of a background job
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackgroundError {
+    /// what failed, e.g. `tags`
+    pub job: &'static str,
+    ///
+    pub message: String,
+}
+
+/// reported but not taken yet, oldest first
+static PENDING: Mutex<Vec<BackgroundError>> = Mutex::new(Vec::new());
+
+/// logs `error` of `job` and keeps it for
+/// `take_background_errors`, notifies
+/// (`AsyncNotification::BackgroundError`)
+pub(crate) fn report_background_error(
+    sender: &Sender<AsyncNotification>,
+    job: &'static str,
+    error: &dyn Display,
+) {
+    log::error!("{} error: {}", job, error);
+
+    if let Ok(mut pending) = PENDING.lock() {
+        if pending.len() >= MAX_PENDING {
+            pending.remove(0);
+        }
+        pending.push(BackgroundError {
+            job,
+            message: error.to_string(),
+        });
+    }
+
+    send_notification(sender, AsyncNotification::BackgroundError);
+}
+
+/// the errors reported since the last call, oldest first
+pub fn take_background_errors() -> Vec<BackgroundError> {
+    PENDING
+        .lock()
+        .map(|mut pending| std::mem::take(&mut *pending))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam_channel::unbounded;
+
+    #[test]
+    fn test_report_and_take() {
+        let (sender, receiver) = unbounded();
+
+        report_background_error(&sender, "test job", &"first");
+        report_background_error(&sender, "test job", &"second");
+
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(AsyncNotification::BackgroundError)
+        );
+
+        // other tests may report at the same time
+        let messages: Vec<String> = take_background_errors()
+            .into_iter()
+            .filter(|e| e.job == "test job")
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(messages, vec!["first", "second"]);
+        assert!(take_background_errors()
+            .iter()
+            .all(|e| e.job != "test job"));
+    }
+}
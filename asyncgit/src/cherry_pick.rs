@@ -0,0 +1,106 @@
+use crate::{
+    error::Result,
+    shutdown::send_notification,
+    sync::{self, CherryPickSequence},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+/// runs a `CherryPickSequence` in the background,
+/// notifying after every commit
+pub struct AsyncCherryPick {
+    sequence: Arc<Mutex<Option<CherryPickSequence>>>,
+    error: Arc<Mutex<Option<String>>>,
+    pending: Arc<AtomicBool>,
+    sender: Sender<AsyncNotification>,
+}
+
+impl AsyncCherryPick {
+    ///
+    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+        Self {
+            sequence: Arc::new(Mutex::new(None)),
+            error: Arc::new(Mutex::new(None)),
+            pending: Arc::new(AtomicBool::new(false)),
+            sender: sender.clone(),
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed)
+    }
+
+    /// state of the sequence after the latest handled commit
+    pub fn sequence(&self) -> Result<Option<CherryPickSequence>> {
+        let sequence = self.sequence.lock()?;
+        Ok(sequence.clone())
+    }
+
+    /// error the sequence stopped with (other than a conflict)
+    pub fn error(&self) -> Result<Option<String>> {
+        let error = self.error.lock()?;
+        Ok(error.clone())
+    }
+
+    /// applies the remaining commits of `sequence`
+    /// until done or stopped on a conflict
+    pub fn request(
+        &mut self,
+        sequence: CherryPickSequence,
+    ) -> Result<()> {
+        log::trace!("request");
+
+        if self.is_pending() {
+            return Ok(());
+        }
+
+        *self.sequence.lock()? = Some(sequence.clone());
+        *self.error.lock()? = None;
+        self.pending.store(true, Ordering::Relaxed);
+
+        let arc_sequence = Arc::clone(&self.sequence);
+        let arc_error = Arc::clone(&self.error);
+        let arc_pending = Arc::clone(&self.pending);
+        let sender = self.sender.clone();
+
+        thread::spawn(move || {
+            if let Err(e) =
+                Self::run(sequence, &arc_sequence, &sender)
+            {
+                log::error!("cherry-pick error: {}", e);
+
+                if let Ok(mut error) = arc_error.lock() {
+                    *error = Some(e.to_string());
+                }
+            }
+
+            arc_pending.store(false, Ordering::Relaxed);
+
+            send_notification(&sender, AsyncNotification::CherryPick);
+        });
+
+        Ok(())
+    }
+
+    fn run(
+        mut sequence: CherryPickSequence,
+        arc_sequence: &Arc<Mutex<Option<CherryPickSequence>>>,
+        sender: &Sender<AsyncNotification>,
+    ) -> Result<()> {
+        while sync::cherry_pick_next(CWD, &mut sequence)?.is_some() {
+            *arc_sequence.lock()? = Some(sequence.clone());
+
+            send_notification(sender, AsyncNotification::CherryPick);
+        }
+
+        Ok(())
+    }
+}
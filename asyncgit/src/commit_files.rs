@@ -1,5 +1,6 @@
 use crate::{
     error::Result,
+    shutdown::send_notification,
     sync::{self, CommitId},
     AsyncNotification, StatusItem, CWD,
 };
@@ -76,9 +77,10 @@ impl AsyncCommitFiles {
 
             arc_pending.fetch_sub(1, Ordering::Relaxed);
 
-            sender
-                .send(AsyncNotification::CommitFiles)
-                .expect("error sending");
+            send_notification(
+                &sender,
+                AsyncNotification::CommitFiles,
+            );
         });
 
         Ok(())
@@ -0,0 +1,134 @@
+use crate::{
+    background_error::report_background_error,
+    error::Result,
+    hash,
+    shutdown::send_notification,
+    sync::{self, Decorations},
+    AsyncNotification,
+};
+use crossbeam_channel::Sender;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+///
+#[derive(Default, Clone)]
+struct DecorationsResult {
+    hash: u64,
+    decorations: Decorations,
+}
+
+/// refs by the commit they point to, only notifies
+/// (`AsyncNotification::Decorations`) if any ref moved
+pub struct AsyncDecorations {
+    repo_path: Arc<str>,
+    last: Arc<Mutex<Option<(Instant, DecorationsResult)>>>,
+    sender: Sender<AsyncNotification>,
+    pending: Arc<AtomicUsize>,
+}
+
+impl AsyncDecorations {
+    ///
+    pub fn new(
+        repo_path: Arc<str>,
+        sender: &Sender<AsyncNotification>,
+    ) -> Self {
+        Self {
+            repo_path,
+            last: Arc::new(Mutex::new(None)),
+            sender: sender.clone(),
+            pending: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// last fetched result
+    pub fn last(&mut self) -> Result<Option<Decorations>> {
+        let last = self.last.lock()?;
+
+        Ok(last.clone().map(|last| last.1.decorations))
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    fn is_outdated(&self, dur: Duration) -> Result<bool> {
+        let last = self.last.lock()?;
+
+        Ok(last
+            .as_ref()
+            .map(|(last_time, _)| last_time.elapsed() > dur)
+            .unwrap_or(true))
+    }
+
+    /// refetches if the last result is older than `dur`
+    pub fn request(
+        &mut self,
+        dur: Duration,
+        force: bool,
+    ) -> Result<()> {
+        log::trace!("request");
+
+        if !force && (self.is_pending() || !self.is_outdated(dur)?) {
+            return Ok(());
+        }
+
+        let repo_path = Arc::clone(&self.repo_path);
+        let arc_last = Arc::clone(&self.last);
+        let sender = self.sender.clone();
+        let arc_pending = Arc::clone(&self.pending);
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            let notify = Self::getter(&repo_path, &arc_last)
+                .unwrap_or_else(|e| {
+                    report_background_error(
+                        &sender,
+                        "decorations",
+                        &e,
+                    );
+                    false
+                });
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            send_notification(
+                &sender,
+                if notify {
+                    AsyncNotification::Decorations
+                } else {
+                    AsyncNotification::FinishUnchanged
+                },
+            );
+        });
+
+        Ok(())
+    }
+
+    fn getter(
+        repo_path: &str,
+        arc_last: &Arc<Mutex<Option<(Instant, DecorationsResult)>>>,
+    ) -> Result<bool> {
+        let decorations = sync::get_decorations(repo_path)?;
+
+        let hash = hash(&decorations);
+
+        let mut last = arc_last.lock()?;
+        let unchanged =
+            last.as_ref().is_some_and(|(_, last)| last.hash == hash);
+
+        // keep the time fresh to not refetch right away
+        *last = Some((
+            Instant::now(),
+            DecorationsResult { hash, decorations },
+        ));
+
+        Ok(!unchanged)
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     error::Result,
     hash,
+    shutdown::send_notification,
     sync::{self, CommitId},
     AsyncNotification, FileDiff, CWD,
 };
@@ -18,6 +19,8 @@ use std::{
 pub enum DiffType {
     /// diff in a given commit
     Commit(CommitId),
+    /// diff from the first commit to the second
+    Commits(CommitId, CommitId),
     /// diff against staged file
     Stage,
     /// diff against file in workdir
@@ -123,13 +126,14 @@ impl AsyncDiff {
 
             arc_pending.fetch_sub(1, Ordering::Relaxed);
 
-            sender
-                .send(if notify {
+            send_notification(
+                &sender,
+                if notify {
                     AsyncNotification::Diff
                 } else {
                     AsyncNotification::FinishUnchanged
-                })
-                .expect("error sending diff");
+                },
+            );
         });
 
         Ok(None)
@@ -155,6 +159,14 @@ impl AsyncDiff {
                 id,
                 params.path.clone(),
             )?,
+            DiffType::Commits(old, new) => {
+                sync::diff::get_diff_commits(
+                    CWD,
+                    old,
+                    new,
+                    params.path.clone(),
+                )?
+            }
         };
 
         let mut notify = false;
@@ -1,34 +1,93 @@
 use std::string::FromUtf8Error;
 use thiserror::Error;
 
+/// errors of the git operations
 #[derive(Error, Debug)]
 pub enum Error {
+    ///
     #[error("`{0}`")]
     Generic(String),
 
+    ///
     #[error("git: no head found")]
     NoHead,
 
+    ///
     #[error("git: remote url not found")]
     UnknownRemote,
 
+    ///
+    #[error("git: branch `{0}` has no upstream configured")]
+    NoUpstream(String),
+
+    ///
+    #[error("git: branch `{0}` already exists")]
+    BranchExists(String),
+
+    ///
     #[error("git: work dir error")]
     NoWorkDir,
 
+    ///
+    #[error("git: rebase stopped on a conflict applying `{0}`")]
+    RebaseConflict(String),
+
+    /// the configured signing program failed, the object was not
+    /// created
+    #[error("signing failed: {0}")]
+    Sign(String),
+
+    ///
     #[error("io error:{0}")]
     Io(#[from] std::io::Error),
 
+    ///
     #[error("git error:{0}")]
     Git(#[from] git2::Error),
 
+    ///
     #[error("utf8 error:{0}")]
     Utf8Error(#[from] FromUtf8Error),
 }
 
+/// result of the git operations
 pub type Result<T> = std::result::Result<T, Error>;
 
+impl Error {
+    /// the repo (or one of its files) is locked by another process,
+    /// trying again later may work
+    pub fn is_locked(&self) -> bool {
+        match self {
+            Self::Git(e) => e.code() == git2::ErrorCode::Locked,
+            Self::Io(e) => e.kind() == std::io::ErrorKind::WouldBlock,
+            _ => false,
+        }
+    }
+}
+
 impl<T> From<std::sync::PoisonError<T>> for Error {
     fn from(error: std::sync::PoisonError<T>) -> Self {
         Error::Generic(format!("poison error: {}", error))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{ErrorClass, ErrorCode};
+
+    #[test]
+    fn test_is_locked() {
+        let git = |code| {
+            Error::Git(git2::Error::new(code, ErrorClass::Index, "x"))
+        };
+
+        assert!(git(ErrorCode::Locked).is_locked());
+        assert!(!git(ErrorCode::NotFound).is_locked());
+        assert!(Error::Io(std::io::Error::from(
+            std::io::ErrorKind::WouldBlock
+        ))
+        .is_locked());
+        assert!(!Error::Generic(String::from("x")).is_locked());
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{
+    error::Result,
+    remote_ops::{
+        RemoteOp, RemoteOpGuard, RemoteOpTicket, RemoteOps,
+    },
+    shutdown::send_notification,
+    sync::{self, cred::BasicAuthCredential},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+use sync::{FetchStats, ProgressNotification, RemoteResult};
+use thread::JoinHandle;
+
+/// minimum time between two progress notifications
+const PROGRESS_NOTIFY_INTERVAL: Duration = Duration::from_millis(100);
+
+///
+#[derive(Default, Clone, Debug)]
+pub struct FetchRequest {
+    ///
+    pub remote: String,
+    ///
+    pub refspec: String,
+    ///
+    pub basic_credential: Option<BasicAuthCredential>,
+}
+
+///
+pub struct AsyncFetch {
+    /// remotes and refspecs being fetched
+    pending: Arc<Mutex<HashSet<(String, String)>>>,
+    last_result: Arc<Mutex<Option<RemoteResult<FetchStats>>>>,
+    progress: Arc<Mutex<Option<ProgressNotification>>>,
+    ticket: Arc<Mutex<Option<RemoteOpTicket>>>,
+    remote_ops: RemoteOps,
+    sender: Sender<AsyncNotification>,
+}
+
+impl AsyncFetch {
+    ///
+    pub fn new(
+        sender: &Sender<AsyncNotification>,
+        remote_ops: &RemoteOps,
+    ) -> Self {
+        Self {
+            pending: Arc::new(Mutex::new(HashSet::new())),
+            last_result: Arc::new(Mutex::new(None)),
+            progress: Arc::new(Mutex::new(None)),
+            ticket: Arc::new(Mutex::new(None)),
+            remote_ops: remote_ops.clone(),
+            sender: sender.clone(),
+        }
+    }
+
+    /// `true` while a fetch from any remote is running
+    pub fn is_pending(&self) -> Result<bool> {
+        let pending = self.pending.lock()?;
+        Ok(!pending.is_empty())
+    }
+
+    /// result of the last finished request
+    pub fn last_result(
+        &self,
+    ) -> Result<Option<RemoteResult<FetchStats>>> {
+        let res = self.last_result.lock()?;
+        Ok(res.clone())
+    }
+
+    /// latest progress of the running fetch
+    pub fn progress(&self) -> Result<Option<ProgressNotification>> {
+        let res = self.progress.lock()?;
+        Ok(res.clone())
+    }
+
+    /// requests to fetch a refspec from a remote that is already
+    /// being fetched are coalesced into the running one
+    pub fn request(&mut self, params: FetchRequest) -> Result<()> {
+        log::trace!("request");
+
+        let key = (params.remote.clone(), params.refspec.clone());
+        if !self.pending.lock()?.insert(key.clone()) {
+            return Ok(());
+        }
+
+        let ticket = match self.remote_ops.enqueue(RemoteOp::Fetch) {
+            Ok(ticket) => ticket,
+            Err(e) => {
+                self.pending.lock()?.remove(&key);
+                *self.last_result.lock()? = Some(Err(e));
+                send_notification(
+                    &self.sender,
+                    AsyncNotification::Fetch,
+                );
+                return Ok(());
+            }
+        };
+
+        *self.progress.lock()? = None;
+        *self.ticket.lock()? = Some(ticket.clone());
+
+        let arc_pending = Arc::clone(&self.pending);
+        let arc_res = Arc::clone(&self.last_result);
+        let arc_progress = Arc::clone(&self.progress);
+        let sender = self.sender.clone();
+        let timeout = self.remote_ops.timeout();
+
+        thread::spawn(move || {
+            let res = match ticket.wait() {
+                Ok(Some(guard)) => Some(Self::fetch(
+                    &params,
+                    guard,
+                    timeout,
+                    sender.clone(),
+                    arc_progress,
+                )),
+                // cancelled before it started
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            };
+
+            if let Some(Err(e)) = &res {
+                log::error!("fetch error: {}", e);
+            }
+
+            if let Ok(mut last) = arc_res.lock() {
+                *last = res;
+            }
+
+            if let Ok(mut pending) = arc_pending.lock() {
+                pending.remove(&key);
+            }
+
+            send_notification(&sender, AsyncNotification::Fetch);
+        });
+
+        Ok(())
+    }
+
+    /// operation the latest requested fetch waits for to finish
+    pub fn waiting_for(&self) -> Result<Option<RemoteOp>> {
+        let ticket = self.ticket.lock()?;
+        Ok(ticket.as_ref().and_then(RemoteOpTicket::waiting_for))
+    }
+
+    /// drops the latest requested fetch if it did not start yet
+    pub fn cancel(&self) -> Result<bool> {
+        let ticket = self.ticket.lock()?;
+        Ok(ticket.as_ref().is_some_and(RemoteOpTicket::cancel))
+    }
+
+    fn fetch(
+        params: &FetchRequest,
+        guard: RemoteOpGuard,
+        timeout: Duration,
+        sender: Sender<AsyncNotification>,
+        arc_progress: Arc<Mutex<Option<ProgressNotification>>>,
+    ) -> RemoteResult<FetchStats> {
+        let (progress_sender, receiver) = unbounded();
+
+        let handle = Self::spawn_receiver_thread(
+            sender,
+            receiver,
+            arc_progress,
+        );
+
+        let params = params.clone();
+        let worker_sender = progress_sender.clone();
+        let res = sync::run_with_watchdog(timeout, move |watchdog| {
+            // the next operation waits for a worker left behind
+            let _guard = guard;
+            sync::fetch(
+                CWD,
+                params.remote.as_str(),
+                params.refspec.as_str(),
+                params.basic_credential,
+                Some(worker_sender),
+                Some(watchdog),
+            )
+        });
+
+        progress_sender
+            .send(ProgressNotification::Done)
+            .expect("closing send failed");
+
+        handle.join().expect("joining thread failed");
+
+        res
+    }
+
+    fn spawn_receiver_thread(
+        sender: Sender<AsyncNotification>,
+        receiver: Receiver<ProgressNotification>,
+        progress: Arc<Mutex<Option<ProgressNotification>>>,
+    ) -> JoinHandle<()> {
+        thread::spawn(move || {
+            let mut last_notify: Option<Instant> = None;
+            loop {
+                match receiver.recv() {
+                    Ok(ProgressNotification::Done) => break,
+                    Ok(update) => {
+                        if let Ok(mut progress) = progress.lock() {
+                            *progress = Some(update);
+                        }
+
+                        let now = Instant::now();
+                        if last_notify.is_none_or(|last| {
+                            now.duration_since(last)
+                                >= PROGRESS_NOTIFY_INTERVAL
+                        }) {
+                            last_notify = Some(now);
+                            send_notification(
+                                &sender,
+                                AsyncNotification::Fetch,
+                            );
+                        }
+                    }
+                    Err(e) => {
+                        log::error!(
+                            "fetch progress receiver error: {}",
+                            e
+                        );
+                        break;
+                    }
+                }
+            }
+        })
+    }
+}
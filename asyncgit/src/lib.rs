@@ -10,21 +10,48 @@
 //TODO: get this in someday since expect still leads us to crashes sometimes
 // #![deny(clippy::expect_used)]
 
+pub mod author_stats;
+mod background_error;
 pub mod cached;
+mod cherry_pick;
 mod commit_files;
+mod decorations;
 mod diff;
 mod error;
+mod fetch;
+mod ls_remote;
 mod push;
+mod remote_ops;
+mod reverts;
 mod revlog;
+mod shutdown;
 mod status;
 pub mod sync;
 mod tags;
 
 pub use crate::{
+    author_stats::{
+        AsyncAuthorStats, AuthorStats, AuthorStatsProgress,
+    },
+    background_error::{take_background_errors, BackgroundError},
+    cherry_pick::AsyncCherryPick,
     commit_files::AsyncCommitFiles,
+    decorations::AsyncDecorations,
     diff::{AsyncDiff, DiffParams, DiffType},
+    error::{Error, Result},
+    fetch::{AsyncFetch, FetchRequest},
+    ls_remote::{AsyncLsRemote, LsRemoteRequest, RemoteRefs},
     push::{AsyncPush, PushProgress, PushProgressState, PushRequest},
-    revlog::{AsyncLog, FetchStatus},
+    remote_ops::{
+        BusyPolicy, RemoteOp, RemoteOpGuard, RemoteOpTicket,
+        RemoteOps,
+    },
+    reverts::AsyncReverts,
+    revlog::{
+        AsyncLog, FetchStatus, LogWorker, WalkGenerations,
+        DEFAULT_LOG_NOTIFY_INTERVAL,
+    },
+    shutdown::{is_shutting_down, shutdown},
     status::{AsyncStatus, StatusParams},
     sync::{
         diff::{DiffLine, DiffLineType, FileDiff},
@@ -53,7 +80,21 @@ pub enum AsyncNotification {
     ///
     Tags,
     ///
+    Decorations,
+    ///
     Push,
+    ///
+    LsRemote,
+    ///
+    Fetch,
+    ///
+    CherryPick,
+    ///
+    AuthorStats,
+    ///
+    Reverts,
+    /// see `take_background_errors`
+    BackgroundError,
 }
 
 /// current working director `./`
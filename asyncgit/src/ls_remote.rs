@@ -0,0 +1,141 @@
+use crate::{
+    error::Result,
+    remote_ops::{RemoteOp, RemoteOpGuard, RemoteOps},
+    shutdown::send_notification,
+    sync::{self, cred::BasicAuthCredential, CommitId},
+    AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use std::{
+    cmp,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+use sync::RemoteResult;
+
+/// ls-remote only runs to list branches in a popup, it gives up
+/// sooner than the configured timeout
+const LS_REMOTE_TIMEOUT: Duration = Duration::from_secs(10);
+
+///
+pub type RemoteRefs = Vec<(String, CommitId)>;
+
+///
+#[derive(Default, Clone, Debug)]
+pub struct LsRemoteRequest {
+    ///
+    pub remote: String,
+    ///
+    pub basic_credential: Option<BasicAuthCredential>,
+}
+
+///
+pub struct AsyncLsRemote {
+    last_result: Arc<Mutex<Option<RemoteResult<RemoteRefs>>>>,
+    pending: Arc<AtomicUsize>,
+    remote_ops: RemoteOps,
+    sender: Sender<AsyncNotification>,
+}
+
+impl AsyncLsRemote {
+    ///
+    pub fn new(
+        sender: &Sender<AsyncNotification>,
+        remote_ops: &RemoteOps,
+    ) -> Self {
+        Self {
+            last_result: Arc::new(Mutex::new(None)),
+            pending: Arc::new(AtomicUsize::new(0)),
+            remote_ops: remote_ops.clone(),
+            sender: sender.clone(),
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    /// result of the last finished request
+    pub fn last_result(
+        &self,
+    ) -> Result<Option<RemoteResult<RemoteRefs>>> {
+        let res = self.last_result.lock()?;
+        Ok(res.clone())
+    }
+
+    ///
+    pub fn request(&mut self, params: LsRemoteRequest) -> Result<()> {
+        log::trace!("request");
+
+        if self.is_pending() {
+            return Ok(());
+        }
+
+        let ticket = match self.remote_ops.enqueue(RemoteOp::LsRemote)
+        {
+            Ok(ticket) => ticket,
+            Err(e) => {
+                *self.last_result.lock()? = Some(Err(e));
+                send_notification(
+                    &self.sender,
+                    AsyncNotification::LsRemote,
+                );
+                return Ok(());
+            }
+        };
+
+        let arc_res = Arc::clone(&self.last_result);
+        let arc_pending = Arc::clone(&self.pending);
+        let sender = self.sender.clone();
+        let timeout =
+            cmp::min(self.remote_ops.timeout(), LS_REMOTE_TIMEOUT);
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        thread::spawn(move || {
+            let res = match ticket.wait() {
+                Ok(Some(guard)) => {
+                    Self::ls_remote(params, guard, timeout)
+                }
+                Ok(None) => Ok(Vec::new()),
+                Err(e) => Err(e),
+            };
+
+            if let Err(e) = &res {
+                log::error!("ls remote error: {}", e);
+            }
+
+            if let Ok(mut last) = arc_res.lock() {
+                *last = Some(res);
+            }
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+
+            send_notification(&sender, AsyncNotification::LsRemote);
+        });
+
+        Ok(())
+    }
+
+    fn ls_remote(
+        params: LsRemoteRequest,
+        guard: RemoteOpGuard,
+        timeout: Duration,
+    ) -> RemoteResult<RemoteRefs> {
+        sync::run_with_watchdog(timeout, move |watchdog| {
+            // the next operation waits for a worker left behind
+            let _guard = guard;
+            sync::ls_remote(
+                CWD,
+                params.remote.as_str(),
+                params.basic_credential,
+                Some(watchdog),
+            )
+        })
+    }
+}
@@ -1,6 +1,10 @@
 use crate::sync::cred::BasicAuthCredential;
 use crate::{
     error::{Error, Result},
+    remote_ops::{
+        RemoteOp, RemoteOpGuard, RemoteOpTicket, RemoteOps,
+    },
+    shutdown::send_notification,
     sync, AsyncNotification, CWD,
 };
 use crossbeam_channel::{unbounded, Receiver, Sender};
@@ -11,7 +15,10 @@ use std::{
     thread,
     time::Duration,
 };
-use sync::ProgressNotification;
+use sync::{
+    HookResult, ProgressNotification, PushUpdate, RemoteError,
+    RemoteResult,
+};
 use thread::JoinHandle;
 
 ///
@@ -23,6 +30,8 @@ pub enum PushProgressState {
     PackingDeltafiction,
     ///
     Pushing,
+    ///
+    ResolvingDeltas,
 }
 
 ///
@@ -67,6 +76,14 @@ impl From<ProgressNotification> for PushProgress {
                     total,
                 ),
             },
+            ProgressNotification::ResolvingDeltas {
+                indexed,
+                total,
+            } => PushProgress::new(
+                PushProgressState::ResolvingDeltas,
+                indexed,
+                total,
+            ),
             ProgressNotification::PushTransfer {
                 current,
                 total,
@@ -91,6 +108,11 @@ pub struct PushRequest {
     pub branch: String,
     ///
     pub basic_credential: Option<BasicAuthCredential>,
+    /// run the `pre-push` hook first, see `sync::hooks_pre_push`
+    pub run_pre_push_hook: bool,
+    /// only compares with the remote (see `sync::push_dry_run`),
+    /// what the push would do ends up in `last_preview`
+    pub dry_run: bool,
 }
 
 #[derive(Default, Clone, Debug)]
@@ -101,22 +123,43 @@ struct PushState {
 ///
 pub struct AsyncPush {
     state: Arc<Mutex<Option<PushState>>>,
-    last_result: Arc<Mutex<Option<String>>>,
+    last_result: Arc<Mutex<Option<RemoteError>>>,
+    last_preview: Arc<Mutex<Option<Vec<PushUpdate>>>>,
     progress: Arc<Mutex<Option<ProgressNotification>>>,
+    ticket: Arc<Mutex<Option<RemoteOpTicket>>>,
+    remote_ops: RemoteOps,
     sender: Sender<AsyncNotification>,
 }
 
 impl AsyncPush {
     ///
-    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+    pub fn new(
+        sender: &Sender<AsyncNotification>,
+        remote_ops: &RemoteOps,
+    ) -> Self {
         Self {
             state: Arc::new(Mutex::new(None)),
             last_result: Arc::new(Mutex::new(None)),
+            last_preview: Arc::new(Mutex::new(None)),
             progress: Arc::new(Mutex::new(None)),
+            ticket: Arc::new(Mutex::new(None)),
+            remote_ops: remote_ops.clone(),
             sender: sender.clone(),
         }
     }
 
+    /// operation the requested push waits for to finish
+    pub fn waiting_for(&self) -> Result<Option<RemoteOp>> {
+        let ticket = self.ticket.lock()?;
+        Ok(ticket.as_ref().and_then(RemoteOpTicket::waiting_for))
+    }
+
+    /// drops the requested push if it did not start yet
+    pub fn cancel(&self) -> Result<bool> {
+        let ticket = self.ticket.lock()?;
+        Ok(ticket.as_ref().is_some_and(RemoteOpTicket::cancel))
+    }
+
     ///
     pub fn is_pending(&self) -> Result<bool> {
         let state = self.state.lock()?;
@@ -124,11 +167,17 @@ impl AsyncPush {
     }
 
     ///
-    pub fn last_result(&self) -> Result<Option<String>> {
+    pub fn last_result(&self) -> Result<Option<RemoteError>> {
         let res = self.last_result.lock()?;
         Ok(res.clone())
     }
 
+    /// what the last dry run found, `None` after a push
+    pub fn last_preview(&self) -> Result<Option<Vec<PushUpdate>>> {
+        let res = self.last_preview.lock()?;
+        Ok(res.clone())
+    }
+
     ///
     pub fn progress(&self) -> Result<Option<PushProgress>> {
         let res = self.progress.lock()?;
@@ -143,47 +192,125 @@ impl AsyncPush {
             return Ok(());
         }
 
+        let ticket = match self.remote_ops.enqueue(RemoteOp::Push) {
+            Ok(ticket) => ticket,
+            Err(e) => {
+                Self::set_result(
+                    self.last_result.clone(),
+                    self.last_preview.clone(),
+                    Err(e),
+                )?;
+                send_notification(
+                    &self.sender,
+                    AsyncNotification::Push,
+                );
+                return Ok(());
+            }
+        };
+
         self.set_request(&params)?;
         Self::set_progress(self.progress.clone(), None)?;
+        *self.ticket.lock()? = Some(ticket.clone());
 
         let arc_state = Arc::clone(&self.state);
         let arc_res = Arc::clone(&self.last_result);
+        let arc_preview = Arc::clone(&self.last_preview);
         let arc_progress = Arc::clone(&self.progress);
         let sender = self.sender.clone();
+        let timeout = self.remote_ops.timeout();
 
         thread::spawn(move || {
-            let (progress_sender, receiver) = unbounded();
+            let res = match ticket.wait() {
+                Ok(Some(guard)) if params.dry_run => {
+                    Self::preview(params, guard, timeout).map(Some)
+                }
+                Ok(Some(guard)) => Self::push(
+                    params,
+                    guard,
+                    timeout,
+                    sender.clone(),
+                    arc_progress,
+                )
+                .map(|_| None),
+                Ok(None) => Err(RemoteError::Cancelled),
+                Err(e) => Err(e),
+            };
+
+            Self::set_result(arc_res, arc_preview, res)
+                .expect("result error");
 
-            let handle = Self::spawn_receiver_thread(
-                sender.clone(),
-                receiver,
-                arc_progress,
-            );
+            Self::clear_request(arc_state).expect("clear error");
+
+            send_notification(&sender, AsyncNotification::Push);
+        });
+
+        Ok(())
+    }
 
-            let res = sync::push(
+    fn push(
+        params: PushRequest,
+        guard: RemoteOpGuard,
+        timeout: Duration,
+        sender: Sender<AsyncNotification>,
+        arc_progress: Arc<Mutex<Option<ProgressNotification>>>,
+    ) -> RemoteResult<()> {
+        if params.run_pre_push_hook {
+            if let HookResult::NotOk(output) = sync::hooks_pre_push(
                 CWD,
                 params.remote.as_str(),
                 params.branch.as_str(),
-                params.basic_credential,
-                progress_sender.clone(),
-            );
+            )? {
+                return Err(RemoteError::HookRejected(output));
+            }
+        }
 
-            progress_sender
-                .send(ProgressNotification::Done)
-                .expect("closing send failed");
+        let (progress_sender, receiver) = unbounded();
 
-            handle.join().expect("joining thread failed");
+        let handle = Self::spawn_receiver_thread(
+            sender,
+            receiver,
+            arc_progress,
+        );
 
-            Self::set_result(arc_res, res).expect("result error");
+        let worker_sender = progress_sender.clone();
+        let res = sync::run_with_watchdog(timeout, move |watchdog| {
+            // the next operation waits for a worker left behind
+            let _guard = guard;
+            sync::push(
+                CWD,
+                params.remote.as_str(),
+                params.branch.as_str(),
+                params.basic_credential,
+                worker_sender,
+                Some(watchdog),
+            )
+        });
 
-            Self::clear_request(arc_state).expect("clear error");
+        progress_sender
+            .send(ProgressNotification::Done)
+            .expect("closing send failed");
 
-            sender
-                .send(AsyncNotification::Push)
-                .expect("error sending push");
-        });
+        handle.join().expect("joining thread failed");
 
-        Ok(())
+        res
+    }
+
+    fn preview(
+        params: PushRequest,
+        guard: RemoteOpGuard,
+        timeout: Duration,
+    ) -> RemoteResult<Vec<PushUpdate>> {
+        sync::run_with_watchdog(timeout, move |watchdog| {
+            // the next operation waits for a worker left behind
+            let _guard = guard;
+            sync::push_dry_run(
+                CWD,
+                params.remote.as_str(),
+                &[params.branch.as_str()],
+                params.basic_credential,
+                Some(watchdog),
+            )
+        })
     }
 
     fn spawn_receiver_thread(
@@ -202,9 +329,10 @@ impl AsyncPush {
                         Some(update.clone()),
                     )
                     .expect("set prgoress failed");
-                    sender
-                        .send(AsyncNotification::Push)
-                        .expect("error sending push");
+                    send_notification(
+                        &sender,
+                        AsyncNotification::Push,
+                    );
 
                     //NOTE: for better debugging
                     thread::sleep(Duration::from_millis(300));
@@ -263,16 +391,22 @@ impl AsyncPush {
     }
 
     fn set_result(
-        arc_result: Arc<Mutex<Option<String>>>,
-        res: Result<()>,
+        arc_result: Arc<Mutex<Option<RemoteError>>>,
+        arc_preview: Arc<Mutex<Option<Vec<PushUpdate>>>>,
+        res: RemoteResult<Option<Vec<PushUpdate>>>,
     ) -> Result<()> {
         let mut last_res = arc_result.lock()?;
+        let mut last_preview = arc_preview.lock()?;
 
+        *last_preview = None;
         *last_res = match res {
-            Ok(_) => None,
+            Ok(preview) => {
+                *last_preview = preview;
+                None
+            }
             Err(e) => {
                 log::error!("push error: {}", e);
-                Some(e.to_string())
+                Some(e)
             }
         };
 
@@ -299,4 +433,20 @@ mod tests {
 
         assert_eq!(prog.progress, 20);
     }
+
+    #[test]
+    fn test_progress_resolving_deltas() {
+        let prog: PushProgress =
+            ProgressNotification::ResolvingDeltas {
+                indexed: 2,
+                total: 5,
+            }
+            .into();
+
+        assert!(matches!(
+            prog.state,
+            PushProgressState::ResolvingDeltas
+        ));
+        assert_eq!(prog.progress, 40);
+    }
 }
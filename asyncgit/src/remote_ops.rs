@@ -0,0 +1,349 @@
+//! serializes operations talking to remotes of one repository
+
+use crate::sync::{
+    RemoteError, RemoteResult, DEFAULT_REMOTE_TIMEOUT,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    time::Duration,
+};
+
+/// kind of remote operation
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RemoteOp {
+    ///
+    Fetch,
+    ///
+    Push,
+    ///
+    LsRemote,
+}
+
+impl fmt::Display for RemoteOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Fetch => "fetch",
+            Self::Push => "push",
+            Self::LsRemote => "ls-remote",
+        })
+    }
+}
+
+/// what happens to a request while another operation is busy
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BusyPolicy {
+    /// wait for the running operation to finish
+    Queue,
+    /// fail right away with `RemoteError::Busy`
+    Reject,
+}
+
+#[derive(Default)]
+struct State {
+    next_id: u64,
+    running: Option<(u64, RemoteOp)>,
+    queue: VecDeque<(u64, RemoteOp)>,
+    policies: HashMap<RemoteOp, BusyPolicy>,
+    timeout: Option<Duration>,
+}
+
+impl State {
+    fn policy(&self, op: RemoteOp) -> BusyPolicy {
+        self.policies.get(&op).copied().unwrap_or(match op {
+            RemoteOp::Fetch | RemoteOp::Push => BusyPolicy::Queue,
+            RemoteOp::LsRemote => BusyPolicy::Reject,
+        })
+    }
+
+    fn busy_with(&self) -> Option<RemoteOp> {
+        self.running
+            .or_else(|| self.queue.back().copied())
+            .map(|(_, op)| op)
+    }
+
+    /// operation running or queued ahead of `id`
+    fn busy_with_before(&self, id: u64) -> Option<RemoteOp> {
+        self.running.map(|(_, op)| op).or_else(|| {
+            self.queue
+                .iter()
+                .take_while(|(queued, _)| *queued != id)
+                .last()
+                .map(|(_, op)| *op)
+        })
+    }
+}
+
+/// coordinator shared by all async jobs talking to remotes,
+/// only one of them is running at any time
+#[derive(Clone, Default)]
+pub struct RemoteOps {
+    state: Arc<(Mutex<State>, Condvar)>,
+}
+
+impl RemoteOps {
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// overrides how requests of `op` behave while busy
+    pub fn set_policy(&self, op: RemoteOp, policy: BusyPolicy) {
+        if let Ok(mut state) = self.lock() {
+            state.policies.insert(op, policy);
+        }
+    }
+
+    /// how long an operation may go without progress before
+    /// it is given up
+    pub fn set_timeout(&self, timeout: Duration) {
+        if let Ok(mut state) = self.lock() {
+            state.timeout = Some(timeout);
+        }
+    }
+
+    /// `DEFAULT_REMOTE_TIMEOUT` unless configured otherwise
+    pub fn timeout(&self) -> Duration {
+        self.lock()
+            .ok()
+            .and_then(|state| state.timeout)
+            .unwrap_or(DEFAULT_REMOTE_TIMEOUT)
+    }
+
+    /// the operation currently running
+    pub fn running(&self) -> Option<RemoteOp> {
+        self.lock()
+            .ok()
+            .and_then(|state| state.running.map(|(_, op)| op))
+    }
+
+    /// queues `op`, fails with `RemoteError::Busy` if another
+    /// operation is busy and `op` is configured to be rejected
+    pub fn enqueue(
+        &self,
+        op: RemoteOp,
+    ) -> RemoteResult<RemoteOpTicket> {
+        let mut state = self.lock()?;
+
+        if state.policy(op) == BusyPolicy::Reject {
+            if let Some(busy) = state.busy_with() {
+                return Err(RemoteError::Busy {
+                    running: busy.to_string(),
+                });
+            }
+        }
+
+        let id = state.next_id;
+        state.next_id += 1;
+        state.queue.push_back((id, op));
+
+        Ok(RemoteOpTicket {
+            place: Arc::new(Place {
+                id,
+                ops: self.clone(),
+            }),
+        })
+    }
+
+    /// takes operation `id` out of the queue,
+    /// `true` if it was still queued
+    fn dequeue(&self, id: u64) -> bool {
+        let dequeued = self.lock().is_ok_and(|mut state| {
+            let len = state.queue.len();
+            state.queue.retain(|(queued, _)| *queued != id);
+            state.queue.len() != len
+        });
+
+        if dequeued {
+            self.state.1.notify_all();
+        }
+
+        dequeued
+    }
+
+    fn lock(&self) -> RemoteResult<MutexGuard<'_, State>> {
+        self.state
+            .0
+            .lock()
+            .map_err(|e| RemoteError::Other(e.to_string()))
+    }
+}
+
+/// an operation in the queue, it leaves the queue once the last
+/// ticket for it is dropped
+struct Place {
+    id: u64,
+    ops: RemoteOps,
+}
+
+impl Drop for Place {
+    fn drop(&mut self) {
+        self.ops.dequeue(self.id);
+    }
+}
+
+/// place of an operation in the queue, dropping all tickets for
+/// an operation that did not start yet cancels it
+#[derive(Clone)]
+pub struct RemoteOpTicket {
+    place: Arc<Place>,
+}
+
+impl RemoteOpTicket {
+    /// blocks until it is this operation's turn,
+    /// `None` if it was cancelled before that
+    pub fn wait(&self) -> RemoteResult<Option<RemoteOpGuard>> {
+        let Place { id, ops } = &*self.place;
+        let (_, cvar) = &*ops.state;
+        let mut state = ops.lock()?;
+
+        loop {
+            if !state.queue.iter().any(|(queued, _)| queued == id) {
+                return Ok(None);
+            }
+
+            if state.running.is_none()
+                && state.queue.front().map(|(queued, _)| queued)
+                    == Some(id)
+            {
+                state.running = state.queue.pop_front();
+                return Ok(Some(RemoteOpGuard { ops: ops.clone() }));
+            }
+
+            state = cvar
+                .wait(state)
+                .map_err(|e| RemoteError::Other(e.to_string()))?;
+        }
+    }
+
+    /// drops the operation if it did not start yet,
+    /// `true` if it was still queued
+    pub fn cancel(&self) -> bool {
+        self.place.ops.dequeue(self.place.id)
+    }
+
+    /// operation this one waits for, `None` once it runs
+    pub fn waiting_for(&self) -> Option<RemoteOp> {
+        let Place { id, ops } = &*self.place;
+        ops.lock().ok().and_then(|state| {
+            if state.queue.iter().any(|(queued, _)| queued == id) {
+                state.busy_with_before(*id)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// marks an operation as running until dropped
+pub struct RemoteOpGuard {
+    ops: RemoteOps,
+}
+
+impl Drop for RemoteOpGuard {
+    fn drop(&mut self) {
+        if let Ok(mut state) = self.ops.lock() {
+            state.running = None;
+        }
+        self.ops.state.1.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn test_runs_one_at_a_time() {
+        let ops = RemoteOps::new();
+
+        let fetch = ops.enqueue(RemoteOp::Fetch).unwrap();
+        let push = ops.enqueue(RemoteOp::Push).unwrap();
+
+        let guard = fetch.wait().unwrap().unwrap();
+        assert_eq!(ops.running(), Some(RemoteOp::Fetch));
+        assert_eq!(push.waiting_for(), Some(RemoteOp::Fetch));
+        assert_eq!(fetch.waiting_for(), None);
+
+        let handle = thread::spawn(move || {
+            let guard = push.wait().unwrap();
+            guard.is_some()
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(ops.running(), Some(RemoteOp::Fetch));
+
+        drop(guard);
+        assert!(handle.join().unwrap());
+        assert_eq!(ops.running(), None);
+    }
+
+    #[test]
+    fn test_reject_policy() {
+        let ops = RemoteOps::new();
+
+        let fetch = ops.enqueue(RemoteOp::Fetch).unwrap();
+        let _guard = fetch.wait().unwrap().unwrap();
+
+        assert_eq!(
+            ops.enqueue(RemoteOp::LsRemote).err(),
+            Some(RemoteError::Busy {
+                running: String::from("fetch")
+            })
+        );
+
+        ops.set_policy(RemoteOp::Push, BusyPolicy::Reject);
+        assert!(ops.enqueue(RemoteOp::Push).is_err());
+
+        ops.set_policy(RemoteOp::LsRemote, BusyPolicy::Queue);
+        assert!(ops.enqueue(RemoteOp::LsRemote).is_ok());
+    }
+
+    #[test]
+    fn test_cancel_before_start() {
+        let ops = RemoteOps::new();
+
+        let fetch = ops.enqueue(RemoteOp::Fetch).unwrap();
+        let push = ops.enqueue(RemoteOp::Push).unwrap();
+        let guard = fetch.wait().unwrap().unwrap();
+
+        let waiting = push.clone();
+        let handle =
+            thread::spawn(move || waiting.wait().unwrap().is_none());
+
+        thread::sleep(Duration::from_millis(50));
+        assert!(push.cancel());
+        assert!(handle.join().unwrap());
+
+        // cancelling a running operation has no effect
+        assert!(!fetch.cancel());
+        drop(guard);
+
+        let next = ops.enqueue(RemoteOp::LsRemote).unwrap();
+        assert!(next.wait().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_drop_before_start() {
+        let ops = RemoteOps::new();
+
+        let fetch = ops.enqueue(RemoteOp::Fetch).unwrap();
+        let push = ops.enqueue(RemoteOp::Push).unwrap();
+        let guard = fetch.wait().unwrap().unwrap();
+
+        // stays queued while a ticket for it is left
+        let kept = push.clone();
+        drop(push);
+        assert_eq!(kept.waiting_for(), Some(RemoteOp::Fetch));
+
+        drop(kept);
+        drop(guard);
+        assert_eq!(ops.running(), None);
+
+        // nothing is left in the queue to be busy with
+        let next = ops.enqueue(RemoteOp::LsRemote).unwrap();
+        assert!(next.wait().unwrap().is_some());
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{
+    background_error::report_background_error,
+    error::Result,
+    shutdown::{is_shutting_down, send_notification},
+    sync::{self, CommitId, Reverts},
+    AsyncNotification,
+};
+use crossbeam_channel::Sender;
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// commits whose messages are looked at between two notifications
+const SCAN_CHUNK: usize = 1000;
+
+/// finds the reverts among the commits of the log as they get
+/// loaded, in the background.
+/// notifies (`AsyncNotification::Reverts`) when it found some
+pub struct AsyncReverts {
+    repo_path: Arc<str>,
+    reverts: Arc<Mutex<Reverts>>,
+    /// commits handed to `scan` before, looked at only once
+    scanned: HashSet<CommitId>,
+    /// scans still running
+    pending: Arc<AtomicUsize>,
+    sender: Sender<AsyncNotification>,
+}
+
+impl AsyncReverts {
+    ///
+    pub fn new(
+        repo_path: Arc<str>,
+        sender: &Sender<AsyncNotification>,
+    ) -> Self {
+        Self {
+            repo_path,
+            reverts: Arc::new(Mutex::new(Reverts::default())),
+            scanned: HashSet::new(),
+            pending: Arc::new(AtomicUsize::new(0)),
+            sender: sender.clone(),
+        }
+    }
+
+    ///
+    pub fn is_pending(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) > 0
+    }
+
+    /// reverts found so far
+    pub fn last(&self) -> Result<Reverts> {
+        Ok(self.reverts.lock()?.clone())
+    }
+
+    /// looks for reverts among `ids`, skips the ones scanned before
+    pub fn scan(&mut self, ids: &[CommitId]) {
+        let ids: Vec<CommitId> = ids
+            .iter()
+            .filter(|id| self.scanned.insert(**id))
+            .copied()
+            .collect();
+
+        if ids.is_empty() {
+            return;
+        }
+
+        log::trace!("scan reverts: {}", ids.len());
+
+        let repo_path = Arc::clone(&self.repo_path);
+        let arc_reverts = Arc::clone(&self.reverts);
+        let arc_pending = Arc::clone(&self.pending);
+        let sender = self.sender.clone();
+
+        self.pending.fetch_add(1, Ordering::Relaxed);
+
+        rayon_core::spawn(move || {
+            for chunk in ids.chunks(SCAN_CHUNK) {
+                if is_shutting_down() {
+                    break;
+                }
+
+                match Self::scan_chunk(
+                    &repo_path,
+                    chunk,
+                    &arc_reverts,
+                ) {
+                    Ok(true) => {
+                        send_notification(
+                            &sender,
+                            AsyncNotification::Reverts,
+                        );
+                    }
+                    Ok(false) => (),
+                    Err(e) => report_background_error(
+                        &sender, "reverts", &e,
+                    ),
+                }
+            }
+
+            arc_pending.fetch_sub(1, Ordering::Relaxed);
+        });
+    }
+
+    /// `true` if it found any
+    fn scan_chunk(
+        repo_path: &str,
+        ids: &[CommitId],
+        arc_reverts: &Arc<Mutex<Reverts>>,
+    ) -> Result<bool> {
+        let found = sync::find_reverts(repo_path, ids)?;
+
+        let mut reverts = arc_reverts.lock()?;
+        for (revert, reverted) in &found {
+            reverts.insert(*revert, *reverted);
+        }
+
+        Ok(!found.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{commit, tests::repo_init_empty};
+    use crossbeam_channel::unbounded;
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    #[test]
+    fn test_scan_incrementally() {
+        let (td, _repo) = repo_init_empty().unwrap();
+        let repo_path: Arc<str> =
+            Arc::from(td.path().to_str().unwrap());
+
+        let foo = commit(&repo_path, "foo").unwrap();
+        let revert = commit(
+            &repo_path,
+            &format!(
+                "Revert \"foo\"\n\nThis reverts commit {}.\n",
+                foo.to_string()
+            ),
+        )
+        .unwrap();
+
+        let (sender, receiver) = unbounded();
+        let mut reverts = AsyncReverts::new(repo_path, &sender);
+        let wait = |reverts: &AsyncReverts| {
+            let start = Instant::now();
+            while reverts.is_pending() {
+                assert!(start.elapsed() < Duration::from_secs(10));
+                thread::sleep(Duration::from_millis(5));
+            }
+        };
+
+        reverts.scan(&[foo]);
+        wait(&reverts);
+        assert!(reverts.last().unwrap().is_empty());
+        assert!(receiver.try_recv().is_err());
+
+        // `foo` again is skipped
+        reverts.scan(&[revert, foo]);
+        wait(&reverts);
+        let last = reverts.last().unwrap();
+        assert_eq!(last.reverts(&revert), Some(foo));
+        assert_eq!(last.reverted_by(&foo), &[revert]);
+        assert_eq!(
+            receiver.try_recv(),
+            Ok(AsyncNotification::Reverts)
+        );
+    }
+}
@@ -1,18 +1,25 @@
 use crate::{
-    error::Result,
-    sync::{utils::repo, CommitId, LogWalker},
-    AsyncNotification, CWD,
+    background_error::report_background_error,
+    error::{Error, Result},
+    shutdown::{is_shutting_down, send_notification},
+    sync::{
+        utils::repo_allow_bare, CommitId, LineRange, LogWalker,
+        LogWalkerFilter, LogWalkerScan, LogWindow,
+    },
+    AsyncNotification,
 };
-use crossbeam_channel::Sender;
-use git2::Oid;
+use crossbeam_channel::{unbounded, Sender};
+use git2::{Oid, Repository};
 use scopetime::scope_time;
 use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc, Mutex,
     },
     thread,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 ///
@@ -28,44 +35,391 @@ pub enum FetchStatus {
 
 ///
 pub struct AsyncLog {
-    current: Arc<Mutex<Vec<CommitId>>>,
+    repo_path: Arc<str>,
+    current: Arc<Mutex<IndexedCommits>>,
+    current_head: Arc<Mutex<Option<CommitId>>>,
     sender: Sender<AsyncNotification>,
     pending: Arc<AtomicBool>,
     background: Arc<AtomicBool>,
+    scanned: Arc<AtomicUsize>,
+    error: Arc<Mutex<Option<String>>>,
+    filter: Option<LogWalkerFilter>,
+    line_range: Option<LineRange>,
+    scan_limit: Option<usize>,
+    window: Option<LogWindow>,
+    topo_order: bool,
+    all_refs: bool,
+    /// fingerprint of the refs the last walk started at, only
+    /// kept with `all_refs`
+    current_refs: Arc<Mutex<Option<u64>>>,
+    notify_interval: Duration,
+    worker: Option<LogWorker>,
+    generations: WalkGenerations,
+}
+
+/// how `fetch_helper` walks the log
+struct WalkOptions {
+    filter: Option<LogWalkerFilter>,
+    line_range: Option<LineRange>,
+    scan_limit: Option<usize>,
+    window: Option<LogWindow>,
+    topo_order: bool,
+    all_refs: bool,
+    notify_interval: Duration,
+    /// the walk stops once a newer one of the same
+    /// `WalkGenerations` started
+    cancel: Option<Generation>,
+}
+
+impl WalkOptions {
+    fn is_cancelled(&self) -> bool {
+        is_shutting_down()
+            || self.cancel.as_ref().is_some_and(Generation::is_stale)
+    }
+}
+
+/// counts the walks of the logs replacing one another (e.g. one
+/// per filter query): starting a walk supersedes the ones still
+/// running, which stop at the next commit they look at instead of
+/// finishing first. clones count together
+#[derive(Clone, Default)]
+pub struct WalkGenerations {
+    latest: Arc<AtomicUsize>,
+}
+
+impl WalkGenerations {
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// supersedes all walks before
+    fn next(&self) -> Generation {
+        let own = self.latest.fetch_add(1, Ordering::Relaxed) + 1;
+        Generation {
+            latest: Arc::clone(&self.latest),
+            own,
+        }
+    }
+}
+
+/// tells a walk apart from the ones after it
+#[derive(Clone)]
+struct Generation {
+    latest: Arc<AtomicUsize>,
+    own: usize,
+}
+
+impl Generation {
+    fn is_stale(&self) -> bool {
+        self.latest.load(Ordering::Relaxed) != self.own
+    }
+}
+
+/// a single walk and where it reports to
+struct WalkJob {
+    repo_path: Arc<str>,
+    current: Arc<Mutex<IndexedCommits>>,
+    pending: Arc<AtomicBool>,
+    background: Arc<AtomicBool>,
+    scanned: Arc<AtomicUsize>,
+    error: Arc<Mutex<Option<String>>>,
+    sender: Sender<AsyncNotification>,
+    options: WalkOptions,
+}
+
+impl WalkJob {
+    fn run(self) {
+        scope_time!("async::revlog");
+
+        if let Err(e) = AsyncLog::fetch_helper(
+            &self.repo_path,
+            Arc::clone(&self.current),
+            Arc::clone(&self.background),
+            Arc::clone(&self.scanned),
+            &self.sender,
+            &self.options,
+        ) {
+            if !self.options.is_cancelled() {
+                report_background_error(&self.sender, "log walk", &e);
+
+                if let Ok(mut error) = self.error.lock() {
+                    *error = Some(e.to_string());
+                }
+            }
+        }
+
+        self.finish();
+    }
+
+    /// ends a walk that got replaced before it started
+    fn finish(self) {
+        self.pending.store(false, Ordering::Relaxed);
+        AsyncLog::notify(&self.sender);
+    }
+}
+
+/// thread that stays around to run one log walk after the other,
+/// cheaper than spawning a task per walk when filtering a lot.
+/// queuing a walk cancels the one still running, clones share
+/// the same thread
+#[derive(Clone)]
+pub struct LogWorker {
+    jobs: Sender<WalkJob>,
+    generations: WalkGenerations,
+}
+
+impl LogWorker {
+    ///
+    pub fn new() -> Self {
+        let (jobs, receiver) = unbounded::<WalkJob>();
+
+        thread::spawn(move || {
+            while let Ok(mut job) = receiver.recv() {
+                // only the latest of the queued walks is of interest
+                for newer in receiver.try_iter() {
+                    job.finish();
+                    job = newer;
+                }
+
+                job.run();
+            }
+        });
+
+        Self {
+            jobs,
+            generations: WalkGenerations::new(),
+        }
+    }
+
+    fn submit(&self, job: WalkJob) {
+        // the thread only goes away by panicking
+        if let Err(e) = self.jobs.send(job) {
+            let job = e.into_inner();
+            rayon_core::spawn(move || job.run());
+        }
+    }
+}
+
+impl Default for LogWorker {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
+/// minimum time between two notifications of a running walk
+pub const DEFAULT_LOG_NOTIFY_INTERVAL: Duration =
+    Duration::from_millis(50);
+
 static LIMIT_COUNT: usize = 3000;
 static SLEEP_FOREGROUND: Duration = Duration::from_millis(2);
 static SLEEP_BACKGROUND: Duration = Duration::from_millis(1000);
+static WAIT_POLL_INTERVAL: Duration = Duration::from_millis(5);
+/// a filtered walk notifies after this many commits even if
+/// none of them matched
+static SCANNED_NOTIFY_COUNT: usize = 5000;
+static CANCELLED: &str = "log walk cancelled";
+
+/// commits found by a walk, kept in the order of their index in
+/// the unfiltered log no matter in which order they arrive
+#[derive(Default)]
+struct IndexedCommits {
+    items: Vec<(usize, CommitId)>,
+}
+
+impl IndexedCommits {
+    /// a batch following everything known so far is appended,
+    /// anything else is sorted in (replacing equal indices)
+    fn insert(&mut self, batch: &[(usize, CommitId)]) {
+        let appends = batch.windows(2).all(|w| w[0].0 < w[1].0)
+            && match (self.items.last(), batch.first()) {
+                (Some(last), Some(first)) => last.0 < first.0,
+                _ => true,
+            };
+
+        if appends {
+            self.items.extend_from_slice(batch);
+            return;
+        }
+
+        for entry in batch {
+            let pos =
+                self.items.partition_point(|(idx, _)| *idx < entry.0);
+            match self.items.get_mut(pos) {
+                Some(existing) if existing.0 == entry.0 => {
+                    *existing = *entry;
+                }
+                _ => self.items.insert(pos, *entry),
+            }
+        }
+    }
+
+    fn slice(&self, start: usize, amount: usize) -> Vec<CommitId> {
+        self.items
+            .iter()
+            .skip(start)
+            .take(amount)
+            .map(|(_, id)| *id)
+            .collect()
+    }
+
+    fn position(&self, id: CommitId) -> Option<usize> {
+        self.items.iter().position(|(_, c)| *c == id)
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+
+/// lets through at most one notification per `interval`
+struct NotifyThrottle {
+    interval: Duration,
+    last: Option<Instant>,
+}
+
+impl NotifyThrottle {
+    const fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last: None,
+        }
+    }
+
+    fn should_notify(&mut self, now: Instant) -> bool {
+        let due = self.last.is_none_or(|last| {
+            now.saturating_duration_since(last) >= self.interval
+        });
+
+        if due {
+            self.last = Some(now);
+        }
+
+        due
+    }
+}
 
 impl AsyncLog {
     ///
-    pub fn new(sender: &Sender<AsyncNotification>) -> Self {
+    pub fn new(
+        repo_path: Arc<str>,
+        sender: &Sender<AsyncNotification>,
+        filter: Option<LogWalkerFilter>,
+    ) -> Self {
         Self {
-            current: Arc::new(Mutex::new(Vec::new())),
+            repo_path,
+            current: Arc::new(Mutex::new(IndexedCommits::default())),
+            current_head: Arc::new(Mutex::new(None)),
             sender: sender.clone(),
             pending: Arc::new(AtomicBool::new(false)),
             background: Arc::new(AtomicBool::new(false)),
+            scanned: Arc::new(AtomicUsize::new(0)),
+            error: Arc::new(Mutex::new(None)),
+            filter,
+            line_range: None,
+            scan_limit: None,
+            window: None,
+            topo_order: false,
+            all_refs: false,
+            current_refs: Arc::new(Mutex::new(None)),
+            notify_interval: DEFAULT_LOG_NOTIFY_INTERVAL,
+            worker: None,
+            generations: WalkGenerations::new(),
         }
     }
 
+    /// walks on `worker` instead of a task of its own, counting
+    /// with the other logs on it
+    pub fn set_worker(&mut self, worker: LogWorker) {
+        self.generations = worker.generations.clone();
+        self.worker = Some(worker);
+    }
+
+    /// a walk of this log supersedes the ones of the other logs
+    /// sharing `generations` and the other way around
+    pub fn set_generations(&mut self, generations: WalkGenerations) {
+        self.generations = generations;
+    }
+
+    /// minimum time between two `AsyncNotification::Log` sent
+    /// while a walk is still running (the final one is always sent)
+    pub fn set_notify_interval(&mut self, interval: Duration) {
+        self.notify_interval = interval;
+    }
+
+    /// only keeps the commits that changed `range`, like
+    /// `git log -L` (the filter only sees those)
+    pub fn set_line_range(&mut self, range: Option<LineRange>) {
+        self.line_range = range;
+    }
+
+    /// stops the walk after looking at the `limit` most recent
+    /// commits, matching or not
+    pub fn set_scan_limit(&mut self, limit: Option<usize>) {
+        self.scan_limit = limit;
+    }
+
+    /// only walks the commits within `window`, the count and
+    /// the filter only see those
+    pub fn set_window(&mut self, window: Option<LogWindow>) {
+        self.window = window;
+    }
+
+    /// walks like `git log --topo-order` instead of by date
+    pub fn set_topo_order(&mut self, topo_order: bool) {
+        self.topo_order = topo_order;
+    }
+
+    /// walks all refs like `git log --all` instead of just `HEAD`
+    pub fn set_all_refs(&mut self, all_refs: bool) {
+        self.all_refs = all_refs;
+    }
+
+    /// the filter the log was created with
+    pub fn filter(&self) -> Option<LogWalkerFilter> {
+        self.filter.clone()
+    }
+
     ///
-    pub fn count(&mut self) -> Result<usize> {
+    pub fn count(&self) -> Result<usize> {
         Ok(self.current.lock()?.len())
     }
 
+    /// commits looked at by the walk so far, more than `count`
+    /// if there is a filter
+    pub fn scanned(&self) -> usize {
+        self.scanned.load(Ordering::Relaxed)
+    }
+
+    /// error the last walk stopped with (e.g. from the filter)
+    pub fn error(&self) -> Result<Option<String>> {
+        Ok(self.error.lock()?.clone())
+    }
+
     ///
     pub fn get_slice(
         &self,
         start_index: usize,
         amount: usize,
     ) -> Result<Vec<CommitId>> {
-        let list = self.current.lock()?;
-        let list_len = list.len();
-        let min = start_index.min(list_len);
-        let max = min + amount;
-        let max = max.min(list_len);
-        Ok(list[min..max].to_vec())
+        Ok(self.current.lock()?.slice(start_index, amount))
+    }
+
+    /// index of `id` in the walk so far
+    pub fn position(&self, id: CommitId) -> Result<Option<usize>> {
+        Ok(self.current.lock()?.position(id))
+    }
+
+    /// replaces the commits found by `ids`, e.g. to reorder them,
+    /// until the next walk starts
+    pub fn set_commits(&self, ids: Vec<CommitId>) -> Result<()> {
+        self.current.lock()?.items =
+            ids.into_iter().enumerate().collect();
+        Ok(())
     }
 
     ///
@@ -73,6 +427,27 @@ impl AsyncLog {
         self.pending.load(Ordering::Relaxed)
     }
 
+    /// stops the running walk at the next commit it looks at,
+    /// superseding the walks sharing its `generations` as well
+    pub fn stop(&self) {
+        self.generations.next();
+    }
+
+    /// blocks until the running walk is done or `timeout` passed,
+    /// returns the amount of commits found unless it timed out
+    pub fn wait(&self, timeout: Duration) -> Result<Option<usize>> {
+        let start = Instant::now();
+
+        while self.is_pending() {
+            if start.elapsed() >= timeout {
+                return Ok(None);
+            }
+            thread::sleep(WAIT_POLL_INTERVAL);
+        }
+
+        Ok(Some(self.current.lock()?.len()))
+    }
+
     ///
     pub fn set_background(&mut self) {
         self.background.store(true, Ordering::Relaxed)
@@ -81,15 +456,14 @@ impl AsyncLog {
     ///
     fn current_head(&self) -> Result<CommitId> {
         Ok(self
-            .current
+            .current_head
             .lock()?
-            .first()
-            .map_or(Oid::zero().into(), |f| *f))
+            .map_or(Oid::zero().into(), |f| f))
     }
 
     ///
     fn head_changed(&self) -> Result<bool> {
-        if let Ok(head) = repo(CWD)?.head() {
+        if let Ok(head) = repo_allow_bare(&self.repo_path)?.head() {
             if let Some(head) = head.target() {
                 return Ok(head != self.current_head()?.into());
             }
@@ -97,6 +471,18 @@ impl AsyncLog {
         Ok(false)
     }
 
+    /// any ref moved since the last walk, always `false` unless
+    /// walking all refs
+    fn refs_changed(&self) -> Result<bool> {
+        if !self.all_refs {
+            return Ok(false);
+        }
+
+        let refs =
+            refs_fingerprint(&repo_allow_bare(&self.repo_path)?)?;
+        Ok(*self.current_refs.lock()? != Some(refs))
+    }
+
     ///
     pub fn fetch(&mut self) -> Result<FetchStatus> {
         self.background.store(false, Ordering::Relaxed);
@@ -105,59 +491,110 @@ impl AsyncLog {
             return Ok(FetchStatus::Pending);
         }
 
-        if !self.head_changed()? {
+        if !self.head_changed()? && !self.refs_changed()? {
             return Ok(FetchStatus::NoChange);
         }
 
         self.clear()?;
 
-        let arc_current = Arc::clone(&self.current);
-        let sender = self.sender.clone();
-        let arc_pending = Arc::clone(&self.pending);
-        let arc_background = Arc::clone(&self.background);
+        let job = WalkJob {
+            repo_path: Arc::clone(&self.repo_path),
+            current: Arc::clone(&self.current),
+            pending: Arc::clone(&self.pending),
+            background: Arc::clone(&self.background),
+            scanned: Arc::clone(&self.scanned),
+            error: Arc::clone(&self.error),
+            sender: self.sender.clone(),
+            options: WalkOptions {
+                filter: self.filter.clone(),
+                line_range: self.line_range.clone(),
+                scan_limit: self.scan_limit,
+                window: self.window.clone(),
+                topo_order: self.topo_order,
+                all_refs: self.all_refs,
+                notify_interval: self.notify_interval,
+                cancel: Some(self.generations.next()),
+            },
+        };
 
         self.pending.store(true, Ordering::Relaxed);
 
-        rayon_core::spawn(move || {
-            scope_time!("async::revlog");
-
-            AsyncLog::fetch_helper(
-                arc_current,
-                arc_background,
-                &sender,
-            )
-            .expect("failed to fetch");
-
-            arc_pending.store(false, Ordering::Relaxed);
+        let repo = repo_allow_bare(&self.repo_path)?;
+        if let Ok(head) = repo.head() {
+            *self.current_head.lock()? =
+                head.target().map(CommitId::new);
+        }
+        if self.all_refs {
+            *self.current_refs.lock()? =
+                Some(refs_fingerprint(&repo)?);
+        }
 
-            Self::notify(&sender);
-        });
+        match &self.worker {
+            Some(worker) => worker.submit(job),
+            None => rayon_core::spawn(move || job.run()),
+        }
 
         Ok(FetchStatus::Started)
     }
 
     fn fetch_helper(
-        arc_current: Arc<Mutex<Vec<CommitId>>>,
+        repo_path: &str,
+        arc_current: Arc<Mutex<IndexedCommits>>,
         arc_background: Arc<AtomicBool>,
+        arc_scanned: Arc<AtomicUsize>,
         sender: &Sender<AsyncNotification>,
+        options: &WalkOptions,
     ) -> Result<()> {
         let mut entries = Vec::with_capacity(LIMIT_COUNT);
-        let r = repo(CWD)?;
-        let mut walker = LogWalker::new(&r);
+        let mut throttle =
+            NotifyThrottle::new(options.notify_interval);
+        let r = repo_allow_bare(repo_path)?;
+        let filtered =
+            options.filter.is_some() || options.line_range.is_some();
+        let mut walker = LogWalker::new(&r)
+            .filter(options.filter.clone())
+            .line_range(options.line_range.clone())
+            .on_scan(filtered.then(|| {
+                Self::count_scanned(
+                    options.cancel.clone(),
+                    &arc_scanned,
+                    sender,
+                )
+            }))
+            .scan_limit(options.scan_limit)
+            .window(options.window.clone())
+            .topo_order(options.topo_order)
+            .all_refs(options.all_refs);
+
         loop {
+            if options.is_cancelled() {
+                return Err(Error::Generic(CANCELLED.to_string()));
+            }
+
             entries.clear();
-            let res_is_err =
-                walker.read(&mut entries, LIMIT_COUNT).is_err();
+            walker.read_indexed(&mut entries, LIMIT_COUNT)?;
 
-            if !res_is_err {
+            {
                 let mut current = arc_current.lock()?;
-                current.extend(entries.iter());
+                // superseded while reading, the batch is stale
+                if options.is_cancelled() {
+                    return Err(Error::Generic(
+                        CANCELLED.to_string(),
+                    ));
+                }
+                current.insert(&entries);
+            }
+            if !filtered {
+                arc_scanned
+                    .fetch_add(entries.len(), Ordering::Relaxed);
             }
 
-            if res_is_err || entries.len() <= 1 {
+            if entries.len() <= 1 {
                 break;
             } else {
-                Self::notify(&sender);
+                if throttle.should_notify(Instant::now()) {
+                    Self::notify(sender);
+                }
 
                 let sleep_duration =
                     if arc_background.load(Ordering::Relaxed) {
@@ -172,12 +609,402 @@ impl AsyncLog {
         Ok(())
     }
 
+    /// counts the commits a filtered walk looks at, notifying
+    /// now and then while it skips many in a row.
+    /// fails once `cancel` went stale or the app quits to end the
+    /// walk early, a slow filter must not hold up the next walk
+    fn count_scanned(
+        cancel: Option<Generation>,
+        arc_scanned: &Arc<AtomicUsize>,
+        sender: &Sender<AsyncNotification>,
+    ) -> LogWalkerScan<'static> {
+        let arc_scanned = Arc::clone(arc_scanned);
+        let sender = sender.clone();
+
+        Box::new(move || {
+            // a slow filter must hold up neither the next walk
+            // nor the exit
+            if is_shutting_down()
+                || cancel.as_ref().is_some_and(Generation::is_stale)
+            {
+                return Err(Error::Generic(CANCELLED.to_string()));
+            }
+
+            let scanned =
+                arc_scanned.fetch_add(1, Ordering::Relaxed) + 1;
+            if scanned.is_multiple_of(SCANNED_NOTIFY_COUNT) {
+                Self::notify(&sender);
+            }
+
+            Ok(())
+        })
+    }
+
     fn clear(&mut self) -> Result<()> {
         self.current.lock()?.clear();
+        self.scanned.store(0, Ordering::Relaxed);
+        *self.error.lock()? = None;
         Ok(())
     }
 
     fn notify(sender: &Sender<AsyncNotification>) {
-        sender.send(AsyncNotification::Log).expect("error sending");
+        send_notification(sender, AsyncNotification::Log);
+    }
+}
+
+/// changes whenever a ref is added, removed or moved
+fn refs_fingerprint(repo: &Repository) -> Result<u64> {
+    let mut hasher = DefaultHasher::new();
+    for reference in repo.references()?.flatten() {
+        reference.name_bytes().hash(&mut hasher);
+        reference.target().hash(&mut hasher);
+    }
+
+    Ok(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit, commit_filter,
+        tests::{repo_init, repo_init_bare},
+        FilterBy, FilterQuery, FilterTerm, Tags,
+        DEFAULT_MAX_FIELD_LEN,
+    };
+    use crossbeam_channel::unbounded;
+
+    #[test]
+    fn test_throttle_coalesces() {
+        let interval = Duration::from_millis(100);
+        let mut throttle = NotifyThrottle::new(interval);
+        let start = Instant::now();
+
+        // one slice every 10ms for one second
+        let sent = (0..100)
+            .filter(|i| {
+                throttle.should_notify(
+                    start + Duration::from_millis(i * 10),
+                )
+            })
+            .count();
+
+        assert_eq!(sent, 10);
+    }
+
+    #[test]
+    fn test_throttle_zero_interval() {
+        let mut throttle =
+            NotifyThrottle::new(Duration::from_secs(0));
+        let now = Instant::now();
+
+        assert!(throttle.should_notify(now));
+        assert!(throttle.should_notify(now));
+    }
+
+    #[test]
+    fn test_indexed_commits_out_of_order() {
+        let id =
+            |i: u8| CommitId::new(Oid::from_bytes(&[i; 20]).unwrap());
+        let batch = |indices: &[usize]| {
+            indices
+                .iter()
+                .map(|i| (*i, id(*i as u8)))
+                .collect::<Vec<_>>()
+        };
+        let window = |commits: &IndexedCommits, start, amount| {
+            commits.slice(start, amount)
+        };
+
+        let mut commits = IndexedCommits::default();
+        commits.insert(&batch(&[2, 5, 9]));
+        // a later source delivers older and newer matches at once
+        commits.insert(&batch(&[7, 0, 12]));
+        commits.insert(&batch(&[3]));
+        commits.insert(&batch(&[]));
+
+        assert_eq!(commits.len(), 7);
+        assert_eq!(window(&commits, 0, 3), vec![id(0), id(2), id(3)]);
+        assert_eq!(window(&commits, 3, 3), vec![id(5), id(7), id(9)]);
+        assert_eq!(window(&commits, 6, 10), vec![id(12)]);
+        assert_eq!(window(&commits, 10, 3), Vec::new());
+        assert_eq!(commits.position(id(7)), Some(4));
+
+        // the same index twice does not duplicate it
+        commits.insert(&batch(&[5]));
+        assert_eq!(commits.len(), 7);
+
+        commits.insert(&batch(&[13, 14]));
+        assert_eq!(
+            window(&commits, 6, 10),
+            vec![id(12), id(13), id(14)]
+        );
+    }
+
+    #[test]
+    fn test_filtered_walk_in_fixture_repo() {
+        let (td, _repo) = repo_init().unwrap();
+        let repo_path: Arc<str> =
+            Arc::from(td.path().to_str().unwrap());
+
+        let fix = commit(&repo_path, "fix the parser").unwrap();
+        commit(&repo_path, "wip").unwrap();
+
+        let query = FilterQuery::Term(FilterTerm::new(
+            "parser",
+            FilterBy::MESSAGE,
+        ));
+        let (sender, receiver) = unbounded();
+        let mut log = AsyncLog::new(
+            Arc::clone(&repo_path),
+            &sender,
+            Some(commit_filter(
+                query,
+                Tags::new(),
+                DEFAULT_MAX_FIELD_LEN,
+            )),
+        );
+
+        assert!(log.fetch().unwrap() == FetchStatus::Started);
+        assert_eq!(
+            log.wait(Duration::from_secs(10)).unwrap(),
+            Some(1)
+        );
+        assert!(receiver.try_recv().is_ok());
+
+        assert_eq!(log.get_slice(0, 10).unwrap(), vec![fix]);
+        assert_eq!(log.scanned(), 3);
+        assert_eq!(log.error().unwrap(), None);
+    }
+
+    #[test]
+    fn test_superseded_walks() {
+        let (td, _repo) = repo_init().unwrap();
+        let repo_path: Arc<str> =
+            Arc::from(td.path().to_str().unwrap());
+
+        for i in 0..20 {
+            commit(&repo_path, &i.to_string()).unwrap();
+        }
+
+        // slow enough for the next query to start first
+        let slow: LogWalkerFilter = Arc::new(Box::new(|_, _| {
+            thread::sleep(Duration::from_millis(10));
+            Ok(true)
+        }));
+
+        let (sender, _receiver) = unbounded();
+        let generations = WalkGenerations::new();
+        let logs = (0..50)
+            .map(|_| {
+                let mut log = AsyncLog::new(
+                    Arc::clone(&repo_path),
+                    &sender,
+                    Some(Arc::clone(&slow)),
+                );
+                log.set_generations(generations.clone());
+                assert!(log.fetch().unwrap() == FetchStatus::Started);
+                log
+            })
+            .collect::<Vec<_>>();
+
+        for log in &logs {
+            assert!(log
+                .wait(Duration::from_secs(10))
+                .unwrap()
+                .is_some());
+            assert_eq!(log.error().unwrap(), None);
+        }
+
+        let (last, superseded) = logs.split_last().unwrap();
+        assert_eq!(last.count().unwrap(), 21);
+        for log in superseded {
+            assert_eq!(log.count().unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn test_stopped_walk() {
+        let (td, _repo) = repo_init().unwrap();
+        let repo_path: Arc<str> =
+            Arc::from(td.path().to_str().unwrap());
+
+        for i in 0..20 {
+            commit(&repo_path, &i.to_string()).unwrap();
+        }
+
+        let (sender, _receiver) = unbounded();
+        let mut log = AsyncLog::new(
+            Arc::clone(&repo_path),
+            &sender,
+            Some(Arc::new(Box::new(|_, _| {
+                thread::sleep(Duration::from_millis(50));
+                Ok(true)
+            }))),
+        );
+
+        assert!(log.fetch().unwrap() == FetchStatus::Started);
+        log.stop();
+
+        assert!(log
+            .wait(Duration::from_millis(500))
+            .unwrap()
+            .is_some());
+        assert_eq!(log.count().unwrap(), 0);
+        assert_eq!(log.error().unwrap(), None);
+    }
+
+    #[test]
+    fn test_walk_without_receiver() {
+        let (td, _repo) = repo_init().unwrap();
+        let repo_path: Arc<str> =
+            Arc::from(td.path().to_str().unwrap());
+
+        commit(&repo_path, "fix the parser").unwrap();
+
+        let query = FilterQuery::Term(FilterTerm::new(
+            "parser",
+            FilterBy::MESSAGE,
+        ));
+        let (sender, receiver) = unbounded();
+        let mut log = AsyncLog::new(
+            Arc::clone(&repo_path),
+            &sender,
+            Some(commit_filter(
+                query,
+                Tags::new(),
+                DEFAULT_MAX_FIELD_LEN,
+            )),
+        );
+
+        // the ui went away while the walk is running
+        drop(receiver);
+
+        assert!(log.fetch().unwrap() == FetchStatus::Started);
+        assert_eq!(
+            log.wait(Duration::from_secs(10)).unwrap(),
+            Some(1)
+        );
+        assert_eq!(log.error().unwrap(), None);
+    }
+
+    #[test]
+    fn test_warm_worker() {
+        let (td, _repo) = repo_init().unwrap();
+        let repo_path: Arc<str> =
+            Arc::from(td.path().to_str().unwrap());
+
+        let fix = commit(&repo_path, "fix the parser").unwrap();
+        let wip = commit(&repo_path, "wip").unwrap();
+
+        let worker = LogWorker::new();
+        let (sender, _receiver) = unbounded();
+        let filtered = |text: &str| {
+            let mut log = AsyncLog::new(
+                Arc::clone(&repo_path),
+                &sender,
+                Some(commit_filter(
+                    FilterQuery::Term(FilterTerm::new(
+                        text,
+                        FilterBy::MESSAGE,
+                    )),
+                    Tags::new(),
+                    DEFAULT_MAX_FIELD_LEN,
+                )),
+            );
+            log.set_worker(worker.clone());
+            assert!(log.fetch().unwrap() == FetchStatus::Started);
+            log
+        };
+        let wait = |log: &AsyncLog| {
+            log.wait(Duration::from_secs(10)).unwrap().unwrap()
+        };
+
+        for (text, expected) in &[
+            ("parser", vec![fix]),
+            ("wip", vec![wip]),
+            ("nope", vec![]),
+        ] {
+            let log = filtered(text);
+            assert_eq!(wait(&log), expected.len());
+            assert_eq!(&log.get_slice(0, 10).unwrap(), expected);
+            assert_eq!(log.error().unwrap(), None);
+        }
+
+        // a walk queued right after another one does not mix with it
+        let first = filtered("parser");
+        let second = filtered("wip");
+        wait(&first);
+        assert_eq!(wait(&second), 1);
+        assert_eq!(second.get_slice(0, 10).unwrap(), vec![wip]);
+        assert_eq!(first.error().unwrap(), None);
+        assert!(first.get_slice(0, 10).unwrap().len() <= 1);
+    }
+
+    #[test]
+    fn test_filter_error() {
+        let (td, _repo) = repo_init().unwrap();
+        let repo_path: Arc<str> =
+            Arc::from(td.path().to_str().unwrap());
+        commit(&repo_path, "wip").unwrap();
+
+        let (sender, _receiver) = unbounded();
+        let mut log = AsyncLog::new(
+            Arc::clone(&repo_path),
+            &sender,
+            Some(Arc::new(Box::new(|_repo, _id| {
+                Err(crate::error::Error::Generic(String::from(
+                    "broken filter",
+                )))
+            }))),
+        );
+
+        assert!(log.fetch().unwrap() == FetchStatus::Started);
+        assert_eq!(
+            log.wait(Duration::from_secs(10)).unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            log.error().unwrap().as_deref(),
+            Some("`broken filter`")
+        );
+    }
+
+    #[test]
+    fn test_walk_bare_repo() {
+        let (td, repo) = repo_init_bare().unwrap();
+        let repo_path: Arc<str> =
+            Arc::from(td.path().to_str().unwrap());
+
+        let sig = git2::Signature::now("name", "email").unwrap();
+        let tree_id =
+            repo.treebuilder(None).unwrap().write().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let first = repo
+            .commit(Some("HEAD"), &sig, &sig, "first", &tree, &[])
+            .unwrap();
+        let second = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "second",
+                &tree,
+                &[&repo.find_commit(first).unwrap()],
+            )
+            .unwrap();
+
+        let (sender, receiver) = unbounded();
+        let mut log = AsyncLog::new(repo_path, &sender, None);
+
+        assert!(log.fetch().unwrap() == FetchStatus::Started);
+        while log.is_pending() {
+            receiver.recv().unwrap();
+        }
+
+        assert_eq!(
+            log.get_slice(0, 10).unwrap(),
+            vec![CommitId::new(second), CommitId::new(first)]
+        );
     }
 }
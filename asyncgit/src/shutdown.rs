@@ -0,0 +1,47 @@
+//! lets background jobs end early once the app quits
+
+use crate::AsyncNotification;
+use crossbeam_channel::Sender;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// set once by `shutdown`, never reset
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// asks all background jobs (log walks and filters, author stats,
+/// revert scans..) to stop as soon as they can, called when the
+/// app exits
+pub fn shutdown() {
+    SHUTTING_DOWN.store(true, Ordering::Relaxed);
+}
+
+/// `true` once `shutdown` was called
+pub fn is_shutting_down() -> bool {
+    SHUTTING_DOWN.load(Ordering::Relaxed)
+}
+
+/// sends `notification` to the ui, nobody listening anymore (the
+/// app quit while the job was running) is fine
+pub(crate) fn send_notification(
+    sender: &Sender<AsyncNotification>,
+    notification: AsyncNotification,
+) {
+    if sender.send(notification).is_err() {
+        log::debug!("dropped notification: {:?}", notification);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossbeam_channel::unbounded;
+
+    #[test]
+    fn test_notify_without_receiver() {
+        let (sender, receiver) = unbounded();
+        send_notification(&sender, AsyncNotification::Log);
+        assert_eq!(receiver.try_recv(), Ok(AsyncNotification::Log));
+
+        drop(receiver);
+        send_notification(&sender, AsyncNotification::Log);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     error::Result,
     hash,
+    shutdown::send_notification,
     sync::{self, status::StatusType},
     AsyncNotification, StatusItem, CWD,
 };
@@ -132,9 +133,7 @@ impl AsyncStatus {
             arc_pending.fetch_sub(1, Ordering::Relaxed);
 
             if ok {
-                sender
-                    .send(AsyncNotification::Status)
-                    .expect("error sending status");
+                send_notification(&sender, AsyncNotification::Status);
             }
         });
 
@@ -8,6 +8,8 @@ use git2::BranchType;
 use scopetime::scope_time;
 use utils::get_head_repo;
 
+use super::get_worktrees;
+
 use super::utils::bytes2string;
 
 /// returns the branch-name head is currently pointing to
@@ -15,7 +17,7 @@ use super::utils::bytes2string;
 pub(crate) fn get_branch_name(repo_path: &str) -> Result<String> {
     scope_time!("get_branch_name");
 
-    let repo = utils::repo(repo_path)?;
+    let repo = utils::repo_allow_bare(repo_path)?;
 
     let iter = repo.branches(None)?;
 
@@ -111,6 +113,62 @@ pub fn branch_compare_upstream(
     Ok(BranchCompare { ahead, behind })
 }
 
+/// commits on HEAD's branch not yet on its upstream
+/// (`@{u}..HEAD`, newest first)
+pub fn get_unpushed_commits(
+    repo_path: &str,
+) -> Result<Vec<CommitId>> {
+    scope_time!("get_unpushed_commits");
+
+    let repo = utils::repo(repo_path)?;
+
+    let name = get_branch_name(repo_path)?;
+    let branch = repo.find_branch(&name, BranchType::Local)?;
+    let upstream = branch
+        .upstream()
+        .map_err(|_| Error::NoUpstream(name.clone()))?;
+
+    let mut walk = repo.revwalk()?;
+    walk.push(branch.into_reference().peel_to_commit()?.id())?;
+    walk.hide(upstream.into_reference().peel_to_commit()?.id())?;
+
+    let mut res = Vec::new();
+    for id in walk {
+        res.push(id?.into());
+    }
+
+    Ok(res)
+}
+
+/// upstream a local branch is configured to track
+#[derive(Debug, Clone, PartialEq)]
+pub struct BranchUpstream {
+    /// name of the remote (e.g. `origin`)
+    pub remote: String,
+    /// ref on the remote (e.g. `refs/heads/master`)
+    pub merge: String,
+}
+
+/// looks up `branch.<name>.remote` and `branch.<name>.merge`
+pub fn get_branch_upstream(
+    repo_path: &str,
+    branch: &str,
+) -> Result<BranchUpstream> {
+    scope_time!("get_branch_upstream");
+
+    let repo = utils::repo(repo_path)?;
+    let config = repo.config()?;
+
+    let remote = config
+        .get_string(&format!("branch.{}.remote", branch))
+        .map_err(|_| Error::NoUpstream(branch.to_string()))?;
+    let merge = config
+        .get_string(&format!("branch.{}.merge", branch))
+        .map_err(|_| Error::NoUpstream(branch.to_string()))?;
+
+    Ok(BranchUpstream { remote, merge })
+}
+
 /// Modify HEAD to point to a branch then checkout head, does not work if there are uncommitted changes
 pub fn checkout_branch(
     repo_path: &str,
@@ -118,6 +176,19 @@ pub fn checkout_branch(
 ) -> Result<()> {
     scope_time!("checkout_branch");
 
+    let checked_out =
+        get_worktrees(repo_path)?.into_iter().find(|wt| {
+            !wt.is_current
+                && wt.branch.as_deref()
+                    == branch_ref.strip_prefix("refs/heads/")
+        });
+    if let Some(worktree) = checked_out {
+        return Err(Error::Generic(format!(
+            "Cannot change branch. It is already checked out in the worktree at `{}`.",
+            worktree.path
+        )));
+    }
+
     // This defaults to a safe checkout, so don't delete anything that
     // hasn't been committed or stashed, in this case it will Err
     let repo = utils::repo(repo_path)?;
@@ -170,12 +241,20 @@ pub fn rename_branch(
     branch_ref: &str,
     new_name: &str,
 ) -> Result<()> {
-    scope_time!("delete_branch");
+    scope_time!("rename_branch");
 
     let repo = utils::repo(repo_path)?;
     let branch_as_ref = repo.find_reference(branch_ref)?;
     let mut branch = git2::Branch::wrap(branch_as_ref);
-    branch.rename(new_name, true)?;
+
+    if branch.name()? == Some(new_name) {
+        return Ok(());
+    }
+    if repo.find_branch(new_name, BranchType::Local).is_ok() {
+        return Err(Error::BranchExists(new_name.to_string()));
+    }
+
+    branch.rename(new_name, false)?;
 
     Ok(())
 }
@@ -200,7 +279,9 @@ pub fn create_branch(repo_path: &str, name: &str) -> Result<()> {
 #[cfg(test)]
 mod tests_branch_name {
     use super::*;
-    use crate::sync::tests::{repo_init, repo_init_empty};
+    use crate::sync::tests::{
+        repo_init, repo_init_bare, repo_init_empty,
+    };
 
     #[test]
     fn test_smoke() {
@@ -214,6 +295,27 @@ mod tests_branch_name {
         );
     }
 
+    #[test]
+    fn test_bare_repo() {
+        let (td, repo) = repo_init_bare().unwrap();
+        let repo_path = td.path().to_str().unwrap();
+
+        let sig = git2::Signature::now("name", "email").unwrap();
+        let tree_id =
+            repo.treebuilder(None).unwrap().write().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "initial", &tree, &[])
+            .unwrap();
+
+        assert_eq!(
+            get_branch_name(repo_path).unwrap().as_str(),
+            "master"
+        );
+        assert!(crate::cached::BranchName::new(repo_path)
+            .lookup()
+            .is_ok());
+    }
+
     #[test]
     fn test_empty_repo() {
         let (_td, repo) = repo_init_empty().unwrap();
@@ -287,6 +389,79 @@ mod tests_branches {
     }
 }
 
+#[cfg(test)]
+mod tests_branch_upstream {
+    use super::*;
+    use crate::sync::{commit, tests::repo_init};
+
+    #[test]
+    fn test_no_upstream() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert!(matches!(
+            get_branch_upstream(repo_path, "master"),
+            Err(Error::NoUpstream(_))
+        ));
+    }
+
+    #[test]
+    fn test_unpushed_commits() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert!(matches!(
+            get_unpushed_commits(repo_path),
+            Err(Error::NoUpstream(_))
+        ));
+
+        let head = repo.head().unwrap().target().unwrap();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/master", head, false, "")
+            .unwrap();
+        repo.find_branch("master", BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("origin/master"))
+            .unwrap();
+
+        assert!(get_unpushed_commits(repo_path).unwrap().is_empty());
+
+        let first = commit(repo_path, "first").unwrap();
+        let second = commit(repo_path, "second").unwrap();
+
+        assert_eq!(
+            get_unpushed_commits(repo_path).unwrap(),
+            vec![second, first]
+        );
+    }
+
+    #[test]
+    fn test_upstream() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        {
+            let mut config = repo.config().unwrap();
+            config.set_str("branch.master.remote", "origin").unwrap();
+            config
+                .set_str("branch.master.merge", "refs/heads/main")
+                .unwrap();
+        }
+
+        assert_eq!(
+            get_branch_upstream(repo_path, "master").unwrap(),
+            BranchUpstream {
+                remote: String::from("origin"),
+                merge: String::from("refs/heads/main"),
+            }
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests_checkout {
     use super::*;
@@ -412,4 +587,37 @@ mod test_rename_branch {
             "AnotherName"
         );
     }
+
+    #[test]
+    fn test_rename_branch_exists() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        create_branch(repo_path, "branch1").unwrap();
+        let head = get_head_repo(&repo).unwrap();
+
+        let res =
+            rename_branch(repo_path, "refs/heads/branch1", "master");
+        assert!(
+            matches!(res, Err(Error::BranchExists(name)) if name == "master")
+        );
+
+        // both are still there
+        assert!(repo
+            .find_branch("master", BranchType::Local)
+            .is_ok());
+        assert_eq!(
+            repo.find_branch("branch1", BranchType::Local)
+                .unwrap()
+                .get()
+                .target(),
+            Some(head.into())
+        );
+
+        // keeping the name is no error
+        rename_branch(repo_path, "refs/heads/branch1", "branch1")
+            .unwrap();
+        assert_eq!(get_branch_name(repo_path).unwrap(), "branch1");
+    }
 }
@@ -0,0 +1,372 @@
+//! cherry-picking a sequence of commits onto `HEAD`
+
+use super::{
+    commit::{commit_on_head, signature_allow_undefined_name},
+    sign::Signer,
+    utils::repo,
+    CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{
+    Commit, Repository, RepositoryState, ResetType, StatusOptions,
+};
+use scopetime::scope_time;
+
+/// commits cherry-picked one after the other, oldest first
+#[derive(Debug, Clone, PartialEq)]
+pub struct CherryPickSequence {
+    /// `HEAD` before the first pick, restored on abort
+    pub original_head: CommitId,
+    /// all commits to apply, oldest first
+    pub commits: Vec<CommitId>,
+    /// amount of `commits` handled so far (applied or skipped)
+    pub done: usize,
+    /// commits skipped because their changes were applied already
+    pub skipped: Vec<CommitId>,
+    /// commit the sequence stopped on, its conflicts are
+    /// left in the index to be resolved
+    pub conflict: Option<CommitId>,
+}
+
+impl CherryPickSequence {
+    /// starts a sequence on the current `HEAD`, fails if another
+    /// operation (merge, rebase..) is in progress or there are
+    /// uncommitted changes an abort would throw away
+    pub fn new(
+        repo_path: &str,
+        commits: Vec<CommitId>,
+    ) -> Result<Self> {
+        let repo = repo(repo_path)?;
+
+        if repo.state() != RepositoryState::Clean {
+            return Err(Error::Generic(format!(
+                "repository is busy: {:?}",
+                repo.state()
+            )));
+        }
+
+        let changes = repo
+            .statuses(Some(
+                StatusOptions::new().include_ignored(false),
+            ))?
+            .len();
+        if changes > 0 {
+            return Err(Error::Generic(format!(
+                "cannot cherry-pick, there are {} uncommitted changes",
+                changes
+            )));
+        }
+
+        let head = repo.head()?.peel_to_commit()?.id();
+
+        Ok(Self {
+            original_head: head.into(),
+            commits,
+            done: 0,
+            skipped: Vec::new(),
+            conflict: None,
+        })
+    }
+
+    /// all commits are handled
+    pub fn is_finished(&self) -> bool {
+        self.conflict.is_none() && self.done >= self.commits.len()
+    }
+
+    /// amount of commits that got a new commit on `HEAD`
+    pub fn applied(&self) -> usize {
+        self.done.saturating_sub(self.skipped.len())
+    }
+}
+
+/// what happened to a single commit of a sequence
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PickOutcome {
+    /// committed on top of `HEAD`
+    Applied,
+    /// its changes are part of `HEAD` already
+    Skipped,
+    /// stopped, the conflicts have to be resolved first
+    Conflict,
+}
+
+/// applies the next commit of `seq`,
+/// `None` if it is finished or stopped on a conflict
+pub fn cherry_pick_next(
+    repo_path: &str,
+    seq: &mut CherryPickSequence,
+) -> Result<Option<PickOutcome>> {
+    scope_time!("cherry_pick_next");
+
+    if seq.conflict.is_some() {
+        return Ok(None);
+    }
+
+    let id = match seq.commits.get(seq.done) {
+        Some(id) => *id,
+        None => return Ok(None),
+    };
+
+    let repo = repo(repo_path)?;
+    let commit = repo.find_commit(id.into())?;
+    repo.cherrypick(&commit, None)?;
+
+    if repo.index()?.has_conflicts() {
+        seq.conflict = Some(id);
+        return Ok(Some(PickOutcome::Conflict));
+    }
+
+    let outcome = commit_picked(&repo, &commit)?;
+    finish_pick(seq, id, outcome);
+
+    Ok(Some(outcome))
+}
+
+/// commits the resolved conflict of a stopped sequence,
+/// after that `cherry_pick_next` carries on with the rest
+pub fn cherry_pick_continue(
+    repo_path: &str,
+    seq: &mut CherryPickSequence,
+) -> Result<PickOutcome> {
+    scope_time!("cherry_pick_continue");
+
+    let id = seq.conflict.ok_or_else(|| {
+        Error::Generic(String::from("no conflict to continue from"))
+    })?;
+
+    let repo = repo(repo_path)?;
+    if repo.index()?.has_conflicts() {
+        return Err(Error::Generic(String::from(
+            "resolve and stage all conflicts first",
+        )));
+    }
+
+    let commit = repo.find_commit(id.into())?;
+    let outcome = commit_picked(&repo, &commit)?;
+    seq.conflict = None;
+    finish_pick(seq, id, outcome);
+
+    Ok(outcome)
+}
+
+/// drops a stopped (or finished) sequence and moves `HEAD` back to
+/// where it was before the first pick
+pub fn cherry_pick_abort(
+    repo_path: &str,
+    seq: &CherryPickSequence,
+) -> Result<()> {
+    scope_time!("cherry_pick_abort");
+
+    let repo = repo(repo_path)?;
+    let original =
+        repo.find_commit(seq.original_head.into())?.into_object();
+
+    repo.cleanup_state()?;
+    repo.reset(&original, ResetType::Hard, None)?;
+
+    Ok(())
+}
+
+fn finish_pick(
+    seq: &mut CherryPickSequence,
+    id: CommitId,
+    outcome: PickOutcome,
+) {
+    if outcome == PickOutcome::Skipped {
+        seq.skipped.push(id);
+    }
+    seq.done += 1;
+}
+
+/// commits the index as `picked` (keeping its author and message),
+/// nothing is committed if the index matches `HEAD` already
+fn commit_picked(
+    repo: &Repository,
+    picked: &Commit,
+) -> Result<PickOutcome> {
+    let tree_id = repo.index()?.write_tree()?;
+    let head = repo.head()?.peel_to_commit()?;
+
+    if head.tree_id() != tree_id {
+        let tree = repo.find_tree(tree_id)?;
+        let signature = signature_allow_undefined_name(repo)?;
+
+        commit_on_head(
+            repo,
+            Signer::for_commits(repo)?.as_ref(),
+            &picked.author(),
+            &signature,
+            picked.message().unwrap_or_default(),
+            &tree,
+            &[&head],
+        )?;
+    }
+
+    repo.cleanup_state()?;
+
+    Ok(if head.tree_id() == tree_id {
+        PickOutcome::Skipped
+    } else {
+        PickOutcome::Applied
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, get_commits_info, get_head, stage_add_file,
+        tests::{commit_file, repo_init},
+    };
+    use std::{fs, path::Path};
+
+    /// creates branch `topic` at `HEAD`
+    fn fork(repo: &Repository) {
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("topic", &head, false).unwrap();
+    }
+
+    fn head_subjects(repo_path: &str, count: usize) -> Vec<String> {
+        let repo = repo(repo_path).unwrap();
+        let mut walk = repo.revwalk().unwrap();
+        walk.push_head().unwrap();
+        let ids = walk
+            .take(count)
+            .map(|id| id.unwrap().into())
+            .collect::<Vec<CommitId>>();
+
+        get_commits_info(repo_path, &ids, 100)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_sequence_skips_applied() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        fork(&repo);
+
+        commit_file(repo_path, "b.txt", "b\n", "b on master");
+
+        checkout_branch(repo_path, "refs/heads/topic").unwrap();
+        let ids = vec![
+            commit_file(repo_path, "a.txt", "a\n", "a"),
+            commit_file(repo_path, "b.txt", "b\n", "b"),
+            commit_file(repo_path, "c.txt", "c\n", "c"),
+        ];
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+        let mut seq =
+            CherryPickSequence::new(repo_path, ids.clone()).unwrap();
+        let mut outcomes = Vec::new();
+        while let Some(outcome) =
+            cherry_pick_next(repo_path, &mut seq).unwrap()
+        {
+            outcomes.push(outcome);
+        }
+
+        assert_eq!(
+            outcomes,
+            vec![
+                PickOutcome::Applied,
+                PickOutcome::Skipped,
+                PickOutcome::Applied
+            ]
+        );
+        assert!(seq.is_finished());
+        assert_eq!(seq.applied(), 2);
+        assert_eq!(seq.skipped, vec![ids[1]]);
+        assert_eq!(
+            head_subjects(repo_path, 3),
+            ["c", "a", "b on master"]
+        );
+        assert_eq!(repo.state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_sequence_conflict() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        fork(&repo);
+
+        let original =
+            commit_file(repo_path, "x.txt", "master\n", "x");
+
+        checkout_branch(repo_path, "refs/heads/topic").unwrap();
+        let ids = vec![
+            commit_file(repo_path, "a.txt", "a\n", "a"),
+            commit_file(repo_path, "x.txt", "topic\n", "x on topic"),
+            commit_file(repo_path, "c.txt", "c\n", "c"),
+        ];
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+        let mut seq =
+            CherryPickSequence::new(repo_path, ids.clone()).unwrap();
+        cherry_pick_next(repo_path, &mut seq).unwrap();
+        assert_eq!(
+            cherry_pick_next(repo_path, &mut seq).unwrap(),
+            Some(PickOutcome::Conflict)
+        );
+        assert_eq!(seq.conflict, Some(ids[1]));
+        assert_eq!(seq.applied(), 1);
+        assert_eq!(
+            cherry_pick_next(repo_path, &mut seq).unwrap(),
+            None
+        );
+        assert!(cherry_pick_continue(repo_path, &mut seq).is_err());
+
+        // resolve and carry on
+        let mut resolved = seq.clone();
+        fs::write(root.join("x.txt"), "both\n").unwrap();
+        stage_add_file(repo_path, Path::new("x.txt")).unwrap();
+        assert_eq!(
+            cherry_pick_continue(repo_path, &mut resolved).unwrap(),
+            PickOutcome::Applied
+        );
+        assert_eq!(
+            cherry_pick_next(repo_path, &mut resolved).unwrap(),
+            Some(PickOutcome::Applied)
+        );
+        assert!(resolved.is_finished());
+        assert_eq!(
+            head_subjects(repo_path, 4),
+            ["c", "x on topic", "a", "x"]
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("x.txt")).unwrap(),
+            "both\n"
+        );
+
+        cherry_pick_abort(repo_path, &seq).unwrap();
+        assert_eq!(get_head(repo_path).unwrap(), original);
+        assert_eq!(repo.state(), RepositoryState::Clean);
+        assert!(!root.join("a.txt").exists());
+    }
+
+    #[test]
+    fn test_sequence_refuses_uncommitted_changes() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        fork(&repo);
+
+        commit_file(repo_path, "x.txt", "x\n", "x");
+
+        checkout_branch(repo_path, "refs/heads/topic").unwrap();
+        let ids = vec![commit_file(repo_path, "a.txt", "a\n", "a")];
+        checkout_branch(repo_path, "refs/heads/master").unwrap();
+
+        // an abort would reset this away
+        fs::write(root.join("x.txt"), "unstaged\n").unwrap();
+
+        assert!(CherryPickSequence::new(repo_path, ids).is_err());
+        assert_eq!(
+            fs::read_to_string(root.join("x.txt")).unwrap(),
+            "unstaged\n"
+        );
+    }
+}
@@ -1,9 +1,18 @@
-use super::{get_head, utils::repo, CommitId};
-use crate::error::Result;
-use git2::{ErrorCode, ObjectType, Repository, Signature};
+use super::{
+    get_head,
+    sign::{signature_header, Signer},
+    utils::repo,
+    CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{
+    Commit, ErrorCode, ObjectType, Oid, Reference, Repository,
+    Signature, Tree,
+};
 use scopetime::scope_time;
 
-///
+/// replaces the commit `id` (`HEAD`) with one of the index and
+/// `msg`, signed if `commit.gpgsign` is set
 pub fn amend(
     repo_path: &str,
     id: CommitId,
@@ -11,6 +20,26 @@ pub fn amend(
 ) -> Result<CommitId> {
     scope_time!("amend");
 
+    amend_with(repo_path, id, msg, true)
+}
+
+/// like `amend` but never signs
+pub fn amend_unsigned(
+    repo_path: &str,
+    id: CommitId,
+    msg: &str,
+) -> Result<CommitId> {
+    scope_time!("amend_unsigned");
+
+    amend_with(repo_path, id, msg, false)
+}
+
+fn amend_with(
+    repo_path: &str,
+    id: CommitId,
+    msg: &str,
+    sign: bool,
+) -> Result<CommitId> {
     let repo = repo(repo_path)?;
     let commit = repo.find_commit(id.into())?;
 
@@ -18,14 +47,40 @@ pub fn amend(
     let tree_id = index.write_tree()?;
     let tree = repo.find_tree(tree_id)?;
 
-    let new_id = commit.amend(
-        Some("HEAD"),
-        None,
-        None,
-        None,
-        Some(msg),
-        Some(&tree),
-    )?;
+    let signer = if sign {
+        Signer::for_commits(&repo)?
+    } else {
+        None
+    };
+
+    let new_id = match signer {
+        Some(signer) => {
+            let parents = commit.parents().collect::<Vec<_>>();
+            let id = commit_signed(
+                &repo,
+                &signer,
+                &commit.author(),
+                &commit.committer(),
+                msg,
+                &tree,
+                &parents.iter().collect::<Vec<_>>(),
+            )?;
+            move_head(
+                &repo,
+                id,
+                &format!("commit (amend): {}", msg),
+            )?;
+            id
+        }
+        None => commit.amend(
+            Some("HEAD"),
+            None,
+            None,
+            None,
+            Some(msg),
+            Some(&tree),
+        )?,
+    };
 
     Ok(CommitId::new(new_id))
 }
@@ -33,7 +88,7 @@ pub fn amend(
 /// Wrap Repository::signature to allow unknown user.name.
 ///
 /// See <https://github.com/extrawurst/gitui/issues/79>.
-fn signature_allow_undefined_name(
+pub(crate) fn signature_allow_undefined_name(
     repo: &Repository,
 ) -> std::result::Result<Signature<'_>, git2::Error> {
     match repo.signature() {
@@ -49,10 +104,29 @@ fn signature_allow_undefined_name(
     }
 }
 
-/// this does not run any git hooks
+/// this does not run any git hooks, signs the commit if
+/// `commit.gpgsign` is set
 pub fn commit(repo_path: &str, msg: &str) -> Result<CommitId> {
     scope_time!("commit");
 
+    commit_with(repo_path, msg, true)
+}
+
+/// like `commit` but never signs
+pub fn commit_unsigned(
+    repo_path: &str,
+    msg: &str,
+) -> Result<CommitId> {
+    scope_time!("commit_unsigned");
+
+    commit_with(repo_path, msg, false)
+}
+
+fn commit_with(
+    repo_path: &str,
+    msg: &str,
+    sign: bool,
+) -> Result<CommitId> {
     let repo = repo(repo_path)?;
 
     let signature = signature_allow_undefined_name(&repo)?;
@@ -68,19 +142,92 @@ pub fn commit(repo_path: &str, msg: &str) -> Result<CommitId> {
 
     let parents = parents.iter().collect::<Vec<_>>();
 
-    Ok(repo
-        .commit(
+    let signer = if sign {
+        Signer::for_commits(&repo)?
+    } else {
+        None
+    };
+
+    Ok(commit_on_head(
+        &repo,
+        signer.as_ref(),
+        &signature,
+        &signature,
+        msg,
+        &tree,
+        parents.as_slice(),
+    )?
+    .into())
+}
+
+/// commits onto `HEAD` like `Repository::commit` does, signed by
+/// `signer` if given
+pub(crate) fn commit_on_head(
+    repo: &Repository,
+    signer: Option<&Signer>,
+    author: &Signature,
+    committer: &Signature,
+    msg: &str,
+    tree: &Tree,
+    parents: &[&Commit],
+) -> Result<Oid> {
+    match signer {
+        Some(signer) => {
+            let id = commit_signed(
+                repo, signer, author, committer, msg, tree, parents,
+            )?;
+            move_head(repo, id, &format!("commit: {}", msg))?;
+            Ok(id)
+        }
+        None => Ok(repo.commit(
             Some("HEAD"),
-            &signature,
-            &signature,
+            author,
+            committer,
             msg,
-            &tree,
-            parents.as_slice(),
-        )?
-        .into())
+            tree,
+            parents,
+        )?),
+    }
+}
+
+/// writes the commit signed by `signer` without moving any ref
+fn commit_signed(
+    repo: &Repository,
+    signer: &Signer,
+    author: &Signature,
+    committer: &Signature,
+    msg: &str,
+    tree: &Tree,
+    parents: &[&Commit],
+) -> Result<Oid> {
+    let buffer = repo.commit_create_buffer(
+        author, committer, msg, tree, parents,
+    )?;
+    let content = buffer.as_str().ok_or_else(|| {
+        Error::Generic(String::from("commit is not valid utf8"))
+    })?;
+    let signature = signer.sign(&buffer, committer)?;
+
+    Ok(repo.commit_signed(content, &signature, None)?)
+}
+
+/// points `HEAD`, or the branch it is on, at `id`
+fn move_head(repo: &Repository, id: Oid, msg: &str) -> Result<()> {
+    let head = repo.find_reference("HEAD")?;
+    let reflog = msg.lines().next().unwrap_or_default();
+
+    match head.symbolic_target() {
+        Some(branch) => {
+            repo.reference(branch, id, true, reflog)?;
+        }
+        None => repo.set_head_detached(id)?,
+    }
+
+    Ok(())
 }
 
-/// Tag a commit.
+/// Tag a commit, annotated with `message`, signed if `tag.gpgSign`
+/// is set.
 ///
 /// This function will return an `Err(…)` variant if the tag’s name is refused
 /// by git or if the tag already exists.
@@ -88,9 +235,32 @@ pub fn tag(
     repo_path: &str,
     commit_id: &CommitId,
     tag: &str,
+    message: &str,
 ) -> Result<CommitId> {
     scope_time!("tag");
 
+    tag_with(repo_path, commit_id, tag, message, true)
+}
+
+/// like `tag` but never signs
+pub fn tag_unsigned(
+    repo_path: &str,
+    commit_id: &CommitId,
+    tag: &str,
+    message: &str,
+) -> Result<CommitId> {
+    scope_time!("tag_unsigned");
+
+    tag_with(repo_path, commit_id, tag, message, false)
+}
+
+fn tag_with(
+    repo_path: &str,
+    commit_id: &CommitId,
+    tag: &str,
+    message: &str,
+    sign: bool,
+) -> Result<CommitId> {
     let repo = repo(repo_path)?;
 
     let signature = signature_allow_undefined_name(&repo)?;
@@ -98,13 +268,66 @@ pub fn tag(
     let target =
         repo.find_object(object_id, Some(ObjectType::Commit))?;
 
-    Ok(repo.tag(tag, &target, &signature, "", false)?.into())
+    let signer = if sign { Signer::for_tags(&repo)? } else { None };
+
+    match signer {
+        Some(signer) => Ok(tag_signed(
+            &repo, &signer, object_id, tag, &signature, message,
+        )?
+        .into()),
+        None => Ok(repo
+            .tag(tag, &target, &signature, message, false)?
+            .into()),
+    }
+}
+
+/// writes the annotated tag object signed by `signer` and its ref
+fn tag_signed(
+    repo: &Repository,
+    signer: &Signer,
+    target: Oid,
+    tag: &str,
+    tagger: &Signature,
+    message: &str,
+) -> Result<Oid> {
+    let refname = format!("refs/tags/{}", tag);
+    if !Reference::is_valid_name(&refname) {
+        return Err(Error::Generic(format!(
+            "invalid tag name `{}`",
+            tag
+        )));
+    }
+    // before asking for a passphrase in vain
+    if repo.refname_to_id(&refname).is_ok() {
+        return Err(Error::Generic(format!(
+            "tag `{}` already exists",
+            tag
+        )));
+    }
+
+    let mut buffer = format!(
+        "object {}\ntype commit\ntag {}\ntagger {}\n\n{}",
+        target,
+        tag,
+        signature_header(tagger),
+        message
+    );
+    if !buffer.ends_with('\n') {
+        buffer.push('\n');
+    }
+    let signature = signer.sign(buffer.as_bytes(), tagger)?;
+    buffer.push_str(&signature);
+
+    let id = repo.odb()?.write(ObjectType::Tag, buffer.as_bytes())?;
+    repo.reference(&refname, id, false, "")?;
+
+    Ok(id)
 }
 
 #[cfg(test)]
 mod tests {
 
-    use crate::error::Result;
+    use crate::error::{Error, Result};
     use crate::sync::{
         commit, get_commit_details, get_commit_files, stage_add_file,
         tags::get_tags,
@@ -112,9 +335,14 @@ mod tests {
         utils::get_head,
         LogWalker,
     };
-    use commit::{amend, tag};
-    use git2::Repository;
-    use std::{fs::File, io::Write, path::Path};
+    use commit::{amend, commit_unsigned, tag};
+    use git2::{ObjectType, Repository};
+    use std::{
+        fs::{self, File},
+        io::Write,
+        path::Path,
+        process::{Command, Stdio},
+    };
 
     fn count_commits(repo: &Repository, max: usize) -> usize {
         let mut items = Vec::new();
@@ -222,21 +450,25 @@ mod tests {
 
         let new_id = commit(repo_path, "commit msg")?;
 
-        tag(repo_path, &new_id, "tag")?;
+        let tag_id = tag(repo_path, &new_id, "tag", "release\n")?;
+        assert_eq!(
+            repo.find_tag(tag_id.into())?.message(),
+            Some("release\n")
+        );
 
         assert_eq!(
             get_tags(repo_path).unwrap()[&new_id],
             vec!["tag"]
         );
 
-        assert!(matches!(tag(repo_path, &new_id, "tag"), Err(_)));
+        assert!(tag(repo_path, &new_id, "tag", "").is_err());
 
         assert_eq!(
             get_tags(repo_path).unwrap()[&new_id],
             vec!["tag"]
         );
 
-        tag(repo_path, &new_id, "second-tag")?;
+        tag(repo_path, &new_id, "second-tag", "")?;
 
         assert_eq!(
             get_tags(repo_path).unwrap()[&new_id],
@@ -245,4 +477,147 @@ mod tests {
 
         Ok(())
     }
+
+    /// checks `signature` over `data` with ssh-keygen against the
+    /// public key of `key`
+    fn ssh_verify(key: &Path, data: &[u8], signature: &[u8]) -> bool {
+        let dir = key.parent().unwrap();
+        let public_key =
+            fs::read_to_string(key.with_extension("pub")).unwrap();
+        fs::write(
+            dir.join("allowed_signers"),
+            format!("signer {}", public_key),
+        )
+        .unwrap();
+        fs::write(dir.join("data.sig"), signature).unwrap();
+
+        let mut child = Command::new("ssh-keygen")
+            .args(&["-Y", "verify", "-I", "signer", "-n", "git"])
+            .arg("-f")
+            .arg(dir.join("allowed_signers"))
+            .arg("-s")
+            .arg(dir.join("data.sig"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(data).unwrap();
+        child.wait().unwrap().success()
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_ssh_signed() -> Result<()> {
+        let (td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let key = td.path().join(".git").join("signing_key");
+        let keygen = Command::new("ssh-keygen")
+            .args(&["-q", "-t", "ed25519", "-N", "", "-f"])
+            .arg(&key)
+            .status()?;
+        assert!(keygen.success());
+
+        let mut config = repo.config()?;
+        config.set_str("gpg.format", "ssh")?;
+        config.set_str("user.signingkey", key.to_str().unwrap())?;
+        config.set_bool("commit.gpgsign", true)?;
+        config.set_bool("tag.gpgsign", true)?;
+
+        File::create(&root.join("a.txt"))?.write_all(b"a")?;
+        stage_add_file(repo_path, Path::new("a.txt"))?;
+        let id = commit(repo_path, "signed")?;
+        assert_eq!(get_head(repo_path)?, id);
+        assert_eq!(count_commits(&repo, 10), 2);
+
+        let (signature, data) =
+            repo.extract_signature(&id.into(), None)?;
+        assert!(ssh_verify(&key, &data, &signature));
+        assert!(!ssh_verify(&key, b"tampered", &signature));
+
+        let amended = amend(repo_path, id, "amended")?;
+        assert_eq!(get_head(repo_path)?, amended);
+        assert_eq!(count_commits(&repo, 10), 2);
+        let (signature, data) =
+            repo.extract_signature(&amended.into(), None)?;
+        assert!(ssh_verify(&key, &data, &signature));
+        assert_eq!(
+            repo.find_commit(amended.into())?.message(),
+            Some("amended")
+        );
+
+        let tag_id = tag(repo_path, &amended, "v1", "release")?;
+        let odb = repo.odb()?;
+        let object = odb.read(tag_id.into())?;
+        assert_eq!(object.kind(), ObjectType::Tag);
+        let content = String::from_utf8_lossy(object.data());
+        let start =
+            content.find("-----BEGIN SSH SIGNATURE-----").unwrap();
+        assert!(ssh_verify(
+            &key,
+            content[..start].as_bytes(),
+            content[start..].as_bytes()
+        ));
+        let tag_object = repo.find_tag(tag_id.into())?;
+        assert_eq!(tag_object.name(), Some("v1"));
+        assert_eq!(tag_object.target_id(), amended.into());
+        assert!(tag_object
+            .message()
+            .unwrap()
+            .starts_with("release\n"));
+        assert_eq!(get_tags(repo_path)?[&amended], vec!["v1"]);
+
+        // no second tag of the same name
+        assert!(tag(repo_path, &amended, "v1", "again").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_sign_failure() -> Result<()> {
+        let (td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let program = td.path().join(".git").join("fake-gpg");
+        fs::write(
+            &program,
+            "#!/bin/sh\necho '[GNUPG:] KEY_CONSIDERED' >&2\necho \"gpg: skipped \\\"$3\\\": No secret key\" >&2\nexit 2\n",
+        )?;
+        Command::new("chmod").arg("+x").arg(&program).status()?;
+
+        let mut config = repo.config()?;
+        config.set_str("gpg.program", program.to_str().unwrap())?;
+        config.set_bool("commit.gpgsign", true)?;
+        let head = get_head(repo_path)?;
+
+        File::create(&root.join("a.txt"))?.write_all(b"a")?;
+        stage_add_file(repo_path, Path::new("a.txt"))?;
+
+        let res = commit(repo_path, "signed");
+        assert!(
+            matches!(
+                &res,
+                Err(Error::Sign(msg)) if msg.ends_with(
+                    "gpg: skipped \"name <email>\": No secret key"
+                )
+            ),
+            "{:?}",
+            res
+        );
+        assert_eq!(get_head(repo_path)?, head);
+
+        let id = commit_unsigned(repo_path, "unsigned")?;
+        assert_eq!(get_head(repo_path)?, id);
+        assert!(repo.extract_signature(&id.into(), None).is_err());
+
+        // tags are only signed with `tag.gpgSign`
+        tag(repo_path, &id, "v1", "")?;
+        assert!(repo.refname_to_id("refs/tags/v1").is_ok());
+
+        Ok(())
+    }
 }
@@ -1,4 +1,6 @@
-use super::{commits_info::get_message, utils::repo, CommitId};
+use super::{
+    commits_info::get_message, utils::repo_allow_bare, CommitId,
+};
 use crate::error::Result;
 use git2::Signature;
 use scopetime::scope_time;
@@ -77,6 +79,8 @@ pub struct CommitDetails {
     pub message: Option<CommitMessage>,
     ///
     pub hash: String,
+    ///
+    pub parents: Vec<CommitId>,
 }
 
 ///
@@ -86,7 +90,7 @@ pub fn get_commit_details(
 ) -> Result<CommitDetails> {
     scope_time!("get_commit_details");
 
-    let repo = repo(repo_path)?;
+    let repo = repo_allow_bare(repo_path)?;
 
     let commit = repo.find_commit(id.into())?;
 
@@ -106,6 +110,7 @@ pub fn get_commit_details(
         committer,
         message: Some(msg),
         hash: id.to_string(),
+        parents: commit.parent_ids().map(CommitId::from).collect(),
     };
 
     Ok(details)
@@ -1,8 +1,12 @@
-use super::{stash::is_stash_commit, utils::repo, CommitId};
+use super::{
+    stash::is_stash_commit,
+    utils::{repo, repo_allow_bare},
+    CommitId,
+};
 use crate::{
     error::Error, error::Result, StatusItem, StatusItemType,
 };
-use git2::{Diff, DiffDelta, DiffOptions, Repository};
+use git2::{Diff, DiffDelta, DiffFormat, DiffOptions, Repository};
 use scopetime::scope_time;
 
 /// get all files that are part of a commit
@@ -12,10 +16,29 @@ pub fn get_commit_files(
 ) -> Result<Vec<StatusItem>> {
     scope_time!("get_commit_files");
 
-    let repo = repo(repo_path)?;
+    let repo = repo_allow_bare(repo_path)?;
 
     let diff = get_commit_diff(&repo, id, None)?;
 
+    diff_files(&diff)
+}
+
+/// files that differ between the trees of the commits `old` and
+/// `new`
+pub fn get_compare_commits_files(
+    repo_path: &str,
+    old: CommitId,
+    new: CommitId,
+) -> Result<Vec<StatusItem>> {
+    scope_time!("get_compare_commits_files");
+
+    let repo = repo(repo_path)?;
+    let diff = get_compare_commits_diff(&repo, old, new, None)?;
+
+    diff_files(&diff)
+}
+
+fn diff_files(diff: &Diff) -> Result<Vec<StatusItem>> {
     let mut res = Vec::new();
 
     diff.foreach(
@@ -38,6 +61,72 @@ pub fn get_commit_files(
     Ok(res)
 }
 
+/// unified diff of a commit against its first parent
+/// (the empty tree for a root commit), binary files only
+/// get the `Binary files .. differ` line
+pub fn get_commit_diff_text(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<String> {
+    scope_time!("get_commit_diff_text");
+
+    diff_text(repo_path, id, None)
+}
+
+/// like `get_commit_diff_text` but only the changes to `path`
+pub fn get_commit_file_diff_text(
+    repo_path: &str,
+    id: CommitId,
+    path: &str,
+) -> Result<String> {
+    scope_time!("get_commit_file_diff_text");
+
+    diff_text(repo_path, id, Some(path.to_string()))
+}
+
+fn diff_text(
+    repo_path: &str,
+    id: CommitId,
+    pathspec: Option<String>,
+) -> Result<String> {
+    let repo = repo(repo_path)?;
+    let diff = get_commit_diff(&repo, id, pathspec)?;
+
+    let mut text = String::new();
+    diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            text.push(line.origin());
+        }
+        text.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })?;
+
+    Ok(text)
+}
+
+/// changes from the tree of `old` to the one of `new`
+pub(crate) fn get_compare_commits_diff(
+    repo: &Repository,
+    old: CommitId,
+    new: CommitId,
+    pathspec: Option<String>,
+) -> Result<Diff<'_>> {
+    let old_tree = repo.find_commit(old.into())?.tree()?;
+    let new_tree = repo.find_commit(new.into())?.tree()?;
+
+    let mut opts = DiffOptions::new();
+    if let Some(p) = pathspec {
+        opts.pathspec(p);
+        opts.show_binary(true);
+    }
+
+    Ok(repo.diff_tree_to_tree(
+        Some(&old_tree),
+        Some(&new_tree),
+        Some(&mut opts),
+    )?)
+}
+
 ///
 pub(crate) fn get_commit_diff(
     repo: &Repository,
@@ -90,11 +179,15 @@ pub(crate) fn get_commit_diff(
 
 #[cfg(test)]
 mod tests {
-    use super::get_commit_files;
+    use super::{
+        get_commit_diff_text, get_commit_file_diff_text,
+        get_commit_files, get_compare_commits_files,
+    };
     use crate::{
         error::Result,
         sync::{
-            commit, stage_add_file, stash_save,
+            commit, get_diff_commits, stage_add_file,
+            stage_addremoved, stash_save,
             tests::{get_statuses, repo_init},
         },
         StatusItemType,
@@ -171,4 +264,110 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_diff_text() -> Result<()> {
+        let file_path = Path::new("file1.txt");
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(&root.join(file_path))?.write_all(b"a\nb\n")?;
+        stage_add_file(repo_path, file_path)?;
+        let first = commit(repo_path, "c1")?;
+
+        File::create(&root.join(file_path))?.write_all(b"a\nc\n")?;
+        stage_add_file(repo_path, file_path)?;
+        let second = commit(repo_path, "c2")?;
+
+        let diff = get_commit_diff_text(repo_path, second)?;
+        assert!(
+            diff.starts_with("diff --git a/file1.txt b/file1.txt\n")
+        );
+        assert!(diff.ends_with("@@ -1,2 +1,2 @@\n a\n-b\n+c\n"));
+
+        // root commit diffs against the empty tree
+        let diff = get_commit_diff_text(repo_path, first)?;
+        assert!(diff.contains("new file mode 100644\n"));
+        assert!(diff.ends_with("@@ -0,0 +1,2 @@\n+a\n+b\n"));
+
+        File::create(&root.join("bin"))?.write_all(b"\0\x01")?;
+        stage_add_file(repo_path, Path::new("bin"))?;
+        let binary = commit(repo_path, "c3")?;
+
+        let diff = get_commit_diff_text(repo_path, binary)?;
+        assert!(diff
+            .ends_with("Binary files /dev/null and b/bin differ\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_diff_text() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        for name in &["a.txt", "b.txt"] {
+            File::create(&root.join(name))?
+                .write_all(format!("{}\n", name).as_bytes())?;
+            stage_add_file(repo_path, Path::new(name))?;
+        }
+        let id = commit(repo_path, "both")?;
+
+        let diff = get_commit_file_diff_text(repo_path, id, "b.txt")?;
+        assert!(diff.starts_with("diff --git a/b.txt b/b.txt\n"));
+        assert!(diff.ends_with("@@ -0,0 +1 @@\n+b.txt\n"));
+        assert!(!diff.contains("a.txt"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare_commits_files() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        let write = |name: &str, content: &str| -> Result<()> {
+            File::create(&root.join(name))?
+                .write_all(content.as_bytes())?;
+            stage_add_file(repo_path, Path::new(name))
+        };
+
+        write("a.txt", "a\n")?;
+        write("b.txt", "b\n")?;
+        let old = commit(repo_path, "old")?;
+
+        write("a.txt", "a\nmore\n")?;
+        std::fs::remove_file(root.join("b.txt"))?;
+        stage_addremoved(repo_path, Path::new("b.txt"))?;
+        commit(repo_path, "in between")?;
+
+        write("c.txt", "c\n")?;
+        let new = commit(repo_path, "new")?;
+
+        let files = get_compare_commits_files(repo_path, old, new)?
+            .into_iter()
+            .map(|item| (item.path, item.status))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            vec![
+                (String::from("a.txt"), StatusItemType::Modified),
+                (String::from("b.txt"), StatusItemType::Deleted),
+                (String::from("c.txt"), StatusItemType::New),
+            ]
+        );
+
+        // the other way round
+        let files = get_compare_commits_files(repo_path, new, old)?;
+        assert_eq!(files[2].status, StatusItemType::Deleted);
+
+        let diff =
+            get_diff_commits(repo_path, old, new, "a.txt".into())?;
+        assert_eq!(diff.hunks.len(), 1);
+        assert_eq!(diff.lines, 3);
+
+        Ok(())
+    }
 }
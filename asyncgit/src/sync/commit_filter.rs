@@ -0,0 +1,1335 @@
+//! matching commits against search terms
+
+use super::{
+    utils::repo_allow_bare, CommitId, LogWalkerFilter, Tags,
+};
+use crate::error::Result;
+use bitflags::bitflags;
+use git2::{Commit, Oid, Repository, Signature};
+use std::{
+    cell::OnceCell,
+    convert::TryFrom,
+    process::{Command, Stdio},
+    sync::Arc,
+};
+
+/// default of how many bytes of a single field (like the message)
+/// are searched, keeps huge messages from stalling the filter
+pub const DEFAULT_MAX_FIELD_LEN: usize = 64 * 1024;
+
+bitflags! {
+    /// which fields a term is matched against and how
+    pub struct FilterBy: u32 {
+        ///
+        const SHA = 0b0000_0001;
+        ///
+        const AUTHOR = 0b0000_0010;
+        ///
+        const COMMITTER = 0b0000_0100;
+        /// first line of the message
+        const MESSAGE = 0b0000_1000;
+        ///
+        const TAGS = 0b0001_0000;
+        /// invert the result of the term
+        const NOT = 0b0010_0000;
+        /// do not ignore case
+        const CASE_SENSITIVE = 0b0100_0000;
+        /// author/committer have to equal the term instead of containing it
+        const EXACT = 0b1000_0000;
+        /// message trailers, the term is `key` or `key=value`
+        const TRAILER = 0b1_0000_0000;
+        /// whole message, subject included
+        const BODY = 0b10_0000_0000;
+        /// the subject starts with the term
+        const SUBJECT_PREFIX = 0b100_0000_0000;
+        /// the commit carries a signature (gpg, ssh..), the term
+        /// text is ignored
+        const SIGNED = 0b1000_0000_0000;
+        /// the signature checks out (`git verify-commit`), a lot
+        /// slower than `SIGNED`
+        const VERIFIED = 0b1_0000_0000_0000;
+        /// one of the parents has a sha starting with the term,
+        /// matches the children of a commit
+        const PARENT = 0b10_0000_0000_0000;
+        /// the subject has more chars than the number in the term
+        const SUBJECT_LONGER = 0b100_0000_0000_0000;
+
+        /// all fields a term without explicit fields is matched
+        /// against by default, the message only by its subject
+        const EVERYWHERE = Self::SHA.bits
+            | Self::AUTHOR.bits
+            | Self::MESSAGE.bits
+            | Self::TAGS.bits;
+    }
+}
+
+/// single search term
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilterTerm {
+    /// text to look for (already lowercase unless `CASE_SENSITIVE`)
+    pub text: String,
+    ///
+    pub by: FilterBy,
+}
+
+impl FilterTerm {
+    ///
+    pub fn new(text: &str, by: FilterBy) -> Self {
+        Self {
+            text: if by.contains(FilterBy::CASE_SENSITIVE) {
+                text.to_string()
+            } else {
+                text.to_lowercase()
+            },
+            by,
+        }
+    }
+}
+
+/// boolean combination of search terms
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterQuery {
+    ///
+    Term(FilterTerm),
+    /// all have to match
+    And(Vec<FilterQuery>),
+    /// any has to match
+    Or(Vec<FilterQuery>),
+    ///
+    Not(Box<FilterQuery>),
+}
+
+impl FilterQuery {
+    /// evaluates the query using `term_matches` for the leaves
+    pub fn matches<F: Fn(&FilterTerm) -> bool>(
+        &self,
+        term_matches: &F,
+    ) -> bool {
+        match self {
+            Self::Term(term) => term_matches(term),
+            Self::And(queries) => {
+                queries.iter().all(|q| q.matches(term_matches))
+            }
+            Self::Or(queries) => {
+                queries.iter().any(|q| q.matches(term_matches))
+            }
+            Self::Not(query) => !query.matches(term_matches),
+        }
+    }
+
+    /// sums up `term_score` of all terms that are not negated,
+    /// used to order matches by relevance
+    pub fn score<F: Fn(&FilterTerm) -> usize>(
+        &self,
+        term_score: &F,
+    ) -> usize {
+        match self {
+            Self::Term(term) if term.by.contains(FilterBy::NOT) => 0,
+            Self::Term(term) => term_score(term),
+            Self::And(queries) | Self::Or(queries) => {
+                queries.iter().map(|q| q.score(term_score)).sum()
+            }
+            Self::Not(_) => 0,
+        }
+    }
+}
+
+/// `true` if the commit matches `query`,
+/// only the first `max_field_len` bytes of each field are searched
+pub fn commit_matches(
+    repo: &Repository,
+    id: &CommitId,
+    query: &FilterQuery,
+    tags: &Tags,
+    max_field_len: usize,
+) -> Result<bool> {
+    let commit = repo.find_commit((*id).into())?;
+    let trailers = OnceCell::new();
+
+    Ok(query.matches(&|term| {
+        term_matches(
+            repo,
+            &commit,
+            id,
+            term,
+            tags,
+            &trailers,
+            max_field_len,
+        )
+    }))
+}
+
+/// amount of fields of the commit matched by the terms of `query`
+pub fn commit_score(
+    repo: &Repository,
+    id: &CommitId,
+    query: &FilterQuery,
+    tags: &Tags,
+    max_field_len: usize,
+) -> Result<usize> {
+    let commit = repo.find_commit((*id).into())?;
+    let trailers = OnceCell::new();
+
+    Ok(query.score(&|term| {
+        fields_matched(
+            repo,
+            &commit,
+            id,
+            term,
+            tags,
+            &trailers,
+            max_field_len,
+        )
+    }))
+}
+
+/// `ids` ordered by `commit_score`, highest first,
+/// commits with the same score keep their order
+pub fn sort_by_relevance(
+    repo_path: &str,
+    ids: &[CommitId],
+    query: &FilterQuery,
+    tags: &Tags,
+    max_field_len: usize,
+) -> Result<Vec<CommitId>> {
+    let repo = repo_allow_bare(repo_path)?;
+
+    let mut scored = ids
+        .iter()
+        .map(|id| {
+            let score =
+                commit_score(&repo, id, query, tags, max_field_len)?;
+            Ok((score, *id))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    Ok(scored.into_iter().map(|(_, id)| id).collect())
+}
+
+/// wraps `query` to be used as `LogWalkerFilter`
+pub fn commit_filter(
+    query: FilterQuery,
+    tags: Tags,
+    max_field_len: usize,
+) -> LogWalkerFilter {
+    Arc::new(Box::new(move |repo, id| {
+        commit_matches(repo, id, &query, &tags, max_field_len)
+    }))
+}
+
+/// index of the first of `ids` that `filter` lets through
+pub fn find_first_match(
+    repo_path: &str,
+    ids: &[CommitId],
+    filter: &LogWalkerFilter,
+) -> Result<Option<usize>> {
+    let repo = repo_allow_bare(repo_path)?;
+
+    for (index, id) in ids.iter().enumerate() {
+        if filter(&repo, id)? {
+            return Ok(Some(index));
+        }
+    }
+
+    Ok(None)
+}
+
+fn term_matches(
+    repo: &Repository,
+    commit: &Commit,
+    id: &CommitId,
+    term: &FilterTerm,
+    tags: &Tags,
+    trailers: &Trailers,
+    max_field_len: usize,
+) -> bool {
+    (fields_matched(
+        repo,
+        commit,
+        id,
+        term,
+        tags,
+        trailers,
+        max_field_len,
+    ) > 0)
+        != term.by.contains(FilterBy::NOT)
+}
+
+/// trailers of a commit, parsed once the first term needs them
+/// and shared by all terms of a query
+type Trailers = OnceCell<Vec<(String, String)>>;
+
+/// how many of the fields of `term` contain it, ignoring `NOT`
+fn fields_matched(
+    repo: &Repository,
+    commit: &Commit,
+    id: &CommitId,
+    term: &FilterTerm,
+    tags: &Tags,
+    trailers: &Trailers,
+    max_field_len: usize,
+) -> usize {
+    let by = term.by;
+    // the full message, not the shortened one of `CommitInfo`
+    let message = truncate_field(
+        commit.message().unwrap_or_default(),
+        max_field_len,
+    );
+    let subject = message.lines().next().unwrap_or_default();
+    let case_sensitive = by.contains(FilterBy::CASE_SENSITIVE);
+    let needle = term.text.as_str();
+    let contains = |s: &str| {
+        let s = truncate_field(s, max_field_len);
+        if case_sensitive {
+            s.contains(needle)
+        } else {
+            contains_lowercase(s, needle)
+        }
+    };
+    let equals = |s: &str| {
+        let s = truncate_field(s, max_field_len);
+        if case_sensitive {
+            s == needle
+        } else {
+            eq_lowercase(s, needle)
+        }
+    };
+    let starts_with = |s: &str| {
+        let s = truncate_field(s, max_field_len);
+        if case_sensitive {
+            s.starts_with(needle)
+        } else {
+            starts_with_lowercase(s, needle)
+        }
+    };
+    let person = |sig: &Signature| {
+        let name = sig.name().unwrap_or_default();
+        let email = sig.email().unwrap_or_default();
+        if by.contains(FilterBy::EXACT) {
+            equals(name) || equals(email)
+        } else {
+            contains(name) || contains(email)
+        }
+    };
+    let mut sha = [0_u8; SHA_HEX_LEN];
+
+    [
+        by.contains(FilterBy::SHA) && contains(sha_hex(id, &mut sha)),
+        by.contains(FilterBy::AUTHOR) && person(&commit.author()),
+        by.contains(FilterBy::COMMITTER)
+            && person(&commit.committer()),
+        by.contains(FilterBy::MESSAGE) && contains(subject),
+        by.contains(FilterBy::BODY) && contains(message),
+        by.contains(FilterBy::SUBJECT_PREFIX) && starts_with(subject),
+        by.contains(FilterBy::TAGS)
+            && tags
+                .get(id)
+                .is_some_and(|tags| tags.iter().any(|t| contains(t))),
+        by.contains(FilterBy::TRAILER)
+            && trailer_matches(
+                trailers.get_or_init(|| get_trailers(message)),
+                term,
+            ),
+        by.contains(FilterBy::SIGNED) && is_signed(commit),
+        by.contains(FilterBy::VERIFIED)
+            && is_signed(commit)
+            && signature_verified(repo, id),
+        by.contains(FilterBy::PARENT)
+            && commit.parent_ids().any(|parent| {
+                sha_hex(&parent.into(), &mut sha).starts_with(needle)
+            }),
+        by.contains(FilterBy::SUBJECT_LONGER)
+            && needle
+                .parse::<usize>()
+                .is_ok_and(|limit| subject.chars().count() > limit),
+    ]
+    .iter()
+    .filter(|found| **found)
+    .count()
+}
+
+/// `gpgsig` (or `gpgsig-sha256`) header line of a signed commit
+fn is_signed(commit: &Commit) -> bool {
+    commit
+        .raw_header_bytes()
+        .split(|b| *b == b'\n')
+        .any(|line| {
+            line.starts_with(b"gpgsig ")
+                || line.starts_with(b"gpgsig-sha256 ")
+        })
+}
+
+/// `git verify-commit` accepts the signature of `id`, needs the
+/// keys of the signers (gpg keyring, `gpg.ssh.allowedSignersFile`)
+fn signature_verified(repo: &Repository, id: &CommitId) -> bool {
+    Command::new("git")
+        .arg("--git-dir")
+        .arg(repo.path())
+        .arg("verify-commit")
+        .arg(id.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// hex digits of a full sha
+const SHA_HEX_LEN: usize = 40;
+
+/// the full sha of `id` in lowercase hex, written to `buf`
+/// instead of a new string
+fn sha_hex<'a>(
+    id: &CommitId,
+    buf: &'a mut [u8; SHA_HEX_LEN],
+) -> &'a str {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    let oid: Oid = (*id).into();
+    for (idx, byte) in oid.as_bytes().iter().enumerate() {
+        buf[idx * 2] = HEX[usize::from(byte >> 4)];
+        buf[idx * 2 + 1] = HEX[usize::from(byte & 0xf)];
+    }
+
+    std::str::from_utf8(buf).unwrap_or_default()
+}
+
+/// `field.to_lowercase()`, skipped for fields with a capital
+/// sigma: its lowercase depends on the letters around it, which
+/// only `str::to_lowercase` gets right
+fn lowercase_chars(
+    field: &str,
+) -> Option<impl Iterator<Item = char> + '_> {
+    (!field.contains('\u{3a3}'))
+        .then(|| field.chars().flat_map(char::to_lowercase))
+}
+
+/// `field.to_lowercase().starts_with(needle)` without allocating,
+/// `needle` has to be lowercase
+fn starts_with_lowercase(field: &str, needle: &str) -> bool {
+    if field.is_ascii() {
+        return field.len() >= needle.len()
+            && eq_lowercase(&field[..needle.len()], needle);
+    }
+
+    match lowercase_chars(field) {
+        Some(mut chars) => {
+            needle.chars().all(|c| chars.next() == Some(c))
+        }
+        None => field.to_lowercase().starts_with(needle),
+    }
+}
+
+/// `field.to_lowercase() == needle` without allocating,
+/// `needle` has to be lowercase
+fn eq_lowercase(field: &str, needle: &str) -> bool {
+    if field.is_ascii() {
+        return field.len() == needle.len()
+            && field
+                .bytes()
+                .zip(needle.bytes())
+                .all(|(a, b)| a.to_ascii_lowercase() == b);
+    }
+
+    match lowercase_chars(field) {
+        Some(chars) => chars.eq(needle.chars()),
+        None => field.to_lowercase() == needle,
+    }
+}
+
+/// `field.to_lowercase().contains(needle)` without allocating,
+/// `needle` has to be lowercase
+fn contains_lowercase(field: &str, needle: &str) -> bool {
+    if needle.is_empty() {
+        return true;
+    }
+
+    if field.is_ascii() {
+        let needle = needle.as_bytes();
+        let first = needle[0];
+
+        return field.as_bytes().windows(needle.len()).any(|w| {
+            w[0].to_ascii_lowercase() == first
+                && w.iter()
+                    .zip(needle)
+                    .all(|(a, b)| a.to_ascii_lowercase() == *b)
+        });
+    }
+
+    if field.contains('\u{3a3}') {
+        return field.to_lowercase().contains(needle);
+    }
+
+    // a match may start within the lowercase of a char that
+    // lowercases to more than one, only the chars whose lowercase
+    // has the first char of `needle` are worth a closer look
+    let mut needle_chars = needle.chars();
+    let first = needle_chars.next().unwrap_or_default();
+    let rest = needle_chars.as_str();
+
+    let matches_at = |idx: usize, skip: usize| {
+        let mut chars = field[idx..]
+            .chars()
+            .flat_map(char::to_lowercase)
+            .skip(skip + 1);
+        rest.chars().all(|c| chars.next() == Some(c))
+    };
+
+    let first_ascii = u8::try_from(first).ok().filter(u8::is_ascii);
+
+    field.bytes().enumerate().any(|(idx, byte)| {
+        if byte.is_ascii() {
+            first_ascii == Some(byte.to_ascii_lowercase())
+                && matches_at(idx, 0)
+        } else if field.is_char_boundary(idx) {
+            field[idx..]
+                .chars()
+                .next()
+                .into_iter()
+                .flat_map(char::to_lowercase)
+                .enumerate()
+                .filter(|(_, lower)| *lower == first)
+                .any(|(skip, _)| matches_at(idx, skip))
+        } else {
+            false
+        }
+    })
+}
+
+/// the first `max_len` bytes of `field`,
+/// shortened further to end on a char boundary
+fn truncate_field(field: &str, max_len: usize) -> &str {
+    if field.len() <= max_len {
+        return field;
+    }
+
+    let end = (0..=max_len)
+        .rev()
+        .find(|idx| field.is_char_boundary(*idx))
+        .unwrap_or_default();
+
+    &field[..end]
+}
+
+/// `Key: value` trailers of the last paragraph of a message (not
+/// counting the subject) the way `git interpret-trailers` finds them:
+/// indented lines continue the previous value and the paragraph only
+/// counts if all its lines are trailers, or at least a quarter of
+/// them including a `Signed-off-by`
+pub fn get_trailers(message: &str) -> Vec<(String, String)> {
+    let mut paragraphs = message
+        .trim()
+        .split("\n\n")
+        .filter(|p| !p.trim().is_empty());
+    paragraphs.next();
+
+    let paragraph = match paragraphs.last() {
+        Some(paragraph) => paragraph,
+        None => return Vec::new(),
+    };
+
+    let mut trailers: Vec<(String, String)> = Vec::new();
+    let mut others = 0_usize;
+    let mut continues = false;
+    for line in paragraph.lines() {
+        if continues && line.starts_with(char::is_whitespace) {
+            if let Some((_, value)) = trailers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((key, value)) = parse_trailer(line) {
+            trailers.push((key.to_string(), value.to_string()));
+            continues = true;
+        } else {
+            others += 1;
+            continues = false;
+        }
+    }
+
+    let signed_off = trailers
+        .iter()
+        .any(|(key, _)| key.eq_ignore_ascii_case("signed-off-by"));
+    if others == 0 || (signed_off && trailers.len() * 3 >= others) {
+        trailers
+    } else {
+        Vec::new()
+    }
+}
+
+/// `Key: value`, the key made of alphanumerics and `-`
+fn parse_trailer(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_at(line.find(':')?);
+    let key = key.trim();
+    let is_key = !key.is_empty()
+        && key.chars().all(|c| c.is_alphanumeric() || c == '-');
+
+    is_key.then(|| (key, value[1..].trim()))
+}
+
+/// `term` is `key`, `key=value` or `=value` for any key
+fn trailer_matches(
+    trailers: &[(String, String)],
+    term: &FilterTerm,
+) -> bool {
+    let (key, value) = term.text.find('=').map_or(
+        (term.text.as_str(), None),
+        |pos| {
+            (
+                term.text[..pos].trim(),
+                Some(term.text[pos + 1..].trim()),
+            )
+        },
+    );
+    let case_sensitive = term.by.contains(FilterBy::CASE_SENSITIVE);
+
+    trailers.iter().any(|(k, v)| {
+        (key.is_empty() || k.eq_ignore_ascii_case(key))
+            && value.is_none_or(|value| {
+                match (
+                    term.by.contains(FilterBy::EXACT),
+                    case_sensitive,
+                ) {
+                    (true, true) => v == value,
+                    (true, false) => eq_lowercase(v, value),
+                    (false, true) => v.contains(value),
+                    (false, false) => contains_lowercase(v, value),
+                }
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::tests::repo_init;
+    use std::time::Instant;
+
+    fn commit_as(
+        repo: &Repository,
+        name: &str,
+        msg: &str,
+    ) -> CommitId {
+        let sig = Signature::now(name, "mail@example.com").unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let tree = head.tree().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, msg, &tree, &[&head])
+            .unwrap()
+            .into()
+    }
+
+    fn matches(
+        repo: &Repository,
+        id: &CommitId,
+        text: &str,
+        by: FilterBy,
+    ) -> bool {
+        commit_matches(
+            repo,
+            id,
+            &FilterQuery::Term(FilterTerm::new(text, by)),
+            &Tags::new(),
+            DEFAULT_MAX_FIELD_LEN,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_author_contains() {
+        let (_td, repo) = repo_init().unwrap();
+        let bob = commit_as(&repo, "Bob", "a");
+        let bobby = commit_as(&repo, "Bobby", "b");
+
+        assert!(matches(&repo, &bob, "bob", FilterBy::AUTHOR));
+        assert!(matches(&repo, &bobby, "bob", FilterBy::AUTHOR));
+    }
+
+    #[test]
+    fn test_author_exact() {
+        let (_td, repo) = repo_init().unwrap();
+        let bob = commit_as(&repo, "Bob", "a");
+        let bobby = commit_as(&repo, "Bobby", "b");
+        let by = FilterBy::AUTHOR | FilterBy::EXACT;
+
+        assert!(matches(&repo, &bob, "bob", by));
+        assert!(!matches(&repo, &bobby, "bob", by));
+        assert!(!matches(
+            &repo,
+            &bob,
+            "bob",
+            by | FilterBy::CASE_SENSITIVE
+        ));
+        assert!(matches(
+            &repo,
+            &bob,
+            "Bob",
+            by | FilterBy::CASE_SENSITIVE
+        ));
+    }
+
+    #[test]
+    fn test_committer_exact() {
+        let (_td, repo) = repo_init().unwrap();
+        let bobby = commit_as(&repo, "Bobby", "b");
+        let by = FilterBy::COMMITTER | FilterBy::EXACT;
+
+        assert!(!matches(&repo, &bobby, "bob", by));
+        assert!(matches(&repo, &bobby, "bobby", by));
+    }
+
+    #[test]
+    fn test_person() {
+        let (_td, repo) = repo_init().unwrap();
+        let author =
+            Signature::now("Bob", "bob@example.com").unwrap();
+        let committer =
+            Signature::now("Alice", "alice@bots.example.com")
+                .unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let tree = head.tree().unwrap();
+        let id: CommitId = repo
+            .commit(
+                Some("HEAD"),
+                &author,
+                &committer,
+                "applied",
+                &tree,
+                &[&head],
+            )
+            .unwrap()
+            .into();
+        let person = FilterBy::AUTHOR | FilterBy::COMMITTER;
+
+        assert!(!matches(
+            &repo,
+            &id,
+            "bots.example",
+            FilterBy::AUTHOR
+        ));
+        assert!(matches(&repo, &id, "bots.example", person));
+        assert!(matches(&repo, &id, "bob", person));
+        assert!(matches(
+            &repo,
+            &id,
+            "alice@bots.example.com",
+            person | FilterBy::EXACT
+        ));
+        assert!(!matches(&repo, &id, "carol", person));
+    }
+
+    #[test]
+    fn test_message_and_not() {
+        let (_td, repo) = repo_init().unwrap();
+        let id = commit_as(&repo, "Bob", "fix the parser");
+
+        assert!(matches(&repo, &id, "Parser", FilterBy::MESSAGE));
+        assert!(!matches(
+            &repo,
+            &id,
+            "parser",
+            FilterBy::MESSAGE | FilterBy::NOT
+        ));
+        assert!(matches(&repo, &id, "bob", FilterBy::EVERYWHERE));
+    }
+
+    #[test]
+    fn test_subject_and_body() {
+        let (_td, repo) = repo_init().unwrap();
+        let id = commit_as(
+            &repo,
+            "Bob",
+            "fix the parser\n\n- changelog: bump lexer\n",
+        );
+
+        assert!(matches(&repo, &id, "parser", FilterBy::MESSAGE));
+        assert!(!matches(&repo, &id, "lexer", FilterBy::MESSAGE));
+        assert!(!matches(&repo, &id, "lexer", FilterBy::EVERYWHERE));
+        assert!(matches(&repo, &id, "lexer", FilterBy::BODY));
+        assert!(matches(&repo, &id, "parser", FilterBy::BODY));
+    }
+
+    #[test]
+    fn test_subject_prefix() {
+        let (_td, repo) = repo_init().unwrap();
+        let revert =
+            commit_as(&repo, "Bob", "Revert \"fix the parser\"");
+        let fixup = commit_as(&repo, "Bob", "fixup! fix the parser");
+        let plain = commit_as(&repo, "Bob", "revert the revert");
+        let by = FilterBy::SUBJECT_PREFIX | FilterBy::CASE_SENSITIVE;
+
+        assert!(matches(&repo, &revert, "Revert ", by));
+        assert!(!matches(&repo, &plain, "Revert ", by));
+        assert!(!matches(&repo, &fixup, "Revert ", by));
+        assert!(matches(&repo, &fixup, "fixup! ", by));
+        assert!(!matches(&repo, &revert, "fix", by));
+        assert!(matches(
+            &repo,
+            &plain,
+            "fixup! ",
+            by | FilterBy::NOT
+        ));
+    }
+
+    #[test]
+    fn test_signed() {
+        let (_td, repo) = repo_init().unwrap();
+        let plain = commit_as(&repo, "Bob", "plain");
+
+        let sig = Signature::now("Bob", "mail@example.com").unwrap();
+        let head = repo.find_commit(plain.into()).unwrap();
+        let content = repo
+            .commit_create_buffer(
+                &sig,
+                &sig,
+                "signed",
+                &head.tree().unwrap(),
+                &[&head],
+            )
+            .unwrap();
+        // no key can verify this one
+        let signed: CommitId = repo
+            .commit_signed(
+                content.as_str().unwrap(),
+                "-----BEGIN PGP SIGNATURE-----\n\
+                 \n\
+                 iQEzBAABCAAdFiEE\n\
+                 -----END PGP SIGNATURE-----",
+                None,
+            )
+            .unwrap()
+            .into();
+
+        assert!(matches(&repo, &signed, "", FilterBy::SIGNED));
+        assert!(!matches(&repo, &plain, "", FilterBy::SIGNED));
+        assert!(matches(
+            &repo,
+            &plain,
+            "",
+            FilterBy::SIGNED | FilterBy::NOT
+        ));
+
+        assert!(!matches(&repo, &signed, "", FilterBy::VERIFIED));
+        assert!(!matches(&repo, &plain, "", FilterBy::VERIFIED));
+    }
+
+    #[test]
+    fn test_parent() {
+        let (_td, repo) = repo_init().unwrap();
+        let parent = commit_as(&repo, "Bob", "parent");
+        let child = commit_as(&repo, "Bob", "child");
+        let sha = parent.to_string();
+
+        assert!(matches(&repo, &child, &sha, FilterBy::PARENT));
+        assert!(matches(&repo, &child, &sha[..7], FilterBy::PARENT));
+        assert!(!matches(
+            &repo,
+            &parent,
+            &sha[..7],
+            FilterBy::PARENT
+        ));
+        assert!(!matches(
+            &repo,
+            &child,
+            &sha[..7],
+            FilterBy::PARENT | FilterBy::NOT
+        ));
+        assert!(!matches(
+            &repo,
+            &child,
+            &child.to_string(),
+            FilterBy::PARENT
+        ));
+        // upper case hex too
+        assert!(matches(
+            &repo,
+            &child,
+            &sha[..7].to_uppercase(),
+            FilterBy::PARENT
+        ));
+    }
+
+    #[test]
+    fn test_subject_longer() {
+        let (_td, repo) = repo_init().unwrap();
+        let short = commit_as(&repo, "Bob", "fix: parser");
+        let long = commit_as(
+            &repo,
+            "Bob",
+            "fix: parser\u{e9}\n\nwith a body that is a lot longer",
+        );
+        let by = FilterBy::SUBJECT_LONGER;
+
+        assert!(!matches(&repo, &short, "11", by));
+        assert!(matches(&repo, &short, "10", by));
+        // chars, not bytes, and the body does not count
+        assert!(matches(&repo, &long, "11", by));
+        assert!(!matches(&repo, &long, "12", by));
+        assert!(matches(&repo, &short, "11", by | FilterBy::NOT));
+        assert!(!matches(&repo, &short, "many", by));
+    }
+
+    #[test]
+    fn test_trailers() {
+        let (_td, repo) = repo_init().unwrap();
+        let id = commit_as(
+            &repo,
+            "Bob",
+            "fix: parser\n\nbody text: not a trailer\n\n\
+             Signed-off-by: Alice <alice@example.com>\n\
+             Co-authored-by: Carol <carol@example.com>\n",
+        );
+        let subject_only = commit_as(&repo, "Bob", "fix: parser");
+
+        let by = FilterBy::TRAILER;
+        assert!(matches(&repo, &id, "signed-off-by", by));
+        assert!(matches(&repo, &id, "Signed-off-by=alice", by));
+        assert!(matches(&repo, &id, "co-authored-by = carol", by));
+        assert!(!matches(&repo, &id, "signed-off-by=carol", by));
+        assert!(!matches(&repo, &id, "body text", by));
+        assert!(!matches(&repo, &subject_only, "fix", by));
+
+        assert!(!matches(
+            &repo,
+            &id,
+            "signed-off-by=alice",
+            by | FilterBy::EXACT
+        ));
+        assert!(matches(
+            &repo,
+            &id,
+            "signed-off-by=alice <alice@example.com>",
+            by | FilterBy::EXACT
+        ));
+        assert!(!matches(
+            &repo,
+            &id,
+            "signed-off-by=ALICE",
+            by | FilterBy::CASE_SENSITIVE
+        ));
+        assert!(matches(
+            &repo,
+            &id,
+            "signed-off-by=Alice",
+            by | FilterBy::CASE_SENSITIVE
+        ));
+    }
+
+    #[test]
+    fn test_trailer_values() {
+        let (_td, repo) = repo_init().unwrap();
+        let id = commit_as(
+            &repo,
+            "Bob",
+            "fix: parser\n\n\
+             Reviewed-by: Alice\n \
+             <alice@example.com>\n\
+             Signed-off-by: Bob\n",
+        );
+        let by = FilterBy::TRAILER;
+
+        // empty key matches any trailer, unknown keys never
+        assert!(matches(&repo, &id, "=alice", by));
+        assert!(matches(&repo, &id, "=bob", by));
+        assert!(!matches(&repo, &id, "=carol", by));
+        assert!(!matches(&repo, &id, "acked-by", by));
+
+        // continuation lines belong to the value
+        assert!(matches(
+            &repo,
+            &id,
+            "reviewed-by=alice <alice@example.com>",
+            by | FilterBy::EXACT
+        ));
+    }
+
+    #[test]
+    fn test_trailer_paragraph_rules() {
+        assert_eq!(
+            get_trailers("s\n\nsee: the docs\nfor details"),
+            vec![]
+        );
+        assert_eq!(
+            get_trailers(
+                "s\n\nsee: the docs\nfor details\nSigned-off-by: Bob"
+            ),
+            vec![
+                (String::from("see"), String::from("the docs")),
+                (String::from("Signed-off-by"), String::from("Bob")),
+            ]
+        );
+        assert_eq!(get_trailers("Signed-off-by: Bob"), vec![]);
+    }
+
+    #[test]
+    fn test_and_or() {
+        let (_td, repo) = repo_init().unwrap();
+        let id = commit_as(&repo, "Bob", "fix the parser");
+        let tags = Tags::new();
+
+        let term = |text| {
+            FilterQuery::Term(FilterTerm::new(
+                text,
+                FilterBy::EVERYWHERE,
+            ))
+        };
+
+        assert!(!commit_matches(
+            &repo,
+            &id,
+            &FilterQuery::And(vec![term("bob"), term("alice")]),
+            &tags,
+            DEFAULT_MAX_FIELD_LEN
+        )
+        .unwrap());
+        assert!(commit_matches(
+            &repo,
+            &id,
+            &FilterQuery::Or(vec![term("alice"), term("parser")]),
+            &tags,
+            DEFAULT_MAX_FIELD_LEN
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_negated_group() {
+        let (_td, repo) = repo_init().unwrap();
+        let bob = commit_as(&repo, "Bob", "fix the parser");
+        let alice = commit_as(&repo, "Alice", "wip");
+        let carol = commit_as(&repo, "Carol", "docs");
+        let tags = Tags::new();
+
+        let term = |text| {
+            FilterQuery::Term(FilterTerm::new(
+                text,
+                FilterBy::EVERYWHERE,
+            ))
+        };
+        // !(bob || wip)
+        let query =
+            FilterQuery::Not(Box::new(FilterQuery::Or(vec![
+                term("bob"),
+                term("wip"),
+            ])));
+
+        let check = |id| {
+            commit_matches(
+                &repo,
+                id,
+                &query,
+                &tags,
+                DEFAULT_MAX_FIELD_LEN,
+            )
+            .unwrap()
+        };
+        assert!(!check(&bob));
+        assert!(!check(&alice));
+        assert!(check(&carol));
+
+        // !(!(bob)) == bob
+        let query = FilterQuery::Not(Box::new(FilterQuery::Not(
+            Box::new(term("bob")),
+        )));
+        assert!(commit_matches(
+            &repo,
+            &bob,
+            &query,
+            &tags,
+            DEFAULT_MAX_FIELD_LEN
+        )
+        .unwrap());
+        assert!(!commit_matches(
+            &repo,
+            &carol,
+            &query,
+            &tags,
+            DEFAULT_MAX_FIELD_LEN
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_find_first_match() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let wip = commit_as(&repo, "alice", "wip");
+        let fix = commit_as(&repo, "bob", "fix");
+        let ids = [fix, wip, fix];
+        let filter = commit_filter(
+            FilterQuery::Term(FilterTerm::new(
+                "wip",
+                FilterBy::MESSAGE,
+            )),
+            Tags::new(),
+            DEFAULT_MAX_FIELD_LEN,
+        );
+
+        assert_eq!(
+            find_first_match(repo_path, &ids, &filter).unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            find_first_match(repo_path, &ids[2..], &filter).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_score() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let none = commit_as(&repo, "carol", "docs");
+        let one = commit_as(&repo, "carol", "fix parser");
+        let two = commit_as(&repo, "bob", "fix parser");
+        let three = commit_as(&repo, "bob", "fix bob's parser");
+
+        let term = |text: &str, by| {
+            FilterQuery::Term(FilterTerm::new(text, by))
+        };
+        let query = FilterQuery::Or(vec![
+            term("fix", FilterBy::MESSAGE),
+            term("bob", FilterBy::AUTHOR | FilterBy::MESSAGE),
+            FilterQuery::Not(Box::new(term(
+                "parser",
+                FilterBy::MESSAGE,
+            ))),
+            term("wip", FilterBy::MESSAGE | FilterBy::NOT),
+        ]);
+        let score = |id| {
+            commit_score(
+                &repo,
+                &id,
+                &query,
+                &Tags::new(),
+                DEFAULT_MAX_FIELD_LEN,
+            )
+            .unwrap()
+        };
+
+        assert_eq!(score(none), 0);
+        assert_eq!(score(one), 1);
+        assert_eq!(score(two), 2);
+        assert_eq!(score(three), 3);
+
+        assert_eq!(
+            sort_by_relevance(
+                repo_path,
+                &[none, two, one, three, one],
+                &query,
+                &Tags::new(),
+                DEFAULT_MAX_FIELD_LEN,
+            )
+            .unwrap(),
+            vec![three, two, one, one, none]
+        );
+    }
+
+    #[test]
+    fn test_max_field_len() {
+        let (_td, repo) = repo_init().unwrap();
+        let id = commit_as(&repo, "bob", "fix the parser\n\nü wip");
+        let query = |text: &str| {
+            FilterQuery::Term(FilterTerm::new(text, FilterBy::BODY))
+        };
+        let check = |text, max_field_len| {
+            commit_matches(
+                &repo,
+                &id,
+                &query(text),
+                &Tags::new(),
+                max_field_len,
+            )
+            .unwrap()
+        };
+
+        assert!(check("wip", DEFAULT_MAX_FIELD_LEN));
+        assert!(check("parser", 14));
+        assert!(!check("wip", 14));
+        assert!(!check("parser", 13));
+
+        // never splits a char
+        assert_eq!(truncate_field("aü", 2), "a");
+        assert_eq!(truncate_field("aü", 3), "aü");
+    }
+
+    #[test]
+    fn test_lowercase_matching() {
+        let fields = [
+            "",
+            "Fix The PARSER",
+            "Über GROẞE Straße",
+            // lowercases to `i` and a combining dot
+            "İstanbul İ",
+            "ΟΔΥΣΣΕΥΣ ΣΑ",
+            "ǅemal",
+        ];
+
+        for field in &fields {
+            let lower = field.to_lowercase();
+            let lower_chars: Vec<char> = lower.chars().collect();
+            let mut needles = vec![String::from("x"), lower.clone()];
+            for start in 0..lower_chars.len() {
+                for end in start..=lower_chars.len() {
+                    needles.push(
+                        lower_chars[start..end].iter().collect(),
+                    );
+                }
+            }
+
+            for needle in &needles {
+                assert_eq!(
+                    contains_lowercase(field, needle),
+                    lower.contains(needle.as_str()),
+                    "{:?} contains {:?}",
+                    field,
+                    needle
+                );
+                assert_eq!(
+                    starts_with_lowercase(field, needle),
+                    lower.starts_with(needle.as_str()),
+                    "{:?} starts with {:?}",
+                    field,
+                    needle
+                );
+                assert_eq!(
+                    eq_lowercase(field, needle),
+                    lower == *needle,
+                    "{:?} equals {:?}",
+                    field,
+                    needle
+                );
+            }
+        }
+    }
+
+    /// filters a generated log with the allocation free matching
+    /// and the `to_lowercase` of each field it replaced, run with
+    /// `cargo test --release -p asyncgit bench_ -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_lowercase_filter() {
+        let (_td, repo) = repo_init().unwrap();
+        let authors = ["Bob", "Alice Ünal", "ΟΔΥΣΣΕΥΣ", "Zoë"];
+        let ids: Vec<CommitId> = (0..5_000)
+            .map(|i| {
+                commit_as(
+                    &repo,
+                    authors[i % authors.len()],
+                    &format!(
+                        "Fix The PARSER #{}\n\nÜber GROẞE Straße {}",
+                        i, i
+                    ),
+                )
+            })
+            .collect();
+
+        let needle = "ünal";
+        let by = FilterBy::AUTHOR | FilterBy::MESSAGE;
+        let query = FilterQuery::Term(FilterTerm::new(needle, by));
+        let lowercased = |id: &CommitId| {
+            let commit = repo.find_commit((*id).into()).unwrap();
+            let author = commit.author();
+            [
+                author.name().unwrap_or_default(),
+                author.email().unwrap_or_default(),
+                commit
+                    .message()
+                    .unwrap_or_default()
+                    .lines()
+                    .next()
+                    .unwrap_or_default(),
+            ]
+            .iter()
+            .any(|field| field.to_lowercase().contains(needle))
+        };
+
+        let start = Instant::now();
+        let matched: Vec<bool> = ids
+            .iter()
+            .map(|id| {
+                commit_matches(
+                    &repo,
+                    id,
+                    &query,
+                    &Tags::new(),
+                    DEFAULT_MAX_FIELD_LEN,
+                )
+                .unwrap()
+            })
+            .collect();
+        let filter_time = start.elapsed();
+
+        let start = Instant::now();
+        let expected: Vec<bool> =
+            ids.iter().map(lowercased).collect();
+        let lowercase_time = start.elapsed();
+
+        assert_eq!(matched, expected);
+        assert_eq!(
+            matched.iter().filter(|m| **m).count(),
+            ids.len() / authors.len()
+        );
+
+        // the fields alone, without looking the commits up
+        let fields: Vec<String> = ids
+            .iter()
+            .flat_map(|id| {
+                let commit = repo.find_commit((*id).into()).unwrap();
+                let author = commit.author();
+                vec![
+                    author.name().unwrap().to_string(),
+                    commit.message().unwrap().to_string(),
+                ]
+            })
+            .collect();
+        let rounds = 20;
+
+        let start = Instant::now();
+        let mut found = 0;
+        for _ in 0..rounds {
+            found += fields
+                .iter()
+                .filter(|f| contains_lowercase(f, needle))
+                .count();
+        }
+        let contains_time = start.elapsed();
+
+        let start = Instant::now();
+        let mut expected_found = 0;
+        for _ in 0..rounds {
+            expected_found += fields
+                .iter()
+                .filter(|f| f.to_lowercase().contains(needle))
+                .count();
+        }
+        let to_lowercase_time = start.elapsed();
+
+        assert_eq!(found, expected_found);
+        println!(
+            "{} commits: filter {:?}, to_lowercase {:?}",
+            ids.len(),
+            filter_time,
+            lowercase_time
+        );
+        println!(
+            "{} fields x{}: contains_lowercase {:?}, to_lowercase {:?}",
+            fields.len(),
+            rounds,
+            contains_time,
+            to_lowercase_time
+        );
+    }
+
+    #[test]
+    fn test_sha_hex() {
+        let (_td, repo) = repo_init().unwrap();
+        let id = commit_as(&repo, "bob", "a");
+        let mut buf = [0_u8; SHA_HEX_LEN];
+
+        assert_eq!(sha_hex(&id, &mut buf), id.to_string());
+    }
+}
@@ -1,10 +1,12 @@
-use super::utils::repo;
+use super::utils::repo_allow_bare;
 use crate::error::Result;
-use git2::{Commit, Error, Oid};
+use git2::{Commit, Oid, Repository};
 use scopetime::scope_time;
 
 /// identifies a single commit
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd,
+)]
 pub struct CommitId(Oid);
 
 impl CommitId {
@@ -24,6 +26,13 @@ impl CommitId {
     }
 }
 
+/// the zero id (`0000000..`)
+impl Default for CommitId {
+    fn default() -> Self {
+        Self(Oid::zero())
+    }
+}
+
 impl ToString for CommitId {
     fn to_string(&self) -> String {
         self.0.to_string()
@@ -63,32 +72,58 @@ pub fn get_commits_info(
 ) -> Result<Vec<CommitInfo>> {
     scope_time!("get_commits_info");
 
-    let repo = repo(repo_path)?;
+    let repo = repo_allow_bare(repo_path)?;
+
+    ids.iter()
+        .map(|id| get_commit_info(&repo, *id, message_length_limit))
+        .collect()
+}
+
+/// like `get_commits_info` but does not give up on the first
+/// commit that fails to load (e.g. a corrupt object),
+/// those are returned as `Err` holding their id
+pub fn get_commits_info_partial(
+    repo_path: &str,
+    ids: &[CommitId],
+    message_length_limit: usize,
+) -> Result<Vec<std::result::Result<CommitInfo, CommitId>>> {
+    scope_time!("get_commits_info_partial");
+
+    let repo = repo_allow_bare(repo_path)?;
 
-    let commits = ids
+    Ok(ids
         .iter()
-        .map(|id| repo.find_commit((*id).into()))
-        .collect::<std::result::Result<Vec<Commit>, Error>>()?
-        .into_iter();
-
-    let res = commits
-        .map(|c: Commit| {
-            let message = get_message(&c, Some(message_length_limit));
-            let author = if let Some(name) = c.author().name() {
-                String::from(name)
-            } else {
-                String::from("<unknown>")
-            };
-            CommitInfo {
-                message,
-                author,
-                time: c.time().seconds(),
-                id: CommitId(c.id()),
-            }
+        .map(|id| {
+            get_commit_info(&repo, *id, message_length_limit).map_err(
+                |e| {
+                    log::error!("unreadable commit {:?}: {}", id, e);
+                    *id
+                },
+            )
         })
-        .collect::<Vec<_>>();
+        .collect())
+}
 
-    Ok(res)
+fn get_commit_info(
+    repo: &Repository,
+    id: CommitId,
+    message_length_limit: usize,
+) -> Result<CommitInfo> {
+    let c = repo.find_commit(id.into())?;
+
+    let message = get_message(&c, Some(message_length_limit));
+    let author = if let Some(name) = c.author().name() {
+        String::from(name)
+    } else {
+        String::from("<unknown>")
+    };
+
+    Ok(CommitInfo {
+        message,
+        author,
+        time: c.time().seconds(),
+        id: CommitId(c.id()),
+    })
 }
 
 ///
@@ -122,12 +157,16 @@ fn limit_str(s: &str, limit: usize) -> &str {
 #[cfg(test)]
 mod tests {
 
-    use super::{get_commits_info, limit_str};
+    use super::{
+        get_commits_info, get_commits_info_partial, limit_str,
+        CommitId,
+    };
     use crate::error::Result;
     use crate::sync::{
         commit, stage_add_file, tests::repo_init_empty,
         utils::get_head_repo,
     };
+    use git2::Oid;
     use std::{fs::File, io::Write, path::Path};
 
     #[test]
@@ -155,6 +194,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_partial() -> Result<()> {
+        let file_path = Path::new("foo");
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(&root.join(file_path))?.write_all(b"a")?;
+        stage_add_file(repo_path, file_path).unwrap();
+        let c1 = commit(repo_path, "commit1").unwrap();
+        File::create(&root.join(file_path))?.write_all(b"b")?;
+        stage_add_file(repo_path, file_path).unwrap();
+        let c2 = commit(repo_path, "commit2").unwrap();
+
+        let missing = CommitId::new(
+            Oid::from_str("0123456789abcdef0123456789abcdef01234567")
+                .unwrap(),
+        );
+        let ids = vec![c2, missing, c1];
+
+        assert!(get_commits_info(repo_path, &ids, 50).is_err());
+
+        let res = get_commits_info_partial(repo_path, &ids, 50)?;
+
+        assert_eq!(res.len(), 3);
+        assert_eq!(res[0].as_ref().unwrap().message, "commit2");
+        assert_eq!(res[1].as_ref().unwrap_err(), &missing);
+        assert_eq!(res[2].as_ref().unwrap().message, "commit1");
+
+        Ok(())
+    }
+
     #[test]
     fn test_invalid_utf8() -> Result<()> {
         let file_path = Path::new("foo");
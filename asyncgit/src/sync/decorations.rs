@@ -0,0 +1,145 @@
+use super::{utils::repo_allow_bare, CommitId};
+use crate::error::Result;
+use scopetime::scope_time;
+use std::collections::BTreeMap;
+
+/// a ref pointing at a commit, like the entries of git's `%d`
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Decoration {
+    /// `HEAD` and the branch it is on, `None` if detached
+    Head(Option<String>),
+    ///
+    LocalBranch(String),
+    /// e.g. `origin/master`
+    RemoteBranch(String),
+    ///
+    Tag(String),
+}
+
+/// refs pointing at a single commit, `HEAD` first followed by
+/// local branches, remote branches and tags
+pub type CommitDecorations = Vec<Decoration>;
+/// commit to the refs pointing at it
+pub type Decorations = BTreeMap<CommitId, CommitDecorations>;
+
+/// collects `HEAD`, branches, remote branches and tags by the
+/// commit they point to (annotated tags are peeled)
+pub fn get_decorations(repo_path: &str) -> Result<Decorations> {
+    scope_time!("get_decorations");
+
+    let repo = repo_allow_bare(repo_path)?;
+    let mut res = Decorations::new();
+
+    // unborn or missing HEAD is simply left out
+    let head = repo.head().ok();
+    let head_branch = head
+        .as_ref()
+        .filter(|head| head.is_branch())
+        .and_then(|head| head.shorthand())
+        .map(String::from);
+    if let Some(commit) =
+        head.as_ref().and_then(|head| head.peel_to_commit().ok())
+    {
+        res.entry(CommitId::new(commit.id()))
+            .or_default()
+            .push(Decoration::Head(head_branch.clone()));
+    }
+
+    for reference in repo.references()? {
+        let reference = reference?;
+        let name = match reference.shorthand() {
+            Some(name) => String::from(name),
+            None => continue,
+        };
+
+        let decoration = if reference.is_branch() {
+            // already shown as part of `HEAD`
+            if head_branch.as_ref() == Some(&name) {
+                continue;
+            }
+            Decoration::LocalBranch(name)
+        } else if reference.is_remote() {
+            Decoration::RemoteBranch(name)
+        } else if reference.is_tag() {
+            Decoration::Tag(name)
+        } else {
+            continue;
+        };
+
+        if let Ok(commit) = reference.peel_to_commit() {
+            res.entry(CommitId::new(commit.id()))
+                .or_default()
+                .push(decoration);
+        }
+    }
+
+    for decorations in res.values_mut() {
+        decorations.sort();
+    }
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{commit, tag, tests::repo_init};
+
+    #[test]
+    fn test_decorations() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first =
+            CommitId::new(repo.head().unwrap().target().unwrap());
+        {
+            let first_commit =
+                repo.find_commit(first.into()).unwrap();
+            repo.branch("feature", &first_commit, false).unwrap();
+        }
+        repo.reference(
+            "refs/remotes/origin/master",
+            first.into(),
+            false,
+            "",
+        )
+        .unwrap();
+        tag(repo_path, &first, "v1", "release").unwrap();
+
+        let second = commit(repo_path, "second").unwrap();
+
+        let res = get_decorations(repo_path).unwrap();
+
+        assert_eq!(
+            res.get(&second),
+            Some(&vec![Decoration::Head(Some(String::from(
+                "master"
+            )))])
+        );
+        assert_eq!(
+            res.get(&first),
+            Some(&vec![
+                Decoration::LocalBranch(String::from("feature")),
+                Decoration::RemoteBranch(String::from(
+                    "origin/master"
+                )),
+                Decoration::Tag(String::from("v1")),
+            ])
+        );
+
+        repo.set_head_detached(first.into()).unwrap();
+        let res = get_decorations(repo_path).unwrap();
+
+        assert_eq!(
+            res.get(&first).and_then(|d| d.first()),
+            Some(&Decoration::Head(None))
+        );
+        assert_eq!(
+            res.get(&second),
+            Some(&vec![Decoration::LocalBranch(String::from(
+                "master"
+            ))])
+        );
+    }
+}
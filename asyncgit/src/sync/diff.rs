@@ -1,7 +1,7 @@
 //! sync git api for fetching a diff
 
 use super::{
-    commit_files::get_commit_diff,
+    commit_files::{get_commit_diff, get_compare_commits_diff},
     utils::{self, get_head_repo, work_dir},
     CommitId,
 };
@@ -154,6 +154,36 @@ pub fn get_diff_commit(
     raw_diff_to_file_diff(&diff, work_dir)
 }
 
+/// diff of the file `p` between the commits `old` and `new`
+pub fn get_diff_commits(
+    repo_path: &str,
+    old: CommitId,
+    new: CommitId,
+    p: String,
+) -> Result<FileDiff> {
+    scope_time!("get_diff_commits");
+
+    let repo = utils::repo(repo_path)?;
+    let work_dir = work_dir(&repo)?;
+    let diff = get_compare_commits_diff(&repo, old, new, Some(p))?;
+
+    raw_diff_to_file_diff(&diff, work_dir)
+}
+
+/// the tool `git difftool` runs: `diff.tool`,
+/// falling back to `merge.tool`, `None` if neither is set
+pub fn get_difftool(repo_path: &str) -> Result<Option<String>> {
+    scope_time!("get_difftool");
+
+    let repo = utils::repo(repo_path)?;
+    let config = repo.config()?;
+
+    Ok(["diff.tool", "merge.tool"]
+        .iter()
+        .filter_map(|key| config.get_string(key).ok())
+        .find(|tool| !tool.trim().is_empty()))
+}
+
 ///
 fn raw_diff_to_file_diff<'a>(
     diff: &'a Diff,
@@ -311,7 +341,7 @@ fn new_file_content(path: &Path) -> Option<Vec<u8>> {
 
 #[cfg(test)]
 mod tests {
-    use super::{get_diff, get_diff_commit};
+    use super::{get_diff, get_diff_commit, get_difftool};
     use crate::error::Result;
     use crate::sync::{
         commit, stage_add_file,
@@ -552,4 +582,25 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_difftool() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        let mut config = repo.config().unwrap();
+
+        config.set_str("merge.tool", "meld").unwrap();
+        config.set_str("diff.tool", "").unwrap();
+        assert_eq!(
+            get_difftool(repo_path).unwrap().as_deref(),
+            Some("meld")
+        );
+
+        config.set_str("diff.tool", "vimdiff").unwrap();
+        assert_eq!(
+            get_difftool(repo_path).unwrap().as_deref(),
+            Some("vimdiff")
+        );
+    }
 }
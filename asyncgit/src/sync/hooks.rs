@@ -1,15 +1,17 @@
 use super::utils::{repo, work_dir};
 use crate::error::{Error, Result};
+use git2::{Oid, Repository};
 use scopetime::scope_time;
 use std::{
     fs::File,
     io::{Read, Write},
     path::{Path, PathBuf},
-    process::Command,
+    process::{Command, Stdio},
 };
 
 const HOOK_POST_COMMIT: &str = ".git/hooks/post-commit";
 const HOOK_PRE_COMMIT: &str = ".git/hooks/pre-commit";
+const HOOK_PRE_PUSH: &str = ".git/hooks/pre-push";
 const HOOK_COMMIT_MSG: &str = ".git/hooks/commit-msg";
 const HOOK_COMMIT_MSG_TEMP_FILE: &str = ".git/COMMIT_EDITMSG";
 
@@ -34,6 +36,7 @@ pub fn hooks_commit_msg(
             work_dir.as_str(),
             HOOK_COMMIT_MSG,
             &[HOOK_COMMIT_MSG_TEMP_FILE],
+            None,
         )?;
 
         // load possibly altered msg
@@ -54,7 +57,7 @@ pub fn hooks_pre_commit(repo_path: &str) -> Result<HookResult> {
     let work_dir = work_dir_as_string(repo_path)?;
 
     if hook_runable(work_dir.as_str(), HOOK_PRE_COMMIT) {
-        Ok(run_hook(work_dir.as_str(), HOOK_PRE_COMMIT, &[])?)
+        Ok(run_hook(work_dir.as_str(), HOOK_PRE_COMMIT, &[], None)?)
     } else {
         Ok(HookResult::Ok)
     }
@@ -67,12 +70,78 @@ pub fn hooks_post_commit(repo_path: &str) -> Result<HookResult> {
     let work_dir_str = work_dir.as_str();
 
     if hook_runable(work_dir_str, HOOK_POST_COMMIT) {
-        Ok(run_hook(work_dir_str, HOOK_POST_COMMIT, &[])?)
+        Ok(run_hook(work_dir_str, HOOK_POST_COMMIT, &[], None)?)
     } else {
         Ok(HookResult::Ok)
     }
 }
 
+/// this hook is documented here https://git-scm.com/docs/githooks#_pre_push
+/// it gets the name and url of `remote` as parameters and
+/// `<local ref> <local sha> <remote ref> <remote sha>` of `refspec`
+/// on stdin. the remote is not asked for its sha, the one of the
+/// remote tracking branch is passed (zeros if there is none)
+pub fn hooks_pre_push(
+    repo_path: &str,
+    remote: &str,
+    refspec: &str,
+) -> Result<HookResult> {
+    scope_time!("hooks_pre_push");
+
+    let work_dir = work_dir_as_string(repo_path)?;
+
+    if hook_runable(work_dir.as_str(), HOOK_PRE_PUSH) {
+        let repo = repo(repo_path)?;
+        let url = {
+            let remote = repo.find_remote(remote)?;
+            remote
+                .pushurl()
+                .or_else(|| remote.url())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let update = pre_push_update(&repo, remote, refspec)?;
+
+        Ok(run_hook(
+            work_dir.as_str(),
+            HOOK_PRE_PUSH,
+            &[remote, url.as_str()],
+            Some(update.as_bytes()),
+        )?)
+    } else {
+        Ok(HookResult::Ok)
+    }
+}
+
+/// the stdin line of the pre-push hook for pushing `refspec`
+/// (`[+]<src>[:<dst>]`) to `remote`
+fn pre_push_update(
+    repo: &Repository,
+    remote: &str,
+    refspec: &str,
+) -> Result<String> {
+    let refspec = refspec.trim_start_matches('+');
+    let (local_ref, remote_ref) =
+        refspec.split_once(':').unwrap_or((refspec, refspec));
+
+    let local_id = repo.refname_to_id(local_ref)?;
+    let remote_id = remote_ref
+        .strip_prefix("refs/heads/")
+        .and_then(|branch| {
+            repo.refname_to_id(&format!(
+                "refs/remotes/{}/{}",
+                remote, branch
+            ))
+            .ok()
+        })
+        .unwrap_or_else(Oid::zero);
+
+    Ok(format!(
+        "{} {} {} {}\n",
+        local_ref, local_id, remote_ref, remote_id
+    ))
+}
+
 fn work_dir_as_string(repo_path: &str) -> Result<String> {
     let repo = repo(repo_path)?;
     work_dir(&repo)?.to_str().map(|s| s.to_string()).ok_or_else(
@@ -102,16 +171,22 @@ pub enum HookResult {
 
 /// this function calls hook scripts based on conventions documented here
 /// https://git-scm.com/docs/githooks
+/// `args` are passed as they are (no word splitting), `input` is
+/// written to the stdin of the hook
 fn run_hook(
     path: &str,
     hook_script: &str,
     args: &[&str],
+    input: Option<&[u8]>,
 ) -> Result<HookResult> {
-    let arg_str = format!("{} {}", hook_script, args.join(" "));
-    let bash_args = vec!["-c".to_string(), arg_str];
-
-    let output = Command::new("bash")
-        .args(bash_args)
+    let script = format!("{} \"$@\"", hook_script);
+
+    let mut command = Command::new("bash");
+    command
+        .arg("-c")
+        .arg(script)
+        .arg(hook_script)
+        .args(args)
         .current_dir(path)
         // This call forces Command to handle the Path environment correctly on windows,
         // the specific env set here does not matter
@@ -119,8 +194,24 @@ fn run_hook(
         .env(
             "DUMMY_ENV_TO_FIX_WINDOWS_CMD_RUNS",
             "FixPathHandlingOnWindows",
-        )
-        .output()?;
+        );
+
+    let output = if let Some(input) = input {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // a hook not reading its input closes the pipe early
+            if let Err(e) = stdin.write_all(input) {
+                log::debug!("hook input not written: {}", e);
+            }
+        }
+        child.wait_with_output()?
+    } else {
+        command.output()?
+    };
 
     if output.status.success() {
         Ok(HookResult::Ok)
@@ -385,4 +476,69 @@ exit 1
             HookResult::NotOk(String::from("rejected\n"))
         );
     }
+
+    #[test]
+    fn test_pre_push() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo.remote("origin", "/some where/remote.git").unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+
+        // no hook, nothing to run
+        assert_eq!(
+            hooks_pre_push(repo_path, "origin", "refs/heads/master")
+                .unwrap(),
+            HookResult::Ok
+        );
+
+        let hook = b"#!/bin/sh
+echo \"$1 $2\" >&2
+cat >&2
+exit 1
+        ";
+        create_hook(root, HOOK_PRE_PUSH, hook);
+
+        assert_eq!(
+            hooks_pre_push(repo_path, "origin", "refs/heads/master")
+                .unwrap(),
+            HookResult::NotOk(format!(
+                "origin /some where/remote.git\nrefs/heads/master {} refs/heads/master {}\n",
+                head,
+                Oid::zero()
+            ))
+        );
+
+        repo.reference("refs/remotes/origin/master", head, true, "")
+            .unwrap();
+        assert_eq!(
+            pre_push_update(
+                &repo,
+                "origin",
+                "+refs/heads/master:refs/heads/main"
+            )
+            .unwrap(),
+            format!(
+                "refs/heads/master {} refs/heads/main {}\n",
+                head,
+                Oid::zero()
+            )
+        );
+        assert_eq!(
+            pre_push_update(&repo, "origin", "refs/heads/master")
+                .unwrap(),
+            format!(
+                "refs/heads/master {} refs/heads/master {}\n",
+                head, head
+            )
+        );
+
+        create_hook(root, HOOK_PRE_PUSH, b"#!/bin/sh\nexit 0\n");
+        assert_eq!(
+            hooks_pre_push(repo_path, "origin", "refs/heads/master")
+                .unwrap(),
+            HookResult::Ok
+        );
+    }
 }
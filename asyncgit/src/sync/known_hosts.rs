@@ -0,0 +1,409 @@
+//! verifies ssh host keys against `known_hosts` using the
+//! `ssh-keygen` and `ssh-keyscan` of the system
+
+use crate::error::{Error, Result};
+use scopetime::scope_time;
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// seconds `ssh-keyscan` waits for the host
+const KEYSCAN_TIMEOUT_SECS: &str = "10";
+
+/// see `set_trust_new_host_keys`
+static TRUST_NEW_HOST_KEYS: AtomicBool = AtomicBool::new(false);
+
+/// add the keys of hosts not in `known_hosts` yet on first
+/// contact instead of refusing them (trust on first use),
+/// changed keys are still refused
+pub fn set_trust_new_host_keys(trust: bool) {
+    TRUST_NEW_HOST_KEYS.store(trust, Ordering::Relaxed);
+}
+
+/// see `set_trust_new_host_keys`
+pub fn trust_new_host_keys() -> bool {
+    TRUST_NEW_HOST_KEYS.load(Ordering::Relaxed)
+}
+
+/// result of looking up a host key in `known_hosts`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostKeyStatus {
+    /// the host is listed with this key
+    Known,
+    /// the host is not listed at all
+    Unknown,
+    /// the host is only listed with other keys
+    Mismatch,
+    /// this key is marked `@revoked`
+    Revoked,
+}
+
+/// a key `ssh-keygen -l -F` lists for a host
+#[derive(Debug, PartialEq)]
+struct ListedKey<'a> {
+    fingerprint: &'a str,
+    revoked: bool,
+}
+
+/// `SHA256:<base64>` the way `ssh-keygen -l` prints it
+pub fn fingerprint_sha256(hash: &[u8; 32]) -> String {
+    format!(
+        "SHA256:{}",
+        base64::encode_config(hash, base64::STANDARD_NO_PAD)
+    )
+}
+
+/// name of a host in `known_hosts`, `[host]:port` unless the
+/// default port is used
+pub fn known_hosts_name(host: &str, port: Option<u16>) -> String {
+    match port {
+        Some(port) if port != 22 => format!("[{}]:{}", host, port),
+        _ => host.to_string(),
+    }
+}
+
+/// `~/.ssh/known_hosts`
+pub fn known_hosts_file() -> Result<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| {
+            PathBuf::from(home).join(".ssh").join("known_hosts")
+        })
+        .ok_or_else(|| {
+            Error::Generic(String::from("home directory not found"))
+        })
+}
+
+/// looks up `host` (see `known_hosts_name`) in `known_hosts`
+pub fn check_host_key(
+    known_hosts: &Path,
+    host: &str,
+    fingerprint: &str,
+) -> Result<HostKeyStatus> {
+    scope_time!("check_host_key");
+
+    if !known_hosts.exists() {
+        return Ok(HostKeyStatus::Unknown);
+    }
+
+    // exits with 1 if the host is not listed, which leaves
+    // stdout empty just the same
+    let output = Command::new("ssh-keygen")
+        .arg("-l")
+        .arg("-F")
+        .arg(host)
+        .arg("-f")
+        .arg(known_hosts)
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    Ok(host_key_status(&listed_keys(&stdout), fingerprint))
+}
+
+/// compares `fingerprint` with all `listed` keys, whatever their
+/// type: libgit2 does not tell the type of the key offered and
+/// asking the host again is no answer to trust
+fn host_key_status(
+    listed: &[ListedKey<'_>],
+    fingerprint: &str,
+) -> HostKeyStatus {
+    if listed.is_empty() {
+        HostKeyStatus::Unknown
+    } else if listed
+        .iter()
+        .any(|key| key.revoked && key.fingerprint == fingerprint)
+    {
+        HostKeyStatus::Revoked
+    } else if listed
+        .iter()
+        .any(|key| !key.revoked && key.fingerprint == fingerprint)
+    {
+        HostKeyStatus::Known
+    } else {
+        HostKeyStatus::Mismatch
+    }
+}
+
+/// `ssh-keyscan` output of `host` (see `known_hosts_name`)
+fn scan_host_keys(host: &str) -> Result<String> {
+    let (name, port) = split_known_hosts_name(host);
+    let output = Command::new("ssh-keyscan")
+        .args(&["-T", KEYSCAN_TIMEOUT_SECS, "-p", port, name])
+        .stderr(Stdio::null())
+        .output()?;
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// asks `host` for its keys and adds the one with `fingerprint`
+/// (the one the user agreed to) to `known_hosts`
+pub fn trust_host_key(
+    known_hosts: &Path,
+    host: &str,
+    fingerprint: &str,
+) -> Result<()> {
+    scope_time!("trust_host_key");
+
+    let line = scanned_key(&scan_host_keys(host)?, fingerprint)?
+        .ok_or_else(|| {
+            Error::Generic(format!(
+                "`{}` did not offer the key {} (anymore)",
+                host, fingerprint
+            ))
+        })?;
+
+    if let Some(dir) = known_hosts.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(known_hosts)?;
+    writeln!(file, "{}", line)?;
+
+    Ok(())
+}
+
+/// `[host]:port` -> `(host, port)`
+fn split_known_hosts_name(name: &str) -> (&str, &str) {
+    name.strip_prefix('[')
+        .and_then(|name| name.split_once("]:"))
+        .unwrap_or((name, "22"))
+}
+
+/// the line of `ssh-keyscan` output with the key with
+/// `fingerprint`, as `known_hosts` lists it
+fn scanned_key(
+    scanned: &str,
+    fingerprint: &str,
+) -> Result<Option<String>> {
+    for line in scanned.lines().filter(|line| {
+        !line.trim().is_empty() && !line.starts_with('#')
+    }) {
+        if key_fingerprint(line)?.as_deref() == Some(fingerprint) {
+            return Ok(Some(line.to_string()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// fingerprint of a single `known_hosts` line
+fn key_fingerprint(line: &str) -> Result<Option<String>> {
+    let mut child = Command::new("ssh-keygen")
+        .args(&["-l", "-f", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    if let Some(stdin) = child.stdin.as_mut() {
+        writeln!(stdin, "{}", line)?;
+    }
+
+    let output = child.wait_with_output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // `<bits> <fingerprint> <comment> (<type>)`
+    Ok(stdout
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .filter(|fingerprint| fingerprint.starts_with("SHA256:"))
+        .map(String::from))
+}
+
+/// keys in `ssh-keygen -l -F` output, each line
+/// `<host> <type> <fingerprint>` comes after a comment saying
+/// whether it is `REVOKED` or a `CA`, which are left out
+fn listed_keys(output: &str) -> Vec<ListedKey<'_>> {
+    let mut keys = Vec::new();
+    let mut marker = "";
+
+    for line in output.lines() {
+        if line.starts_with('#') {
+            marker = line.split_whitespace().last().unwrap_or("");
+            continue;
+        }
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if let [_, _, fingerprint] = words[..] {
+            if fingerprint.starts_with("SHA256:") && marker != "CA" {
+                keys.push(ListedKey {
+                    fingerprint,
+                    revoked: marker == "REVOKED",
+                });
+            }
+        }
+    }
+
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    static KEY: &str = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIFkgSinHFA9HyQQXrh+y1Pab3cBoXnTu3Vyev5jLh1fT";
+    static FINGERPRINT: &str =
+        "SHA256:37VuzoJyrgKvs+j2V9evkz7SRFseVjR60p18q32X9hk";
+
+    #[test]
+    fn test_fingerprint_format() {
+        assert_eq!(
+            fingerprint_sha256(&[0; 32]),
+            format!("SHA256:{}", "A".repeat(43))
+        );
+    }
+
+    #[test]
+    fn test_known_hosts_name() {
+        assert_eq!(known_hosts_name("a.com", None), "a.com");
+        assert_eq!(known_hosts_name("a.com", Some(22)), "a.com");
+        assert_eq!(
+            known_hosts_name("a.com", Some(2222)),
+            "[a.com]:2222"
+        );
+
+        assert_eq!(split_known_hosts_name("a.com"), ("a.com", "22"));
+        assert_eq!(
+            split_known_hosts_name("[a.com]:2222"),
+            ("a.com", "2222")
+        );
+    }
+
+    #[test]
+    fn test_check_host_key() {
+        let td = TempDir::new().unwrap();
+        let known_hosts = td.path().join("known_hosts");
+
+        assert_eq!(
+            check_host_key(&known_hosts, "a.com", FINGERPRINT)
+                .unwrap(),
+            HostKeyStatus::Unknown
+        );
+
+        fs::write(
+            &known_hosts,
+            format!("a.com {}\n[b.com]:2222 {}\n", KEY, KEY),
+        )
+        .unwrap();
+
+        assert_eq!(
+            check_host_key(&known_hosts, "a.com", FINGERPRINT)
+                .unwrap(),
+            HostKeyStatus::Known
+        );
+        assert_eq!(
+            check_host_key(&known_hosts, "[b.com]:2222", FINGERPRINT)
+                .unwrap(),
+            HostKeyStatus::Known
+        );
+        assert_eq!(
+            check_host_key(&known_hosts, "b.com", FINGERPRINT)
+                .unwrap(),
+            HostKeyStatus::Unknown
+        );
+        assert_eq!(
+            check_host_key(
+                &known_hosts,
+                "a.com",
+                &fingerprint_sha256(&[0; 32])
+            )
+            .unwrap(),
+            HostKeyStatus::Mismatch
+        );
+
+        fs::write(&known_hosts, format!("@revoked a.com {}\n", KEY))
+            .unwrap();
+
+        assert_eq!(
+            check_host_key(&known_hosts, "a.com", FINGERPRINT)
+                .unwrap(),
+            HostKeyStatus::Revoked
+        );
+    }
+
+    #[test]
+    fn test_host_key_status() {
+        // an rsa key offered by a host listed with an ed25519 one
+        let other = fingerprint_sha256(&[0; 32]);
+        let listed = |fingerprint, revoked| ListedKey {
+            fingerprint,
+            revoked,
+        };
+        let ed25519 = [listed(FINGERPRINT, false)];
+
+        assert_eq!(
+            host_key_status(&[], &other),
+            HostKeyStatus::Unknown
+        );
+        assert_eq!(
+            host_key_status(&ed25519, FINGERPRINT),
+            HostKeyStatus::Known
+        );
+        assert_eq!(
+            host_key_status(&ed25519, &other),
+            HostKeyStatus::Mismatch
+        );
+
+        let revoked =
+            [listed(FINGERPRINT, true), listed(FINGERPRINT, false)];
+        assert_eq!(
+            host_key_status(&revoked, FINGERPRINT),
+            HostKeyStatus::Revoked
+        );
+        // a host listed with revoked keys only is not new either
+        assert_eq!(
+            host_key_status(&revoked[..1], &other),
+            HostKeyStatus::Mismatch
+        );
+    }
+
+    #[test]
+    fn test_listed_keys() {
+        let output = format!(
+            "# Host a.com found: line 1 \na.com ED25519 {0}\n\
+             # Host a.com found: line 2 REVOKED\na.com RSA {0}\n\
+             # Host a.com found: line 3 CA\na.com RSA {0}\n",
+            FINGERPRINT
+        );
+
+        assert_eq!(
+            listed_keys(&output),
+            vec![
+                ListedKey {
+                    fingerprint: FINGERPRINT,
+                    revoked: false,
+                },
+                ListedKey {
+                    fingerprint: FINGERPRINT,
+                    revoked: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scanned_key() {
+        let scanned =
+            format!("# a.com:22 SSH-2.0-OpenSSH\na.com {}\n", KEY);
+
+        assert_eq!(
+            scanned_key(&scanned, FINGERPRINT).unwrap(),
+            Some(format!("a.com {}", KEY))
+        );
+        assert_eq!(
+            scanned_key(&scanned, &fingerprint_sha256(&[0; 32]))
+                .unwrap(),
+            None
+        );
+    }
+}
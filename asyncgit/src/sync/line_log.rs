@@ -0,0 +1,377 @@
+//! `git log -L` like filtering: commits that changed a range of
+//! lines of a file, following the range back through history
+
+use super::CommitId;
+use crate::error::Result;
+use git2::{DiffOptions, Patch, Repository, Tree};
+use std::{collections::HashMap, path::Path};
+
+/// lines `start..=end` (1-based) of the file at `path`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineRange {
+    ///
+    pub path: String,
+    ///
+    pub start: usize,
+    ///
+    pub end: usize,
+}
+
+/// `(old_start, old_lines, new_start, new_lines)` of `@@ -a,b +c,d @@`
+type Hunk = (usize, usize, usize, usize);
+
+/// `(start, end)` of the range in a parent commit
+type ParentRange = (CommitId, (usize, usize));
+
+/// where the range is in the commits a walk gets to next, created
+/// by `LogWalker` for each walk.
+///
+/// the range is moved along with the lines inserted or removed
+/// above it on the way back from the tips, renames are not followed
+pub(crate) struct LineRangeWalk {
+    path: String,
+    ranges: HashMap<CommitId, (usize, usize)>,
+}
+
+impl LineRangeWalk {
+    /// `range` is where the lines are in each of the `tips`
+    pub(crate) fn new(range: &LineRange, tips: &[CommitId]) -> Self {
+        Self {
+            path: range.path.clone(),
+            ranges: tips
+                .iter()
+                .map(|tip| (*tip, (range.start, range.end)))
+                .collect(),
+        }
+    }
+
+    /// whether commit `id` changed the range, expects the commits
+    /// of the walk in order (children before parents)
+    pub(crate) fn touched(
+        &mut self,
+        repo: &Repository,
+        id: &CommitId,
+    ) -> Result<bool> {
+        let lines = match self.ranges.remove(id) {
+            Some(lines) => lines,
+            None => return Ok(false),
+        };
+
+        let (touched, parents) =
+            follow_range(repo, id, &self.path, lines)?;
+        for (parent, (start, end)) in parents {
+            // reached from several children, keep all
+            let lines =
+                self.ranges.entry(parent).or_insert((start, end));
+            *lines = (lines.0.min(start), lines.1.max(end));
+        }
+
+        Ok(touched)
+    }
+}
+
+/// whether commit `id` changed `lines` of `path` and where those
+/// lines are in its parents
+fn follow_range(
+    repo: &Repository,
+    id: &CommitId,
+    path: &str,
+    lines: (usize, usize),
+) -> Result<(bool, Vec<ParentRange>)> {
+    let commit = repo.find_commit((*id).into())?;
+    let tree = commit.tree()?;
+
+    if tree.get_path(Path::new(path)).is_err() {
+        return Ok((false, Vec::new()));
+    }
+
+    // a root commit added the file
+    let mut touched = commit.parent_count() == 0;
+    let mut parents = Vec::new();
+
+    for (idx, parent) in commit.parents().enumerate() {
+        let parent_tree = parent.tree()?;
+
+        let (changed, in_parent) =
+            if parent_tree.get_path(Path::new(path)).is_err() {
+                (true, None)
+            } else {
+                let hunks =
+                    diff_hunks(repo, &parent_tree, &tree, path)?;
+                map_range(&hunks, lines)
+            };
+
+        // like `git log`, a merge only counts if it differs from
+        // all its parents
+        touched = if idx == 0 {
+            changed
+        } else {
+            touched && changed
+        };
+
+        if let Some(in_parent) = in_parent {
+            parents.push((parent.id().into(), in_parent));
+        }
+    }
+
+    Ok((touched, parents))
+}
+
+fn diff_hunks(
+    repo: &Repository,
+    old: &Tree,
+    new: &Tree,
+    path: &str,
+) -> Result<Vec<Hunk>> {
+    let mut opts = DiffOptions::new();
+    opts.pathspec(path)
+        .disable_pathspec_match(true)
+        .context_lines(0);
+
+    let diff = repo.diff_tree_to_tree(
+        Some(old),
+        Some(new),
+        Some(&mut opts),
+    )?;
+
+    let mut hunks = Vec::new();
+    for idx in 0..diff.deltas().len() {
+        if let Some(patch) = Patch::from_diff(&diff, idx)? {
+            for hunk_idx in 0..patch.num_hunks() {
+                let (hunk, _) = patch.hunk(hunk_idx)?;
+                hunks.push((
+                    hunk.old_start() as usize,
+                    hunk.old_lines() as usize,
+                    hunk.new_start() as usize,
+                    hunk.new_lines() as usize,
+                ));
+            }
+        }
+    }
+
+    Ok(hunks)
+}
+
+/// whether `hunks` (sorted, without context) change `lines` of the
+/// new file and where `lines` are in the old one, `None` if all of
+/// them were added
+fn map_range(
+    hunks: &[Hunk],
+    (start, end): (usize, usize),
+) -> (bool, Option<(usize, usize)>) {
+    let touched =
+        hunks.iter().any(|&(_, _, new_start, new_lines)| {
+            if new_lines == 0 {
+                // lines removed between `new_start` and the next one
+                start <= new_start && new_start < end
+            } else {
+                new_start <= end && start < new_start + new_lines
+            }
+        });
+
+    let old_start = to_old_line(hunks, start, true);
+    let old_end = to_old_line(hunks, end, false);
+
+    (
+        touched,
+        (old_start <= old_end).then_some((old_start, old_end)),
+    )
+}
+
+/// line `line` of the new file in the old one, lines changed by a
+/// hunk map to the first (`first`) or last line the hunk replaced
+fn to_old_line(hunks: &[Hunk], line: usize, first: bool) -> usize {
+    let mut offset: isize = 0;
+
+    for &(old_start, old_lines, new_start, new_lines) in hunks {
+        let inside = new_lines > 0
+            && new_start <= line
+            && line < new_start + new_lines;
+
+        if inside {
+            return match (first, old_lines) {
+                // all new, continues after `old_start`
+                (true, 0) => old_start + 1,
+                (false, 0) => old_start,
+                (true, _) => old_start,
+                (false, _) => old_start + old_lines - 1,
+            };
+        }
+
+        let before = if new_lines == 0 {
+            new_start < line
+        } else {
+            new_start + new_lines <= line
+        };
+        if !before {
+            break;
+        }
+
+        offset += old_lines as isize - new_lines as isize;
+    }
+
+    (line as isize + offset).max(0) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit, stage_add_file, tests::repo_init_empty, LogWalker,
+        LogWalkerFilter,
+    };
+    use std::{fs::File, io::Write, sync::Arc};
+
+    #[test]
+    fn test_map_range() {
+        // line 2 changed
+        assert_eq!(
+            map_range(&[(2, 1, 2, 1)], (1, 3)),
+            (true, Some((1, 3)))
+        );
+        assert_eq!(
+            map_range(&[(2, 1, 2, 1)], (3, 4)),
+            (false, Some((3, 4)))
+        );
+
+        // 3 lines inserted at the top
+        assert_eq!(
+            map_range(&[(0, 0, 1, 3)], (4, 6)),
+            (false, Some((1, 3)))
+        );
+        assert_eq!(map_range(&[(0, 0, 1, 3)], (1, 3)), (true, None));
+        assert_eq!(
+            map_range(&[(0, 0, 1, 3)], (2, 5)),
+            (true, Some((1, 2)))
+        );
+
+        // 2 lines removed after line 4
+        assert_eq!(
+            map_range(&[(5, 2, 4, 0)], (3, 6)),
+            (true, Some((3, 8)))
+        );
+        assert_eq!(
+            map_range(&[(5, 2, 4, 0)], (6, 7)),
+            (false, Some((8, 9)))
+        );
+    }
+
+    #[test]
+    fn test_line_range() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        let file = Path::new("foo.txt");
+
+        let mut lines: Vec<String> =
+            ('a'..='j').map(String::from).collect();
+        let commit_lines = |lines: &[String], msg: &str| {
+            File::create(root.join(file))
+                .unwrap()
+                .write_all(
+                    format!("{}\n", lines.join("\n")).as_bytes(),
+                )
+                .unwrap();
+            stage_add_file(repo_path, file).unwrap();
+            commit(repo_path, msg).unwrap()
+        };
+
+        let c1 = commit_lines(&lines, "add");
+        lines[1] = String::from("B");
+        let c2 = commit_lines(&lines, "change b");
+        for l in &["x", "y", "z"] {
+            lines.insert(0, (*l).to_string());
+        }
+        let c3 = commit_lines(&lines, "insert on top");
+        lines[8] = String::from("F");
+        commit_lines(&lines, "change f");
+
+        let walk =
+            |range: &LineRange, filter: Option<LogWalkerFilter>| {
+                let mut items = Vec::new();
+                LogWalker::new(&repo)
+                    .line_range(Some(range.clone()))
+                    .filter(filter)
+                    .read(&mut items, 100)
+                    .unwrap();
+                items
+            };
+
+        // `a`, `B` and `c` after the insertion on top
+        let range = LineRange {
+            path: String::from("foo.txt"),
+            start: 4,
+            end: 6,
+        };
+        assert_eq!(walk(&range, None), vec![c2, c1]);
+
+        // each walk starts over at the new `HEAD`
+        lines.insert(0, String::from("w"));
+        let c5 = commit_lines(&lines, "insert another one on top");
+        // now `x`, `a` and `B`, `x` was added by `c3`
+        assert_eq!(walk(&range, None), vec![c3, c2, c1]);
+
+        let range = LineRange {
+            path: String::from("foo.txt"),
+            start: 1,
+            end: 1,
+        };
+        let only_c1: LogWalkerFilter =
+            Arc::new(Box::new(move |_, id| Ok(*id == c1)));
+        assert_eq!(walk(&range, None), vec![c5]);
+        assert_eq!(walk(&range, Some(only_c1)), Vec::new());
+    }
+
+    #[test]
+    fn test_line_range_all_refs() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        let file = Path::new("foo.txt");
+
+        let commit_content = |content: &str, msg: &str| {
+            File::create(root.join(file))
+                .unwrap()
+                .write_all(content.as_bytes())
+                .unwrap();
+            stage_add_file(repo_path, file).unwrap();
+            commit(repo_path, msg).unwrap()
+        };
+
+        let c1 = commit_content("a\nb\n", "add");
+        repo.branch(
+            "other",
+            &repo.find_commit(c1.into()).unwrap(),
+            false,
+        )
+        .unwrap();
+        let c2 = commit_content("a\nB\n", "change b");
+
+        repo.set_head("refs/heads/other").unwrap();
+        repo.checkout_head(Some(
+            git2::build::CheckoutBuilder::new().force(),
+        ))
+        .unwrap();
+        let c3 = commit_content("A\nb\n", "change a on other");
+        repo.set_head("refs/heads/master").unwrap();
+
+        let walk = |start, all_refs| {
+            let mut items = Vec::new();
+            LogWalker::new(&repo)
+                .line_range(Some(LineRange {
+                    path: String::from("foo.txt"),
+                    start,
+                    end: start,
+                }))
+                .all_refs(all_refs)
+                .read(&mut items, 100)
+                .unwrap();
+            items
+        };
+
+        assert_eq!(walk(1, false), vec![c1]);
+        // followed from the tip of `other` as well
+        assert_eq!(walk(1, true), vec![c3, c1]);
+        assert_eq!(walk(2, true), vec![c2, c1]);
+    }
+}
@@ -1,11 +1,137 @@
-use super::CommitId;
-use crate::error::Result;
-use git2::{Repository, Revwalk};
+use super::{
+    line_log::{LineRange, LineRangeWalk},
+    utils::{repo, repo_allow_bare},
+    worktree::get_common_dir,
+    CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{Commit, Oid, Repository, Revwalk, Sort};
+use scopetime::scope_time;
+use std::{
+    collections::{BinaryHeap, HashSet},
+    fs,
+    sync::Arc,
+};
+
+/// predicate deciding whether a commit shows up in the log
+pub type LogWalkerFilter = Arc<
+    Box<dyn Fn(&Repository, &CommitId) -> Result<bool> + Send + Sync>,
+>;
+
+/// called for every commit the walk looks at (before the filter),
+/// failing ends the walk
+pub type LogWalkerScan<'a> = Box<dyn FnMut() -> Result<()> + 'a>;
+
+/// how far back `LogWalker` goes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LogWindow {
+    /// commits from this time (seconds since the epoch) on,
+    /// the walk ends at the first older one unless in
+    /// topological order, which only skips it
+    Since(i64),
+    /// commits not reachable from this revision, like `rev..HEAD`
+    SinceRev(String),
+    /// the `n` most recent commits
+    Last(usize),
+}
+
+/// walks the history of a shallow clone newest first, `Revwalk`
+/// gives up on the first commit whose parents were not fetched
+struct ShallowWalk {
+    queue: BinaryHeap<(i64, Oid)>,
+    seen: HashSet<Oid>,
+}
+
+impl ShallowWalk {
+    /// leaves out `hide` and everything reachable from it,
+    /// starts at all refs instead of just `HEAD` if `all_refs`
+    fn new(
+        repo: &Repository,
+        hide: Option<Oid>,
+        tips: &[Commit],
+    ) -> Self {
+        let mut walk = Self {
+            queue: BinaryHeap::new(),
+            seen: HashSet::new(),
+        };
+
+        let mut hidden: Vec<Oid> = hide.into_iter().collect();
+        while let Some(id) = hidden.pop() {
+            if walk.seen.insert(id) {
+                if let Ok(commit) = repo.find_commit(id) {
+                    hidden.extend(commit.parent_ids());
+                }
+            }
+        }
+
+        for tip in tips {
+            if walk.seen.insert(tip.id()) {
+                walk.queue.push((tip.time().seconds(), tip.id()));
+            }
+        }
+
+        walk
+    }
+
+    fn next(&mut self, repo: &Repository) -> Option<Oid> {
+        let (_, id) = self.queue.pop()?;
+
+        if let Ok(commit) = repo.find_commit(id) {
+            for parent in commit.parent_ids() {
+                if !self.seen.insert(parent) {
+                    continue;
+                }
+                // missing beyond the shallow boundary
+                if let Ok(parent) = repo.find_commit(parent) {
+                    self.queue
+                        .push((parent.time().seconds(), parent.id()));
+                }
+            }
+        }
+
+        Some(id)
+    }
+}
+
+/// the commits a walk starts at: `HEAD` and, if `all_refs`, every
+/// ref pointing to a commit
+fn walk_tips(
+    repo: &Repository,
+    all_refs: bool,
+) -> Result<Vec<Commit<'_>>> {
+    let mut tips = vec![repo.head()?.peel_to_commit()?];
+    if all_refs {
+        for reference in repo.references()?.flatten() {
+            if let Ok(commit) = reference.peel_to_commit() {
+                tips.push(commit);
+            }
+        }
+    }
+
+    Ok(tips)
+}
+
+enum Walk<'a> {
+    Revwalk(Revwalk<'a>),
+    Shallow(ShallowWalk),
+}
 
 ///
 pub struct LogWalker<'a> {
     repo: &'a Repository,
-    revwalk: Option<Revwalk<'a>>,
+    walk: Option<Walk<'a>>,
+    filter: Option<LogWalkerFilter>,
+    on_scan: Option<LogWalkerScan<'a>>,
+    line_range: Option<LineRange>,
+    /// follows `line_range` through the walk started by `read`
+    line_walk: Option<LineRangeWalk>,
+    scan_limit: Option<usize>,
+    window: Option<LogWindow>,
+    topo_order: bool,
+    all_refs: bool,
+    scanned: usize,
+    /// reached the end of the window
+    ended: bool,
 }
 
 impl<'a> LogWalker<'a> {
@@ -13,28 +139,193 @@ impl<'a> LogWalker<'a> {
     pub fn new(repo: &'a Repository) -> Self {
         Self {
             repo,
-            revwalk: None,
+            walk: None,
+            filter: None,
+            on_scan: None,
+            line_range: None,
+            line_walk: None,
+            scan_limit: None,
+            window: None,
+            topo_order: false,
+            all_refs: false,
+            scanned: 0,
+            ended: false,
         }
     }
 
+    /// only commits the `filter` returns `true` for are returned by `read`
+    pub fn filter(self, filter: Option<LogWalkerFilter>) -> Self {
+        Self { filter, ..self }
+    }
+
+    /// `on_scan` is called for each commit looked at, including
+    /// the ones left out by `filter` or `line_range`
+    pub fn on_scan(self, on_scan: Option<LogWalkerScan<'a>>) -> Self {
+        Self { on_scan, ..self }
+    }
+
+    /// like `git log -L`, only returns the commits that changed
+    /// `range` (before `filter` gets to see them). the range is
+    /// followed back from every commit the walk starts at
+    pub fn line_range(self, line_range: Option<LineRange>) -> Self {
+        Self { line_range, ..self }
+    }
+
+    /// stops after `limit` commits were looked at,
+    /// no matter how many of them passed the filter
+    pub fn scan_limit(self, scan_limit: Option<usize>) -> Self {
+        Self { scan_limit, ..self }
+    }
+
+    /// only walks the commits within `window`
+    pub fn window(self, window: Option<LogWindow>) -> Self {
+        Self { window, ..self }
+    }
+
+    /// like `git log --topo-order`, the lines of history of a
+    /// merge follow one another instead of being mixed by date.
+    /// shallow clones are always walked by date
+    pub fn topo_order(self, topo_order: bool) -> Self {
+        Self { topo_order, ..self }
+    }
+
+    /// like `git log --all`, starts at every ref (branches, remote
+    /// branches, tags..) and not just at `HEAD`
+    pub fn all_refs(self, all_refs: bool) -> Self {
+        Self { all_refs, ..self }
+    }
+
     ///
     pub fn read(
         &mut self,
         out: &mut Vec<CommitId>,
         limit: usize,
+    ) -> Result<usize> {
+        self.read_with(limit, |_, id| out.push(id))
+    }
+
+    /// like `read` but pairs each commit with its index in the
+    /// unfiltered log
+    pub fn read_indexed(
+        &mut self,
+        out: &mut Vec<(usize, CommitId)>,
+        limit: usize,
+    ) -> Result<usize> {
+        self.read_with(limit, |idx, id| out.push((idx, id)))
+    }
+
+    fn read_with<F: FnMut(usize, CommitId)>(
+        &mut self,
+        limit: usize,
+        mut push: F,
     ) -> Result<usize> {
         let mut count = 0_usize;
 
-        if self.revwalk.is_none() {
-            let mut walk = self.repo.revwalk()?;
-            walk.push_head()?;
-            self.revwalk = Some(walk);
+        if self.walk.is_none() {
+            let hide = match &self.window {
+                Some(LogWindow::SinceRev(rev)) => {
+                    Some(resolve_rev_repo(self.repo, rev)?.into())
+                }
+                _ => None,
+            };
+
+            let shallow = self.repo.is_shallow();
+            let tips = if shallow || self.line_range.is_some() {
+                walk_tips(self.repo, self.all_refs)?
+            } else {
+                Vec::new()
+            };
+
+            self.line_walk = self.line_range.as_ref().map(|range| {
+                let tips: Vec<CommitId> =
+                    tips.iter().map(|tip| tip.id().into()).collect();
+                LineRangeWalk::new(range, &tips)
+            });
+
+            self.walk = Some(if shallow {
+                Walk::Shallow(ShallowWalk::new(
+                    self.repo, hide, &tips,
+                ))
+            } else {
+                let mut walk = self.repo.revwalk()?;
+                if self.topo_order {
+                    walk.set_sorting(Sort::TOPOLOGICAL)?;
+                }
+                walk.push_head()?;
+                if self.all_refs {
+                    // skips refs to anything but commits
+                    walk.push_glob("*")?;
+                }
+                if let Some(hide) = hide {
+                    walk.hide(hide)?;
+                }
+                Walk::Revwalk(walk)
+            });
         }
 
-        if let Some(ref mut walk) = self.revwalk {
-            for id in walk {
+        let scan_limit = match self.window {
+            Some(LogWindow::Last(n)) => {
+                self.scan_limit.map_or(n, |limit| limit.min(n))
+            }
+            _ => self.scan_limit.unwrap_or(usize::MAX),
+        };
+        let since = match self.window {
+            Some(LogWindow::Since(time)) => Some(time),
+            _ => None,
+        };
+
+        let by_date = !self.topo_order
+            || matches!(self.walk, Some(Walk::Shallow(_)));
+
+        if let Some(ref mut walk) = self.walk {
+            while !self.ended && self.scanned < scan_limit {
+                let id = match walk {
+                    Walk::Revwalk(walk) => walk.next(),
+                    Walk::Shallow(walk) => {
+                        walk.next(self.repo).map(Ok)
+                    }
+                };
+                let id = match id {
+                    Some(id) => id,
+                    None => break,
+                };
+
+                if let (Some(since), Ok(id)) = (since, &id) {
+                    let time = self.repo.find_commit(*id)?.time();
+                    if time.seconds() < since {
+                        // newer commits can still follow an older
+                        // one in topological order
+                        if by_date {
+                            self.ended = true;
+                            break;
+                        }
+                        continue;
+                    }
+                }
+
+                let idx = self.scanned;
+                self.scanned += 1;
+
+                if let Some(ref mut on_scan) = self.on_scan {
+                    on_scan()?;
+                }
+
                 if let Ok(id) = id {
-                    out.push(id.into());
+                    let id = CommitId::from(id);
+
+                    if let Some(ref mut line_walk) = self.line_walk {
+                        if !line_walk.touched(self.repo, &id)? {
+                            continue;
+                        }
+                    }
+
+                    if let Some(ref filter) = self.filter {
+                        if !filter(self.repo, &id)? {
+                            continue;
+                        }
+                    }
+
+                    push(idx, id);
                     count += 1;
 
                     if count == limit {
@@ -48,6 +339,82 @@ impl<'a> LogWalker<'a> {
     }
 }
 
+/// commit `rev` (like `v1.0` or `HEAD~10`) points to
+pub fn resolve_rev(repo_path: &str, rev: &str) -> Result<CommitId> {
+    scope_time!("resolve_rev");
+
+    resolve_rev_repo(&repo_allow_bare(repo_path)?, rev)
+}
+
+fn resolve_rev_repo(
+    repo: &Repository,
+    rev: &str,
+) -> Result<CommitId> {
+    let commit = repo.revparse_single(rev)?.peel_to_commit()?;
+    Ok(commit.id().into())
+}
+
+/// commits of a shallow clone whose parents were not fetched,
+/// empty unless the repo is shallow
+pub fn get_shallow_boundary(
+    repo_path: &str,
+) -> Result<HashSet<CommitId>> {
+    scope_time!("get_shallow_boundary");
+
+    let repo = repo_allow_bare(repo_path)?;
+    if !repo.is_shallow() {
+        return Ok(HashSet::new());
+    }
+
+    let shallow =
+        fs::read_to_string(get_common_dir(&repo).join("shallow"))?;
+
+    Ok(shallow
+        .lines()
+        .filter_map(|line| Oid::from_str(line.trim()).ok())
+        .map(CommitId::new)
+        .collect())
+}
+
+/// commits on the ancestry path between two related commits
+/// (newest first, including both ends)
+pub fn get_commits_between(
+    repo_path: &str,
+    a: CommitId,
+    b: CommitId,
+) -> Result<Vec<CommitId>> {
+    scope_time!("get_commits_between");
+
+    let repo = repo(repo_path)?;
+
+    let (ancestor, descendant) =
+        if a == b || repo.graph_descendant_of(b.into(), a.into())? {
+            (a, b)
+        } else if repo.graph_descendant_of(a.into(), b.into())? {
+            (b, a)
+        } else {
+            return Err(Error::Generic(String::from(
+                "commits are not ancestor/descendant of each other",
+            )));
+        };
+
+    let mut walk = repo.revwalk()?;
+    walk.push(descendant.into())?;
+    walk.hide(ancestor.into())?;
+
+    let mut res = Vec::new();
+    for id in walk {
+        let id = id?;
+        // only keep commits actually leading back to `ancestor`
+        if repo.graph_descendant_of(id, ancestor.into())? {
+            res.push(id.into());
+        }
+    }
+    res.push(ancestor);
+
+    Ok(res)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,7 +422,91 @@ mod tests {
         commit, get_commits_info, stage_add_file,
         tests::repo_init_empty,
     };
-    use std::{fs::File, io::Write, path::Path};
+    use git2::{Signature, Time};
+    use std::{
+        fs::File,
+        io::Write,
+        path::Path,
+        process::Command,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_commits_between() -> Result<()> {
+        let file_path = Path::new("foo");
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let mut ids = Vec::new();
+        for i in 0..4 {
+            File::create(&root.join(file_path))?
+                .write_all(format!("{}", i).as_bytes())?;
+            stage_add_file(repo_path, file_path).unwrap();
+            ids.push(commit(repo_path, &format!("c{}", i)).unwrap());
+        }
+
+        let res = get_commits_between(repo_path, ids[3], ids[1])?;
+        assert_eq!(res, vec![ids[3], ids[2], ids[1]]);
+
+        // order of arguments does not matter
+        let res = get_commits_between(repo_path, ids[1], ids[3])?;
+        assert_eq!(res, vec![ids[3], ids[2], ids[1]]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_commits_between_unrelated() -> Result<()> {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let sig = repo.signature()?;
+        let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+        let a = repo.commit(None, &sig, &sig, "a", &tree, &[])?;
+        let b = repo.commit(None, &sig, &sig, "b", &tree, &[])?;
+
+        assert!(get_commits_between(repo_path, a.into(), b.into())
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_filter() -> Result<()> {
+        let file_path = Path::new("foo");
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(&root.join(file_path))?.write_all(b"a")?;
+        stage_add_file(repo_path, file_path).unwrap();
+        let oid1 = commit(repo_path, "commit1").unwrap();
+        File::create(&root.join(file_path))?.write_all(b"b")?;
+        stage_add_file(repo_path, file_path).unwrap();
+        commit(repo_path, "commit2").unwrap();
+
+        let filter: LogWalkerFilter =
+            Arc::new(Box::new(move |_, id| Ok(*id == oid1)));
+
+        let mut items = Vec::new();
+        let mut walk =
+            LogWalker::new(&repo).filter(Some(Arc::clone(&filter)));
+        walk.read(&mut items, 100).unwrap();
+
+        assert_eq!(items, vec![oid1]);
+
+        // the index is the position in the unfiltered log
+        let mut items = Vec::new();
+        let mut walk = LogWalker::new(&repo).filter(Some(filter));
+        walk.read_indexed(&mut items, 100).unwrap();
+
+        assert_eq!(items, vec![(1, oid1)]);
+
+        Ok(())
+    }
 
     #[test]
     fn test_limit() -> Result<()> {
@@ -81,6 +532,270 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_scan_limit() -> Result<()> {
+        let file_path = Path::new("foo");
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let mut ids = Vec::new();
+        for i in 0..4 {
+            File::create(&root.join(file_path))?
+                .write_all(format!("{}", i).as_bytes())?;
+            stage_add_file(repo_path, file_path).unwrap();
+            ids.push(commit(repo_path, &format!("c{}", i)).unwrap());
+        }
+
+        let seen = Arc::new(AtomicUsize::new(0));
+        let filter: LogWalkerFilter = {
+            let seen = Arc::clone(&seen);
+            Arc::new(Box::new(move |_, _| {
+                seen.fetch_add(1, Ordering::Relaxed);
+                Ok(false)
+            }))
+        };
+
+        // nothing matches but the walk still ends after 2 commits
+        let mut items = Vec::new();
+        let mut walk = LogWalker::new(&repo)
+            .filter(Some(filter))
+            .scan_limit(Some(2));
+        assert_eq!(walk.read(&mut items, 100)?, 0);
+        assert_eq!(walk.read(&mut items, 100)?, 0);
+        assert_eq!(seen.load(Ordering::Relaxed), 2);
+
+        let mut walk = LogWalker::new(&repo).scan_limit(Some(3));
+        walk.read(&mut items, 2)?;
+        walk.read(&mut items, 2)?;
+        assert_eq!(items, vec![ids[3], ids[2], ids[1]]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_window() -> Result<()> {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+        let mut ids: Vec<CommitId> = Vec::new();
+        for time in &[1000, 2000, 3000, 4000] {
+            let sig = Signature::new(
+                "name",
+                "email",
+                &Time::new(*time, 0),
+            )?;
+            let parent = ids
+                .last()
+                .map(|id| repo.find_commit((*id).into()))
+                .transpose()?;
+            let parents: Vec<_> = parent.iter().collect();
+            ids.push(
+                repo.commit(
+                    Some("HEAD"),
+                    &sig,
+                    &sig,
+                    "msg",
+                    &tree,
+                    &parents,
+                )?
+                .into(),
+            );
+        }
+        repo.reference("refs/tags/v1", ids[1].into(), false, "")?;
+
+        let walk = |window: LogWindow, scan_limit: Option<usize>| {
+            let mut items = Vec::new();
+            let mut walk = LogWalker::new(&repo)
+                .window(Some(window))
+                .scan_limit(scan_limit);
+            walk.read(&mut items, 100).unwrap();
+            // nothing more after the end of the window
+            assert_eq!(walk.read(&mut items, 100).unwrap(), 0);
+            items
+        };
+
+        assert_eq!(
+            walk(LogWindow::Last(2), None),
+            vec![ids[3], ids[2]]
+        );
+        assert_eq!(walk(LogWindow::Last(3), Some(1)), vec![ids[3]]);
+        assert_eq!(
+            walk(LogWindow::Since(2000), None),
+            vec![ids[3], ids[2], ids[1]]
+        );
+        assert_eq!(walk(LogWindow::Since(5000), None), vec![]);
+        assert_eq!(
+            walk(LogWindow::SinceRev("v1".into()), None),
+            vec![ids[3], ids[2]]
+        );
+        assert_eq!(
+            walk(LogWindow::SinceRev("HEAD".into()), None),
+            vec![]
+        );
+
+        assert_eq!(resolve_rev(repo_path, "v1")?, ids[1]);
+        assert!(resolve_rev(repo_path, "nope").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_topo_order() -> Result<()> {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+
+        let commit = |time: i64, parents: &[CommitId]| {
+            let sig =
+                Signature::new("name", "email", &Time::new(time, 0))
+                    .unwrap();
+            let parents: Vec<_> = parents
+                .iter()
+                .map(|id| repo.find_commit((*id).into()).unwrap())
+                .collect();
+            let parents: Vec<_> = parents.iter().collect();
+            CommitId::from(
+                repo.commit(None, &sig, &sig, "msg", &tree, &parents)
+                    .unwrap(),
+            )
+        };
+
+        // two lines of history with interleaved dates, merged
+        let base = commit(1000, &[]);
+        let feature1 = commit(2000, &[base]);
+        let main1 = commit(3000, &[base]);
+        let feature2 = commit(4000, &[feature1]);
+        let main2 = commit(5000, &[main1]);
+        let merge = commit(6000, &[main2, feature2]);
+        repo.reference("refs/heads/master", merge.into(), true, "")?;
+
+        let walk_since = |topo_order: bool, since: Option<i64>| {
+            let mut items = Vec::new();
+            LogWalker::new(&repo)
+                .topo_order(topo_order)
+                .window(since.map(LogWindow::Since))
+                .read(&mut items, 100)
+                .unwrap();
+            items
+        };
+        let walk = |topo_order: bool| walk_since(topo_order, None);
+
+        assert_eq!(
+            walk(false),
+            vec![merge, main2, feature2, main1, feature1, base]
+        );
+
+        let topo = walk(true);
+        let pos = |id| topo.iter().position(|i| *i == id).unwrap();
+        assert_eq!(topo.len(), 6);
+        assert_eq!(topo[0], merge);
+        assert_eq!(topo[5], base);
+        assert_eq!(pos(main1), pos(main2) + 1);
+        assert_eq!(pos(feature1), pos(feature2) + 1);
+
+        // `feature1` is older but followed by newer commits
+        let mut since = walk_since(true, Some(2500));
+        since.sort();
+        let mut expected = vec![merge, main2, feature2, main1];
+        expected.sort();
+        assert_eq!(since, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_all_refs() -> Result<()> {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let base = commit(repo_path, "base").unwrap();
+        let feature = commit(repo_path, "feature").unwrap();
+        repo.branch(
+            "feature",
+            &repo.find_commit(feature.into())?,
+            false,
+        )?;
+        repo.reset(
+            repo.find_commit(base.into())?.as_object(),
+            git2::ResetType::Hard,
+            None,
+        )?;
+        let master = commit(repo_path, "master").unwrap();
+        // an annotated tag of a blob is no tip
+        let blob = repo.blob(b"blob")?;
+        repo.tag(
+            "blob",
+            &repo.find_object(blob, None)?,
+            &repo.signature()?,
+            "tag",
+            false,
+        )?;
+
+        let walk = |all_refs: bool| {
+            let mut items = Vec::new();
+            LogWalker::new(&repo)
+                .all_refs(all_refs)
+                .read(&mut items, 100)
+                .unwrap();
+            items.sort();
+            items
+        };
+
+        let mut all = vec![base, feature, master];
+        all.sort();
+        let mut head = vec![base, master];
+        head.sort();
+
+        assert_eq!(walk(false), head);
+        assert_eq!(walk(true), all);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_shallow_clone() -> Result<()> {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let ids: Vec<CommitId> = (0..4)
+            .map(|i| commit(repo_path, &format!("c{}", i)).unwrap())
+            .collect();
+
+        let td = TempDir::new()?;
+        let clone = td.path().join("clone");
+        assert!(Command::new("git")
+            .args(&["clone", "-q", "--depth", "2"])
+            .arg(format!("file://{}", repo_path))
+            .arg(&clone)
+            .current_dir(td.path())
+            .status()?
+            .success());
+        let clone_path = clone.to_str().unwrap();
+        let shallow = Repository::open(&clone)?;
+
+        let mut items = Vec::new();
+        LogWalker::new(&shallow).read(&mut items, 100)?;
+        assert_eq!(items, vec![ids[3], ids[2]]);
+
+        let mut items = Vec::new();
+        LogWalker::new(&shallow)
+            .window(Some(LogWindow::SinceRev("HEAD~1".into())))
+            .read(&mut items, 100)?;
+        assert_eq!(items, vec![ids[3]]);
+
+        assert_eq!(
+            get_shallow_boundary(clone_path)?,
+            vec![ids[2]].into_iter().collect()
+        );
+        assert!(get_shallow_boundary(repo_path)?.is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_logwalker() -> Result<()> {
         let file_path = Path::new("foo");
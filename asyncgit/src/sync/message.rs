@@ -0,0 +1,266 @@
+//! composing commit and tag messages in an editor the way git does
+
+use super::utils::repo;
+use crate::error::{Error, Result};
+use git2::Config;
+use scopetime::scope_time;
+use std::{fs, path::PathBuf};
+
+/// how an edited message is tidied up, see `commit.cleanup`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageCleanup {
+    /// drops comment lines on top of `Whitespace` (the default)
+    Strip,
+    /// drops trailing whitespace as well as leading, trailing and
+    /// repeated empty lines
+    Whitespace,
+    /// leaves the message alone
+    Verbatim,
+    /// like `Whitespace` but cuts off the message at the scissors
+    /// line
+    Scissors,
+}
+
+/// what the editor for a message is started with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageConfig {
+    /// `core.editor`
+    pub editor: Option<String>,
+    /// contents of the file `commit.template` points to
+    pub template: Option<String>,
+    /// `commit.cleanup`
+    pub cleanup: MessageCleanup,
+    /// `core.commentChar`, starts the lines `Strip` drops
+    pub comment_char: char,
+    /// where git keeps files like `COMMIT_EDITMSG`
+    pub git_dir: PathBuf,
+}
+
+/// `core.editor`, if set
+pub fn get_core_editor(repo_path: &str) -> Result<Option<String>> {
+    scope_time!("get_core_editor");
+
+    let repo = repo(repo_path)?;
+
+    Ok(core_editor(&repo.config()?))
+}
+
+fn core_editor(config: &Config) -> Option<String> {
+    config
+        .get_string("core.editor")
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+}
+
+/// reads what composing a message needs from the git config
+pub fn get_message_config(repo_path: &str) -> Result<MessageConfig> {
+    scope_time!("get_message_config");
+
+    let repo = repo(repo_path)?;
+    let config = repo.config()?;
+
+    let editor = core_editor(&config);
+
+    let template = match config.get_path("commit.template") {
+        Ok(path) => {
+            let path = match repo.workdir() {
+                Some(work_dir) if path.is_relative() => {
+                    work_dir.join(path)
+                }
+                _ => path,
+            };
+            let template =
+                fs::read_to_string(&path).map_err(|e| {
+                    Error::Generic(format!(
+                        "could not read commit template {:?}: {}",
+                        path, e
+                    ))
+                })?;
+            Some(template)
+        }
+        Err(_) => None,
+    };
+
+    let cleanup = match config.get_string("commit.cleanup") {
+        Err(_) => MessageCleanup::Strip,
+        Ok(mode) => match mode.as_str() {
+            "default" | "strip" => MessageCleanup::Strip,
+            "whitespace" => MessageCleanup::Whitespace,
+            "verbatim" => MessageCleanup::Verbatim,
+            "scissors" => MessageCleanup::Scissors,
+            _ => {
+                return Err(Error::Generic(format!(
+                    "invalid commit.cleanup mode `{}`",
+                    mode
+                )))
+            }
+        },
+    };
+
+    // `auto` picks a char not used in the message, `#` will do
+    let comment_char = config
+        .get_string("core.commentChar")
+        .ok()
+        .filter(|c| c != "auto")
+        .and_then(|c| c.chars().next())
+        .unwrap_or('#');
+
+    Ok(MessageConfig {
+        editor,
+        template,
+        cleanup,
+        comment_char,
+        git_dir: repo.path().to_path_buf(),
+    })
+}
+
+/// the line `Scissors` cuts the message off at
+pub fn scissors_line(comment_char: char) -> String {
+    format!(
+        "{} ------------------------ >8 ------------------------",
+        comment_char
+    )
+}
+
+/// `message` tidied up like git does after the editor exits,
+/// without a trailing newline
+pub fn cleanup_message(
+    message: &str,
+    cleanup: MessageCleanup,
+    comment_char: char,
+) -> String {
+    if cleanup == MessageCleanup::Verbatim {
+        return message.to_string();
+    }
+
+    let scissors = scissors_line(comment_char);
+    let mut lines: Vec<&str> = Vec::new();
+
+    for line in message.lines() {
+        if cleanup == MessageCleanup::Scissors && line == scissors {
+            break;
+        }
+        if cleanup == MessageCleanup::Strip
+            && line.starts_with(comment_char)
+        {
+            continue;
+        }
+
+        let line = line.trim_end();
+        // leading and repeated empty lines
+        if line.is_empty()
+            && lines.last().is_none_or(|last| last.is_empty())
+        {
+            continue;
+        }
+
+        lines.push(line);
+    }
+
+    while lines.last().is_some_and(|last| last.is_empty()) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::tests::repo_init;
+
+    #[test]
+    fn test_cleanup_strip() {
+        let message = "\n\n  \nfix: the thing  \n\n\n\n# comment\nbody\t\n  indented\n#\n\n\n";
+
+        assert_eq!(
+            cleanup_message(message, MessageCleanup::Strip, '#'),
+            "fix: the thing\n\nbody\n  indented"
+        );
+        assert_eq!(
+            cleanup_message(
+                "# only\n#comments\n\n",
+                MessageCleanup::Strip,
+                '#'
+            ),
+            ""
+        );
+        // comments only start at the beginning of a line
+        assert_eq!(
+            cleanup_message(
+                " # kept\n; dropped",
+                MessageCleanup::Strip,
+                ';'
+            ),
+            " # kept"
+        );
+    }
+
+    #[test]
+    fn test_cleanup_modes() {
+        let message = "subject \n\n\n# kept\n";
+        let scissors =
+            format!("subject\n{}\ncut off\n", scissors_line('#'));
+
+        assert_eq!(
+            cleanup_message(message, MessageCleanup::Whitespace, '#'),
+            "subject\n\n# kept"
+        );
+        assert_eq!(
+            cleanup_message(message, MessageCleanup::Verbatim, '#'),
+            message
+        );
+        assert_eq!(
+            cleanup_message(&scissors, MessageCleanup::Scissors, '#'),
+            "subject"
+        );
+        assert_eq!(
+            cleanup_message(
+                &scissors,
+                MessageCleanup::Whitespace,
+                '#'
+            ),
+            scissors.trim_end()
+        );
+    }
+
+    #[test]
+    fn test_message_config() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        let mut config = repo.config().unwrap();
+
+        let defaults = get_message_config(repo_path).unwrap();
+        assert_eq!(defaults.template, None);
+        assert_eq!(defaults.cleanup, MessageCleanup::Strip);
+        assert_eq!(defaults.git_dir, repo.path());
+
+        fs::write(root.join("template.txt"), "feat: \n\n# why?\n")
+            .unwrap();
+        config.set_str("commit.template", "template.txt").unwrap();
+        config.set_str("commit.cleanup", "scissors").unwrap();
+        config.set_str("core.commentChar", ";").unwrap();
+        config.set_str("core.editor", "nano -w").unwrap();
+
+        let message = get_message_config(repo_path).unwrap();
+        assert_eq!(
+            message.template.as_deref(),
+            Some("feat: \n\n# why?\n")
+        );
+        assert_eq!(message.cleanup, MessageCleanup::Scissors);
+        assert_eq!(message.comment_char, ';');
+        assert_eq!(message.editor.as_deref(), Some("nano -w"));
+        assert_eq!(
+            get_core_editor(repo_path).unwrap().as_deref(),
+            Some("nano -w")
+        );
+
+        config.set_str("commit.cleanup", "tidy").unwrap();
+        assert!(get_message_config(repo_path).is_err());
+        config.set_str("commit.cleanup", "verbatim").unwrap();
+
+        config.set_str("commit.template", "missing.txt").unwrap();
+        assert!(get_message_config(repo_path).is_err());
+    }
+}
@@ -4,60 +4,124 @@
 #![deny(clippy::expect_used)]
 
 mod branch;
+mod cherry_pick;
 mod commit;
 mod commit_details;
 mod commit_files;
+pub mod commit_filter;
 mod commits_info;
 pub mod cred;
+mod decorations;
 pub mod diff;
 mod hooks;
 mod hunks;
 mod ignore;
+pub mod known_hosts;
+mod line_log;
 mod logwalker;
+mod message;
+mod patches;
+mod rebase;
+mod reflog;
 mod remotes;
 mod reset;
+mod revert_file;
+mod reverts;
+mod sign;
 mod stash;
 pub mod status;
 mod tags;
 pub mod utils;
+mod worktree;
 
 pub(crate) use branch::get_branch_name;
 pub use branch::{
     branch_compare_upstream, checkout_branch, create_branch,
-    delete_branch, get_branches_to_display, rename_branch,
-    BranchCompare, BranchForDisplay,
+    delete_branch, get_branch_upstream, get_branches_to_display,
+    get_unpushed_commits, rename_branch, BranchCompare,
+    BranchForDisplay, BranchUpstream,
+};
+pub use cherry_pick::{
+    cherry_pick_abort, cherry_pick_continue, cherry_pick_next,
+    CherryPickSequence, PickOutcome,
+};
+pub use commit::{
+    amend, amend_unsigned, commit, commit_unsigned, tag, tag_unsigned,
 };
-pub use commit::{amend, commit, tag};
 pub use commit_details::{
-    get_commit_details, CommitDetails, CommitMessage,
+    get_commit_details, CommitDetails, CommitMessage, CommitSignature,
+};
+pub use commit_files::{
+    get_commit_diff_text, get_commit_file_diff_text,
+    get_commit_files, get_compare_commits_files,
+};
+pub use commit_filter::{
+    commit_filter, find_first_match, get_trailers, sort_by_relevance,
+    FilterBy, FilterQuery, FilterTerm, DEFAULT_MAX_FIELD_LEN,
+};
+pub use commits_info::{
+    get_commits_info, get_commits_info_partial, CommitId, CommitInfo,
 };
-pub use commit_files::get_commit_files;
-pub use commits_info::{get_commits_info, CommitId, CommitInfo};
-pub use diff::get_diff_commit;
+pub use decorations::{
+    get_decorations, CommitDecorations, Decoration, Decorations,
+};
+pub use diff::{get_diff_commit, get_diff_commits, get_difftool};
 pub use hooks::{
-    hooks_commit_msg, hooks_post_commit, hooks_pre_commit, HookResult,
+    hooks_commit_msg, hooks_post_commit, hooks_pre_commit,
+    hooks_pre_push, HookResult,
 };
 pub use hunks::{reset_hunk, stage_hunk, unstage_hunk};
 pub use ignore::add_to_ignore;
-pub use logwalker::LogWalker;
+pub use line_log::LineRange;
+pub use logwalker::{
+    get_commits_between, get_shallow_boundary, resolve_rev,
+    LogWalker, LogWalkerFilter, LogWalkerScan, LogWindow,
+};
+pub use message::{
+    cleanup_message, get_core_editor, get_message_config,
+    scissors_line, MessageCleanup, MessageConfig,
+};
+pub use patches::export_patches;
+pub use rebase::{rebase_abort, rebase_branch};
+pub use reflog::{
+    get_reflog, is_ref_pushed, reflog_entries, restore_ref,
+    ReflogEntry,
+};
 pub use remotes::{
-    fetch_origin, get_remotes, push, ProgressNotification,
-    DEFAULT_REMOTE_NAME,
+    fetch, fetch_origin, fetch_refspec, fetch_upstream, get_remotes,
+    get_remotes_info, ls_remote, pull_rebase, push, push_dry_run,
+    run_with_watchdog, set_remote_push_url, FetchStats,
+    ProgressNotification, PushUpdate, RemoteError, RemoteInfo,
+    RemoteResult, Watchdog, DEFAULT_REMOTE_NAME,
+    DEFAULT_REMOTE_TIMEOUT,
+};
+pub use reset::{reset_hard, reset_stage, reset_workdir};
+pub use revert_file::{revert_commit_file, RevertFileOutcome};
+pub use reverts::{find_reverts, Reverts};
+pub use stash::{
+    get_stashes, stash_apply, stash_apply_onto, stash_drop,
+    stash_save,
+};
+pub use tags::{
+    describe_commit, get_commits_since_tag, get_first_tag_containing,
+    get_tags, CommitTags, CommitsSinceTag, Tags,
 };
-pub use reset::{reset_stage, reset_workdir};
-pub use stash::{get_stashes, stash_apply, stash_drop, stash_save};
-pub use tags::{get_tags, CommitTags, Tags};
 pub use utils::{
     get_head, get_head_tuple, is_bare_repo, is_repo, stage_add_all,
     stage_add_file, stage_addremoved, Head,
 };
+pub use worktree::{get_worktrees, is_linked_worktree, WorktreeInfo};
 
 #[cfg(test)]
-mod tests {
-    use super::status::{get_status, StatusType};
+pub(crate) mod tests {
+    use super::{
+        commit, stage_add_file,
+        status::{get_status, StatusType},
+        CommitId,
+    };
     use crate::error::Result;
     use git2::Repository;
-    use std::process::Command;
+    use std::{fs, path::Path, process::Command};
     use tempfile::TempDir;
 
     ///
@@ -72,6 +136,13 @@ mod tests {
         Ok((td, repo))
     }
 
+    ///
+    pub fn repo_init_bare() -> Result<(TempDir, Repository)> {
+        let td = TempDir::new()?;
+        let repo = Repository::init_bare(td.path())?;
+        Ok((td, repo))
+    }
+
     ///
     pub fn repo_init() -> Result<(TempDir, Repository)> {
         let td = TempDir::new()?;
@@ -98,6 +169,18 @@ mod tests {
         Ok((td, repo))
     }
 
+    /// writes `content` to `name`, stages and commits it
+    pub fn commit_file(
+        repo_path: &str,
+        name: &str,
+        content: &str,
+        msg: &str,
+    ) -> CommitId {
+        fs::write(Path::new(repo_path).join(name), content).unwrap();
+        stage_add_file(repo_path, Path::new(name)).unwrap();
+        commit(repo_path, msg).unwrap()
+    }
+
     /// helper returning amount of files with changes in the (wd,stage)
     pub fn get_statuses(repo_path: &str) -> (usize, usize) {
         (
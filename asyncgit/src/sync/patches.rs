@@ -0,0 +1,118 @@
+//! exporting commits as patch files
+
+use super::{utils::repo, CommitId};
+use crate::error::Result;
+use scopetime::scope_time;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// file names use at most this many chars of the subject
+const MAX_SUBJECT_LEN: usize = 52;
+
+/// writes one `git format-patch` style file per commit into `dir`,
+/// numbered in the order of `ids` (which should be oldest first)
+pub fn export_patches(
+    repo_path: &str,
+    ids: &[CommitId],
+    dir: &Path,
+) -> Result<Vec<PathBuf>> {
+    scope_time!("export_patches");
+
+    let repo = repo(repo_path)?;
+    let mut files = Vec::with_capacity(ids.len());
+
+    for (idx, id) in ids.iter().enumerate() {
+        let commit = repo.find_commit((*id).into())?;
+        let parent_tree = if commit.parent_count() > 0 {
+            Some(commit.parent(0)?.tree()?)
+        } else {
+            None
+        };
+        let mut diff = repo.diff_tree_to_tree(
+            parent_tree.as_ref(),
+            Some(&commit.tree()?),
+            None,
+        )?;
+        let patch =
+            diff.format_email(idx + 1, ids.len(), &commit, None)?;
+
+        let path = dir.join(format!(
+            "{:04}-{}.patch",
+            idx + 1,
+            patch_file_name(commit.summary().unwrap_or_default())
+        ));
+        fs::write(&path, &*patch)?;
+        files.push(path);
+    }
+
+    Ok(files)
+}
+
+/// the subject reduced to `[a-zA-Z0-9.]` separated by single `-`
+fn patch_file_name(subject: &str) -> String {
+    let name = subject
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    let name = name.get(..MAX_SUBJECT_LEN).unwrap_or(&name);
+
+    name.trim_end_matches(['-', '.']).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{commit, stage_add_file, tests::repo_init};
+    use std::{fs::File, io::Write};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_patch_file_name() {
+        assert_eq!(
+            patch_file_name("fix: parse `a b` (again)"),
+            "fix-parse-a-b-again"
+        );
+        assert_eq!(patch_file_name("bump to v1.2."), "bump-to-v1.2");
+        assert_eq!(patch_file_name("  "), "");
+    }
+
+    #[test]
+    fn test_export_patches() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let mut ids = Vec::new();
+        for (name, content) in &[("a.txt", "a\n"), ("b.txt", "b\n")] {
+            File::create(&root.join(name))
+                .unwrap()
+                .write_all(content.as_bytes())
+                .unwrap();
+            stage_add_file(repo_path, Path::new(name)).unwrap();
+            ids.push(
+                commit(repo_path, &format!("add {}", name)).unwrap(),
+            );
+        }
+
+        let out = TempDir::new().unwrap();
+        let files =
+            export_patches(repo_path, &ids, out.path()).unwrap();
+
+        assert_eq!(
+            files,
+            vec![
+                out.path().join("0001-add-a.txt.patch"),
+                out.path().join("0002-add-b.txt.patch"),
+            ]
+        );
+
+        let patch = fs::read_to_string(&files[1]).unwrap();
+        assert!(patch.contains("Subject: [PATCH 2/2] add b.txt"));
+        assert!(patch.contains("+b"));
+        assert!(!patch.contains("a.txt"));
+    }
+}
@@ -0,0 +1,117 @@
+//! rebasing a local branch onto another ref
+
+use super::{
+    commit::signature_allow_undefined_name, utils::repo, CommitId,
+};
+use crate::error::{Error, Result};
+use git2::{ErrorCode, RepositoryState};
+use scopetime::scope_time;
+
+/// rebases the checked out `branch` onto `upstream` (a full refname),
+/// fast-forwards if there is nothing local to replay.
+///
+/// stops with `Error::RebaseConflict` on the first commit that does
+/// not apply cleanly, its conflicts are left in the index and the
+/// rebase can be dropped with `rebase_abort`
+pub fn rebase_branch(
+    repo_path: &str,
+    branch: &str,
+    upstream: &str,
+) -> Result<()> {
+    scope_time!("rebase_branch");
+
+    let repo = repo(repo_path)?;
+
+    if repo.state() != RepositoryState::Clean {
+        return Err(Error::Generic(format!(
+            "repository is busy: {:?}",
+            repo.state()
+        )));
+    }
+
+    let branch_ref =
+        repo.find_branch(branch, git2::BranchType::Local)?;
+    if !branch_ref.is_head() {
+        return Err(Error::Generic(format!(
+            "branch `{}` is not checked out",
+            branch
+        )));
+    }
+
+    let local =
+        repo.reference_to_annotated_commit(branch_ref.get())?;
+    let upstream = repo.reference_to_annotated_commit(
+        &repo.find_reference(upstream)?,
+    )?;
+
+    let signature = signature_allow_undefined_name(&repo)?;
+    let mut rebase =
+        repo.rebase(Some(&local), Some(&upstream), None, None)?;
+
+    while let Some(op) = rebase.next() {
+        let id = CommitId::new(op?.id());
+
+        if repo.index()?.has_conflicts() {
+            return Err(Error::RebaseConflict(id.get_short_string()));
+        }
+
+        match rebase.commit(None, &signature, None) {
+            // its changes are upstream already
+            Err(e) if e.code() == ErrorCode::Applied => (),
+            res => {
+                res?;
+            }
+        }
+    }
+
+    rebase.finish(Some(&signature))?;
+
+    Ok(())
+}
+
+/// drops a stopped rebase and moves back to where it started
+pub fn rebase_abort(repo_path: &str) -> Result<()> {
+    scope_time!("rebase_abort");
+
+    let repo = repo(repo_path)?;
+    repo.open_rebase(None)?.abort()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        tests::{commit_file, repo_init},
+        utils::get_head_repo,
+    };
+
+    #[test]
+    fn test_rebase_conflict_abort() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let base = get_head_repo(&repo).unwrap();
+        let other = commit_file(repo_path, "foo.txt", "a\n", "other");
+        repo.reference("refs/heads/other", other.into(), true, "")
+            .unwrap();
+        repo.reset(
+            &repo.find_object(base.into(), None).unwrap(),
+            git2::ResetType::Hard,
+            None,
+        )
+        .unwrap();
+        let local = commit_file(repo_path, "foo.txt", "b\n", "local");
+
+        let res =
+            rebase_branch(repo_path, "master", "refs/heads/other");
+        assert!(matches!(res, Err(Error::RebaseConflict(_))));
+        assert_eq!(repo.state(), RepositoryState::RebaseMerge);
+
+        rebase_abort(repo_path).unwrap();
+        assert_eq!(repo.state(), RepositoryState::Clean);
+        assert_eq!(get_head_repo(&repo).unwrap(), local);
+    }
+}
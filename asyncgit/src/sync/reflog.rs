@@ -0,0 +1,312 @@
+//! reading reflogs and restoring refs from them
+
+use super::{reset_hard, utils::repo, CommitId};
+use crate::error::{Error, Result};
+use git2::{BranchType, Oid, Repository};
+use scopetime::scope_time;
+
+/// single entry of a reflog
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReflogEntry {
+    /// ref the entry belongs to, e.g. `HEAD` or `refs/heads/master`
+    pub refname: String,
+    /// commit the ref pointed to after the update
+    pub id: CommitId,
+    /// commit the ref pointed to before the update
+    pub previous: CommitId,
+    /// what moved the ref, e.g. `commit: fix typo`
+    pub message: String,
+    /// seconds since epoch
+    pub time: i64,
+}
+
+/// reflog entries of `HEAD`, the most recent first (`HEAD@{0}`)
+pub fn get_reflog(repo_path: &str) -> Result<Vec<ReflogEntry>> {
+    scope_time!("get_reflog");
+
+    read_reflog(&repo(repo_path)?, "HEAD", usize::MAX)
+}
+
+/// the latest `limit` updates of `HEAD` and all local branches,
+/// the most recent first
+pub fn reflog_entries(
+    repo_path: &str,
+    limit: usize,
+) -> Result<Vec<ReflogEntry>> {
+    scope_time!("reflog_entries");
+
+    let repo = repo(repo_path)?;
+    let mut entries = read_reflog(&repo, "HEAD", limit)?;
+
+    for branch in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch?;
+        if let Some(refname) = branch.get().name() {
+            entries.extend(read_reflog(&repo, refname, limit)?);
+        }
+    }
+
+    // stable, keeps `HEAD` ahead of branches updated alongside it
+    entries.sort_by_key(|e| std::cmp::Reverse(e.time));
+    entries.truncate(limit);
+
+    Ok(entries)
+}
+
+/// up to `limit` reflog entries of `refname`, the most recent first
+fn read_reflog(
+    repo: &Repository,
+    refname: &str,
+    limit: usize,
+) -> Result<Vec<ReflogEntry>> {
+    let reflog = repo.reflog(refname)?;
+
+    Ok(reflog
+        .iter()
+        .take(limit)
+        .map(|entry| ReflogEntry {
+            refname: refname.to_string(),
+            id: entry.id_new().into(),
+            previous: entry.id_old().into(),
+            message: entry.message().unwrap_or_default().to_string(),
+            time: entry.committer().when().seconds(),
+        })
+        .collect())
+}
+
+/// local branch `refname` stands for, `HEAD` resolves to the
+/// branch checked out (`None` if detached)
+fn branch_of<'a>(
+    repo: &'a Repository,
+    refname: &str,
+) -> Result<Option<git2::Branch<'a>>> {
+    let reference = if refname == "HEAD" {
+        let head = repo.find_reference("HEAD")?;
+        match head.symbolic_target() {
+            Some(target) => repo.find_reference(target)?,
+            None => return Ok(None),
+        }
+    } else {
+        repo.find_reference(refname)?
+    };
+
+    Ok(if reference.is_branch() {
+        Some(git2::Branch::wrap(reference))
+    } else {
+        None
+    })
+}
+
+/// `true` if the current tip of `refname` is contained in the
+/// upstream of its branch, moving it would rewrite pushed history
+pub fn is_ref_pushed(repo_path: &str, refname: &str) -> Result<bool> {
+    let repo = repo(repo_path)?;
+
+    let branch = match branch_of(&repo, refname)? {
+        Some(branch) => branch,
+        None => return Ok(false),
+    };
+    let tip = branch.get().peel_to_commit()?.id();
+    let upstream = match branch.upstream() {
+        Ok(upstream) => upstream.get().peel_to_commit()?.id(),
+        Err(_) => return Ok(false),
+    };
+
+    Ok(tip == upstream || repo.graph_descendant_of(upstream, tip)?)
+}
+
+/// points `refname` back to `id`, refs checked out are reset
+/// hard, refused if its tip was pushed unless `allow_pushed`
+pub fn restore_ref(
+    repo_path: &str,
+    refname: &str,
+    id: CommitId,
+    allow_pushed: bool,
+) -> Result<()> {
+    scope_time!("restore_ref");
+
+    if Oid::zero() == id.into() {
+        return Err(Error::Generic(format!(
+            "`{}` did not exist before",
+            refname
+        )));
+    }
+
+    if !allow_pushed && is_ref_pushed(repo_path, refname)? {
+        return Err(Error::Generic(format!(
+            "`{}` was pushed already",
+            refname
+        )));
+    }
+
+    let repo = repo(repo_path)?;
+    let head = repo.find_reference("HEAD")?;
+    let checked_out =
+        refname == "HEAD" || head.symbolic_target() == Some(refname);
+
+    if checked_out {
+        reset_hard(repo_path, id)
+    } else {
+        repo.find_reference(refname)?.set_target(
+            id.into(),
+            &format!("restore: moving to {}", id.to_string()),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        commit, reset_hard, tests::repo_init, utils::get_head_repo,
+    };
+
+    #[test]
+    fn test_reflog_after_commits_and_reset() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let initial = get_head_repo(&repo).unwrap();
+        let first = commit(repo_path, "first").unwrap();
+        let second = commit(repo_path, "second").unwrap();
+
+        reset_hard(repo_path, first).unwrap();
+
+        let reflog = get_reflog(repo_path).unwrap();
+
+        assert_eq!(reflog.len(), 4);
+
+        assert_eq!(reflog[0].id, first);
+        assert_eq!(reflog[0].previous, second);
+        assert!(reflog[0].message.starts_with("reset: moving to"));
+
+        assert_eq!(reflog[1].id, second);
+        assert_eq!(reflog[1].message, "commit: second");
+
+        assert_eq!(reflog[2].id, first);
+        assert_eq!(reflog[2].previous, initial);
+
+        assert_eq!(reflog[3].id, initial);
+        assert!(reflog.iter().all(|e| e.refname == "HEAD"));
+    }
+
+    #[test]
+    fn test_reset_hard_restores_lost_commit() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = commit(repo_path, "first").unwrap();
+        let second = commit(repo_path, "second").unwrap();
+        reset_hard(repo_path, first).unwrap();
+
+        let lost = get_reflog(repo_path).unwrap()[1].id;
+        reset_hard(repo_path, lost).unwrap();
+
+        assert_eq!(get_head_repo(&repo).unwrap(), second);
+    }
+
+    #[test]
+    fn test_reflog_entries() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        commit(repo_path, "first").unwrap();
+
+        let updates = reflog_entries(repo_path, 100).unwrap();
+        let refs = updates
+            .iter()
+            .map(|e| e.refname.as_str())
+            .collect::<Vec<_>>();
+
+        assert!(refs.contains(&"HEAD"));
+        assert!(refs.contains(&"refs/heads/master"));
+        assert!(updates.windows(2).all(|w| w[0].time >= w[1].time));
+
+        assert_eq!(reflog_entries(repo_path, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_restore_branch() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = commit(repo_path, "first").unwrap();
+        let second = commit(repo_path, "second").unwrap();
+        let head = repo.find_commit(second.into()).unwrap();
+        repo.branch("other", &head, false).unwrap();
+
+        restore_ref(repo_path, "refs/heads/other", first, false)
+            .unwrap();
+
+        let other = repo
+            .find_reference("refs/heads/other")
+            .unwrap()
+            .target()
+            .unwrap();
+        assert_eq!(CommitId::from(other), first);
+        assert_eq!(get_head_repo(&repo).unwrap(), second);
+
+        // the branch checked out moves HEAD and the workdir
+        restore_ref(repo_path, "refs/heads/master", first, false)
+            .unwrap();
+        assert_eq!(get_head_repo(&repo).unwrap(), first);
+
+        let created = reflog_entries(repo_path, 100)
+            .unwrap()
+            .into_iter()
+            .rev()
+            .find(|e| e.refname == "refs/heads/other")
+            .unwrap();
+        assert!(restore_ref(
+            repo_path,
+            &created.refname,
+            created.previous,
+            false
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_restore_pushed_branch() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = commit(repo_path, "first").unwrap();
+        commit(repo_path, "second").unwrap();
+
+        assert!(!is_ref_pushed(repo_path, "HEAD").unwrap());
+
+        // fake a remote tracking branch at the current tip
+        let head = get_head_repo(&repo).unwrap();
+        repo.reference(
+            "refs/remotes/origin/master",
+            head.into(),
+            false,
+            "",
+        )
+        .unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("branch.master.remote", "origin").unwrap();
+        config
+            .set_str("branch.master.merge", "refs/heads/master")
+            .unwrap();
+        repo.remote("origin", "https://example.com/repo").unwrap();
+
+        assert!(is_ref_pushed(repo_path, "HEAD").unwrap());
+        assert!(
+            is_ref_pushed(repo_path, "refs/heads/master").unwrap()
+        );
+        assert!(restore_ref(repo_path, "HEAD", first, false).is_err());
+        assert_eq!(get_head_repo(&repo).unwrap(), head);
+
+        restore_ref(repo_path, "HEAD", first, true).unwrap();
+        assert_eq!(get_head_repo(&repo).unwrap(), first);
+        // still part of what was pushed
+        assert!(is_ref_pushed(repo_path, "HEAD").unwrap());
+    }
+}
@@ -2,14 +2,189 @@
 
 use super::CommitId;
 use crate::{
-    error::Result, sync::cred::BasicAuthCredential, sync::utils,
+    error::{Error, Result},
+    sync::cred::{
+        forget_credential, remember_credential, ssh_agent_available,
+        ssh_key_file, BasicAuthCredential,
+    },
+    sync::known_hosts::{
+        check_host_key, fingerprint_sha256, known_hosts_file,
+        known_hosts_name, trust_host_key, trust_new_host_keys,
+        HostKeyStatus,
+    },
+    sync::{get_branch_upstream, rebase_branch, utils},
 };
-use crossbeam_channel::Sender;
+use crossbeam_channel::{bounded, RecvTimeoutError, Sender};
 use git2::{
-    Cred, Error as GitError, FetchOptions, PackBuilderStage,
-    PushOptions, RemoteCallbacks,
+    cert::{Cert, CertHostkey},
+    Cred, Direction, Error as GitError, ErrorClass, ErrorCode,
+    FetchOptions, Oid, PackBuilderStage, PushOptions, Remote,
+    RemoteCallbacks, Repository,
 };
 use scopetime::scope_time;
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+/// classified failure of a remote operation (fetch/push)
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum RemoteError {
+    /// credentials were missing or rejected
+    #[error("authentication failed")]
+    Auth,
+
+    /// remote could not be reached
+    #[error("network error: could not reach `{url}`: {message}")]
+    Network {
+        ///
+        url: String,
+        /// what libgit2 reported, e.g. "connection refused"
+        message: String,
+    },
+
+    /// remote refused to update a ref (e.g. non-fast-forward)
+    #[error("`{refname}` was rejected: {reason}")]
+    Rejected {
+        ///
+        refname: String,
+        ///
+        reason: String,
+    },
+
+    /// remote (or requested ref) does not exist
+    #[error("remote not found")]
+    NotFound,
+
+    /// refspec given by the user is malformed
+    #[error("invalid refspec `{refspec}`: {reason}")]
+    InvalidRefspec {
+        ///
+        refspec: String,
+        ///
+        reason: String,
+    },
+
+    /// ssh host key is not in `known_hosts` yet
+    #[error("unknown host key of `{host}` ({fingerprint})")]
+    UnknownHostKey {
+        /// name of the host in `known_hosts`
+        host: String,
+        ///
+        fingerprint: String,
+    },
+
+    /// ssh host key differs from the one in `known_hosts`
+    #[error("host key of `{host}` does not match known_hosts ({fingerprint})")]
+    HostKeyMismatch {
+        /// name of the host in `known_hosts`
+        host: String,
+        ///
+        fingerprint: String,
+    },
+
+    /// ssh host key is marked `@revoked` in `known_hosts`
+    #[error("host key of `{host}` is revoked in known_hosts ({fingerprint})")]
+    HostKeyRevoked {
+        /// name of the host in `known_hosts`
+        host: String,
+        ///
+        fingerprint: String,
+    },
+
+    /// fetched fine but rebasing onto it stopped on a conflict,
+    /// the rebase is left to be resolved or aborted
+    #[error("rebase stopped on a conflict applying `{commit}`")]
+    RebaseConflict {
+        /// short id of the local commit that did not apply
+        commit: String,
+    },
+
+    /// remote did not report any progress for `secs` seconds
+    #[error("timed out after {secs}s with no progress")]
+    Timeout {
+        ///
+        secs: u64,
+    },
+
+    /// the `pre-push` hook failed, with what it printed
+    #[error("pre-push hook rejected the push:\n{0}")]
+    HookRejected(String),
+
+    /// another remote operation is running
+    #[error("`{running}` is still running")]
+    Busy {
+        ///
+        running: String,
+    },
+
+    /// the operation was cancelled while it waited for its turn
+    #[error("cancelled before it started")]
+    Cancelled,
+
+    ///
+    #[error("{0}")]
+    Other(String),
+}
+
+impl RemoteError {
+    /// classify a `git2::Error` raised while talking to `url` about
+    /// `refname`, `None` if it was not raised talking to a remote
+    pub(crate) fn from_git(
+        e: &GitError,
+        url: Option<&str>,
+        refname: &str,
+    ) -> Self {
+        match (e.code(), e.class(), url) {
+            (ErrorCode::Auth, ..) => Self::Auth,
+            (ErrorCode::NotFastForward, ..) => Self::Rejected {
+                refname: refname.to_string(),
+                reason: e.message().to_string(),
+            },
+            (ErrorCode::NotFound, _, Some(_)) => Self::NotFound,
+            // libssh2 failing to decrypt the key file
+            (_, ErrorClass::Ssh, _)
+                if e.message().contains("Wrong passphrase") =>
+            {
+                Self::Auth
+            }
+            (_, ErrorClass::Net, Some(url))
+            | (_, ErrorClass::Ssl, Some(url))
+            | (_, ErrorClass::Ssh, Some(url)) => Self::Network {
+                url: url.to_string(),
+                message: e.message().to_string(),
+            },
+            _ => Self::Other(e.message().to_string()),
+        }
+    }
+}
+
+impl From<Error> for RemoteError {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Git(e) => Self::from_git(&e, None, ""),
+            Error::UnknownRemote => Self::NotFound,
+            Error::RebaseConflict(commit) => {
+                Self::RebaseConflict { commit }
+            }
+            Error::NoUpstream(branch) => Self::Other(format!(
+                "branch `{}` has no upstream configured",
+                branch
+            )),
+            e => Self::Other(e.to_string()),
+        }
+    }
+}
+
+///
+pub type RemoteResult<T> = std::result::Result<T, RemoteError>;
 
 ///
 #[derive(Debug, Clone)]
@@ -30,6 +205,13 @@ pub enum ProgressNotification {
         ///
         total_objects: usize,
     },
+    /// all objects received, indexing deltas
+    ResolvingDeltas {
+        ///
+        indexed: usize,
+        ///
+        total: usize,
+    },
     ///
     PushTransfer {
         ///
@@ -55,6 +237,127 @@ pub enum ProgressNotification {
 ///
 pub const DEFAULT_REMOTE_NAME: &str = "origin";
 
+/// how long a remote operation may go without progress
+pub const DEFAULT_REMOTE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// last sign of life of a running remote operation,
+/// fed by its progress callbacks
+#[derive(Clone, Debug)]
+pub struct Watchdog {
+    last_progress: Arc<Mutex<Instant>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Default for Watchdog {
+    fn default() -> Self {
+        Self {
+            last_progress: Arc::new(Mutex::new(Instant::now())),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl Watchdog {
+    /// resets the deadline, `false` once the operation was
+    /// cancelled and libgit2 should abort it
+    pub fn alive(&self) -> bool {
+        if let Ok(mut last) = self.last_progress.lock() {
+            *last = Instant::now();
+        }
+        !self.is_cancelled()
+    }
+
+    /// time since the last progress
+    pub fn idle(&self) -> Duration {
+        self.last_progress
+            .lock()
+            .map_or(Duration::default(), |last| last.elapsed())
+    }
+
+    ///
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    ///
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// runs the blocking remote operation `f` on a worker and gives up
+/// once `timeout` passed without `f` reporting progress to its
+/// `Watchdog`. a worker stuck in libgit2 is left behind and
+/// aborts with its next progress callback, `f` has to own the
+/// `RemoteOpGuard` so the next operation waits for that
+pub fn run_with_watchdog<T, F>(
+    timeout: Duration,
+    f: F,
+) -> RemoteResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce(Watchdog) -> RemoteResult<T> + Send + 'static,
+{
+    let watchdog = Watchdog::default();
+    let (sender, receiver) = bounded(1);
+
+    let worker = watchdog.clone();
+    thread::spawn(move || {
+        sender.send(f(worker)).ok();
+    });
+
+    loop {
+        let idle = watchdog.idle();
+        if idle >= timeout {
+            watchdog.cancel();
+            return Err(RemoteError::Timeout {
+                secs: timeout.as_secs(),
+            });
+        }
+
+        match receiver.recv_timeout(timeout - idle) {
+            Ok(res) => return res,
+            Err(RecvTimeoutError::Timeout) => (),
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(RemoteError::Other(String::from(
+                    "remote operation worker died",
+                )))
+            }
+        }
+    }
+}
+
+/// transfer statistics of a finished fetch
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct FetchStats {
+    ///
+    pub received_bytes: usize,
+    ///
+    pub received_objects: usize,
+    /// objects that were already present locally
+    pub local_objects: usize,
+    ///
+    pub total_objects: usize,
+}
+
+/// name and urls of a configured remote
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteInfo {
+    ///
+    pub name: String,
+    ///
+    pub url: Option<String>,
+    /// `remote.<name>.pushurl` if configured
+    pub push_url: Option<String>,
+}
+
+impl RemoteInfo {
+    /// the url actually used when pushing
+    pub fn effective_push_url(&self) -> Option<&str> {
+        self.push_url.as_deref().or(self.url.as_deref())
+    }
+}
+
 ///
 pub fn get_remotes(repo_path: &str) -> Result<Vec<String>> {
     scope_time!("get_remotes");
@@ -68,21 +371,178 @@ pub fn get_remotes(repo_path: &str) -> Result<Vec<String>> {
 }
 
 ///
-pub fn fetch_origin(repo_path: &str, branch: &str) -> Result<usize> {
+pub fn get_remotes_info(repo_path: &str) -> Result<Vec<RemoteInfo>> {
+    scope_time!("get_remotes_info");
+
+    let repo = utils::repo(repo_path)?;
+    let remotes = repo.remotes()?;
+
+    remotes
+        .iter()
+        .flatten()
+        .map(|name| {
+            let remote = repo.find_remote(name)?;
+            Ok(RemoteInfo {
+                name: name.to_string(),
+                url: remote.url().map(String::from),
+                push_url: remote.pushurl().map(String::from),
+            })
+        })
+        .collect()
+}
+
+/// sets (or with `None` clears) `remote.<name>.pushurl`
+pub fn set_remote_push_url(
+    repo_path: &str,
+    remote: &str,
+    push_url: Option<&str>,
+) -> Result<()> {
+    scope_time!("set_remote_push_url");
+
+    let repo = utils::repo(repo_path)?;
+    repo.find_remote(remote)?;
+
+    if push_url.is_some() {
+        repo.remote_set_pushurl(remote, push_url)?;
+    } else {
+        // libgit2 fails deleting a config entry that does not exist
+        let mut config = repo.config()?;
+        let key = format!("remote.{}.pushurl", remote);
+        if config.get_entry(&key).is_ok() {
+            config.remove(&key)?;
+        }
+    }
+
+    Ok(())
+}
+
+///
+pub fn fetch_origin(
+    repo_path: &str,
+    branch: &str,
+) -> RemoteResult<FetchStats> {
     scope_time!("fetch_origin");
 
+    fetch(repo_path, DEFAULT_REMOTE_NAME, branch, None, None, None)
+}
+
+/// fetches the upstream (`branch.<name>.remote`/`merge`) of `branch`
+pub fn fetch_upstream(
+    repo_path: &str,
+    branch: &str,
+) -> RemoteResult<FetchStats> {
+    scope_time!("fetch_upstream");
+
+    let upstream = get_branch_upstream(repo_path, branch)?;
+
+    fetch(
+        repo_path,
+        &upstream.remote,
+        &upstream.merge,
+        None,
+        None,
+        None,
+    )
+}
+
+/// fetches `refspec` from `remote`
+pub fn fetch(
+    repo_path: &str,
+    remote: &str,
+    refspec: &str,
+    basic_credential: Option<BasicAuthCredential>,
+    progress_sender: Option<Sender<ProgressNotification>>,
+    watchdog: Option<Watchdog>,
+) -> RemoteResult<FetchStats> {
+    scope_time!("fetch");
+
     let repo = utils::repo(repo_path)?;
-    let mut remote = repo.find_remote(DEFAULT_REMOTE_NAME)?;
+    let mut remote = find_remote(&repo, remote)?;
+    let url = remote.url().unwrap_or_default().to_string();
+    let host_keys = HostKeyCheck::new(&url);
 
     let mut options = FetchOptions::new();
-    options.remote_callbacks(match remote_callbacks(None, None) {
-        Ok(callback) => callback,
-        Err(e) => return Err(e),
-    });
+    options.remote_callbacks(remote_callbacks(
+        progress_sender,
+        basic_credential.clone(),
+        watchdog,
+        host_keys.clone(),
+    )?);
+
+    let res = remote
+        .fetch(&[refspec], Some(&mut options), None)
+        .map_err(|e| classify(&e, &url, refspec, host_keys.as_ref()));
+    update_session_credential(&url, basic_credential.as_ref(), &res);
+    res?;
+
+    let stats = remote.stats();
+
+    Ok(FetchStats {
+        received_bytes: stats.received_bytes(),
+        received_objects: stats.received_objects(),
+        local_objects: stats.local_objects(),
+        total_objects: stats.total_objects(),
+    })
+}
+
+/// fetches `branch` from `remote` and rebases the local `branch`
+/// (which has to be checked out) onto the fetched `<remote>/<branch>`
+pub fn pull_rebase(
+    repo_path: &str,
+    remote: &str,
+    branch: &str,
+    basic_credential: Option<BasicAuthCredential>,
+    progress_sender: Option<Sender<ProgressNotification>>,
+    watchdog: Option<Watchdog>,
+) -> RemoteResult<FetchStats> {
+    scope_time!("pull_rebase");
+
+    let stats = fetch(
+        repo_path,
+        remote,
+        branch,
+        basic_credential,
+        progress_sender,
+        watchdog,
+    )?;
+
+    rebase_branch(
+        repo_path,
+        branch,
+        &format!("refs/remotes/{}/{}", remote, branch),
+    )?;
+
+    Ok(stats)
+}
+
+/// fetches an arbitrary user given refspec from `remote`,
+/// e.g. `+refs/pull/*/head:refs/remotes/origin/pr/*`
+pub fn fetch_refspec(
+    repo_path: &str,
+    remote: &str,
+    refspec: &str,
+    basic_credential: Option<BasicAuthCredential>,
+    progress_sender: Option<Sender<ProgressNotification>>,
+    watchdog: Option<Watchdog>,
+) -> RemoteResult<FetchStats> {
+    scope_time!("fetch_refspec");
 
-    remote.fetch(&[branch], Some(&mut options), None)?;
+    let refspec = refspec.trim();
+    validate_refspec(refspec).map_err(|reason| {
+        RemoteError::InvalidRefspec {
+            refspec: refspec.to_string(),
+            reason: reason.to_string(),
+        }
+    })?;
 
-    Ok(remote.stats().received_bytes())
+    fetch(
+        repo_path,
+        remote,
+        refspec,
+        basic_credential,
+        progress_sender,
+        watchdog,
+    )
 }
 
 ///
@@ -92,38 +552,446 @@ pub fn push(
     branch: &str,
     basic_credential: Option<BasicAuthCredential>,
     progress_sender: Sender<ProgressNotification>,
-) -> Result<()> {
+    watchdog: Option<Watchdog>,
+) -> RemoteResult<()> {
     scope_time!("push_origin");
 
     let repo = utils::repo(repo_path)?;
-    let mut remote = repo.find_remote(remote)?;
+    let mut remote = find_remote(&repo, remote)?;
+    let url = remote
+        .pushurl()
+        .or_else(|| remote.url())
+        .unwrap_or_default()
+        .to_string();
 
-    let mut options = PushOptions::new();
+    let rejected: Rc<RefCell<Option<(String, String)>>> =
+        Rc::new(RefCell::new(None));
 
-    options.remote_callbacks(
-        match remote_callbacks(
-            Some(progress_sender),
-            basic_credential,
-        ) {
-            Ok(callbacks) => callbacks,
-            Err(e) => return Err(e),
-        },
-    );
+    let host_keys = HostKeyCheck::new(&url);
+    let mut callbacks = remote_callbacks(
+        Some(progress_sender),
+        basic_credential.clone(),
+        watchdog.clone(),
+        host_keys.clone(),
+    )?;
+
+    let rejected_clone = Rc::clone(&rejected);
+    callbacks.push_update_reference(move |refname, status| {
+        if watchdog.as_ref().is_some_and(Watchdog::is_cancelled) {
+            return Err(GitError::from_str("cancelled"));
+        }
+
+        if let Some(reason) = status {
+            log::debug!("push rejected: '{}' ({})", refname, reason);
+            *rejected_clone.borrow_mut() =
+                Some((refname.to_string(), reason.to_string()));
+        }
+        Ok(())
+    });
+
+    let mut options = PushOptions::new();
+    options.remote_callbacks(callbacks);
     options.packbuilder_parallelism(0);
 
-    remote.push(&[branch], Some(&mut options))?;
+    let res = remote
+        .push(&[branch], Some(&mut options))
+        .map_err(|e| classify(&e, &url, branch, host_keys.as_ref()));
+    update_session_credential(&url, basic_credential.as_ref(), &res);
+    res?;
+
+    if let Some((refname, reason)) = rejected.borrow_mut().take() {
+        return Err(RemoteError::Rejected { refname, reason });
+    }
 
     Ok(())
 }
 
+/// what pushing a single refspec would do to the remote
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushUpdate {
+    /// ref on the remote
+    pub refname: String,
+    /// current tip on the remote, `None` if the ref does not exist yet
+    pub old_remote_tip: Option<CommitId>,
+    /// local tip that would be pushed
+    pub new_local_tip: CommitId,
+    /// `false` means the remote would reject it without force
+    pub fast_forward: bool,
+}
+
+/// connects to `remote` and compares its refs against the local
+/// sources of `refspecs` without sending anything
+pub fn push_dry_run(
+    repo_path: &str,
+    remote: &str,
+    refspecs: &[&str],
+    basic_credential: Option<BasicAuthCredential>,
+    watchdog: Option<Watchdog>,
+) -> RemoteResult<Vec<PushUpdate>> {
+    scope_time!("push_dry_run");
+
+    let repo = utils::repo(repo_path)?;
+    let mut remote = find_remote(&repo, remote)?;
+    let url = remote
+        .pushurl()
+        .or_else(|| remote.url())
+        .unwrap_or_default()
+        .to_string();
+
+    let host_keys = HostKeyCheck::new(&url);
+    let callbacks = remote_callbacks(
+        None,
+        basic_credential.clone(),
+        watchdog,
+        host_keys.clone(),
+    )?;
+    let connection = remote
+        .connect_auth(Direction::Push, Some(callbacks), None)
+        .map_err(|e| classify(&e, &url, "", host_keys.as_ref()));
+    update_session_credential(
+        &url,
+        basic_credential.as_ref(),
+        &connection,
+    );
+    let connection = connection?;
+
+    let remote_heads = connection
+        .list()
+        .map_err(|e| RemoteError::from_git(&e, Some(&url), ""))?
+        .iter()
+        .map(|head| (head.name().to_string(), head.oid()))
+        .collect::<Vec<_>>();
+
+    refspecs
+        .iter()
+        .map(|refspec| {
+            let (src, dst) = split_refspec(refspec);
+
+            let new_local_tip =
+                repo.refname_to_id(src).map_err(|_| {
+                    RemoteError::Other(format!(
+                        "local ref `{}` not found",
+                        src
+                    ))
+                })?;
+
+            let old_remote_tip = remote_heads
+                .iter()
+                .find(|(name, _)| name == dst)
+                .map(|(_, oid)| *oid);
+
+            Ok(PushUpdate {
+                refname: dst.to_string(),
+                old_remote_tip: old_remote_tip.map(CommitId::from),
+                new_local_tip: new_local_tip.into(),
+                fast_forward: is_fast_forward(
+                    &repo,
+                    old_remote_tip,
+                    new_local_tip,
+                ),
+            })
+        })
+        .collect()
+}
+
+/// lists the refs that currently exist on `remote` without fetching
+pub fn ls_remote(
+    repo_path: &str,
+    remote: &str,
+    basic_credential: Option<BasicAuthCredential>,
+    watchdog: Option<Watchdog>,
+) -> RemoteResult<Vec<(String, CommitId)>> {
+    scope_time!("ls_remote");
+
+    let repo = utils::repo(repo_path)?;
+    let mut remote = find_remote(&repo, remote)?;
+    let url = remote.url().unwrap_or_default().to_string();
+
+    let host_keys = HostKeyCheck::new(&url);
+    let callbacks = remote_callbacks(
+        None,
+        basic_credential.clone(),
+        watchdog,
+        host_keys.clone(),
+    )?;
+    let connection = remote
+        .connect_auth(Direction::Fetch, Some(callbacks), None)
+        .map_err(|e| classify(&e, &url, "", host_keys.as_ref()));
+    update_session_credential(
+        &url,
+        basic_credential.as_ref(),
+        &connection,
+    );
+    let connection = connection?;
+
+    let heads = connection
+        .list()
+        .map_err(|e| RemoteError::from_git(&e, Some(&url), ""))?
+        .iter()
+        .map(|head| (head.name().to_string(), head.oid().into()))
+        .collect();
+
+    Ok(heads)
+}
+
+/// `[+]src[:dst]` -> `(src, dst)`
+fn split_refspec(refspec: &str) -> (&str, &str) {
+    let refspec = refspec.trim_start_matches('+');
+    match refspec.find(':') {
+        Some(idx) => (&refspec[..idx], &refspec[idx + 1..]),
+        None => (refspec, refspec),
+    }
+}
+
+/// catches the obvious mistakes before handing `refspec` to git
+fn validate_refspec(refspec: &str) -> std::result::Result<(), &str> {
+    let (src, dst) = split_refspec(refspec);
+
+    if src.is_empty() {
+        Err("source is empty")
+    } else if refspec.contains(char::is_whitespace) {
+        Err("must not contain whitespace")
+    } else if refspec.matches(':').count() > 1 {
+        Err("more than one `:`")
+    } else if refspec.ends_with(':') {
+        Err("destination is empty")
+    } else if src.matches('*').count() > 1 {
+        Err("more than one `*` in the source")
+    } else if src.contains('*') != dst.contains('*') {
+        Err("`*` has to be used on both sides")
+    } else {
+        Ok(())
+    }
+}
+
+fn is_fast_forward(
+    repo: &Repository,
+    old: Option<Oid>,
+    new: Oid,
+) -> bool {
+    old.is_none_or(|old| {
+        old == new
+            || repo.graph_descendant_of(new, old).unwrap_or(false)
+    })
+}
+
+/// checks ssh host keys against `known_hosts` during a single
+/// remote operation and keeps what it found wrong with them
+#[derive(Clone)]
+struct HostKeyCheck {
+    port: Option<u16>,
+    error: Rc<RefCell<Option<RemoteError>>>,
+}
+
+impl HostKeyCheck {
+    /// `None` unless `url` is an ssh remote
+    fn new(url: &str) -> Option<Self> {
+        let port = if url.contains("://") {
+            let url = url::Url::parse(url).ok()?;
+            if !url.scheme().contains("ssh") {
+                return None;
+            }
+            url.port()
+        } else {
+            // scp like `user@host:path`, but no local `c:/path`
+            let colon = url.find(':')?;
+            if colon < 2 || url[..colon].contains('/') {
+                return None;
+            }
+            None
+        };
+
+        Some(Self {
+            port,
+            error: Rc::new(RefCell::new(None)),
+        })
+    }
+
+    fn verify(&self, cert: &Cert<'_>, host: &str) -> bool {
+        self.verify_hash(
+            cert.as_hostkey().and_then(CertHostkey::hash_sha256),
+            host,
+        )
+    }
+
+    /// `known_hosts` lists sha256 fingerprints, a key without
+    /// that hash can not be checked and is refused
+    fn verify_hash(
+        &self,
+        hash: Option<&[u8; 32]>,
+        host: &str,
+    ) -> bool {
+        let host = known_hosts_name(host, self.port);
+
+        let hash = if let Some(hash) = hash {
+            hash
+        } else {
+            let error = RemoteError::Other(format!(
+                "could not verify the host key of `{}`: no sha256 hash of it",
+                host
+            ));
+            log::warn!("host key check failed: {}", error);
+            *self.error.borrow_mut() = Some(error);
+            return false;
+        };
+
+        let fingerprint = fingerprint_sha256(hash);
+
+        let error = match known_hosts_file().and_then(|file| {
+            let status = check_host_key(&file, &host, &fingerprint)?;
+            Ok((file, status))
+        }) {
+            Ok((file, status)) => match decide_host_key(
+                status,
+                &host,
+                &fingerprint,
+                trust_new_host_keys(),
+            ) {
+                HostKeyDecision::Accept => return true,
+                HostKeyDecision::Trust => {
+                    match trust_host_key(&file, &host, &fingerprint) {
+                        Ok(()) => {
+                            log::info!(
+                                "trusted new host key of `{}` ({})",
+                                host,
+                                fingerprint
+                            );
+                            return true;
+                        }
+                        Err(e) => RemoteError::Other(format!(
+                            "could not add the host key of `{}` to known_hosts: {}",
+                            host, e
+                        )),
+                    }
+                }
+                HostKeyDecision::Refuse(error) => error,
+            },
+            Err(e) => RemoteError::Other(format!(
+                "could not verify the host key of `{}`: {}",
+                host, e
+            )),
+        };
+
+        log::warn!("host key check failed: {}", error);
+        *self.error.borrow_mut() = Some(error);
+
+        false
+    }
+}
+
+/// what the host key check does about a key `known_hosts` lists
+/// as `status`
+#[derive(Debug, PartialEq)]
+enum HostKeyDecision {
+    Accept,
+    /// add it to `known_hosts` and accept it
+    Trust,
+    Refuse(RemoteError),
+}
+
+/// keys of hosts `known_hosts` does not list at all are only
+/// trusted with `trust_new`, other keys of listed hosts never
+fn decide_host_key(
+    status: HostKeyStatus,
+    host: &str,
+    fingerprint: &str,
+    trust_new: bool,
+) -> HostKeyDecision {
+    match status {
+        HostKeyStatus::Known => HostKeyDecision::Accept,
+        HostKeyStatus::Unknown if trust_new => HostKeyDecision::Trust,
+        HostKeyStatus::Unknown => {
+            HostKeyDecision::Refuse(RemoteError::UnknownHostKey {
+                host: host.to_string(),
+                fingerprint: fingerprint.to_string(),
+            })
+        }
+        HostKeyStatus::Mismatch => {
+            HostKeyDecision::Refuse(RemoteError::HostKeyMismatch {
+                host: host.to_string(),
+                fingerprint: fingerprint.to_string(),
+            })
+        }
+        HostKeyStatus::Revoked => {
+            HostKeyDecision::Refuse(RemoteError::HostKeyRevoked {
+                host: host.to_string(),
+                fingerprint: fingerprint.to_string(),
+            })
+        }
+    }
+}
+
+/// libgit2 only reports a generic certificate error after the
+/// host key check refused, prefer what the check found
+fn classify(
+    e: &GitError,
+    url: &str,
+    refname: &str,
+    host_keys: Option<&HostKeyCheck>,
+) -> RemoteError {
+    host_keys
+        .and_then(|check| check.error.borrow_mut().take())
+        .unwrap_or_else(|| {
+            RemoteError::from_git(e, Some(url), refname)
+        })
+}
+
+/// the remote `name` of `repo`, `NotFound` if there is none
+fn find_remote<'a>(
+    repo: &'a Repository,
+    name: &str,
+) -> RemoteResult<Remote<'a>> {
+    repo.find_remote(name).map_err(|e| {
+        if e.code() == ErrorCode::NotFound {
+            RemoteError::NotFound
+        } else {
+            RemoteError::from_git(&e, None, "")
+        }
+    })
+}
+
+/// keeps credentials the remote accepted for the rest of the
+/// session and drops them again as soon as it rejects them
+fn update_session_credential<T>(
+    url: &str,
+    basic_credential: Option<&BasicAuthCredential>,
+    res: &RemoteResult<T>,
+) {
+    match (res, basic_credential) {
+        (Err(RemoteError::Auth), _) => forget_credential(url),
+        // a key passphrase comes without a username
+        (Ok(_), Some(cred))
+            if cred.is_complete() || cred.is_passphrase() =>
+        {
+            remember_credential(url, cred);
+        }
+        _ => (),
+    }
+}
+
 fn remote_callbacks<'a>(
     sender: Option<Sender<ProgressNotification>>,
     basic_credential: Option<BasicAuthCredential>,
+    watchdog: Option<Watchdog>,
+    host_keys: Option<HostKeyCheck>,
 ) -> Result<RemoteCallbacks<'a>> {
+    // every callback counts as progress, the ones that can
+    // abort the operation do so once it was cancelled
+    let alive = move || watchdog.as_ref().is_none_or(Watchdog::alive);
+
     let mut callbacks = RemoteCallbacks::new();
+
+    if let Some(host_keys) = host_keys {
+        callbacks.certificate_check(move |cert, host| {
+            host_keys.verify(cert, host)
+        });
+    }
+
     let sender_clone = sender.clone();
+    let alive_clone = alive.clone();
     callbacks.push_transfer_progress(move |current, total, bytes| {
         log::debug!("progress: {}/{} ({} B)", current, total, bytes,);
+        alive_clone();
 
         sender_clone.clone().map(|sender| {
             sender.send(ProgressNotification::PushTransfer {
@@ -134,7 +1002,11 @@ fn remote_callbacks<'a>(
         });
     });
 
+    let alive_clone = alive.clone();
+    callbacks.sideband_progress(move |_| alive_clone());
+
     let sender_clone = sender.clone();
+    let alive_clone = alive.clone();
     callbacks.update_tips(move |name, a, b| {
         log::debug!("update tips: '{}' [{}] [{}]", name, a, b);
 
@@ -145,10 +1017,11 @@ fn remote_callbacks<'a>(
                 b: b.into(),
             })
         });
-        true
+        alive_clone()
     });
 
     let sender_clone = sender.clone();
+    let alive_clone = alive.clone();
     callbacks.transfer_progress(move |p| {
         log::debug!(
             "transfer: {}/{}",
@@ -156,17 +1029,27 @@ fn remote_callbacks<'a>(
             p.total_objects()
         );
 
-        sender_clone.clone().map(|sender| {
-            sender.send(ProgressNotification::Transfer {
+        let notification = if p.total_deltas() > 0
+            && p.received_objects() == p.total_objects()
+        {
+            ProgressNotification::ResolvingDeltas {
+                indexed: p.indexed_deltas(),
+                total: p.total_deltas(),
+            }
+        } else {
+            ProgressNotification::Transfer {
                 objects: p.received_objects(),
                 total_objects: p.total_objects(),
-            })
-        });
-        true
+            }
+        };
+
+        sender_clone.clone().map(|sender| sender.send(notification));
+        alive_clone()
     });
 
     callbacks.pack_progress(move |stage, current, total| {
         log::debug!("packing: {:?} - {}/{}", stage, current, total);
+        alive();
 
         sender.clone().map(|sender| {
             sender.send(ProgressNotification::Packing {
@@ -194,16 +1077,34 @@ fn remote_callbacks<'a>(
             if first_call_to_credentials {
                 first_call_to_credentials = false;
             } else {
-                return Err(GitError::from_str("Bad credentials."));
+                return Err(GitError::new(
+                    ErrorCode::Auth,
+                    ErrorClass::Callback,
+                    "Bad credentials.",
+                ));
             }
 
             match &basic_credential {
                 _ if allowed_types.is_ssh_key() => {
                     match username_from_url {
-                        Some(username) => {
-                            Cred::ssh_key_from_agent(username)
-                        }
-                        None => Err(GitError::from_str(
+                        Some(username) => match ssh_key_file() {
+                            Some(key) if !ssh_agent_available() => {
+                                Cred::ssh_key(
+                                    username,
+                                    None,
+                                    &key,
+                                    basic_credential
+                                        .as_ref()
+                                        .and_then(|cred| {
+                                            cred.password.as_deref()
+                                        }),
+                                )
+                            }
+                            _ => Cred::ssh_key_from_agent(username),
+                        },
+                        None => Err(GitError::new(
+                            ErrorCode::Auth,
+                            ErrorClass::Callback,
                             " Couldn't extract username from url.",
                         )),
                     }
@@ -221,7 +1122,9 @@ fn remote_callbacks<'a>(
                     Cred::username(user)
                 }
                 _ if allowed_types.is_default() => Cred::default(),
-                _ => Err(GitError::from_str(
+                _ => Err(GitError::new(
+                    ErrorCode::Auth,
+                    ErrorClass::Callback,
                     "Couldn't find credentials",
                 )),
             }
@@ -234,9 +1137,132 @@ fn remote_callbacks<'a>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sync::tests::debug_cmd_print;
+    use crate::remote_ops::{RemoteOp, RemoteOps};
+    use crate::sync::{
+        commit,
+        cred::remembered_credential,
+        rebase_abort,
+        tests::{
+            commit_file, debug_cmd_print, repo_init, repo_init_bare,
+            repo_init_empty,
+        },
+        utils::get_head_repo,
+    };
+    use git2::RepositoryState;
     use tempfile::TempDir;
 
+    fn push_master(repo: &Repository) {
+        let root = repo.path().parent().unwrap();
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+        push(
+            root.as_os_str().to_str().unwrap(),
+            DEFAULT_REMOTE_NAME,
+            "refs/heads/master",
+            None,
+            sender,
+            None,
+        )
+        .unwrap();
+    }
+
+    /// two clones of a bare remote sharing its `master`
+    fn clones() -> (Vec<TempDir>, Repository, Repository) {
+        let (remote_dir, remote_repo) = repo_init_bare().unwrap();
+        let remote_path = remote_repo.path().to_str().unwrap();
+
+        let (td, upstream) = repo_init().unwrap();
+        let upstream_path =
+            upstream.path().parent().unwrap().to_str().unwrap();
+        upstream.remote(DEFAULT_REMOTE_NAME, remote_path).unwrap();
+        commit_file(upstream_path, "foo.txt", "a\n", "a");
+        push_master(&upstream);
+
+        let (td2, local) = repo_init_empty().unwrap();
+        let local_path =
+            local.path().parent().unwrap().to_str().unwrap();
+        local.remote(DEFAULT_REMOTE_NAME, remote_path).unwrap();
+        fetch_origin(local_path, "master").unwrap();
+        let head = get_head_repo(&upstream).unwrap();
+        local
+            .reference("refs/heads/master", head.into(), true, "")
+            .unwrap();
+        local
+            .checkout_head(Some(
+                git2::build::CheckoutBuilder::new().force(),
+            ))
+            .unwrap();
+
+        (vec![remote_dir, td, td2], upstream, local)
+    }
+
+    #[test]
+    fn test_pull_rebase_fast_forward() {
+        let (_tds, upstream, local) = clones();
+        let local_path =
+            local.path().parent().unwrap().to_str().unwrap();
+        let upstream_path =
+            upstream.path().parent().unwrap().to_str().unwrap();
+
+        commit_file(upstream_path, "foo.txt", "b\n", "b");
+        push_master(&upstream);
+
+        pull_rebase(
+            local_path,
+            DEFAULT_REMOTE_NAME,
+            "master",
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_head_repo(&local).unwrap(),
+            get_head_repo(&upstream).unwrap()
+        );
+        assert_eq!(local.state(), RepositoryState::Clean);
+        assert_eq!(
+            std::fs::read_to_string(
+                local.path().parent().unwrap().join("foo.txt")
+            )
+            .unwrap(),
+            "b\n"
+        );
+    }
+
+    #[test]
+    fn test_pull_rebase_conflict() {
+        let (_tds, upstream, local) = clones();
+        let local_path =
+            local.path().parent().unwrap().to_str().unwrap();
+        let upstream_path =
+            upstream.path().parent().unwrap().to_str().unwrap();
+
+        commit_file(upstream_path, "foo.txt", "b\n", "b");
+        push_master(&upstream);
+        let mine = commit_file(local_path, "foo.txt", "c\n", "c");
+
+        let res = pull_rebase(
+            local_path,
+            DEFAULT_REMOTE_NAME,
+            "master",
+            None,
+            None,
+            None,
+        );
+        assert_eq!(
+            res,
+            Err(RemoteError::RebaseConflict {
+                commit: mine.get_short_string()
+            })
+        );
+        assert_eq!(local.state(), RepositoryState::RebaseMerge);
+
+        rebase_abort(local_path).unwrap();
+        assert_eq!(local.state(), RepositoryState::Clean);
+        assert_eq!(get_head_repo(&local).unwrap(), mine);
+    }
+
     #[test]
     fn test_smoke() {
         let td = TempDir::new().unwrap();
@@ -255,4 +1281,697 @@ mod tests {
 
         fetch_origin(repo_path, "master").unwrap();
     }
+
+    #[test]
+    fn test_classify_auth() {
+        let e = GitError::new(
+            ErrorCode::Auth,
+            ErrorClass::Callback,
+            "Bad credentials.",
+        );
+
+        assert_eq!(
+            RemoteError::from_git(
+                &e,
+                Some("https://host/repo"),
+                "master"
+            ),
+            RemoteError::Auth
+        );
+    }
+
+    #[test]
+    fn test_classify_wrong_passphrase() {
+        let e = GitError::new(
+            ErrorCode::GenericError,
+            ErrorClass::Ssh,
+            "Failed to authenticate SSH session: Unable to extract public key from private key file: Wrong passphrase or invalid/unrecognized private key file format",
+        );
+
+        assert_eq!(
+            RemoteError::from_git(
+                &e,
+                Some("ssh://host/repo"),
+                "master"
+            ),
+            RemoteError::Auth
+        );
+    }
+
+    #[test]
+    fn test_classify_network() {
+        let e = GitError::new(
+            ErrorCode::GenericError,
+            ErrorClass::Net,
+            "failed to resolve address",
+        );
+
+        assert_eq!(
+            RemoteError::from_git(
+                &e,
+                Some("https://host/repo"),
+                "master"
+            ),
+            RemoteError::Network {
+                url: String::from("https://host/repo"),
+                message: String::from("failed to resolve address"),
+            }
+        );
+        // not raised talking to a remote
+        assert_eq!(
+            RemoteError::from_git(&e, None, "master"),
+            RemoteError::Other(String::from(
+                "failed to resolve address"
+            ))
+        );
+
+        let e = GitError::new(
+            ErrorCode::GenericError,
+            ErrorClass::Os,
+            "could not open file",
+        );
+        assert_eq!(
+            RemoteError::from_git(
+                &e,
+                Some("https://host/repo"),
+                "master"
+            ),
+            RemoteError::Other(String::from("could not open file"))
+        );
+    }
+
+    #[test]
+    fn test_classify_rejected() {
+        let e = GitError::new(
+            ErrorCode::NotFastForward,
+            ErrorClass::Reference,
+            "cannot push non-fastforwardable reference",
+        );
+
+        assert_eq!(
+            RemoteError::from_git(
+                &e,
+                Some("https://host/repo"),
+                "master"
+            ),
+            RemoteError::Rejected {
+                refname: String::from("master"),
+                reason: String::from(
+                    "cannot push non-fastforwardable reference"
+                ),
+            }
+        );
+    }
+
+    #[test]
+    fn test_classify_not_found() {
+        let e = GitError::new(
+            ErrorCode::NotFound,
+            ErrorClass::Config,
+            "remote 'foo' does not exist",
+        );
+
+        assert_eq!(
+            RemoteError::from_git(
+                &e,
+                Some("https://host/repo"),
+                "master"
+            ),
+            RemoteError::NotFound
+        );
+        assert_eq!(
+            RemoteError::from(Error::UnknownRemote),
+            RemoteError::NotFound
+        );
+
+        let (_td, repo) = repo_init().unwrap();
+        assert_eq!(
+            find_remote(&repo, "foo").err(),
+            Some(RemoteError::NotFound)
+        );
+
+        // a missing local ref is no missing remote
+        let e = GitError::new(
+            ErrorCode::NotFound,
+            ErrorClass::Reference,
+            "reference 'refs/heads/foo' not found",
+        );
+        assert_eq!(
+            RemoteError::from(Error::Git(e)),
+            RemoteError::Other(String::from(
+                "reference 'refs/heads/foo' not found"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_classify_other() {
+        let e = GitError::new(
+            ErrorCode::GenericError,
+            ErrorClass::Odb,
+            "object corrupt",
+        );
+
+        assert_eq!(
+            RemoteError::from_git(&e, None, "master"),
+            RemoteError::Other(String::from("object corrupt"))
+        );
+    }
+
+    #[test]
+    fn test_fetch_upstream_missing() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert!(matches!(
+            fetch_upstream(repo_path, "master"),
+            Err(RemoteError::Other(_))
+        ));
+    }
+
+    #[test]
+    fn test_split_refspec() {
+        assert_eq!(
+            split_refspec("refs/heads/master"),
+            ("refs/heads/master", "refs/heads/master")
+        );
+        assert_eq!(
+            split_refspec("+refs/heads/a:refs/heads/b"),
+            ("refs/heads/a", "refs/heads/b")
+        );
+    }
+
+    #[test]
+    fn test_validate_refspec() {
+        assert!(validate_refspec("master").is_ok());
+        assert!(validate_refspec(
+            "+refs/pull/*/head:refs/remotes/origin/pr/*"
+        )
+        .is_ok());
+        assert!(validate_refspec("").is_err());
+        assert!(validate_refspec(":refs/heads/a").is_err());
+        assert!(validate_refspec("refs/heads/a:").is_err());
+        assert!(validate_refspec("a:b:c").is_err());
+        assert!(validate_refspec("refs/heads/a b").is_err());
+        assert!(validate_refspec("refs/*/*:refs/*").is_err());
+        assert!(validate_refspec("refs/heads/*:refs/a").is_err());
+    }
+
+    #[test]
+    fn test_fetch_refspec() {
+        let (_remote_dir, remote_repo) = repo_init_bare().unwrap();
+        let remote_path = remote_repo.path().to_str().unwrap();
+
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        repo.remote(DEFAULT_REMOTE_NAME, remote_path).unwrap();
+        commit(repo_path, "pr head").unwrap();
+
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+        push(
+            repo_path,
+            DEFAULT_REMOTE_NAME,
+            "refs/heads/master",
+            None,
+            sender,
+            None,
+        )
+        .unwrap();
+
+        // github style pull request ref outside of `refs/heads`
+        let head = get_head_repo(&repo).unwrap();
+        remote_repo
+            .reference("refs/pull/1/head", head.into(), false, "")
+            .unwrap();
+
+        let (_td2, other) = repo_init().unwrap();
+        let other_root = other.path().parent().unwrap();
+        let other_path = other_root.as_os_str().to_str().unwrap();
+        other.remote(DEFAULT_REMOTE_NAME, remote_path).unwrap();
+
+        fetch_refspec(
+            other_path,
+            DEFAULT_REMOTE_NAME,
+            "+refs/pull/*/head:refs/remotes/origin/pr/*",
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            other
+                .find_reference("refs/remotes/origin/pr/1")
+                .unwrap()
+                .target(),
+            Some(head.into())
+        );
+
+        assert!(matches!(
+            fetch_refspec(
+                other_path,
+                DEFAULT_REMOTE_NAME,
+                "refs/pull/*/head:refs/pr",
+                None,
+                None,
+                None,
+            ),
+            Err(RemoteError::InvalidRefspec { .. })
+        ));
+    }
+
+    #[test]
+    fn test_remotes_info_push_url() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo.remote(DEFAULT_REMOTE_NAME, "https://a.com/r").unwrap();
+
+        let info = get_remotes_info(repo_path).unwrap();
+        assert_eq!(info.len(), 1);
+        assert_eq!(info[0].push_url, None);
+        assert_eq!(
+            info[0].effective_push_url(),
+            Some("https://a.com/r")
+        );
+
+        set_remote_push_url(
+            repo_path,
+            DEFAULT_REMOTE_NAME,
+            Some("https://b.com/r"),
+        )
+        .unwrap();
+
+        let info = get_remotes_info(repo_path).unwrap();
+        assert_eq!(info[0].url.as_deref(), Some("https://a.com/r"));
+        assert_eq!(
+            info[0].effective_push_url(),
+            Some("https://b.com/r")
+        );
+
+        set_remote_push_url(repo_path, DEFAULT_REMOTE_NAME, None)
+            .unwrap();
+        // clearing twice is fine
+        set_remote_push_url(repo_path, DEFAULT_REMOTE_NAME, None)
+            .unwrap();
+
+        let info = get_remotes_info(repo_path).unwrap();
+        assert_eq!(info[0].push_url, None);
+    }
+
+    #[test]
+    fn test_ls_remote() {
+        let (_remote_dir, remote_repo) = repo_init_bare().unwrap();
+        let remote_path = remote_repo.path().to_str().unwrap();
+
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo.remote(DEFAULT_REMOTE_NAME, remote_path).unwrap();
+
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+        push(
+            repo_path,
+            DEFAULT_REMOTE_NAME,
+            "refs/heads/master",
+            None,
+            sender,
+            None,
+        )
+        .unwrap();
+        let head = get_head_repo(&repo).unwrap();
+
+        let refs =
+            ls_remote(repo_path, DEFAULT_REMOTE_NAME, None, None)
+                .unwrap();
+
+        assert!(refs
+            .iter()
+            .any(|(name, id)| name == "refs/heads/master"
+                && *id == head));
+    }
+
+    #[test]
+    fn test_fetch_origin_local() {
+        let (_remote_dir, remote_repo) = repo_init_bare().unwrap();
+        let remote_path = remote_repo.path().to_str().unwrap();
+
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        repo.remote(DEFAULT_REMOTE_NAME, remote_path).unwrap();
+        commit(repo_path, "fetch me").unwrap();
+
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+        push(
+            repo_path,
+            DEFAULT_REMOTE_NAME,
+            "refs/heads/master",
+            None,
+            sender,
+            None,
+        )
+        .unwrap();
+
+        let (_td2, other) = repo_init().unwrap();
+        let other_root = other.path().parent().unwrap();
+        let other_path = other_root.as_os_str().to_str().unwrap();
+        other.remote(DEFAULT_REMOTE_NAME, remote_path).unwrap();
+
+        let head = get_head_repo(&repo).unwrap();
+        assert!(other.find_commit(head.into()).is_err());
+
+        // the local transport does not report transfer stats
+        let stats = fetch_origin(other_path, "master").unwrap();
+        assert!(stats.received_objects <= stats.total_objects);
+
+        assert!(other.find_commit(head.into()).is_ok());
+    }
+
+    #[test]
+    fn test_decide_host_key() {
+        let decide = |status, trust_new| {
+            decide_host_key(status, "a.com", "SHA256:x", trust_new)
+        };
+        let unknown = RemoteError::UnknownHostKey {
+            host: String::from("a.com"),
+            fingerprint: String::from("SHA256:x"),
+        };
+        let mismatch = RemoteError::HostKeyMismatch {
+            host: String::from("a.com"),
+            fingerprint: String::from("SHA256:x"),
+        };
+        let revoked = RemoteError::HostKeyRevoked {
+            host: String::from("a.com"),
+            fingerprint: String::from("SHA256:x"),
+        };
+
+        for trust_new in &[false, true] {
+            assert_eq!(
+                decide(HostKeyStatus::Known, *trust_new),
+                HostKeyDecision::Accept
+            );
+            assert_eq!(
+                decide(HostKeyStatus::Mismatch, *trust_new),
+                HostKeyDecision::Refuse(mismatch.clone())
+            );
+            assert_eq!(
+                decide(HostKeyStatus::Revoked, *trust_new),
+                HostKeyDecision::Refuse(revoked.clone())
+            );
+        }
+
+        assert_eq!(
+            decide(HostKeyStatus::Unknown, false),
+            HostKeyDecision::Refuse(unknown)
+        );
+        assert_eq!(
+            decide(HostKeyStatus::Unknown, true),
+            HostKeyDecision::Trust
+        );
+    }
+
+    #[test]
+    fn test_trust_new_keeps_listed_host() {
+        let td = TempDir::new().unwrap();
+        let known_hosts = td.path().join("known_hosts");
+        std::fs::write(
+            &known_hosts,
+            "a.com ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIFkgSinHFA9HyQQXrh+y1Pab3cBoXnTu3Vyev5jLh1fT\n",
+        )
+        .unwrap();
+
+        // the host offers an rsa key instead of its ed25519 one
+        let rsa = fingerprint_sha256(&[0; 32]);
+        let status =
+            check_host_key(&known_hosts, "a.com", &rsa).unwrap();
+
+        assert_eq!(
+            decide_host_key(status, "a.com", &rsa, true),
+            HostKeyDecision::Refuse(RemoteError::HostKeyMismatch {
+                host: String::from("a.com"),
+                fingerprint: rsa,
+            })
+        );
+    }
+
+    #[test]
+    fn test_host_key_check_only_for_ssh() {
+        let port = |url: &str| HostKeyCheck::new(url).map(|c| c.port);
+
+        assert_eq!(port("git@github.com:user/repo.git"), Some(None));
+        assert_eq!(port("ssh://git@host/repo"), Some(None));
+        assert_eq!(
+            port("ssh://git@host:2222/repo"),
+            Some(Some(2222))
+        );
+        assert_eq!(port("git+ssh://host/repo"), Some(None));
+
+        assert_eq!(port("https://github.com/user/repo"), None);
+        assert_eq!(port("file:///tmp/repo"), None);
+        assert_eq!(port("/tmp/repo"), None);
+        assert_eq!(port("./repo:with-colon"), None);
+        assert_eq!(port("c:/repo"), None);
+    }
+
+    #[test]
+    fn test_host_key_without_hash() {
+        let check =
+            HostKeyCheck::new("ssh://git@host:2222/repo").unwrap();
+
+        assert!(!check.verify_hash(None, "host"));
+        assert_eq!(
+            check.error.borrow().clone(),
+            Some(RemoteError::Other(String::from(
+                "could not verify the host key of `[host]:2222`: no sha256 hash of it"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_watchdog_timeout() {
+        let timeout = Duration::from_millis(100);
+
+        let (cancelled_sender, cancelled) = bounded(1);
+        let res: RemoteResult<()> =
+            run_with_watchdog(timeout, move |watchdog| {
+                thread::sleep(timeout * 3);
+                cancelled_sender.send(!watchdog.alive()).ok();
+                Ok(())
+            });
+
+        assert_eq!(res, Err(RemoteError::Timeout { secs: 0 }));
+        // the next progress callback aborts the stuck operation
+        assert!(cancelled.recv().unwrap());
+    }
+
+    #[test]
+    fn test_watchdog_timeout_keeps_op_running() {
+        let timeout = Duration::from_millis(100);
+        let ops = RemoteOps::new();
+        let ticket = ops.enqueue(RemoteOp::Fetch).unwrap();
+        let guard = ticket.wait().unwrap().unwrap();
+
+        let res: RemoteResult<()> =
+            run_with_watchdog(timeout, move |_| {
+                let _guard = guard;
+                thread::sleep(timeout * 3);
+                Ok(())
+            });
+        assert_eq!(res, Err(RemoteError::Timeout { secs: 0 }));
+
+        // the worker is still busy with the remote
+        assert_eq!(ops.running(), Some(RemoteOp::Fetch));
+        thread::sleep(timeout * 4);
+        assert_eq!(ops.running(), None);
+    }
+
+    #[test]
+    fn test_watchdog_progress_resets() {
+        let timeout = Duration::from_millis(100);
+
+        // takes longer than `timeout` but keeps making progress
+        let res = run_with_watchdog(timeout, move |watchdog| {
+            for _ in 0..6 {
+                thread::sleep(timeout / 2);
+                assert!(watchdog.alive());
+            }
+            Ok(42)
+        });
+
+        assert_eq!(res, Ok(42));
+    }
+
+    #[test]
+    fn test_ls_remote_missing() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        assert_eq!(
+            ls_remote(repo_path, DEFAULT_REMOTE_NAME, None, None),
+            Err(RemoteError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_push_dry_run() {
+        let (_remote_dir, remote_repo) = repo_init_bare().unwrap();
+        let remote_path = remote_repo.path().to_str().unwrap();
+
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        repo.remote(DEFAULT_REMOTE_NAME, remote_path).unwrap();
+
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+        push(
+            repo_path,
+            DEFAULT_REMOTE_NAME,
+            "refs/heads/master",
+            None,
+            sender,
+            None,
+        )
+        .unwrap();
+        let pushed = get_head_repo(&repo).unwrap();
+
+        // ref does not exist on remote yet
+        let res = push_dry_run(
+            repo_path,
+            DEFAULT_REMOTE_NAME,
+            &["refs/heads/master:refs/heads/other"],
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].old_remote_tip, None);
+        assert_eq!(res[0].fast_forward, true);
+
+        // descendant of remote tip
+        let sig = repo.signature().unwrap();
+        let parent = repo.find_commit(pushed.into()).unwrap();
+        let tree = parent.tree().unwrap();
+        let new = repo
+            .commit(
+                Some("HEAD"),
+                &sig,
+                &sig,
+                "next",
+                &tree,
+                &[&parent],
+            )
+            .unwrap();
+
+        let spec = ["refs/heads/master"];
+
+        let res = push_dry_run(
+            repo_path,
+            DEFAULT_REMOTE_NAME,
+            &spec,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            res[0],
+            PushUpdate {
+                refname: String::from(spec[0]),
+                old_remote_tip: Some(pushed),
+                new_local_tip: new.into(),
+                fast_forward: true,
+            }
+        );
+
+        // unrelated history
+        let orphan = repo
+            .commit(None, &sig, &sig, "orphan", &tree, &[])
+            .unwrap();
+        repo.reference("refs/heads/master", orphan, true, "test")
+            .unwrap();
+
+        let res = push_dry_run(
+            repo_path,
+            DEFAULT_REMOTE_NAME,
+            &spec,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(res[0].fast_forward, false);
+    }
+
+    #[test]
+    fn test_push_missing_remote() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let (sender, _receiver) = crossbeam_channel::unbounded();
+
+        assert_eq!(
+            push(repo_path, "origin", "master", None, sender, None),
+            Err(RemoteError::NotFound)
+        );
+    }
+
+    #[test]
+    fn test_session_credential_until_rejected() {
+        let url = "https://remotes@example.com/repo";
+        let cred = BasicAuthCredential::new(
+            Some("remotes".to_owned()),
+            Some("secret".to_owned()),
+        );
+
+        update_session_credential(
+            url,
+            Some(&BasicAuthCredential::new(
+                Some("remotes".to_owned()),
+                None,
+            )),
+            &Ok(()),
+        );
+        assert_eq!(remembered_credential(url), None);
+
+        update_session_credential(url, Some(&cred), &Ok(()));
+        assert_eq!(remembered_credential(url), Some(cred.clone()));
+
+        update_session_credential::<()>(
+            url,
+            None,
+            &Err(RemoteError::NotFound),
+        );
+        assert_eq!(remembered_credential(url), Some(cred));
+
+        update_session_credential::<()>(
+            url,
+            None,
+            &Err(RemoteError::Auth),
+        );
+        assert_eq!(remembered_credential(url), None);
+    }
+
+    #[test]
+    fn test_session_ssh_passphrase_until_rejected() {
+        let url = "ssh://git@passphrase.example.com/repo";
+        let cred =
+            BasicAuthCredential::new(None, Some("secret".to_owned()));
+
+        update_session_credential(url, Some(&cred), &Ok(()));
+        assert_eq!(remembered_credential(url), Some(cred));
+
+        // a wrong passphrase
+        update_session_credential::<()>(
+            url,
+            None,
+            &Err(RemoteError::Auth),
+        );
+        assert_eq!(remembered_credential(url), None);
+    }
 }
@@ -1,6 +1,9 @@
-use super::utils::{get_head_repo, repo};
+use super::{
+    utils::{get_head_repo, repo},
+    CommitId,
+};
 use crate::error::Result;
-use git2::{build::CheckoutBuilder, ObjectType};
+use git2::{build::CheckoutBuilder, ObjectType, ResetType};
 use scopetime::scope_time;
 
 ///
@@ -38,6 +41,20 @@ pub fn reset_workdir(repo_path: &str, path: &str) -> Result<()> {
     Ok(())
 }
 
+/// moves HEAD to `id`, discarding all changes in index and workdir
+pub fn reset_hard(repo_path: &str, id: CommitId) -> Result<()> {
+    scope_time!("reset_hard");
+
+    let repo = repo(repo_path)?;
+
+    let obj =
+        repo.find_object(id.into(), Some(ObjectType::Commit))?;
+
+    repo.reset(&obj, ResetType::Hard, None)?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::{reset_stage, reset_workdir};
@@ -0,0 +1,173 @@
+//! undoing the changes a commit made to a single file
+
+use super::{utils::repo, CommitId};
+use crate::error::{Error, Result};
+use git2::{build::CheckoutBuilder, Status};
+use scopetime::scope_time;
+use std::{fs, path::Path};
+
+/// what reverting a file of a commit did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevertFileOutcome {
+    /// the reverse change is staged and in the work dir
+    Reverted,
+    /// the file was changed since, its conflict is left in the
+    /// index and the work dir to be resolved
+    Conflict,
+}
+
+/// applies the reverse of the changes commit `id` made to `path`
+/// on top of `HEAD`, staged and in the work dir.
+/// fails if `path` has uncommitted changes
+pub fn revert_commit_file(
+    repo_path: &str,
+    id: CommitId,
+    path: &str,
+) -> Result<RevertFileOutcome> {
+    scope_time!("revert_commit_file");
+
+    let repo = repo(repo_path)?;
+    let file = Path::new(path);
+
+    let status = repo.status_file(file).unwrap_or(Status::CURRENT);
+    if !(status - Status::IGNORED).is_empty() {
+        return Err(Error::Generic(format!(
+            "`{}` has uncommitted changes",
+            path
+        )));
+    }
+
+    let commit = repo.find_commit(id.into())?;
+    let head = repo.head()?.peel_to_commit()?;
+    // merges are reverted relative to their first parent
+    let mainline = if commit.parent_count() > 1 { 1 } else { 0 };
+    let reverted =
+        repo.revert_commit(&commit, &head, mainline, None)?;
+
+    let mut index = repo.index()?;
+    let conflict = reverted.conflicts()?.find_map(|conflict| {
+        conflict.ok().filter(|conflict| {
+            [&conflict.ancestor, &conflict.our, &conflict.their]
+                .iter()
+                .any(|entry| {
+                    entry.as_ref().is_some_and(|entry| {
+                        entry.path == path.as_bytes()
+                    })
+                })
+        })
+    });
+
+    let outcome = if let Some(conflict) = conflict {
+        index.remove_path(file)?;
+        for entry in [conflict.ancestor, conflict.our, conflict.their]
+            .iter()
+            .flatten()
+        {
+            index.add(entry)?;
+        }
+        RevertFileOutcome::Conflict
+    } else if let Some(entry) = reverted.get_path(file, 0) {
+        index.add(&entry)?;
+        RevertFileOutcome::Reverted
+    } else {
+        // the commit added the file
+        index.remove_path(file)?;
+        let work_file =
+            repo.workdir().ok_or(Error::NoWorkDir)?.join(file);
+        if work_file.exists() {
+            fs::remove_file(work_file)?;
+        }
+        index.write()?;
+        return Ok(RevertFileOutcome::Reverted);
+    };
+
+    index.write()?;
+
+    // writes the conflict markers if there is a conflict
+    repo.checkout_index(
+        Some(&mut index),
+        Some(
+            CheckoutBuilder::new()
+                .path(path)
+                .force()
+                .allow_conflicts(true)
+                .conflict_style_merge(true),
+        ),
+    )?;
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        stage_add_file,
+        tests::{commit_file, repo_init},
+    };
+
+    fn read(repo_path: &str, name: &str) -> String {
+        fs::read_to_string(Path::new(repo_path).join(name)).unwrap()
+    }
+
+    #[test]
+    fn test_revert_file() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        commit_file(repo_path, "a.txt", "a\n", "add a");
+        fs::write(root.join("b.txt"), "b\n").unwrap();
+        stage_add_file(repo_path, Path::new("b.txt")).unwrap();
+        let id = commit_file(repo_path, "a.txt", "changed\n", "both");
+
+        assert_eq!(
+            revert_commit_file(repo_path, id, "a.txt").unwrap(),
+            RevertFileOutcome::Reverted
+        );
+        assert_eq!(read(repo_path, "a.txt"), "a\n");
+        assert_eq!(read(repo_path, "b.txt"), "b\n");
+        assert_eq!(
+            repo.status_file(Path::new("a.txt")).unwrap(),
+            Status::INDEX_MODIFIED
+        );
+        assert_eq!(
+            repo.status_file(Path::new("b.txt")).unwrap(),
+            Status::CURRENT
+        );
+
+        // not again while the revert is not committed
+        assert!(revert_commit_file(repo_path, id, "a.txt").is_err());
+
+        // a file the commit added goes away
+        assert_eq!(
+            revert_commit_file(repo_path, id, "b.txt").unwrap(),
+            RevertFileOutcome::Reverted
+        );
+        assert!(!root.join("b.txt").exists());
+        assert_eq!(
+            repo.status_file(Path::new("b.txt")).unwrap(),
+            Status::INDEX_DELETED
+        );
+    }
+
+    #[test]
+    fn test_revert_file_conflict() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        commit_file(repo_path, "a.txt", "a\n", "add a");
+        let id = commit_file(repo_path, "a.txt", "b\n", "a to b");
+        commit_file(repo_path, "a.txt", "c\n", "b to c");
+
+        assert_eq!(
+            revert_commit_file(repo_path, id, "a.txt").unwrap(),
+            RevertFileOutcome::Conflict
+        );
+        let mut index = repo.index().unwrap();
+        index.read(true).unwrap();
+        assert!(index.has_conflicts());
+        assert!(read(repo_path, "a.txt").contains("<<<<<<<"));
+    }
+}
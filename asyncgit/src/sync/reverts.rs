@@ -0,0 +1,165 @@
+//! revert relations between commits, found by the
+//! `This reverts commit <sha>.` line `git revert` writes
+
+use super::{utils::repo_allow_bare, CommitId};
+use crate::error::Result;
+use scopetime::scope_time;
+use std::collections::HashMap;
+
+const REVERT_LINE: &str = "This reverts commit ";
+
+/// shortest abbreviated sha accepted in a revert line
+const MIN_SHA_LEN: usize = 7;
+
+/// which commits revert which, looked up both ways
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Reverts {
+    reverts: HashMap<CommitId, CommitId>,
+    reverted_by: HashMap<CommitId, Vec<CommitId>>,
+}
+
+impl Reverts {
+    /// `revert` reverts `reverted`
+    pub fn insert(&mut self, revert: CommitId, reverted: CommitId) {
+        if self.reverts.insert(revert, reverted).is_none() {
+            self.reverted_by
+                .entry(reverted)
+                .or_default()
+                .push(revert);
+        }
+    }
+
+    /// the commit `id` reverts
+    pub fn reverts(&self, id: &CommitId) -> Option<CommitId> {
+        self.reverts.get(id).copied()
+    }
+
+    /// the commits reverting `id`, in the order they were found
+    pub fn reverted_by(&self, id: &CommitId) -> &[CommitId] {
+        self.reverted_by.get(id).map_or(&[], Vec::as_slice)
+    }
+
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.reverts.is_empty()
+    }
+}
+
+/// the (possibly abbreviated) sha the revert line of `message`
+/// names, `None` if it has none
+fn reverted_sha(message: &str) -> Option<&str> {
+    message.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix(REVERT_LINE)?;
+        let len = rest
+            .find(|c: char| !c.is_ascii_hexdigit())
+            .unwrap_or(rest.len());
+
+        (len >= MIN_SHA_LEN).then(|| &rest[..len])
+    })
+}
+
+/// `(revert, reverted)` pairs of the reverts among `ids`,
+/// reverted commits missing in the repository are left out
+pub fn find_reverts(
+    repo_path: &str,
+    ids: &[CommitId],
+) -> Result<Vec<(CommitId, CommitId)>> {
+    scope_time!("find_reverts");
+
+    let repo = repo_allow_bare(repo_path)?;
+    let mut found = Vec::new();
+
+    for id in ids {
+        let commit = repo.find_commit((*id).into())?;
+        let sha = match reverted_sha(
+            commit.message().unwrap_or_default(),
+        ) {
+            Some(sha) => sha,
+            None => continue,
+        };
+
+        if let Ok(reverted) = repo
+            .revparse_single(sha)
+            .and_then(|obj| obj.peel_to_commit())
+        {
+            found.push((*id, reverted.id().into()));
+        }
+    }
+
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{commit, tests::repo_init_empty};
+
+    #[test]
+    fn test_reverted_sha() {
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+
+        assert_eq!(
+            reverted_sha(&format!(
+                "Revert \"foo\"\n\n{}{}.\n",
+                REVERT_LINE, sha
+            )),
+            Some(sha)
+        );
+        assert_eq!(
+            reverted_sha(&format!(
+                "Revert \"foo\"\n\n{}{}, reversing\nchanges made to x.\n",
+                REVERT_LINE, &sha[..7]
+            )),
+            Some(&sha[..7])
+        );
+        assert_eq!(
+            reverted_sha(&format!(
+                "foo\n\n{}{}.\n",
+                REVERT_LINE, "abc"
+            )),
+            None
+        );
+        assert_eq!(reverted_sha("Revert \"foo\""), None);
+    }
+
+    #[test]
+    fn test_find_reverts() {
+        let (_td, repo) = repo_init_empty().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let foo = commit(repo_path, "foo").unwrap();
+        let revert = commit(
+            repo_path,
+            &format!(
+                "Revert \"foo\"\n\n{}{}.\n",
+                REVERT_LINE,
+                foo.to_string()
+            ),
+        )
+        .unwrap();
+        let missing = commit(
+            repo_path,
+            &format!(
+                "Revert \"bar\"\n\n{}{}.\n",
+                REVERT_LINE,
+                "f".repeat(40)
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_reverts(repo_path, &[missing, revert, foo]).unwrap(),
+            vec![(revert, foo)]
+        );
+
+        let mut reverts = Reverts::default();
+        assert!(reverts.is_empty());
+        reverts.insert(revert, foo);
+        reverts.insert(revert, foo);
+        assert_eq!(reverts.reverts(&revert), Some(foo));
+        assert_eq!(reverts.reverts(&foo), None);
+        assert_eq!(reverts.reverted_by(&foo), &[revert]);
+        assert_eq!(reverts.reverted_by(&revert), &[]);
+    }
+}
@@ -0,0 +1,227 @@
+//! signing commits and tags the way `commit.gpgsign` and
+//! `tag.gpgSign` ask for
+
+use crate::error::{Error, Result};
+use git2::{Config, Repository, Signature};
+use std::{
+    env,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+/// what `gpg.format` selects
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SignFormat {
+    OpenPgp,
+    Ssh,
+}
+
+/// signs object buffers with the program and key from git config
+#[derive(Debug)]
+pub(crate) struct Signer {
+    format: SignFormat,
+    program: String,
+    key: Option<String>,
+}
+
+impl Signer {
+    /// the signer for new commits, `None` unless `commit.gpgsign`
+    pub(crate) fn for_commits(
+        repo: &Repository,
+    ) -> Result<Option<Self>> {
+        Self::if_enabled(repo, "commit.gpgsign")
+    }
+
+    /// the signer for new tags, `None` unless `tag.gpgSign`
+    pub(crate) fn for_tags(
+        repo: &Repository,
+    ) -> Result<Option<Self>> {
+        Self::if_enabled(repo, "tag.gpgsign")
+    }
+
+    fn if_enabled(
+        repo: &Repository,
+        enabled: &str,
+    ) -> Result<Option<Self>> {
+        let config = repo.config()?;
+        if config.get_bool(enabled).unwrap_or(false) {
+            Self::from_config(&config).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn from_config(config: &Config) -> Result<Self> {
+        let format = match config.get_string("gpg.format") {
+            Err(_) => SignFormat::OpenPgp,
+            Ok(format) if format == "openpgp" => SignFormat::OpenPgp,
+            Ok(format) if format == "ssh" => SignFormat::Ssh,
+            Ok(format) => {
+                return Err(Error::Sign(format!(
+                    "gpg.format `{}` is not supported",
+                    format
+                )))
+            }
+        };
+
+        let program = match format {
+            SignFormat::OpenPgp => config
+                .get_string("gpg.openpgp.program")
+                .or_else(|_| config.get_string("gpg.program"))
+                .unwrap_or_else(|_| String::from("gpg")),
+            SignFormat::Ssh => config
+                .get_string("gpg.ssh.program")
+                .unwrap_or_else(|_| String::from("ssh-keygen")),
+        };
+
+        let key = config
+            .get_string("user.signingkey")
+            .ok()
+            .filter(|key| !key.trim().is_empty());
+
+        Ok(Self {
+            format,
+            program,
+            key,
+        })
+    }
+
+    /// the armored signature of `buffer`, gpg signs with the key of
+    /// `signer` if `user.signingkey` is not set
+    pub(crate) fn sign(
+        &self,
+        buffer: &[u8],
+        signer: &Signature,
+    ) -> Result<String> {
+        match self.format {
+            SignFormat::OpenPgp => {
+                let key = self.key.clone().unwrap_or_else(|| {
+                    format!(
+                        "{} <{}>",
+                        String::from_utf8_lossy(signer.name_bytes()),
+                        String::from_utf8_lossy(signer.email_bytes())
+                    )
+                });
+                self.run(&["--status-fd=2", "-bsau", &key], buffer)
+            }
+            SignFormat::Ssh => self.sign_ssh(buffer),
+        }
+    }
+
+    fn sign_ssh(&self, buffer: &[u8]) -> Result<String> {
+        let key = self.key.as_deref().ok_or_else(|| {
+            Error::Sign(String::from(
+                "user.signingkey needs to be set to sign with ssh",
+            ))
+        })?;
+
+        // a literal public key, its private key is in the agent
+        let literal = key
+            .strip_prefix("key::")
+            .or_else(|| key.starts_with("ssh-").then_some(key));
+
+        match literal {
+            Some(public_key) => {
+                // removed once dropped
+                let mut file = tempfile::Builder::new()
+                    .prefix("gitui-signing-key-")
+                    .suffix(".pub")
+                    .tempfile()?;
+                file.write_all(public_key.as_bytes())?;
+                self.run(
+                    &[
+                        "-Y",
+                        "sign",
+                        "-n",
+                        "git",
+                        "-U",
+                        "-f",
+                        &file.path().to_string_lossy(),
+                    ],
+                    buffer,
+                )
+            }
+            None => self.run(
+                &[
+                    "-Y",
+                    "sign",
+                    "-n",
+                    "git",
+                    "-f",
+                    &expand_home(key).to_string_lossy(),
+                ],
+                buffer,
+            ),
+        }
+    }
+
+    /// runs the program with `buffer` as its input, the signature is
+    /// what it prints
+    fn run(&self, args: &[&str], buffer: &[u8]) -> Result<String> {
+        let mut child = Command::new(&self.program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                Error::Sign(format!(
+                    "could not run `{}`: {}",
+                    self.program, e
+                ))
+            })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(buffer)?;
+        }
+
+        let output = child.wait_with_output()?;
+        let signature = String::from_utf8_lossy(&output.stdout);
+
+        if !output.status.success() || signature.trim().is_empty() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // gpg mixes its status lines in
+            let problem = stderr
+                .lines()
+                .filter(|line| !line.starts_with("[GNUPG:]"))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            return Err(Error::Sign(format!(
+                "`{}` failed: {}",
+                self.program,
+                problem.trim()
+            )));
+        }
+
+        Ok(signature.into_owned())
+    }
+}
+
+/// `~/` at the start of `path` replaced with the home directory
+fn expand_home(path: &str) -> PathBuf {
+    let home =
+        env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+
+    match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// `name <email> time offset` as in the header of commits and tags
+pub(crate) fn signature_header(signature: &Signature) -> String {
+    let when = signature.when();
+    let offset = when.offset_minutes();
+
+    format!(
+        "{} <{}> {} {}{:02}{:02}",
+        String::from_utf8_lossy(signature.name_bytes()),
+        String::from_utf8_lossy(signature.email_bytes()),
+        when.seconds(),
+        if offset < 0 { '-' } else { '+' },
+        offset.abs() / 60,
+        offset.abs() % 60
+    )
+}
@@ -1,6 +1,9 @@
 use super::{utils::repo, CommitId};
 use crate::error::{Error, Result};
-use git2::{Oid, Repository, StashFlags};
+use git2::{
+    build::CheckoutBuilder, Oid, Repository, StashFlags,
+    StatusOptions,
+};
 use scopetime::scope_time;
 
 ///
@@ -57,6 +60,55 @@ pub fn stash_apply(
     Ok(())
 }
 
+/// checks out `commit` (detaching HEAD) and applies the latest stash
+/// on top, refuses to run on a dirty worktree or without a stash
+pub fn stash_apply_onto(
+    repo_path: &str,
+    commit: CommitId,
+) -> Result<()> {
+    scope_time!("stash_apply_onto");
+
+    if get_stashes(repo_path)?.is_empty() {
+        return Err(Error::Generic(String::from(
+            "no stash to apply",
+        )));
+    }
+
+    let mut repo = repo(repo_path)?;
+
+    let changes = repo
+        .statuses(Some(StatusOptions::new().include_ignored(false)))?
+        .len();
+    if changes > 0 {
+        return Err(Error::Generic(format!(
+            "cannot checkout commit, there are {} uncommitted changes",
+            changes
+        )));
+    }
+
+    {
+        let target = repo.find_commit(commit.into())?;
+        repo.checkout_tree(
+            target.as_object(),
+            Some(CheckoutBuilder::new().safe()),
+        )?;
+    }
+    repo.set_head_detached(commit.into())?;
+
+    repo.stash_apply(0, None)?;
+
+    let conflicts = repo.index()?.conflicts()?.count();
+    if conflicts > 0 {
+        return Err(Error::Generic(format!(
+            "stash conflicts with {} in {} files (HEAD stays detached there)",
+            commit.get_short_string(),
+            conflicts
+        )));
+    }
+
+    Ok(())
+}
+
 fn get_stash_index(
     repo: &mut Repository,
     stash_id: Oid,
@@ -111,7 +163,11 @@ mod tests {
         commit, get_commit_files, get_commits_info, stage_add_file,
         tests::{debug_cmd_print, get_statuses, repo_init},
     };
-    use std::{fs::File, io::Write, path::Path};
+    use std::{
+        fs::{read_to_string, File},
+        io::Write,
+        path::Path,
+    };
 
     #[test]
     fn test_smoke() {
@@ -184,6 +240,69 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_stash_apply_onto() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(&root.join("foo.txt"))?.write_all(b"a")?;
+        stage_add_file(repo_path, Path::new("foo.txt"))?;
+        let old = commit(repo_path, "c1")?;
+
+        File::create(&root.join("bar.txt"))?.write_all(b"b")?;
+        stage_add_file(repo_path, Path::new("bar.txt"))?;
+        commit(repo_path, "c2")?;
+
+        assert!(stash_apply_onto(repo_path, old).is_err());
+
+        File::create(&root.join("foo.txt"))?.write_all(b"fix")?;
+        stash_save(repo_path, None, false, false)?;
+
+        File::create(&root.join("bar.txt"))?.write_all(b"dirty")?;
+        assert!(stash_apply_onto(repo_path, old).is_err());
+        File::create(&root.join("bar.txt"))?.write_all(b"b")?;
+
+        stash_apply_onto(repo_path, old)?;
+
+        assert!(repo.head_detached()?);
+        assert_eq!(
+            repo.head()?.target().map(CommitId::new),
+            Some(old)
+        );
+        assert!(!root.join("bar.txt").exists());
+        assert_eq!(read_to_string(root.join("foo.txt"))?, "fix");
+        assert_eq!(get_stashes(repo_path)?.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_stash_apply_onto_conflict() -> Result<()> {
+        let (_td, repo) = repo_init()?;
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        File::create(&root.join("foo.txt"))?.write_all(b"a")?;
+        stage_add_file(repo_path, Path::new("foo.txt"))?;
+        let old = commit(repo_path, "c1")?;
+
+        File::create(&root.join("foo.txt"))?.write_all(b"b")?;
+        stage_add_file(repo_path, Path::new("foo.txt"))?;
+        commit(repo_path, "c2")?;
+
+        File::create(&root.join("foo.txt"))?.write_all(b"c")?;
+        stash_save(repo_path, None, false, false)?;
+
+        let err = stash_apply_onto(repo_path, old).unwrap_err();
+
+        assert!(err.to_string().contains("stash conflicts"));
+        assert!(read_to_string(root.join("foo.txt"))?
+            .starts_with("<<<<<<<"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_stash_without_2nd_parent() -> Result<()> {
         let file_path1 = Path::new("file1.txt");
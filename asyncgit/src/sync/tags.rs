@@ -1,5 +1,9 @@
-use super::{utils::repo, CommitId};
+use super::{
+    utils::{repo, repo_allow_bare},
+    CommitId,
+};
 use crate::error::Result;
+use git2::{DescribeOptions, ErrorClass};
 use scopetime::scope_time;
 use std::collections::BTreeMap;
 
@@ -21,7 +25,7 @@ pub fn get_tags(repo_path: &str) -> Result<Tags> {
         }
     };
 
-    let repo = repo(repo_path)?;
+    let repo = repo_allow_bare(repo_path)?;
 
     repo.tag_foreach(|id, name| {
         if let Ok(name) =
@@ -45,10 +49,143 @@ pub fn get_tags(repo_path: &str) -> Result<Tags> {
     Ok(res)
 }
 
+/// commits reachable from a commit but not from the nearest
+/// tagged commit before it
+#[derive(Debug, Default, PartialEq)]
+pub struct CommitsSinceTag {
+    /// name of the nearest tag, `None` if no ancestor is tagged
+    pub tag: Option<String>,
+    /// the commits after it, newest first
+    pub commits: Vec<CommitId>,
+}
+
+/// looks up the nearest tagged ancestor of `id` (not `id` itself)
+/// in `tags` and lists up to `limit` commits since then
+pub fn get_commits_since_tag(
+    repo_path: &str,
+    id: CommitId,
+    tags: &Tags,
+    limit: usize,
+) -> Result<CommitsSinceTag> {
+    scope_time!("get_commits_since_tag");
+
+    let repo = repo(repo_path)?;
+
+    let mut walk = repo.revwalk()?;
+    walk.push(id.into())?;
+    let mut previous = None;
+    for oid in walk {
+        let commit = CommitId::new(oid?);
+        if commit != id {
+            if let Some(names) = tags.get(&commit) {
+                previous = Some((commit, names.first().cloned()));
+                break;
+            }
+        }
+    }
+
+    let mut walk = repo.revwalk()?;
+    walk.push(id.into())?;
+    if let Some((commit, _)) = &previous {
+        walk.hide((*commit).into())?;
+    }
+    let commits = walk
+        .take(limit)
+        .map(|oid| oid.map(CommitId::new))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    Ok(CommitsSinceTag {
+        tag: previous.and_then(|(_, name)| name),
+        commits,
+    })
+}
+
+/// the tag that first contained `id` (like `git describe --contains`),
+/// that is the tagged descendant of `id` (or `id` itself) with the
+/// oldest commit time, tags on commits older than `id` are skipped
+pub fn get_first_tag_containing(
+    repo_path: &str,
+    id: CommitId,
+    tags: &Tags,
+) -> Result<Option<String>> {
+    scope_time!("get_first_tag_containing");
+
+    let repo = repo_allow_bare(repo_path)?;
+    let time = repo.find_commit(id.into())?.time().seconds();
+
+    let mut candidates = tags
+        .iter()
+        .filter_map(|(target, names)| {
+            let target_time = repo
+                .find_commit((*target).into())
+                .ok()?
+                .time()
+                .seconds();
+            (target_time >= time).then_some((
+                target_time,
+                *target,
+                names,
+            ))
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|(time, _, _)| *time);
+
+    let contains = |target: CommitId| -> Result<bool> {
+        Ok(target == id
+            || repo.graph_descendant_of(target.into(), id.into())?)
+    };
+
+    let mut first: Option<(i64, CommitId, &CommitTags)> = None;
+    for (time, target, names) in candidates {
+        match first {
+            Some((first_time, _, _)) if time > first_time => break,
+            // same time, only an ancestor of the current pick is older
+            Some((_, first_target, _))
+                if !repo.graph_descendant_of(
+                    first_target.into(),
+                    target.into(),
+                )? => {}
+            _ => {
+                if contains(target)? {
+                    first = Some((time, target, names));
+                }
+            }
+        }
+    }
+
+    Ok(first.and_then(|(_, _, names)| names.first().cloned()))
+}
+
+/// names `id` like `git describe`: the nearest annotated tag it
+/// descends from, the number of commits since and the abbreviated
+/// hash (`v1.2.3-4-gabcdef0`), only the tag on a tagged commit.
+/// `None` if no annotated tag is reachable
+pub fn describe_commit(
+    repo_path: &str,
+    id: CommitId,
+) -> Result<Option<String>> {
+    scope_time!("describe_commit");
+
+    let repo = repo(repo_path)?;
+    let commit = repo.find_commit(id.into())?;
+
+    let describe =
+        match commit.as_object().describe(&DescribeOptions::new()) {
+            Ok(describe) => describe,
+            // no tags at all or none reachable from `id`
+            Err(e) if e.class() == ErrorClass::Describe => {
+                return Ok(None)
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+    Ok(Some(describe.format(None)?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::sync::tests::repo_init;
+    use crate::sync::{commit, tag, tests::repo_init};
     use git2::ObjectType;
 
     #[test]
@@ -83,4 +220,118 @@ mod tests {
             vec!["a", "b"]
         );
     }
+
+    #[test]
+    fn test_commits_since_tag() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = commit(repo_path, "first").unwrap();
+        tag(repo_path, &first, "v1", "").unwrap();
+        let second = commit(repo_path, "second").unwrap();
+        let third = commit(repo_path, "third").unwrap();
+        let tags = get_tags(repo_path).unwrap();
+
+        assert_eq!(
+            get_commits_since_tag(repo_path, third, &tags, 10)
+                .unwrap(),
+            CommitsSinceTag {
+                tag: Some(String::from("v1")),
+                commits: vec![third, second],
+            }
+        );
+        assert_eq!(
+            get_commits_since_tag(repo_path, third, &tags, 1)
+                .unwrap()
+                .commits,
+            vec![third]
+        );
+
+        // the tag on the commit itself does not count
+        let since_first =
+            get_commits_since_tag(repo_path, first, &tags, 10)
+                .unwrap();
+        assert_eq!(since_first.tag, None);
+        assert_eq!(since_first.commits[0], first);
+        assert_eq!(since_first.commits.len(), 2);
+    }
+
+    #[test]
+    fn test_first_tag_containing() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = commit(repo_path, "first").unwrap();
+        let second = commit(repo_path, "second").unwrap();
+        tag(repo_path, &second, "v1", "").unwrap();
+        let third = commit(repo_path, "third").unwrap();
+        let fourth = commit(repo_path, "fourth").unwrap();
+        tag(repo_path, &fourth, "v2", "").unwrap();
+        let fifth = commit(repo_path, "fifth").unwrap();
+
+        // a tag on a side branch forked off `second`
+        let parent = repo.find_commit(second.into()).unwrap();
+        let sig = repo.signature().unwrap();
+        let side = repo
+            .commit(
+                None,
+                &sig,
+                &sig,
+                "side",
+                &parent.tree().unwrap(),
+                &[&parent],
+            )
+            .unwrap();
+        repo.tag_lightweight(
+            "side",
+            &repo.find_object(side, None).unwrap(),
+            false,
+        )
+        .unwrap();
+
+        let tags = get_tags(repo_path).unwrap();
+        let first_tag = |id| {
+            get_first_tag_containing(repo_path, id, &tags).unwrap()
+        };
+
+        assert_eq!(first_tag(first).as_deref(), Some("v1"));
+        assert_eq!(first_tag(second).as_deref(), Some("v1"));
+        assert_eq!(first_tag(third).as_deref(), Some("v2"));
+        assert_eq!(first_tag(fourth).as_deref(), Some("v2"));
+        assert_eq!(first_tag(fifth), None);
+    }
+
+    #[test]
+    fn test_describe_commit() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+
+        let first = commit(repo_path, "first").unwrap();
+        assert_eq!(describe_commit(repo_path, first).unwrap(), None);
+
+        tag(repo_path, &first, "v1.2.3", "release").unwrap();
+        commit(repo_path, "second").unwrap();
+        let third = commit(repo_path, "third").unwrap();
+
+        // lightweight tags are not used
+        repo.tag_lightweight(
+            "wip",
+            &repo.find_object(third.into(), None).unwrap(),
+            false,
+        )
+        .unwrap();
+        let fourth = commit(repo_path, "fourth").unwrap();
+
+        assert_eq!(
+            describe_commit(repo_path, fourth).unwrap(),
+            Some(format!("v1.2.3-3-g{}", &fourth.to_string()[..7]))
+        );
+        assert_eq!(
+            describe_commit(repo_path, first).unwrap().as_deref(),
+            Some("v1.2.3")
+        );
+    }
 }
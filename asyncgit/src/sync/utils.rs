@@ -38,16 +38,24 @@ pub fn is_bare_repo(repo_path: &str) -> Result<bool> {
 
 ///
 pub(crate) fn repo(repo_path: &str) -> Result<Repository> {
+    let repo = repo_allow_bare(repo_path)?;
+
+    if repo.is_bare() {
+        return Err(Error::Generic("bare repo".to_string()));
+    }
+
+    Ok(repo)
+}
+
+/// opens bare repos as well, only for the read-only paths the
+/// log of a bare repo needs (walk, tags, details, decorations)
+pub(crate) fn repo_allow_bare(repo_path: &str) -> Result<Repository> {
     let repo = Repository::open_ext(
         repo_path,
         RepositoryOpenFlags::empty(),
         Vec::<&Path>::new(),
     )?;
 
-    if repo.is_bare() {
-        return Err(Error::Generic("bare repo".to_string()));
-    }
-
     Ok(repo)
 }
 
@@ -68,13 +76,13 @@ pub fn repo_work_dir(repo_path: &str) -> Result<String> {
 
 ///
 pub fn get_head(repo_path: &str) -> Result<CommitId> {
-    let repo = repo(repo_path)?;
+    let repo = repo_allow_bare(repo_path)?;
     get_head_repo(&repo)
 }
 
 ///
 pub fn get_head_tuple(repo_path: &str) -> Result<Head> {
-    let repo = repo(repo_path)?;
+    let repo = repo_allow_bare(repo_path)?;
     let id = get_head_repo(&repo)?;
     let name = get_head_refname(&repo)?;
 
@@ -156,7 +164,8 @@ mod tests {
         commit,
         status::{get_status, StatusType},
         tests::{
-            debug_cmd_print, get_statuses, repo_init, repo_init_empty,
+            debug_cmd_print, get_statuses, repo_init, repo_init_bare,
+            repo_init_empty,
         },
     };
     use std::{
@@ -315,4 +324,16 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_bare_repo() -> Result<()> {
+        let (td, _repo) = repo_init_bare()?;
+        let repo_path = td.path().to_str().unwrap();
+
+        assert!(repo(repo_path).is_err());
+        assert!(stage_add_all(repo_path, "*").is_err());
+        assert!(repo_allow_bare(repo_path).is_ok());
+
+        Ok(())
+    }
 }
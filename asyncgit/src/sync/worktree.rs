@@ -0,0 +1,147 @@
+//! listing the worktrees of a repository
+
+use super::utils::repo;
+use crate::error::Result;
+use git2::Repository;
+use scopetime::scope_time;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// a worktree of the repository
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorktreeInfo {
+    /// name of a linked worktree, `None` for the main one
+    pub name: Option<String>,
+    /// path of the working directory
+    pub path: String,
+    /// branch checked out, `None` if detached or unknown
+    pub branch: Option<String>,
+    /// the worktree `repo_path` belongs to
+    pub is_current: bool,
+}
+
+/// main worktree (unless bare) followed by all linked ones
+pub fn get_worktrees(repo_path: &str) -> Result<Vec<WorktreeInfo>> {
+    scope_time!("get_worktrees");
+
+    let repo = repo(repo_path)?;
+    let current = repo.workdir().and_then(|p| p.canonicalize().ok());
+    let main = Repository::open(get_common_dir(&repo))?;
+
+    let info = |name: Option<String>, path: &Path| WorktreeInfo {
+        name,
+        path: path.to_string_lossy().to_string(),
+        branch: get_checked_out_branch(path),
+        is_current: path.canonicalize().ok() == current,
+    };
+
+    let mut res = Vec::new();
+
+    if let Some(workdir) = main.workdir() {
+        res.push(info(None, workdir));
+    }
+
+    for name in main.worktrees()?.iter().flatten() {
+        let worktree = main.find_worktree(name)?;
+        res.push(info(Some(name.to_string()), worktree.path()));
+    }
+
+    Ok(res)
+}
+
+/// `true` if `repo_path` is a linked worktree
+pub fn is_linked_worktree(repo_path: &str) -> Result<bool> {
+    Ok(repo(repo_path)?.is_worktree())
+}
+
+/// the `.git` dir shared by all worktrees, linked worktrees
+/// point to it from their `commondir` file
+pub(crate) fn get_common_dir(repo: &Repository) -> PathBuf {
+    fs::read_to_string(repo.path().join("commondir")).map_or_else(
+        |_| repo.path().to_path_buf(),
+        |common_dir| repo.path().join(common_dir.trim()),
+    )
+}
+
+fn get_checked_out_branch(path: &Path) -> Option<String> {
+    let repo = Repository::open(path).ok()?;
+    let head = repo.head().ok()?;
+
+    if head.is_branch() {
+        head.shorthand().map(String::from)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sync::{
+        checkout_branch, get_branch_name, tests::repo_init,
+    };
+    use git2::WorktreeAddOptions;
+    use tempfile::TempDir;
+
+    fn add_worktree(
+        repo: &Repository,
+        branch: &str,
+        td: &TempDir,
+    ) -> String {
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let branch = repo.branch(branch, &head, false).unwrap();
+        let reference = branch.into_reference();
+        let path = td.path().join("wt");
+
+        let mut opts = WorktreeAddOptions::new();
+        opts.reference(Some(&reference));
+        repo.worktree("wt", &path, Some(&opts)).unwrap();
+
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_list_worktrees() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        let wt_dir = TempDir::new().unwrap();
+        let wt_path = add_worktree(&repo, "feature", &wt_dir);
+
+        let worktrees = get_worktrees(repo_path).unwrap();
+
+        assert_eq!(worktrees.len(), 2);
+        assert_eq!(worktrees[0].name, None);
+        assert_eq!(worktrees[0].branch.as_deref(), Some("master"));
+        assert!(worktrees[0].is_current);
+        assert_eq!(worktrees[1].name.as_deref(), Some("wt"));
+        assert_eq!(worktrees[1].branch.as_deref(), Some("feature"));
+        assert!(!worktrees[1].is_current);
+        assert!(!is_linked_worktree(repo_path).unwrap());
+
+        // operating from inside the linked worktree
+        assert!(is_linked_worktree(&wt_path).unwrap());
+        assert_eq!(get_branch_name(&wt_path).unwrap(), "feature");
+        let worktrees = get_worktrees(&wt_path).unwrap();
+        assert!(!worktrees[0].is_current);
+        assert!(worktrees[1].is_current);
+    }
+
+    #[test]
+    fn test_checkout_branch_of_other_worktree() {
+        let (_td, repo) = repo_init().unwrap();
+        let root = repo.path().parent().unwrap();
+        let repo_path = root.as_os_str().to_str().unwrap();
+        let wt_dir = TempDir::new().unwrap();
+        let wt_path = add_worktree(&repo, "feature", &wt_dir);
+
+        let err = checkout_branch(repo_path, "refs/heads/feature")
+            .unwrap_err()
+            .to_string();
+
+        assert!(err.contains(&wt_path));
+        assert_eq!(get_branch_name(repo_path).unwrap(), "master");
+    }
+}
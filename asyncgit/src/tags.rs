@@ -1,6 +1,8 @@
 use crate::{
+    background_error::report_background_error,
     error::Result,
     hash,
+    shutdown::send_notification,
     sync::{self},
     AsyncNotification, CWD,
 };
@@ -78,18 +80,22 @@ impl AsyncTags {
         self.pending.fetch_add(1, Ordering::Relaxed);
 
         rayon_core::spawn(move || {
-            let notify = AsyncTags::getter(arc_last)
-                .expect("error getting tags");
+            let notify =
+                AsyncTags::getter(arc_last).unwrap_or_else(|e| {
+                    report_background_error(&sender, "tags", &e);
+                    false
+                });
 
             arc_pending.fetch_sub(1, Ordering::Relaxed);
 
-            sender
-                .send(if notify {
+            send_notification(
+                &sender,
+                if notify {
                     AsyncNotification::Tags
                 } else {
                     AsyncNotification::FinishUnchanged
-                })
-                .expect("error sending notify");
+                },
+            );
         });
 
         Ok(())
@@ -2,52 +2,70 @@ use crate::{
     accessors,
     cmdbar::CommandBar,
     components::{
-        event_pump, CommandBlocking, CommandInfo, CommitComponent,
-        Component, CreateBranchComponent, DrawableComponent,
-        ExternalEditorComponent, HelpComponent,
+        event_pump, AuthorStatsComponent, CherryPickComponent,
+        CommandBlocking, CommandInfo, CommitComponent,
+        CompareCommitsComponent, Component, CreateBranchComponent,
+        DrawableComponent, ErrorConsoleComponent, ExportLogComponent,
+        ExternalEditorComponent, FindCommitComponent, HelpComponent,
         InspectCommitComponent, MsgComponent, PushComponent,
-        RenameBranchComponent, ResetComponent, SelectBranchComponent,
-        StashMsgComponent, TagCommitComponent,
+        PushUrlComponent, ReflogComponent, RenameBranchComponent,
+        ResetComponent, SelectBranchComponent, Severity,
+        StashMsgComponent, Suspend, TagCommitComponent,
+        UndoComponent, WorktreesComponent,
     },
     input::{Input, InputEvent, InputState},
     keys::{KeyConfig, SharedKeyConfig},
-    queue::{Action, InternalEvent, NeedsUpdate, Queue},
+    locale,
+    options::{Options, SharedOptions},
+    queue::{
+        Action, InternalEvent, MessageEditor, NeedsUpdate, Queue,
+    },
     strings::{self, order},
     tabs::{Revlog, StashList, Stashing, Status},
     ui::style::{SharedTheme, Theme},
 };
 use anyhow::{bail, Result};
-use asyncgit::{sync, AsyncNotification, CWD};
+use asyncgit::{
+    sync::{
+        self,
+        known_hosts::{
+            known_hosts_file, set_trust_new_host_keys, trust_host_key,
+        },
+        CommitId, RemoteError,
+    },
+    AsyncNotification, RemoteOps, CWD,
+};
 use crossbeam_channel::Sender;
 use crossterm::event::{Event, KeyEvent};
 use std::{
     cell::{Cell, RefCell},
     path::Path,
     rc::Rc,
+    sync::Arc,
 };
 use tui::{
     backend::Backend,
-    layout::{Constraint, Direction, Layout, Margin, Rect},
+    layout::{
+        Alignment, Constraint, Direction, Layout, Margin, Rect,
+    },
     text::{Span, Spans},
-    widgets::{Block, Borders, Tabs},
+    widgets::{Block, Borders, Paragraph, Tabs},
     Frame,
 };
 
+const TAB_LOG: usize = 1;
+
 ///
 pub struct App {
     do_quit: bool,
     help: HelpComponent,
     msg: MsgComponent,
     reset: ResetComponent,
-    commit: CommitComponent,
-    stashmsg_popup: StashMsgComponent,
-    inspect_commit_popup: InspectCommitComponent,
+    commit_popups: CommitPopups,
+    branch_popups: BranchPopups,
+    log_popups: LogPopups,
     external_editor_popup: ExternalEditorComponent,
-    push_popup: PushComponent,
-    tag_commit_popup: TagCommitComponent,
-    create_branch_popup: CreateBranchComponent,
-    rename_branch_popup: RenameBranchComponent,
-    select_branch_popup: SelectBranchComponent,
+    error_console: ErrorConsoleComponent,
     cmdbar: RefCell<CommandBar>,
     tab: usize,
     revlog: Revlog,
@@ -62,70 +80,67 @@ pub struct App {
     // "Flags"
     requires_redraw: Cell<bool>,
     file_to_open: Option<String>,
+    message_to_edit: Option<MessageEditor>,
+    difftool_commits: Option<(Option<CommitId>, CommitId)>,
+    /// done once input polling paused
+    suspend: Option<Suspend>,
+    /// see `Options::difftool_command`
+    difftool_command: Option<String>,
+    /// only the log tab works without a worktree
+    bare: bool,
+    repo_path: Arc<str>,
 }
 
 // public interface
 impl App {
     ///
     pub fn new(
+        repo_path: Arc<str>,
         sender: &Sender<AsyncNotification>,
         input: Input,
-    ) -> Self {
+    ) -> Result<Self> {
         let queue = Queue::default();
+        let bare = sync::is_bare_repo(&repo_path).unwrap_or_default();
 
         let theme = Rc::new(Theme::init());
         let key_config = Rc::new(KeyConfig::init());
+        let (options, remote_ops) = init_options(&queue, &key_config);
 
-        Self {
+        let mut app = Self {
             input,
-            reset: ResetComponent::new(
-                queue.clone(),
-                theme.clone(),
-                key_config.clone(),
-            ),
-            commit: CommitComponent::new(
-                queue.clone(),
-                theme.clone(),
-                key_config.clone(),
-            ),
-            stashmsg_popup: StashMsgComponent::new(
-                queue.clone(),
-                theme.clone(),
-                key_config.clone(),
-            ),
-            inspect_commit_popup: InspectCommitComponent::new(
+            commit_popups: CommitPopups::new(
                 &queue,
                 sender,
-                theme.clone(),
-                key_config.clone(),
-            ),
-            external_editor_popup: ExternalEditorComponent::new(
-                theme.clone(),
-                key_config.clone(),
+                &options,
+                &theme,
+                &key_config,
             ),
-            push_popup: PushComponent::new(
+            branch_popups: BranchPopups::new(
                 &queue,
                 sender,
-                theme.clone(),
-                key_config.clone(),
+                &remote_ops,
+                &options,
+                &theme,
+                &key_config,
             ),
-            tag_commit_popup: TagCommitComponent::new(
-                queue.clone(),
-                theme.clone(),
-                key_config.clone(),
+            log_popups: LogPopups::new(
+                &repo_path,
+                &queue,
+                sender,
+                &options,
+                &theme,
+                &key_config,
             ),
-            create_branch_popup: CreateBranchComponent::new(
+            reset: ResetComponent::new(
                 queue.clone(),
                 theme.clone(),
                 key_config.clone(),
             ),
-            rename_branch_popup: RenameBranchComponent::new(
-                queue.clone(),
+            external_editor_popup: ExternalEditorComponent::new(
                 theme.clone(),
                 key_config.clone(),
             ),
-            select_branch_popup: SelectBranchComponent::new(
-                queue.clone(),
+            error_console: ErrorConsoleComponent::new(
                 theme.clone(),
                 key_config.clone(),
             ),
@@ -141,14 +156,18 @@ impl App {
             msg: MsgComponent::new(theme.clone(), key_config.clone()),
             tab: 0,
             revlog: Revlog::new(
+                Arc::clone(&repo_path),
+                bare,
                 &queue,
                 sender,
+                &options,
                 theme.clone(),
                 key_config.clone(),
             ),
             status_tab: Status::new(
                 &queue,
                 sender,
+                &remote_ops,
                 theme.clone(),
                 key_config.clone(),
             ),
@@ -168,7 +187,19 @@ impl App {
             key_config,
             requires_redraw: Cell::new(false),
             file_to_open: None,
+            message_to_edit: None,
+            difftool_commits: None,
+            suspend: None,
+            difftool_command: options.difftool_command.clone(),
+            bare,
+            repo_path,
+        };
+
+        if bare {
+            app.set_tab(TAB_LOG)?;
         }
+
+        Ok(app)
     }
 
     ///
@@ -239,6 +270,15 @@ impl App {
                 } else if k == self.key_config.cmd_bar_toggle {
                     self.cmdbar.borrow_mut().toggle_more();
                     NeedsUpdate::empty()
+                } else if k == self.key_config.suspend && cfg!(unix) {
+                    self.suspend(Suspend::Process);
+                    NeedsUpdate::empty()
+                } else if k == self.key_config.open_shell {
+                    self.suspend(Suspend::Shell);
+                    NeedsUpdate::empty()
+                } else if k == self.key_config.open_error_console {
+                    self.error_console.show()?;
+                    NeedsUpdate::COMMANDS
                 } else {
                     NeedsUpdate::empty()
                 };
@@ -249,19 +289,46 @@ impl App {
             self.process_queue(flags)?;
         } else if let InputEvent::State(polling_state) = ev {
             self.external_editor_popup.hide();
-            if let InputState::Paused = polling_state {
-                let result = match self.file_to_open.take() {
-                    Some(path) => {
-                        ExternalEditorComponent::open_file_in_editor(
-                            Path::new(&path),
+            if let (InputState::Paused, Some(suspend)) =
+                (polling_state, self.suspend.take())
+            {
+                if let Err(e) = suspend.run() {
+                    let msg = strings::suspend_failed_msg(
+                        &self.key_config,
+                        &e.to_string(),
+                    );
+                    log::error!("{}", msg.as_str());
+                    self.msg.show_error(msg.as_str())?;
+                }
+
+                self.requires_redraw.set(true);
+                self.input.set_polling(true);
+            } else if matches!(polling_state, InputState::Paused) {
+                let result = if let Some((old, new)) =
+                    self.difftool_commits.take()
+                {
+                    ExternalEditorComponent::open_commit_in_difftool(
+                        &self.repo_path,
+                        old,
+                        new,
+                        self.difftool_command.as_deref(),
+                    )
+                    .map_err(|e| {
+                        strings::difftool_failed_msg(
+                            &self.key_config,
+                            &e.to_string(),
+                        )
+                    })
+                } else {
+                    self.open_external_editor().map_err(|e| {
+                        strings::editor_failed_msg(
+                            &self.key_config,
+                            &e.to_string(),
                         )
-                    }
-                    None => self.commit.show_editor(),
+                    })
                 };
 
-                if let Err(e) = result {
-                    let msg =
-                        format!("failed to launch editor:\n{}", e);
+                if let Err(msg) = result {
                     log::error!("{}", msg.as_str());
                     self.msg.show_error(msg.as_str())?;
                 }
@@ -274,6 +341,33 @@ impl App {
         Ok(())
     }
 
+    /// the message or file queued to be edited, if any
+    fn open_external_editor(&mut self) -> Result<()> {
+        if let Some(editor) = self.message_to_edit.take() {
+            match editor {
+                MessageEditor::Commit => {
+                    self.commit_popups.commit.show_editor()
+                }
+                MessageEditor::Tag => {
+                    self.commit_popups.tag_commit.show_editor()
+                }
+            }
+        } else if let Some(path) = self.file_to_open.take() {
+            ExternalEditorComponent::open_file_in_editor(Path::new(
+                &path,
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// gets the ui out of the way for `suspend` as soon as input
+    /// polling paused
+    pub fn suspend(&mut self, suspend: Suspend) {
+        self.input.set_polling(false);
+        self.suspend = Some(suspend);
+    }
+
     //TODO: do we need this?
     /// forward ticking to components that require it
     pub fn update(&mut self) -> Result<()> {
@@ -299,8 +393,16 @@ impl App {
         self.status_tab.update_git(ev)?;
         self.stashing_tab.update_git(ev)?;
         self.revlog.update_git(ev)?;
-        self.inspect_commit_popup.update_git(ev)?;
-        self.push_popup.update_git(ev)?;
+        self.log_popups
+            .find_commit
+            .set_filter_status(self.revlog.filter_status()?);
+        self.commit_popups.inspect_commit.update_git(ev)?;
+        self.log_popups.compare_commits.update_git(ev)?;
+        self.branch_popups.push.update_git(ev)?;
+        self.log_popups.cherry_pick.update_git(ev)?;
+        self.log_popups.author_stats.update_git(ev)?;
+        self.branch_popups.select_branch.update_git(ev)?;
+        self.error_console.update_git(ev);
 
         //TODO: better system for this
         // can we simply process the queue here and everyone just uses the queue to schedule a cmd update?
@@ -319,10 +421,25 @@ impl App {
         self.status_tab.anything_pending()
             || self.revlog.any_work_pending()
             || self.stashing_tab.anything_pending()
-            || self.inspect_commit_popup.any_work_pending()
+            || self.commit_popups.inspect_commit.any_work_pending()
+            || self.log_popups.compare_commits.any_work_pending()
+            || self.log_popups.cherry_pick.any_work_pending()
+            || self.log_popups.author_stats.any_work_pending()
+            || self.log_popups.find_commit.any_work_pending()
             || self.input.is_state_changing()
     }
 
+    /// starts a live filter whose debounce ran out,
+    /// `true` if the ui needs a redraw
+    pub fn update_debounced(&mut self) -> Result<bool> {
+        if self.log_popups.find_commit.update_live() {
+            self.process_queue(NeedsUpdate::COMMANDS)?;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
     ///
     pub fn requires_redraw(&self) -> bool {
         if self.requires_redraw.get() {
@@ -341,15 +458,25 @@ impl App {
         [
             msg,
             reset,
-            commit,
-            stashmsg_popup,
-            inspect_commit_popup,
+            commit_popups.commit,
+            commit_popups.stashmsg,
+            commit_popups.inspect_commit,
+            log_popups.compare_commits,
             external_editor_popup,
-            push_popup,
-            tag_commit_popup,
-            create_branch_popup,
-            rename_branch_popup,
-            select_branch_popup,
+            branch_popups.push,
+            commit_popups.tag_commit,
+            log_popups.find_commit,
+            branch_popups.push_url,
+            log_popups.export_log,
+            branch_popups.create_branch,
+            branch_popups.rename_branch,
+            branch_popups.select_branch,
+            log_popups.reflog,
+            log_popups.worktrees,
+            log_popups.author_stats,
+            log_popups.undo,
+            log_popups.cherry_pick,
+            error_console,
             help,
             revlog,
             status_tab,
@@ -361,6 +488,7 @@ impl App {
     fn check_quit_key(&mut self, ev: Event) -> bool {
         if let Event::Key(e) = ev {
             if e == self.key_config.exit {
+                self.revlog.stop_filter();
                 self.do_quit = true;
                 return true;
             }
@@ -392,7 +520,7 @@ impl App {
         if k == self.key_config.tab_status {
             self.set_tab(0)?
         } else if k == self.key_config.tab_log {
-            self.set_tab(1)?
+            self.set_tab(TAB_LOG)?
         } else if k == self.key_config.tab_stashing {
             self.set_tab(2)?
         } else if k == self.key_config.tab_stashes {
@@ -403,6 +531,10 @@ impl App {
     }
 
     fn set_tab(&mut self, tab: usize) -> Result<()> {
+        if self.bare && tab != TAB_LOG {
+            return Ok(());
+        }
+
         let tabs = self.get_tabs();
         for (i, t) in tabs.into_iter().enumerate() {
             if tab == i {
@@ -434,7 +566,8 @@ impl App {
         //NOTE: set when any tree component changed selection
         if flags.contains(NeedsUpdate::DIFF) {
             self.status_tab.update_diff()?;
-            self.inspect_commit_popup.update_diff()?;
+            self.commit_popups.inspect_commit.update_diff()?;
+            self.log_popups.compare_commits.update_diff()?;
         }
         if flags.contains(NeedsUpdate::COMMANDS) {
             self.update_commands();
@@ -465,80 +598,303 @@ impl App {
     ) -> Result<NeedsUpdate> {
         let mut flags = NeedsUpdate::empty();
         match ev {
-            InternalEvent::ConfirmedAction(action) => match action {
-                Action::Reset(r) => {
-                    if self.status_tab.reset(&r) {
-                        flags.insert(NeedsUpdate::ALL);
-                    }
-                }
-                Action::StashDrop(s) => {
-                    if StashList::drop(s) {
-                        flags.insert(NeedsUpdate::ALL);
-                    }
-                }
-                Action::ResetHunk(path, hash) => {
-                    sync::reset_hunk(CWD, path, hash)?;
-                    flags.insert(NeedsUpdate::ALL);
-                }
-                Action::DeleteBranch(branch_ref) => {
-                    if let Err(e) =
-                        sync::delete_branch(CWD, &branch_ref)
-                    {
-                        self.queue.borrow_mut().push_back(
-                            InternalEvent::ShowErrorMsg(
-                                e.to_string(),
-                            ),
-                        )
-                    } else {
-                        flags.insert(NeedsUpdate::ALL);
-                        self.select_branch_popup.hide();
-                    }
-                }
-            },
-            InternalEvent::ConfirmAction(action) => {
-                self.reset.open(action)?;
-                flags.insert(NeedsUpdate::COMMANDS);
+            InternalEvent::ConfirmedAction(action) => {
+                flags.insert(self.confirmed_action(action)?);
             }
             InternalEvent::ShowErrorMsg(msg) => {
                 self.msg.show_error(msg.as_str())?;
                 flags
                     .insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
             }
+            InternalEvent::ShowInfoMsg(msg) => {
+                self.msg.show_info(msg.as_str())?;
+                flags
+                    .insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::ReportProblem(severity, msg) => {
+                self.error_console.push(severity, &msg);
+            }
+            InternalEvent::ShowRemoteError {
+                action,
+                retry,
+                error,
+            } => {
+                if let RemoteError::UnknownHostKey {
+                    host,
+                    fingerprint,
+                } = error
+                {
+                    self.reset.open(Action::TrustHostKey {
+                        host,
+                        fingerprint,
+                    })?;
+                } else {
+                    self.msg.show_error(
+                        &strings::remote_error_msg(
+                            &self.key_config,
+                            action,
+                            retry,
+                            &error,
+                        ),
+                    )?;
+                }
+                flags
+                    .insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS);
+            }
             InternalEvent::Update(u) => flags.insert(u),
-            InternalEvent::OpenCommit => self.commit.show()?,
+            InternalEvent::ShowCommitInLog(id) => {
+                self.revlog.select_commit_on_show(id);
+                self.set_tab(1)?;
+            }
+            InternalEvent::FilterLog(filter) => {
+                self.revlog.filter(&filter)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::SearchLog(query) => {
+                self.revlog.search(&query)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::TabSwitch => self.set_tab(0)?,
+            InternalEvent::OpenExternalEditor(path) => {
+                self.input.set_polling(false);
+                self.external_editor_popup.show()?;
+                self.file_to_open = Some(path);
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::EditMessage(editor) => {
+                self.input.set_polling(false);
+                self.external_editor_popup.show()?;
+                self.message_to_edit = Some(editor);
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            InternalEvent::OpenDifftool(old, new) => {
+                if self.difftool_command.is_some()
+                    || sync::get_difftool(CWD)?.is_some()
+                {
+                    self.input.set_polling(false);
+                    self.external_editor_popup.show()?;
+                    self.difftool_commits = Some((old, new));
+                    flags.insert(NeedsUpdate::COMMANDS)
+                } else {
+                    self.msg.show_error(
+                        strings::difftool_not_configured(),
+                    )?;
+                }
+            }
+            InternalEvent::Push(branch) => {
+                self.branch_popups.push.push(branch)?;
+                flags.insert(NeedsUpdate::ALL)
+            }
+            InternalEvent::PushDryRun(branch) => {
+                self.branch_popups.push.push_dry_run(branch)?;
+                flags.insert(NeedsUpdate::ALL)
+            }
+            InternalEvent::PushTag(tag) => {
+                self.branch_popups.push.push_tag(tag)?;
+                flags.insert(NeedsUpdate::ALL)
+            }
+            ev => flags.insert(self.open_popup(ev)?),
+        }
+
+        Ok(flags)
+    }
+
+    /// opens the popup `ev` asks for
+    fn open_popup(
+        &mut self,
+        ev: InternalEvent,
+    ) -> Result<NeedsUpdate> {
+        let mut flags = NeedsUpdate::empty();
+        match ev {
+            InternalEvent::ConfirmAction(action) => {
+                self.reset.open(action)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenCommit => {
+                self.commit_popups.commit.show()?
+            }
             InternalEvent::PopupStashing(opts) => {
-                self.stashmsg_popup.options(opts);
-                self.stashmsg_popup.show()?
+                self.commit_popups.stashmsg.options(opts);
+                self.commit_popups.stashmsg.show()?
+            }
+            InternalEvent::TagCommit(id, tags) => {
+                self.commit_popups.tag_commit.open(id, tags)?;
+            }
+            InternalEvent::FindCommit => {
+                self.log_popups.find_commit.open(false)?;
             }
-            InternalEvent::TagCommit(id) => {
-                self.tag_commit_popup.open(id)?;
+            InternalEvent::SearchCommit => {
+                self.log_popups.find_commit.open(true)?;
+            }
+            InternalEvent::EditPushUrl(remote) => {
+                self.branch_popups.push_url.open(remote)?;
             }
             InternalEvent::CreateBranch => {
-                self.create_branch_popup.open()?;
+                self.branch_popups.create_branch.open()?;
             }
-            InternalEvent::RenameBranch(branch_ref, cur_name) => {
-                self.rename_branch_popup
-                    .open(branch_ref, cur_name)?;
+            InternalEvent::RenameBranch(
+                branch_ref,
+                cur_name,
+                to_branches,
+            ) => {
+                self.branch_popups.rename_branch.open(
+                    branch_ref,
+                    cur_name,
+                    to_branches,
+                )?;
             }
             InternalEvent::SelectBranch => {
-                self.select_branch_popup.open()?;
+                self.branch_popups.select_branch.open()?;
+            }
+            InternalEvent::OpenReflog => {
+                self.log_popups.reflog.open()?;
+            }
+            InternalEvent::OpenWorktrees => {
+                self.log_popups.worktrees.open()?;
+            }
+            InternalEvent::ExportLog(commits, filtered) => {
+                self.log_popups.export_log.open(commits, filtered)?;
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+            InternalEvent::OpenAuthorStats(commits, filtered) => {
+                self.log_popups
+                    .author_stats
+                    .open(commits, filtered)?;
+            }
+            InternalEvent::OpenUndo => {
+                self.log_popups.undo.open()?;
             }
-            InternalEvent::TabSwitch => self.set_tab(0)?,
             InternalEvent::InspectCommit(id, tags) => {
-                self.inspect_commit_popup.open(id, tags)?;
+                self.commit_popups.inspect_commit.open(id, tags)?;
                 flags.insert(NeedsUpdate::ALL | NeedsUpdate::COMMANDS)
             }
-            InternalEvent::OpenExternalEditor(path) => {
-                self.input.set_polling(false);
-                self.external_editor_popup.show()?;
-                self.file_to_open = path;
+            InternalEvent::CompareCommits(old, new) => {
+                self.log_popups.compare_commits.open(old, new)?;
                 flags.insert(NeedsUpdate::COMMANDS)
             }
-            InternalEvent::Push(branch) => {
-                self.push_popup.push(branch)?;
+            InternalEvent::CherryPick(commits) => {
+                self.log_popups.cherry_pick.open(commits)?;
+                flags.insert(NeedsUpdate::COMMANDS)
+            }
+            _ => (),
+        }
+
+        Ok(flags)
+    }
+
+    /// shows the error `res` failed with, `true` if it did not
+    fn ok_or_show<E: ToString>(&self, res: Result<(), E>) -> bool {
+        if let Err(e) = res {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(e.to_string()),
+            );
+            false
+        } else {
+            true
+        }
+    }
+
+    /// adds the key of `host` to `known_hosts`
+    fn trust_host_key(&self, host: &str, fingerprint: &str) {
+        let res = known_hosts_file().and_then(|file| {
+            trust_host_key(&file, host, fingerprint)
+        });
+        self.queue.borrow_mut().push_back(match res {
+            Ok(()) => InternalEvent::ShowInfoMsg(
+                strings::host_key_trusted_msg(&self.key_config, host),
+            ),
+            Err(e) => InternalEvent::ShowErrorMsg(
+                strings::host_key_store_failed_msg(
+                    &self.key_config,
+                    &e.to_string(),
+                ),
+            ),
+        });
+    }
+
+    /// runs `action` the user confirmed
+    fn confirmed_action(
+        &mut self,
+        action: Action,
+    ) -> Result<NeedsUpdate> {
+        let mut flags = NeedsUpdate::empty();
+        match action {
+            Action::Reset(r) => {
+                if self.status_tab.reset(&r) {
+                    flags.insert(NeedsUpdate::ALL);
+                }
+            }
+            Action::StashDrop(s) => {
+                if StashList::drop(s) {
+                    flags.insert(NeedsUpdate::ALL);
+                }
+            }
+            Action::ResetHunk(path, hash) => {
+                sync::reset_hunk(CWD, path, hash)?;
+                flags.insert(NeedsUpdate::ALL);
+            }
+            Action::DeleteBranch(branch_ref) => {
+                if self
+                    .ok_or_show(sync::delete_branch(CWD, &branch_ref))
+                {
+                    flags.insert(NeedsUpdate::ALL);
+                    self.branch_popups.select_branch.hide();
+                }
+            }
+            Action::ResetHard(id) => {
+                if self.ok_or_show(sync::reset_hard(CWD, id)) {
+                    flags.insert(NeedsUpdate::ALL);
+                    self.log_popups.reflog.hide();
+                }
+            }
+            Action::RestoreRef {
+                refname,
+                id,
+                pushed,
+            } => {
+                if self.ok_or_show(sync::restore_ref(
+                    CWD, &refname, id, pushed,
+                )) {
+                    flags.insert(NeedsUpdate::ALL);
+                    self.log_popups.undo.hide();
+                }
+            }
+            Action::StashApplyOnto(id) => {
+                self.ok_or_show(sync::stash_apply_onto(CWD, id));
+                flags.insert(NeedsUpdate::ALL);
+            }
+            Action::Push(branch, _, _) => {
+                self.branch_popups.push.push(branch)?;
                 flags.insert(NeedsUpdate::ALL)
             }
-        };
+            Action::CommitUnsigned { amend, msg, .. } => {
+                self.commit_popups
+                    .commit
+                    .commit_unsigned(amend, &msg)?;
+                flags.insert(NeedsUpdate::ALL);
+            }
+            Action::TagUnsigned {
+                commit,
+                tag,
+                message,
+                push,
+                ..
+            } => {
+                self.commit_popups
+                    .tag_commit
+                    .tag_unsigned(commit, tag, &message, push);
+                flags.insert(NeedsUpdate::ALL);
+            }
+            Action::AbortCherryPick => {
+                let res = self.log_popups.cherry_pick.abort();
+                self.ok_or_show(res);
+                flags.insert(NeedsUpdate::ALL);
+            }
+            Action::TrustHostKey { host, fingerprint } => {
+                self.trust_host_key(&host, &fingerprint);
+                flags.insert(NeedsUpdate::COMMANDS);
+            }
+        }
 
         Ok(flags)
     }
@@ -574,6 +930,22 @@ impl App {
             .order(order::NAV),
         );
 
+        res.push(CommandInfo::new(
+            strings::commands::open_error_console(&self.key_config),
+            true,
+            !self.any_popup_visible(),
+        ));
+        res.push(CommandInfo::new(
+            strings::commands::open_shell(&self.key_config),
+            true,
+            !self.any_popup_visible(),
+        ));
+        res.push(CommandInfo::new(
+            strings::commands::suspend(&self.key_config),
+            true,
+            cfg!(unix) && !self.any_popup_visible(),
+        ));
+
         res.push(
             CommandInfo::new(
                 strings::commands::quit(&self.key_config),
@@ -588,18 +960,28 @@ impl App {
 
     //TODO: make this automatic, i keep forgetting to add popups here
     fn any_popup_visible(&self) -> bool {
-        self.commit.is_visible()
+        self.commit_popups.commit.is_visible()
             || self.help.is_visible()
             || self.reset.is_visible()
             || self.msg.is_visible()
-            || self.stashmsg_popup.is_visible()
-            || self.inspect_commit_popup.is_visible()
+            || self.commit_popups.stashmsg.is_visible()
+            || self.commit_popups.inspect_commit.is_visible()
+            || self.log_popups.compare_commits.is_visible()
             || self.external_editor_popup.is_visible()
-            || self.tag_commit_popup.is_visible()
-            || self.create_branch_popup.is_visible()
-            || self.push_popup.is_visible()
-            || self.select_branch_popup.is_visible()
-            || self.rename_branch_popup.is_visible()
+            || self.commit_popups.tag_commit.is_visible()
+            || self.log_popups.find_commit.is_visible()
+            || self.branch_popups.push_url.is_visible()
+            || self.log_popups.export_log.is_visible()
+            || self.branch_popups.create_branch.is_visible()
+            || self.branch_popups.push.is_visible()
+            || self.branch_popups.select_branch.is_visible()
+            || self.branch_popups.rename_branch.is_visible()
+            || self.log_popups.reflog.is_visible()
+            || self.log_popups.worktrees.is_visible()
+            || self.log_popups.author_stats.is_visible()
+            || self.log_popups.undo.is_visible()
+            || self.log_popups.cherry_pick.is_visible()
+            || self.error_console.is_visible()
     }
 
     fn draw_popups<B: Backend>(
@@ -617,16 +999,26 @@ impl App {
             )
             .split(f.size())[0];
 
-        self.commit.draw(f, size)?;
-        self.stashmsg_popup.draw(f, size)?;
+        self.commit_popups.commit.draw(f, size)?;
+        self.commit_popups.stashmsg.draw(f, size)?;
         self.help.draw(f, size)?;
-        self.inspect_commit_popup.draw(f, size)?;
+        self.commit_popups.inspect_commit.draw(f, size)?;
+        self.log_popups.compare_commits.draw(f, size)?;
         self.external_editor_popup.draw(f, size)?;
-        self.tag_commit_popup.draw(f, size)?;
-        self.select_branch_popup.draw(f, size)?;
-        self.create_branch_popup.draw(f, size)?;
-        self.rename_branch_popup.draw(f, size)?;
-        self.push_popup.draw(f, size)?;
+        self.commit_popups.tag_commit.draw(f, size)?;
+        self.log_popups.find_commit.draw(f, size)?;
+        self.branch_popups.push_url.draw(f, size)?;
+        self.log_popups.export_log.draw(f, size)?;
+        self.branch_popups.select_branch.draw(f, size)?;
+        self.log_popups.reflog.draw(f, size)?;
+        self.log_popups.worktrees.draw(f, size)?;
+        self.log_popups.author_stats.draw(f, size)?;
+        self.log_popups.undo.draw(f, size)?;
+        self.log_popups.cherry_pick.draw(f, size)?;
+        self.error_console.draw(f, size)?;
+        self.branch_popups.create_branch.draw(f, size)?;
+        self.branch_popups.rename_branch.draw(f, size)?;
+        self.branch_popups.push.draw(f, size)?;
         self.reset.draw(f, size)?;
         self.msg.draw(f, size)?;
 
@@ -664,5 +1056,226 @@ impl App {
                 .select(self.tab),
             r,
         );
+
+        let unread = self.error_console.unread();
+        if unread > 0 {
+            f.render_widget(
+                Paragraph::new(Span::styled(
+                    strings::error_console_badge(
+                        &self.key_config,
+                        unread,
+                    ),
+                    self.theme.text_danger(),
+                ))
+                .alignment(Alignment::Right),
+                r,
+            );
+        }
+    }
+}
+
+/// loads the options and applies the ones that are not passed to
+/// a component, a catalog that can not be read is reported
+fn init_options(
+    queue: &Queue,
+    key_config: &SharedKeyConfig,
+) -> (SharedOptions, RemoteOps) {
+    let options = Rc::new(Options::init());
+    if let Err(e) = locale::init(options.locale.as_deref()) {
+        queue.borrow_mut().push_back(InternalEvent::ReportProblem(
+            Severity::Warning,
+            strings::invalid_catalog_msg(key_config, &e.to_string()),
+        ));
+    }
+    let remote_ops = RemoteOps::new();
+    match options.remote_timeout() {
+        Ok(timeout) => remote_ops.set_timeout(timeout),
+        Err(e) => queue.borrow_mut().push_back(
+            InternalEvent::ReportProblem(
+                Severity::Warning,
+                strings::invalid_remote_timeout_msg(
+                    key_config,
+                    &e.to_string(),
+                ),
+            ),
+        ),
+    }
+    for (op, policy) in options.remote.busy_policy.policies() {
+        remote_ops.set_policy(op, policy);
+    }
+    set_trust_new_host_keys(options.remote.trust_new_host_keys);
+
+    (options, remote_ops)
+}
+
+/// popups opened from the log tab
+struct LogPopups {
+    compare_commits: CompareCommitsComponent,
+    find_commit: FindCommitComponent,
+    export_log: ExportLogComponent,
+    reflog: ReflogComponent,
+    worktrees: WorktreesComponent,
+    author_stats: AuthorStatsComponent,
+    undo: UndoComponent,
+    cherry_pick: CherryPickComponent,
+}
+
+impl LogPopups {
+    fn new(
+        repo_path: &Arc<str>,
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        options: &SharedOptions,
+        theme: &SharedTheme,
+        key_config: &SharedKeyConfig,
+    ) -> Self {
+        Self {
+            compare_commits: CompareCommitsComponent::new(
+                queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            find_commit: FindCommitComponent::new(
+                queue.clone(),
+                options,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            export_log: ExportLogComponent::new(
+                Arc::clone(repo_path),
+                queue.clone(),
+                options.log.export_template.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            reflog: ReflogComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            worktrees: WorktreesComponent::new(
+                theme.clone(),
+                key_config.clone(),
+            ),
+            author_stats: AuthorStatsComponent::new(
+                Arc::clone(repo_path),
+                queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            undo: UndoComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            cherry_pick: CherryPickComponent::new(
+                queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            ),
+        }
+    }
+}
+
+/// popups about branches and pushing them
+struct BranchPopups {
+    push: PushComponent,
+    push_url: PushUrlComponent,
+    create_branch: CreateBranchComponent,
+    rename_branch: RenameBranchComponent,
+    select_branch: SelectBranchComponent,
+}
+
+impl BranchPopups {
+    fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        remote_ops: &RemoteOps,
+        options: &SharedOptions,
+        theme: &SharedTheme,
+        key_config: &SharedKeyConfig,
+    ) -> Self {
+        Self {
+            push: PushComponent::new(
+                queue,
+                sender,
+                remote_ops,
+                options.remote.run_pre_push_hook,
+                theme.clone(),
+                key_config.clone(),
+            ),
+            push_url: PushUrlComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            create_branch: CreateBranchComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            rename_branch: RenameBranchComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            select_branch: SelectBranchComponent::new(
+                queue.clone(),
+                sender,
+                remote_ops,
+                theme.clone(),
+                key_config.clone(),
+            ),
+        }
+    }
+}
+
+/// popups creating and inspecting commits
+struct CommitPopups {
+    commit: CommitComponent,
+    stashmsg: StashMsgComponent,
+    inspect_commit: InspectCommitComponent,
+    tag_commit: TagCommitComponent,
+}
+
+impl CommitPopups {
+    fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        options: &SharedOptions,
+        theme: &SharedTheme,
+        key_config: &SharedKeyConfig,
+    ) -> Self {
+        Self {
+            commit: CommitComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            stashmsg: StashMsgComponent::new(
+                queue.clone(),
+                theme.clone(),
+                key_config.clone(),
+            ),
+            inspect_commit: InspectCommitComponent::new(
+                queue,
+                sender,
+                theme.clone(),
+                key_config.clone(),
+            )
+            .with_message_limits(
+                options.commit_subject_limit,
+                options.commit_body_limit,
+            ),
+            tag_commit: TagCommitComponent::new(
+                queue.clone(),
+                options.remote.tag_push_after_create,
+                theme.clone(),
+                key_config.clone(),
+            ),
+        }
     }
 }
@@ -0,0 +1,316 @@
+use super::{
+    utils::time_to_string, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent, ScrollType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, calc_scroll_top, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::CommitId, AsyncAuthorStats, AsyncNotification,
+    AuthorStatsProgress,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use std::{cell::Cell, cmp, sync::Arc};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// rows moved by page up/down
+const SCROLL_PAGE: usize = 10;
+
+/// commits per author of the log (or the filter results) with
+/// their first and last commit, enter filters the log to one of them
+pub struct AuthorStatsComponent {
+    git_stats: AsyncAuthorStats,
+    progress: AuthorStatsProgress,
+    filtered: bool,
+    visible: bool,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for AuthorStatsComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(60, 60);
+            const MIN_SIZE: Size = Size::new(60, 10);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height_in_lines =
+                (area.height as usize).saturating_sub(2);
+
+            self.scroll_top.set(calc_scroll_top(
+                self.scroll_top.get(),
+                height_in_lines,
+                self.selection,
+            ));
+
+            let progress = (self.progress.done, self.progress.total);
+            let title = strings::author_stats_title(
+                &self.key_config,
+                self.filtered,
+                self.git_stats.is_pending().then_some(progress),
+            );
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height_in_lines))
+                    .block(
+                        Block::default()
+                            .title(Span::styled(
+                                title,
+                                self.theme.title(true),
+                            ))
+                            .border_type(BorderType::Thick)
+                            .borders(Borders::ALL)
+                            .border_style(self.theme.block(true)),
+                    )
+                    .alignment(Alignment::Left),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for AuthorStatsComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::author_stats_filter(
+                    &self.key_config,
+                ),
+                self.selected_author().is_some(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide()
+                } else if e == self.key_config.enter {
+                    self.filter_selected();
+                } else if e == self.key_config.move_down {
+                    self.move_selection(ScrollType::Down);
+                } else if e == self.key_config.move_up {
+                    self.move_selection(ScrollType::Up);
+                } else if e == self.key_config.page_down {
+                    self.move_selection(ScrollType::PageDown);
+                } else if e == self.key_config.page_up {
+                    self.move_selection(ScrollType::PageUp);
+                } else if e == self.key_config.home {
+                    self.move_selection(ScrollType::Home);
+                } else if e == self.key_config.end {
+                    self.move_selection(ScrollType::End);
+                }
+            }
+
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl AuthorStatsComponent {
+    ///
+    pub fn new(
+        repo_path: Arc<str>,
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            git_stats: AsyncAuthorStats::new(repo_path, sender),
+            progress: AuthorStatsProgress::default(),
+            filtered: false,
+            visible: false,
+            selection: 0,
+            scroll_top: Cell::new(0),
+            queue: queue.clone(),
+            theme,
+            key_config,
+        }
+    }
+
+    /// counts the authors of `commits` (the filter results if
+    /// `filtered`) in the background
+    pub fn open(
+        &mut self,
+        commits: Vec<CommitId>,
+        filtered: bool,
+    ) -> Result<()> {
+        self.git_stats.request(commits)?;
+        self.progress = self.git_stats.progress()?;
+        self.filtered = filtered;
+        self.selection = 0;
+        self.scroll_top.set(0);
+
+        self.show()
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_stats.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if ev == AsyncNotification::AuthorStats {
+            self.progress = self.git_stats.progress()?;
+            self.selection = cmp::min(
+                self.selection,
+                self.progress.stats.len().saturating_sub(1),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn selected_author(&self) -> Option<&str> {
+        self.progress
+            .stats
+            .get(self.selection)
+            .map(|stats| stats.name.as_str())
+    }
+
+    fn filter_selected(&mut self) {
+        if let Some(author) = self.selected_author() {
+            let filter = format!(":a {}", author);
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::FilterLog(filter));
+            self.hide();
+        }
+    }
+
+    fn move_selection(&mut self, scroll: ScrollType) {
+        let max = self.progress.stats.len().saturating_sub(1);
+
+        let new_selection = match scroll {
+            ScrollType::Up => self.selection.saturating_sub(1),
+            ScrollType::Down => self.selection.saturating_add(1),
+            ScrollType::PageUp => {
+                self.selection.saturating_sub(SCROLL_PAGE)
+            }
+            ScrollType::PageDown => {
+                self.selection.saturating_add(SCROLL_PAGE)
+            }
+            ScrollType::Home => 0,
+            ScrollType::End => max,
+        };
+
+        self.selection = cmp::min(new_selection, max);
+    }
+
+    fn get_text(&self, height: usize) -> Text {
+        let count_width = self
+            .progress
+            .stats
+            .first()
+            .map_or(0, |stats| stats.count.to_string().len());
+
+        let txt = self
+            .progress
+            .stats
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(i, stats)| {
+                let selected = i == self.selection;
+
+                Spans::from(vec![
+                    Span::styled(
+                        format!(
+                            "{:>w$} ",
+                            stats.count,
+                            w = count_width
+                        ),
+                        self.theme.text(true, selected),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{} - {} ",
+                            time_to_string(stats.first, true),
+                            time_to_string(stats.last, true),
+                        ),
+                        self.theme.commit_time(selected),
+                    ),
+                    Span::styled(
+                        stats.name.clone(),
+                        self.theme.commit_author(selected),
+                    ),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        Text::from(txt)
+    }
+}
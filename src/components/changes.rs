@@ -254,7 +254,7 @@ impl Component for ChangesComponent {
                 } else if e == self.key_config.enter {
                     try_or_popup!(
                         self,
-                        "staging error:",
+                        strings::staging_error(),
                         self.index_add_remove()
                     );
 
@@ -268,7 +268,7 @@ impl Component for ChangesComponent {
                     if self.is_working_dir {
                         try_or_popup!(
                             self,
-                            "staging error:",
+                            strings::staging_error(),
                             self.index_add_all()
                         );
                     } else {
@@ -0,0 +1,339 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{Action, InternalEvent, NeedsUpdate, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{self, CherryPickSequence, CommitId},
+    AsyncCherryPick, AsyncNotification, CWD,
+};
+use crossbeam_channel::Sender;
+use crossterm::event::Event;
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    text::Span,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// cherry-picks a sequence of commits and shows how far it got,
+/// a sequence stopped on a conflict is kept until it is continued
+/// or aborted, one that failed until it is aborted
+pub struct CherryPickComponent {
+    visible: bool,
+    git_cherry_pick: AsyncCherryPick,
+    sequence: Option<CherryPickSequence>,
+    /// error the sequence stopped with other than a conflict
+    failed: Option<String>,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for CherryPickComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        _rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            let height = if self.failed.is_some() { 10 } else { 7 };
+            let area =
+                ui::centered_rect_absolute(60, height, f.size());
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text())
+                    .block(
+                        Block::default()
+                            .title(Span::styled(
+                                strings::cherry_pick_popup_msg(),
+                                self.theme.title(true),
+                            ))
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick)
+                            .border_style(self.theme.block(true)),
+                    )
+                    .alignment(Alignment::Left)
+                    .wrap(Wrap { trim: true }),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for CherryPickComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::cherry_pick_continue(
+                    &self.key_config,
+                ),
+                self.is_stopped(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::cherry_pick_abort(
+                    &self.key_config,
+                ),
+                self.can_abort(),
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    if self.failed.take().is_some() {
+                        self.sequence = None;
+                    }
+                    self.hide();
+                } else if e == self.key_config.enter
+                    && self.is_stopped()
+                {
+                    self.continue_sequence()?;
+                } else if e == self.key_config.cherry_pick_abort
+                    && self.can_abort()
+                {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ConfirmAction(
+                            Action::AbortCherryPick,
+                        ),
+                    );
+                }
+            }
+
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl CherryPickComponent {
+    ///
+    pub fn new(
+        queue: &Queue,
+        sender: &Sender<AsyncNotification>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            visible: false,
+            git_cherry_pick: AsyncCherryPick::new(sender),
+            sequence: None,
+            failed: None,
+            queue: queue.clone(),
+            theme,
+            key_config,
+        }
+    }
+
+    /// starts picking `commits` (oldest first) onto `HEAD`,
+    /// shows the stopped or failed sequence instead if there is one
+    pub fn open(&mut self, commits: Vec<CommitId>) -> Result<()> {
+        if self.sequence.is_none() {
+            match CherryPickSequence::new(CWD, commits) {
+                Ok(sequence) => self.start(sequence)?,
+                Err(e) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(
+                            strings::cherry_pick_error_msg(
+                                &self.key_config,
+                                &e.to_string(),
+                            ),
+                        ),
+                    );
+                    return Ok(());
+                }
+            }
+        }
+
+        self.show()
+    }
+
+    /// moves `HEAD` back to where it was before the stopped or
+    /// failed sequence
+    pub fn abort(&mut self) -> Result<()> {
+        if let Some(sequence) = self.sequence.take() {
+            sync::cherry_pick_abort(CWD, &sequence)?;
+        }
+        self.failed = None;
+        self.hide();
+
+        Ok(())
+    }
+
+    ///
+    pub fn any_work_pending(&self) -> bool {
+        self.git_cherry_pick.is_pending()
+    }
+
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if ev == AsyncNotification::CherryPick {
+            self.update()?;
+        }
+
+        Ok(())
+    }
+
+    fn update(&mut self) -> Result<()> {
+        self.sequence = self.git_cherry_pick.sequence()?;
+
+        if self.git_cherry_pick.is_pending() {
+            return Ok(());
+        }
+
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+
+        if let Some(e) = self.git_cherry_pick.error()? {
+            // the commits picked so far stay until aborted
+            self.failed = Some(e);
+            self.show()?;
+        } else if let Some(sequence) = &self.sequence {
+            if sequence.is_finished() {
+                let msg = strings::cherry_pick_done(
+                    &self.key_config,
+                    sequence.applied(),
+                    &sequence
+                        .skipped
+                        .iter()
+                        .map(CommitId::get_short_string)
+                        .collect::<Vec<_>>(),
+                );
+                self.sequence = None;
+                self.hide();
+                self.queue
+                    .borrow_mut()
+                    .push_back(InternalEvent::ShowInfoMsg(msg));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn start(&mut self, sequence: CherryPickSequence) -> Result<()> {
+        self.sequence = Some(sequence.clone());
+        self.failed = None;
+        self.git_cherry_pick.request(sequence)?;
+
+        Ok(())
+    }
+
+    fn continue_sequence(&mut self) -> Result<()> {
+        if let Some(mut sequence) = self.sequence.clone() {
+            match sync::cherry_pick_continue(CWD, &mut sequence) {
+                Ok(_) => self.start(sequence)?,
+                Err(e) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(
+                            strings::cherry_pick_error_msg(
+                                &self.key_config,
+                                &e.to_string(),
+                            ),
+                        ),
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_stopped(&self) -> bool {
+        !self.git_cherry_pick.is_pending()
+            && self
+                .sequence
+                .as_ref()
+                .is_some_and(|s| s.conflict.is_some())
+    }
+
+    fn can_abort(&self) -> bool {
+        self.is_stopped()
+            || (!self.git_cherry_pick.is_pending()
+                && self.sequence.is_some()
+                && self.failed.is_some())
+    }
+
+    fn get_text(&self) -> String {
+        self.sequence.as_ref().map_or_else(String::new, |sequence| {
+            if let Some(error) = &self.failed {
+                return strings::cherry_pick_failed(
+                    &self.key_config,
+                    sequence.applied(),
+                    sequence.commits.len(),
+                    &sequence
+                        .commits
+                        .get(sequence.done)
+                        .map(CommitId::get_short_string)
+                        .unwrap_or_default(),
+                    error,
+                );
+            }
+
+            sequence.conflict.map_or_else(
+                || {
+                    strings::cherry_pick_progress(
+                        &self.key_config,
+                        sequence.done + 1,
+                        sequence.commits.len(),
+                    )
+                },
+                |conflict| {
+                    strings::cherry_pick_conflict(
+                        &self.key_config,
+                        sequence.applied(),
+                        sequence.commits.len(),
+                        &conflict.get_short_string(),
+                    )
+                },
+            )
+        })
+    }
+}
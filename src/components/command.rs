@@ -1,3 +1,5 @@
+use crate::strings;
+
 ///
 #[derive(Clone, PartialEq, PartialOrd, Ord, Eq)]
 pub struct CommandText {
@@ -78,6 +80,18 @@ impl CommandInfo {
         res
     }
 
+    /// disables the command in a bare repo, the help then
+    /// shows why instead of the description
+    pub fn needs_worktree(self, bare: bool) -> Self {
+        let mut res = self;
+        if bare {
+            res.enabled = false;
+            res.quick_bar = false;
+            res.text.desc = strings::needs_worktree();
+        }
+        res
+    }
+
     ///
     pub const fn show_in_quickbar(&self) -> bool {
         self.quick_bar && self.available
@@ -4,23 +4,19 @@ use super::{
     ExternalEditorComponent,
 };
 use crate::{
-    get_app_config_path,
     keys::SharedKeyConfig,
-    queue::{InternalEvent, NeedsUpdate, Queue},
+    queue::{
+        Action, InternalEvent, MessageEditor, NeedsUpdate, Queue,
+    },
     strings,
     ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
     sync::{self, CommitId, HookResult},
-    CWD,
+    Error, CWD,
 };
 use crossterm::event::Event;
-use std::{
-    fs::File,
-    io::{Read, Write},
-    path::PathBuf,
-};
 use tui::{backend::Backend, layout::Rect, Frame};
 
 pub struct CommitComponent {
@@ -90,7 +86,9 @@ impl Component for CommitComponent {
                     self.amend()?;
                 } else if e == self.key_config.open_commit_editor {
                     self.queue.borrow_mut().push_back(
-                        InternalEvent::OpenExternalEditor(None),
+                        InternalEvent::EditMessage(
+                            MessageEditor::Commit,
+                        ),
                     );
                     self.hide();
                 } else {
@@ -143,52 +141,60 @@ impl CommitComponent {
         }
     }
 
+    /// lets the message typed so far (or `commit.template`) be
+    /// edited in the editor, an empty or unchanged template
+    /// message aborts the commit
     pub fn show_editor(&mut self) -> Result<()> {
-        const COMMIT_MSG_FILE_NAME: &str = "COMMITMSG_EDITOR";
-        //TODO: use a tmpfile here
-        let mut config_path: PathBuf = get_app_config_path()?;
-        config_path.push(COMMIT_MSG_FILE_NAME);
+        let config = sync::get_message_config(CWD)?;
+        let template = config.template.as_deref().unwrap_or_default();
+        let message = if self.input.get_text().trim().is_empty() {
+            template
+        } else {
+            self.input.get_text()
+        };
+
+        let message = ExternalEditorComponent::edit_message(
+            &config,
+            "COMMIT_EDITMSG",
+            message,
+            |comment_char| {
+                strings::commit_editor_msg(
+                    &self.key_config,
+                    comment_char,
+                )
+            },
+        )?;
+
+        let template = sync::cleanup_message(
+            template,
+            config.cleanup,
+            config.comment_char,
+        );
 
-        {
-            let mut file = File::create(&config_path)?;
-            file.write_fmt(format_args!(
-                "{}\n",
-                self.input.get_text()
-            ))?;
-            file.write_all(
-                strings::commit_editor_msg(&self.key_config)
-                    .as_bytes(),
-            )?;
+        match message {
+            None => self.abort(strings::commit_aborted_empty()),
+            Some(message)
+                if !template.is_empty() && message == template =>
+            {
+                self.abort(strings::commit_aborted_template())
+            }
+            Some(message) => {
+                self.input.set_text(message);
+                self.input.show()?;
+            }
         }
 
-        ExternalEditorComponent::open_file_in_editor(&config_path)?;
-
-        let mut message = String::new();
-
-        let mut file = File::open(&config_path)?;
-        file.read_to_string(&mut message)?;
-        drop(file);
-        std::fs::remove_file(&config_path)?;
-
-        let message: String = message
-            .lines()
-            .flat_map(|l| {
-                if l.starts_with('#') {
-                    vec![]
-                } else {
-                    vec![l, "\n"]
-                }
-            })
-            .collect();
-
-        let message = message.trim().to_string();
-
-        self.input.set_text(message);
-        self.input.show()?;
-
         Ok(())
     }
 
+    fn abort(&mut self, msg: &str) {
+        self.input.clear();
+        self.hide();
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::ShowInfoMsg(msg.to_string()));
+    }
+
     fn commit(&mut self) -> Result<()> {
         self.commit_msg(self.input.get_text().clone())
     }
@@ -222,17 +228,59 @@ impl CommitComponent {
             || sync::commit(CWD, &msg),
             |amend| sync::amend(CWD, amend, &msg),
         );
-        if let Err(e) = res {
-            log::error!("commit error: {}", &e);
-            self.queue.borrow_mut().push_back(
-                InternalEvent::ShowErrorMsg(format!(
-                    "commit failed:\n{}",
-                    &e
-                )),
-            );
-            return Ok(());
+        match res {
+            Ok(_) => self.committed(),
+            Err(Error::Sign(problem)) => {
+                self.hide();
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ConfirmAction(
+                        Action::CommitUnsigned {
+                            amend: self.amend,
+                            msg,
+                            problem,
+                        },
+                    ),
+                );
+                Ok(())
+            }
+            Err(e) => {
+                self.commit_failed(&e);
+                Ok(())
+            }
         }
+    }
+
+    /// commits `msg` without signing it after that failed, the
+    /// hooks before committing ran already
+    pub fn commit_unsigned(
+        &mut self,
+        amend: Option<CommitId>,
+        msg: &str,
+    ) -> Result<()> {
+        let res = amend.map_or_else(
+            || sync::commit_unsigned(CWD, msg),
+            |amend| sync::amend_unsigned(CWD, amend, msg),
+        );
+        match res {
+            Ok(_) => self.committed(),
+            Err(e) => {
+                self.commit_failed(&e);
+                Ok(())
+            }
+        }
+    }
+
+    fn commit_failed(&self, e: &Error) {
+        log::error!("commit error: {}", e);
+        self.queue.borrow_mut().push_back(
+            InternalEvent::ShowErrorMsg(format!(
+                "commit failed:\n{}",
+                e
+            )),
+        );
+    }
 
+    fn committed(&mut self) -> Result<()> {
         if let HookResult::NotOk(e) = sync::hooks_post_commit(CWD)? {
             log::error!("post-commit hook error: {}", e);
             self.queue.borrow_mut().push_back(
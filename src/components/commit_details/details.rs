@@ -26,14 +26,31 @@ use tui::{
 };
 enum Detail {
     Author,
+    CoAuthors,
     Date,
     Commiter,
     Sha,
+    Parents,
+    Reverts,
+    RevertedBy,
+    Released,
 }
 
 pub struct DetailsComponent {
     data: Option<CommitDetails>,
+    /// `Co-authored-by` trailers other than the author
+    co_authors: Vec<String>,
     tags: Vec<String>,
+    /// first tag containing the commit, looked up once per commit
+    released_in: Option<(CommitId, Option<String>)>,
+    /// the commit this one reverts
+    reverts: Option<CommitId>,
+    /// the commits reverting this one
+    reverted_by: Vec<CommitId>,
+    /// chars of the subject before it is too long, 0 for no limit
+    subject_limit: usize,
+    /// same for the lines of the body, which also get a ruler
+    body_limit: usize,
     theme: SharedTheme,
     focused: bool,
     current_size: Cell<(u16, u16)>,
@@ -53,7 +70,13 @@ impl DetailsComponent {
     ) -> Self {
         Self {
             data: None,
+            co_authors: Vec::new(),
             tags: Vec::new(),
+            released_in: None,
+            reverts: None,
+            reverted_by: Vec::new(),
+            subject_limit: 0,
+            body_limit: 0,
             theme,
             focused,
             current_size: Cell::new((0, 0)),
@@ -62,6 +85,17 @@ impl DetailsComponent {
         }
     }
 
+    /// highlight message lines longer than these, 0 for no limit
+    pub const fn with_message_limits(
+        mut self,
+        subject: usize,
+        body: usize,
+    ) -> Self {
+        self.subject_limit = subject;
+        self.body_limit = body;
+        self
+    }
+
     pub fn set_commit(
         &mut self,
         id: Option<CommitId>,
@@ -69,10 +103,46 @@ impl DetailsComponent {
     ) -> Result<()> {
         self.tags.clear();
 
+        let previous = self.data.take().map(|data| data.hash);
         self.data =
             id.and_then(|id| sync::get_commit_details(CWD, id).ok());
 
-        self.scroll_top.set(0);
+        self.co_authors =
+            self.data.as_ref().map_or_else(Vec::new, |data| {
+                match &data.message {
+                    Some(CommitMessage {
+                        subject,
+                        body: Some(body),
+                    }) => get_co_authors(
+                        &format!("{}\n\n{}", subject, body),
+                        &data.author.email,
+                    ),
+                    _ => Vec::new(),
+                }
+            });
+
+        // updates of the same commit keep the scroll position
+        if self.data.as_ref().map(|data| &data.hash)
+            != previous.as_ref()
+        {
+            self.scroll_top.set(0);
+        }
+
+        self.released_in =
+            id.map(|id| match self.released_in.take() {
+                Some((cached, tag)) if cached == id => (id, tag),
+                _ => (
+                    id,
+                    sync::get_tags(CWD)
+                        .and_then(|tags| {
+                            sync::get_first_tag_containing(
+                                CWD, id, &tags,
+                            )
+                        })
+                        .ok()
+                        .flatten(),
+                ),
+            });
 
         if let Some(tags) = tags {
             self.tags.extend(tags)
@@ -81,6 +151,16 @@ impl DetailsComponent {
         Ok(())
     }
 
+    /// revert relations of the commit, as far as known
+    pub fn set_reverts(
+        &mut self,
+        reverts: Option<CommitId>,
+        reverted_by: &[CommitId],
+    ) {
+        self.reverts = reverts;
+        self.reverted_by = reverted_by.to_vec();
+    }
+
     fn wrap_commit_details(
         message: &CommitMessage,
         width: usize,
@@ -135,22 +215,85 @@ impl DetailsComponent {
     ) -> Vec<Spans> {
         let (wrapped_title, wrapped_message) =
             self.get_wrapped_lines(width);
+        let (subject, body) = self
+            .data
+            .as_ref()
+            .and_then(|data| data.message.as_ref())
+            .map_or(("", ""), |message| {
+                (
+                    message.subject.as_str(),
+                    message.body.as_deref().unwrap_or_default(),
+                )
+            });
+        let columns = [
+            wrapped_columns(subject, &wrapped_title),
+            wrapped_columns(body, &wrapped_message),
+        ]
+        .concat();
 
         [&wrapped_title[..], &wrapped_message[..]]
             .concat()
             .iter()
+            .zip(columns)
             .enumerate()
             .skip(self.scroll_top.get())
             .take(height)
-            .map(|(i, line)| {
-                Spans::from(vec![Span::styled(
-                    line.clone(),
-                    self.get_theme_for_line(i < wrapped_title.len()),
-                )])
+            .map(|(i, (line, column))| {
+                let is_subject = i < wrapped_title.len();
+                self.limit_line(
+                    line,
+                    column,
+                    if is_subject {
+                        self.subject_limit
+                    } else {
+                        self.body_limit
+                    },
+                    is_subject,
+                    width,
+                )
             })
             .collect()
     }
 
+    /// `line` (starting at char `column` of its message line) with
+    /// the chars past `limit` highlighted, body lines that end
+    /// before it get a ruler at `limit` if `width` has room for it
+    fn limit_line<'a>(
+        &self,
+        line: &Cow<'a, str>,
+        column: usize,
+        limit: usize,
+        is_subject: bool,
+        width: usize,
+    ) -> Spans<'a> {
+        let style = self.get_theme_for_line(is_subject);
+        if limit == 0 {
+            return Spans::from(vec![Span::styled(
+                line.clone(),
+                style,
+            )]);
+        }
+
+        let (within, past) = split_at_limit(line, column, limit);
+        let mut spans = vec![Span::styled(within.to_string(), style)];
+        if !past.is_empty() {
+            spans.push(Span::styled(
+                past.to_string(),
+                style.patch(self.theme.message_overlength()),
+            ));
+        } else if !is_subject && column == 0 && limit < width {
+            spans.push(Span::raw(
+                " ".repeat(limit - within.chars().count()),
+            ));
+            spans.push(Span::styled(
+                "\u{2502}",
+                self.theme.message_ruler(),
+            ));
+        }
+
+        Spans::from(spans)
+    }
+
     fn style_detail(&self, field: &Detail) -> Span {
         match field {
             Detail::Author => Span::styled(
@@ -159,6 +302,12 @@ impl DetailsComponent {
                 )),
                 self.theme.text(false, false),
             ),
+            Detail::CoAuthors => Span::styled(
+                Cow::from(strings::commit::details_co_authors(
+                    &self.key_config,
+                )),
+                self.theme.text(false, false),
+            ),
             Detail::Date => Span::styled(
                 Cow::from(strings::commit::details_date(
                     &self.key_config,
@@ -177,7 +326,53 @@ impl DetailsComponent {
                 )),
                 self.theme.text(false, false),
             ),
+            Detail::Parents => Span::styled(
+                Cow::from(strings::commit::details_parents(
+                    &self.key_config,
+                )),
+                self.theme.text(false, false),
+            ),
+            Detail::Reverts => Span::styled(
+                Cow::from(strings::commit::details_reverts(
+                    &self.key_config,
+                )),
+                self.theme.text(false, false),
+            ),
+            Detail::RevertedBy => Span::styled(
+                Cow::from(strings::commit::details_reverted_by(
+                    &self.key_config,
+                )),
+                self.theme.text(false, false),
+            ),
+            Detail::Released => Span::styled(
+                Cow::from(strings::commit::details_released(
+                    &self.key_config,
+                )),
+                self.theme.text(false, false),
+            ),
+        }
+    }
+
+    /// short ids of `parents`, marked if it is a merge commit
+    fn get_parents_line(
+        &self,
+        parents: &[CommitId],
+    ) -> Option<String> {
+        if parents.is_empty() {
+            return None;
+        }
+
+        let mut line =
+            parents.iter().map(CommitId::get_short_string).join(" ");
+
+        if parents.len() > 1 {
+            line.push(' ');
+            line.push_str(&strings::commit::details_merge(
+                &self.key_config,
+            ));
         }
+
+        Some(line)
     }
 
     fn get_text_info(&self) -> Vec<Spans> {
@@ -205,6 +400,16 @@ impl DetailsComponent {
                 ]),
             ];
 
+            if !self.co_authors.is_empty() {
+                res.push(Spans::from(vec![
+                    self.style_detail(&Detail::CoAuthors),
+                    Span::styled(
+                        Cow::from(self.co_authors.join(", ")),
+                        self.theme.text(true, false),
+                    ),
+                ]));
+            }
+
             if let Some(ref committer) = data.committer {
                 res.extend(vec![
                     Spans::from(vec![
@@ -243,26 +448,8 @@ impl DetailsComponent {
                 ),
             ]));
 
-            if !self.tags.is_empty() {
-                res.push(Spans::from(
-                    self.style_detail(&Detail::Sha),
-                ));
-                res.push(Spans::from(
-                    self.tags
-                        .iter()
-                        .map(|tag| {
-                            Span::styled(
-                                Cow::from(tag),
-                                self.theme.text(true, false),
-                            )
-                        })
-                        .intersperse(Span::styled(
-                            Cow::from(","),
-                            self.theme.text(true, false),
-                        ))
-                        .collect::<Vec<Span>>(),
-                ));
-            }
+            res.extend(self.get_relation_lines(data));
+            res.extend(self.get_tag_lines());
 
             res
         } else {
@@ -270,10 +457,86 @@ impl DetailsComponent {
         }
     }
 
-    fn move_scroll_top(
-        &mut self,
-        move_type: ScrollType,
-    ) -> Result<bool> {
+    /// parents of the commit and the reverts in both directions
+    fn get_relation_lines(&self, data: &CommitDetails) -> Vec<Spans> {
+        let mut res = Vec::new();
+
+        if let Some(parents) = self.get_parents_line(&data.parents) {
+            res.push(Spans::from(vec![
+                self.style_detail(&Detail::Parents),
+                Span::styled(
+                    Cow::from(parents),
+                    self.theme.commit_hash(false),
+                ),
+            ]));
+        }
+
+        if let Some(reverts) = self.reverts {
+            res.push(Spans::from(vec![
+                self.style_detail(&Detail::Reverts),
+                Span::styled(
+                    Cow::from(reverts.get_short_string()),
+                    self.theme.log_revert(false, false),
+                ),
+            ]));
+        }
+
+        if !self.reverted_by.is_empty() {
+            res.push(Spans::from(vec![
+                self.style_detail(&Detail::RevertedBy),
+                Span::styled(
+                    Cow::from(
+                        self.reverted_by
+                            .iter()
+                            .map(CommitId::get_short_string)
+                            .join(" "),
+                    ),
+                    self.theme.log_revert(true, false),
+                ),
+            ]));
+        }
+
+        res
+    }
+
+    /// tags on the commit and the first release containing it
+    fn get_tag_lines(&self) -> Vec<Spans> {
+        let mut res = Vec::new();
+
+        if !self.tags.is_empty() {
+            res.push(Spans::from(self.style_detail(&Detail::Sha)));
+            res.push(Spans::from(
+                self.tags
+                    .iter()
+                    .map(|tag| {
+                        Span::styled(
+                            Cow::from(tag),
+                            self.theme.text(true, false),
+                        )
+                    })
+                    .intersperse(Span::styled(
+                        Cow::from(","),
+                        self.theme.text(true, false),
+                    ))
+                    .collect::<Vec<Span>>(),
+            ));
+        }
+
+        if let Some((_, Some(tag))) = &self.released_in {
+            res.push(Spans::from(vec![
+                self.style_detail(&Detail::Released),
+                Span::styled(
+                    Cow::from(tag.as_str()),
+                    self.theme.text(true, false),
+                ),
+            ]));
+        }
+
+        res
+    }
+
+    /// scrolls the message, pages are as high as the last draw
+    pub fn move_scroll_top(&self, move_type: ScrollType) -> bool {
         if self.data.is_some() {
             let old = self.scroll_top.get();
             let width = self.current_size.get().0 as usize;
@@ -288,21 +551,43 @@ impl DetailsComponent {
                 ScrollType::Up => old.saturating_sub(1),
                 ScrollType::Home => 0,
                 ScrollType::End => max,
-                _ => old,
+                ScrollType::PageUp => old.saturating_sub(height),
+                ScrollType::PageDown => {
+                    old.saturating_add(height).min(max)
+                }
             };
 
             if new_scroll_top > max {
-                return Ok(false);
+                return false;
             }
 
             self.scroll_top.set(new_scroll_top);
 
-            return Ok(true);
+            return true;
         }
-        Ok(false)
+        false
     }
 }
 
+/// the people named in the `Co-authored-by` trailers of `message`,
+/// each once and leaving out the author
+fn get_co_authors(message: &str, author_email: &str) -> Vec<String> {
+    let author_email = format!("<{}>", author_email);
+    let mut co_authors: Vec<String> = Vec::new();
+
+    for (key, value) in sync::get_trailers(message) {
+        if key.eq_ignore_ascii_case("co-authored-by")
+            && !value.is_empty()
+            && !value.ends_with(&author_email)
+            && !co_authors.contains(&value)
+        {
+            co_authors.push(value);
+        }
+    }
+
+    co_authors
+}
+
 impl DrawableComponent for DetailsComponent {
     fn draw<B: Backend>(
         &self,
@@ -312,7 +597,7 @@ impl DrawableComponent for DetailsComponent {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints(
-                [Constraint::Length(8), Constraint::Min(10)].as_ref(),
+                [Constraint::Length(9), Constraint::Min(10)].as_ref(),
             )
             .split(rect);
 
@@ -397,17 +682,17 @@ impl Component for DetailsComponent {
         if self.focused {
             if let Event::Key(e) = event {
                 return if e == self.key_config.move_up {
-                    self.move_scroll_top(ScrollType::Up)
+                    Ok(self.move_scroll_top(ScrollType::Up))
                 } else if e == self.key_config.move_down {
-                    self.move_scroll_top(ScrollType::Down)
+                    Ok(self.move_scroll_top(ScrollType::Down))
                 } else if e == self.key_config.home
                     || e == self.key_config.shift_up
                 {
-                    self.move_scroll_top(ScrollType::Home)
+                    Ok(self.move_scroll_top(ScrollType::Home))
                 } else if e == self.key_config.end
                     || e == self.key_config.shift_down
                 {
-                    self.move_scroll_top(ScrollType::End)
+                    Ok(self.move_scroll_top(ScrollType::End))
                 } else {
                     Ok(false)
                 };
@@ -436,6 +721,46 @@ impl Component for DetailsComponent {
     }
 }
 
+/// the char column each of the `wrapped` lines of `text` starts
+/// at in its line of `text`
+fn wrapped_columns(text: &str, wrapped: &[Cow<str>]) -> Vec<usize> {
+    let mut cursor = 0;
+
+    wrapped
+        .iter()
+        .map(|line| {
+            if line.is_empty() {
+                // an empty line of its own, the next one starts after it
+                cursor = text[cursor..]
+                    .find('\n')
+                    .map_or(text.len(), |i| cursor + i + 1);
+                return 0;
+            }
+
+            text[cursor..].find(line.as_ref()).map_or(0, |i| {
+                let start = cursor + i;
+                cursor = start + line.len();
+                let line_start =
+                    text[..start].rfind('\n').map_or(0, |i| i + 1);
+                text[line_start..start].chars().count()
+            })
+        })
+        .collect()
+}
+
+/// `line` split into the chars before and past char `limit` of
+/// its message line, given `line` starts at char `column` of it
+fn split_at_limit(
+    line: &str,
+    column: usize,
+    limit: usize,
+) -> (&str, &str) {
+    let within = limit.saturating_sub(column);
+    line.char_indices()
+        .nth(within)
+        .map_or((line, ""), |(i, _)| line.split_at(i))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -450,6 +775,110 @@ mod tests {
         [&wrapped_title[..], &wrapped_message[..]].concat()
     }
 
+    #[test]
+    fn test_parents_line() {
+        let details = DetailsComponent::new(
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+            false,
+        );
+        let a = CommitId::default();
+        let short = a.get_short_string();
+
+        assert_eq!(details.get_parents_line(&[]), None);
+        assert_eq!(
+            details.get_parents_line(&[a]),
+            Some(short.clone())
+        );
+        assert_eq!(
+            details.get_parents_line(&[a, a]),
+            Some(format!("{} {} (merge)", short, short))
+        );
+    }
+
+    #[test]
+    fn test_co_authors() {
+        assert_eq!(
+            get_co_authors(
+                "pair on it\n\nbody\n\nCo-authored-by: Alice <a@x.org>\nco-authored-by: Bob <b@x.org>\nCo-authored-by: Alice <a@x.org>\nCo-authored-by: Me <me@x.org>\nSigned-off-by: Me <me@x.org>",
+                "me@x.org"
+            ),
+            vec!["Alice <a@x.org>", "Bob <b@x.org>"]
+        );
+        // not a trailer paragraph
+        assert!(get_co_authors(
+            "subject\n\nCo-authored-by: Alice <a@x.org>\nthanks to her",
+            "me@x.org"
+        )
+        .is_empty());
+        assert!(
+            get_co_authors("Co-authored-by: Alice", "").is_empty()
+        );
+    }
+
+    #[test]
+    fn test_scroll_pages() {
+        let mut details = DetailsComponent::new(
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+            false,
+        );
+        details.data = Some(CommitDetails {
+            author: sync::CommitSignature {
+                name: String::from("Bob"),
+                email: String::from("bob@example.com"),
+                time: 0,
+            },
+            committer: None,
+            message: Some(CommitMessage::from(
+                "subject\n\n1\n2\n3\n4\n5\n6\n7\n8\n9",
+            )),
+            hash: String::new(),
+            parents: Vec::new(),
+        });
+        // 10 lines, 4 shown
+        details.current_size.set((20, 4));
+
+        details.move_scroll_top(ScrollType::PageDown);
+        assert_eq!(details.scroll_top.get(), 4);
+        details.move_scroll_top(ScrollType::PageDown);
+        assert_eq!(details.scroll_top.get(), 6);
+        details.move_scroll_top(ScrollType::Up);
+        details.move_scroll_top(ScrollType::PageUp);
+        assert_eq!(details.scroll_top.get(), 1);
+        details.move_scroll_top(ScrollType::PageUp);
+        assert_eq!(details.scroll_top.get(), 0);
+    }
+
+    #[test]
+    fn test_wrapped_columns() {
+        let text = "\nab cd ef\n\n  gh";
+        let wrapped = textwrap::wrap(text, 5);
+        assert_eq!(wrapped, vec!["", "ab cd", "ef", "", "  gh"]);
+        assert_eq!(
+            wrapped_columns(text, &wrapped),
+            vec![0, 0, 6, 0, 0]
+        );
+
+        // the first line of the body is not shown
+        assert_eq!(
+            wrapped_columns(text, &wrapped[1..]),
+            vec![0, 6, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_split_at_limit() {
+        assert_eq!(split_at_limit("abcdef", 0, 4), ("abcd", "ef"));
+        assert_eq!(split_at_limit("abcdef", 2, 4), ("ab", "cdef"));
+        assert_eq!(split_at_limit("abcdef", 6, 4), ("", "abcdef"));
+        assert_eq!(split_at_limit("abc", 0, 4), ("abc", ""));
+        assert_eq!(
+            split_at_limit("\u{e9}t\u{e9}", 0, 2),
+            ("\u{e9}t", "\u{e9}")
+        );
+    }
+
     #[test]
     fn test_textwrap() {
         let message = CommitMessage::from("Commit message");
@@ -2,16 +2,20 @@ mod details;
 
 use super::{
     command_pump, event_pump, CommandBlocking, CommandInfo,
-    Component, DrawableComponent, FileTreeComponent,
+    Component, DrawableComponent, FileTreeComponent, ScrollType,
 };
 use crate::{
-    accessors, keys::SharedKeyConfig, queue::Queue, strings,
+    accessors,
+    clipboard::copy_string,
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, NeedsUpdate, Queue},
+    strings, try_or_popup,
     ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
-    sync::{CommitId, CommitTags},
-    AsyncCommitFiles, AsyncNotification,
+    sync::{self, CommitId, CommitTags, RevertFileOutcome},
+    AsyncCommitFiles, AsyncNotification, CWD,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
@@ -26,6 +30,8 @@ pub struct CommitDetailsComponent {
     details: DetailsComponent,
     file_tree: FileTreeComponent,
     git_commit_files: AsyncCommitFiles,
+    commit: Option<CommitId>,
+    queue: Queue,
     visible: bool,
     key_config: SharedKeyConfig,
 }
@@ -54,11 +60,29 @@ impl CommitDetailsComponent {
                 theme,
                 key_config.clone(),
             ),
+            commit: None,
+            queue: queue.clone(),
             visible: false,
             key_config,
         }
     }
 
+    /// highlight message lines longer than these, 0 for no limit
+    pub fn with_message_limits(
+        mut self,
+        subject: usize,
+        body: usize,
+    ) -> Self {
+        self.details =
+            self.details.with_message_limits(subject, body);
+        self
+    }
+
+    /// scrolls the commit message without focusing it
+    pub fn scroll_details(&self, scroll: ScrollType) -> bool {
+        self.details.move_scroll_top(scroll)
+    }
+
     fn get_files_title(&self) -> String {
         let files_count = self.file_tree.file_count();
 
@@ -76,6 +100,7 @@ impl CommitDetailsComponent {
         tags: Option<CommitTags>,
     ) -> Result<()> {
         self.details.set_commit(id, tags)?;
+        self.commit = id;
 
         if let Some(id) = id {
             if let Some((fetched_id, res)) =
@@ -98,6 +123,15 @@ impl CommitDetailsComponent {
         Ok(())
     }
 
+    /// see `DetailsComponent::set_reverts`
+    pub fn set_reverts(
+        &mut self,
+        reverts: Option<CommitId>,
+        reverted_by: &[CommitId],
+    ) {
+        self.details.set_reverts(reverts, reverted_by);
+    }
+
     ///
     pub fn any_work_pending(&self) -> bool {
         self.git_commit_files.is_pending()
@@ -107,6 +141,57 @@ impl CommitDetailsComponent {
     pub const fn files(&self) -> &FileTreeComponent {
         &self.file_tree
     }
+
+    /// the commit and the path of the file selected in the file list
+    fn selected_file(&self) -> Option<(CommitId, String)> {
+        self.commit.zip(
+            self.file_tree.selection_file().map(|file| file.path),
+        )
+    }
+
+    fn copy_file_diff(&self) -> Result<()> {
+        if let Some((id, path)) = self.selected_file() {
+            copy_string(&sync::get_commit_file_diff_text(
+                CWD, id, &path,
+            )?)?;
+        }
+
+        Ok(())
+    }
+
+    /// undoes the changes of the commit to the selected file
+    fn revert_file(&self) -> Result<()> {
+        if let Some((id, path)) = self.selected_file() {
+            let commit = id.get_short_string();
+            let msg = match sync::revert_commit_file(CWD, id, &path)?
+            {
+                RevertFileOutcome::Reverted => {
+                    InternalEvent::ShowInfoMsg(
+                        strings::file_reverted_msg(
+                            &self.key_config,
+                            &path,
+                            &commit,
+                        ),
+                    )
+                }
+                RevertFileOutcome::Conflict => {
+                    InternalEvent::ShowErrorMsg(
+                        strings::file_revert_conflict_msg(
+                            &self.key_config,
+                            &path,
+                            &commit,
+                        ),
+                    )
+                }
+            };
+
+            let mut queue = self.queue.borrow_mut();
+            queue.push_back(InternalEvent::Update(NeedsUpdate::ALL));
+            queue.push_back(msg);
+        }
+
+        Ok(())
+    }
 }
 
 impl DrawableComponent for CommitDetailsComponent {
@@ -145,6 +230,22 @@ impl Component for CommitDetailsComponent {
                 force_all,
                 self.components().as_slice(),
             );
+
+            out.push(CommandInfo::new(
+                strings::commands::commit_copy_file_diff(
+                    &self.key_config,
+                ),
+                self.selected_file().is_some(),
+                self.file_tree.focused() || force_all,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::commit_revert_file(
+                    &self.key_config,
+                ),
+                self.selected_file().is_some(),
+                self.file_tree.focused() || force_all,
+            ));
         }
 
         CommandBlocking::PassingOn
@@ -169,6 +270,26 @@ impl Component for CommitDetailsComponent {
                     self.file_tree.focus(false);
                     self.details.focus(true);
                     Ok(true)
+                } else if e == self.key_config.copy
+                    && self.file_tree.focused()
+                    && self.selected_file().is_some()
+                {
+                    try_or_popup!(
+                        self,
+                        strings::copy_file_diff_error(),
+                        self.copy_file_diff()
+                    );
+                    Ok(true)
+                } else if e == self.key_config.commit_revert_file
+                    && self.file_tree.focused()
+                    && self.selected_file().is_some()
+                {
+                    try_or_popup!(
+                        self,
+                        strings::revert_file_error(),
+                        self.revert_file()
+                    );
+                    Ok(true)
                 } else {
                     Ok(false)
                 };
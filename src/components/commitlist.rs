@@ -10,33 +10,170 @@ use crate::{
     ui::style::{SharedTheme, Theme},
 };
 use anyhow::Result;
-use asyncgit::sync::Tags;
-use crossterm::event::Event;
+use asyncgit::sync::{
+    CommitId, Decoration, Decorations, Reverts, Tags,
+};
+use bitflags::bitflags;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
 use std::{
-    borrow::Cow, cell::Cell, cmp, convert::TryFrom, time::Instant,
+    borrow::Cow,
+    cell::Cell,
+    cmp,
+    collections::HashSet,
+    convert::TryFrom,
+    time::{Duration, Instant},
 };
 use tui::{
     backend::Backend,
-    layout::{Alignment, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 const ELEMENTS_PER_LINE: usize = 10;
+/// ends messages cut off at the edge unless `log.ellipsis` is set
+pub const DEFAULT_ELLIPSIS: &str = "\u{2026}";
+const SHORT_HASH_WIDTH: usize = 7;
+const FULL_HASH_WIDTH: usize = 40;
+const DATE_WIDTH: usize = 10;
+/// the full hash is only shown if this much is left for the message
+const MIN_MSG_WIDTH: usize = 20;
+/// width of the author legend including its border
+const LEGEND_WIDTH: u16 = 28;
+/// the legend is left out unless the list keeps this much width
+const LEGEND_MIN_LIST_WIDTH: u16 = 80;
+/// a count prefix not followed by a movement within this time
+/// is dropped
+const COUNT_TIMEOUT: Duration = Duration::from_secs(3);
+/// count prefixes stop growing here
+const MAX_COUNT: usize = 99_999;
+
+bitflags! {
+    /// how the owner walks the log, the list only shows these in
+    /// its title
+    pub struct WalkModes: u8 {
+        /// oldest first, list rows are log indices counted from
+        /// the end
+        const REVERSED = 0b001;
+        /// like `git log --topo-order` instead of by date
+        const TOPO_ORDER = 0b010;
+        /// like `git log --all` instead of from `HEAD`
+        const ALL_REFS = 0b100;
+    }
+}
+
+bitflags! {
+    /// display toggles of the rows
+    struct ViewModes: u8 {
+        /// drop date and author
+        const ONELINE = 0b0001;
+        /// full instead of short hash column
+        const FULL_HASH = 0b0010;
+        /// authors in colors of their own
+        const COLOR_AUTHORS = 0b0100;
+        /// authors on screen listed next to the log
+        const AUTHOR_LEGEND = 0b1000;
+    }
+}
+
+/// `count` with `digit` typed after it, a leading `0` starts no count
+fn push_count_digit(
+    count: Option<usize>,
+    digit: u32,
+) -> Option<usize> {
+    let digit = usize::try_from(digit).ok()?;
+
+    match count {
+        None if digit == 0 => None,
+        None => Some(digit),
+        Some(count) => Some(cmp::min(
+            count.saturating_mul(10).saturating_add(digit),
+            MAX_COUNT,
+        )),
+    }
+}
+
+/// widths of the columns in front of the refs and message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Columns {
+    hash: usize,
+    /// `None` in oneline mode, which drops date and author
+    author: Option<usize>,
+}
+
+impl Columns {
+    /// falls back to the short hash if the full one would
+    /// squeeze the message below `MIN_MSG_WIDTH`
+    fn new(width: usize, full_hash: bool, oneline: bool) -> Self {
+        let full = Self::with_hash(width, FULL_HASH_WIDTH, oneline);
+        if full_hash && full.width() + MIN_MSG_WIDTH <= width {
+            full
+        } else {
+            Self::with_hash(width, SHORT_HASH_WIDTH, oneline)
+        }
+    }
+
+    fn with_hash(width: usize, hash: usize, oneline: bool) -> Self {
+        let author = (!oneline).then(|| {
+            (width.saturating_sub(hash + DATE_WIDTH + 2) / 3)
+                .clamp(3, 20)
+        });
+        Self { hash, author }
+    }
+
+    /// all columns including their separators
+    fn width(&self) -> usize {
+        self.hash
+            + 1
+            + self.author.map_or(0, |author| DATE_WIDTH + author + 2)
+    }
+}
+
+/// commits a log action applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogSelection {
+    /// the commit under the cursor
+    Single(CommitId),
+    /// log positions `start..=end` of the range selection
+    Range(usize, usize),
+}
 
 ///
 pub struct CommitList {
     title: String,
+    searching: bool,
     selection: usize,
     branch: Option<String>,
     count_total: usize,
     items: ItemBatch,
+    marked: Vec<CommitId>,
+    range_anchor: Option<usize>,
+    view: ViewModes,
+    /// appended to messages that do not fit
+    ellipsis: String,
+    /// rows are oldest first with `REVERSED`, the owner fills
+    /// them that way
+    walk: WalkModes,
+    /// the log window the owner walks within
+    window: Option<String>,
+    /// digits typed in front of a movement (`10j`) are a count
+    count_prefix: bool,
+    /// count typed so far and when its last digit came in
+    pending_count: Option<(usize, Instant)>,
     scroll_state: (Instant, f32),
     tags: Option<Tags>,
+    decorations: Option<Decorations>,
+    /// badges reverts and reverted commits
+    reverts: Reverts,
+    /// commits of a shallow clone whose parents are missing
+    shallow_boundary: HashSet<CommitId>,
     current_size: Cell<(u16, u16)>,
     scroll_top: Cell<usize>,
+    /// viewport row the selection is put back on by the next draw,
+    /// set when paging keeps the row
+    keep_row: Cell<Option<usize>>,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
 }
@@ -50,29 +187,152 @@ impl CommitList {
     ) -> Self {
         Self {
             items: ItemBatch::default(),
+            marked: Vec::new(),
+            range_anchor: None,
+            view: ViewModes::empty(),
+            ellipsis: String::from(DEFAULT_ELLIPSIS),
+            walk: WalkModes::empty(),
+            window: None,
+            count_prefix: false,
+            pending_count: None,
             selection: 0,
             branch: None,
             count_total: 0,
             scroll_state: (Instant::now(), 0_f32),
             tags: None,
+            decorations: None,
+            reverts: Reverts::default(),
+            shallow_boundary: HashSet::new(),
             current_size: Cell::new((0, 0)),
             scroll_top: Cell::new(0),
+            keep_row: Cell::new(None),
             theme,
             key_config,
             title: String::from(title),
+            searching: false,
         }
     }
 
+    /// start with the full instead of the short hash column
+    pub fn with_full_hash(mut self, full_hash: bool) -> Self {
+        self.view.set(ViewModes::FULL_HASH, full_hash);
+        self
+    }
+
+    /// end messages cut off at the edge with `ellipsis` instead
+    pub fn with_ellipsis(mut self, ellipsis: String) -> Self {
+        self.ellipsis = ellipsis;
+        self
+    }
+
+    /// take digits typed in front of a movement as its count
+    pub const fn with_count_prefix(
+        mut self,
+        count_prefix: bool,
+    ) -> Self {
+        self.count_prefix = count_prefix;
+        self
+    }
+
     ///
     pub fn items(&mut self) -> &mut ItemBatch {
         &mut self.items
     }
 
+    ///
+    pub fn set_title(&mut self, title: &str) {
+        self.title = String::from(title);
+    }
+
+    /// how the rows are walked, `REVERSED` rows are filled oldest
+    /// first
+    pub const fn set_walk_modes(&mut self, walk: WalkModes) {
+        self.walk = walk;
+    }
+
+    /// older commits are left out, shown in the title and after
+    /// the last commit
+    pub fn set_window(&mut self, window: Option<String>) {
+        self.window = window;
+    }
+
+    /// hints at a search still looking for its match
+    pub const fn set_searching(&mut self, searching: bool) {
+        self.searching = searching;
+    }
+
+    ///
+    pub fn marked(&self) -> &[CommitId] {
+        &self.marked
+    }
+
+    ///
+    pub fn clear_marked(&mut self) {
+        self.marked.clear();
+    }
+
+    fn mark(&mut self) {
+        if let Some(e) = self.selected_entry() {
+            let id = e.id;
+            if let Some(idx) =
+                self.marked.iter().position(|marked| *marked == id)
+            {
+                self.marked.remove(idx);
+            } else {
+                self.marked.push(id);
+            }
+        }
+    }
+
+    /// inclusive log positions of the range selection, if active
+    pub fn range(&self) -> Option<(usize, usize)> {
+        self.range_anchor.map(|anchor| {
+            (
+                cmp::min(anchor, self.selection),
+                cmp::max(anchor, self.selection),
+            )
+        })
+    }
+
+    ///
+    pub const fn clear_range(&mut self) {
+        self.range_anchor = None;
+    }
+
+    /// the range if one is selected, the cursor's commit otherwise
+    pub fn log_selection(&self) -> Option<LogSelection> {
+        self.range().map_or_else(
+            || {
+                self.selected_entry()
+                    .map(|e| LogSelection::Single(e.id))
+            },
+            |(start, end)| Some(LogSelection::Range(start, end)),
+        )
+    }
+
+    const fn toggle_range(&mut self) {
+        self.range_anchor = if self.range_anchor.is_some() {
+            None
+        } else {
+            Some(self.selection)
+        };
+    }
+
     ///
     pub fn set_branch(&mut self, name: Option<String>) {
         self.branch = name;
     }
 
+    /// the log ends at these commits, see `get_shallow_boundary`
+    pub fn set_shallow_boundary(&mut self, ids: HashSet<CommitId>) {
+        self.shallow_boundary = ids;
+    }
+
+    /// `true` if the parents of `id` were not fetched
+    pub fn is_shallow_boundary(&self, id: CommitId) -> bool {
+        self.shallow_boundary.contains(&id)
+    }
+
     ///
     pub const fn selection(&self) -> usize {
         self.selection
@@ -83,11 +343,34 @@ impl CommitList {
         self.current_size.get()
     }
 
+    ///
+    pub const fn count_total(&self) -> usize {
+        self.count_total
+    }
+
     ///
     pub fn set_count_total(&mut self, total: usize) {
         self.count_total = total;
         self.selection =
             cmp::min(self.selection, self.selection_max());
+        self.range_anchor = self
+            .range_anchor
+            .map(|anchor| cmp::min(anchor, self.selection_max()));
+    }
+
+    /// rows between the top of the visible list and the selection
+    pub const fn selection_row(&self) -> usize {
+        self.selection.saturating_sub(self.scroll_top.get())
+    }
+
+    /// scrolls to `top`, the selection is still kept visible
+    pub fn set_scroll_top(&self, top: usize) {
+        self.scroll_top.set(top);
+    }
+
+    ///
+    pub fn select_entry(&mut self, position: usize) {
+        self.selection = cmp::min(position, self.selection_max());
     }
 
     ///
@@ -114,6 +397,22 @@ impl CommitList {
         self.tags = Some(tags);
     }
 
+    /// shows branches, remote branches, tags and `HEAD` in place
+    /// of just the tags
+    pub fn set_decorations(&mut self, decorations: Decorations) {
+        self.decorations = Some(decorations);
+    }
+
+    ///
+    pub fn set_reverts(&mut self, reverts: Reverts) {
+        self.reverts = reverts;
+    }
+
+    ///
+    pub const fn reverts(&self) -> &Reverts {
+        &self.reverts
+    }
+
     ///
     pub fn selected_entry(&self) -> Option<&LogEntry> {
         self.items.iter().nth(
@@ -121,15 +420,63 @@ impl CommitList {
         )
     }
 
-    fn move_selection(&mut self, scroll: ScrollType) -> Result<bool> {
+    /// count typed in front of the next movement, if any
+    pub fn pending_count(&self) -> Option<usize> {
+        self.pending_count.map(|(count, _)| count)
+    }
+
+    /// adds a typed digit to the pending count, `false` if it does
+    /// not belong to one
+    fn push_count_key(&mut self, k: KeyEvent) -> bool {
+        if !self.count_prefix || k.modifiers != KeyModifiers::empty()
+        {
+            return false;
+        }
+
+        let digit = match k.code {
+            KeyCode::Char(c) => c.to_digit(10),
+            _ => None,
+        };
+
+        match digit.and_then(|digit| {
+            push_count_digit(self.pending_count(), digit)
+        }) {
+            Some(count) => {
+                self.pending_count = Some((count, Instant::now()));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// takes the pending count unless it timed out
+    fn take_count(&mut self) -> Option<usize> {
+        self.pending_count
+            .take()
+            .filter(|(_, typed)| typed.elapsed() < COUNT_TIMEOUT)
+            .map(|(count, _)| count)
+    }
+
+    /// moves `count` rows (or pages) at once if given, else by
+    /// the current scroll speed
+    fn move_selection(
+        &mut self,
+        scroll: ScrollType,
+        count: Option<usize>,
+    ) -> Result<bool> {
         self.update_scroll_speed();
 
         #[allow(clippy::cast_possible_truncation)]
-        let speed_int =
-            usize::try_from(self.scroll_state.1 as i64)?.max(1);
+        let speed_int = match count {
+            Some(count) => count,
+            None => {
+                usize::try_from(self.scroll_state.1 as i64)?.max(1)
+            }
+        };
 
-        let page_offset =
-            usize::from(self.current_size.get().1).saturating_sub(1);
+        let page_offset = usize::from(self.current_size.get().1)
+            .max(1)
+            .saturating_mul(count.unwrap_or(1));
 
         let new_selection = match scroll {
             ScrollType::Up => {
@@ -158,6 +505,46 @@ impl CommitList {
         Ok(needs_update)
     }
 
+    /// pages like a pager: the view moves along with the selection,
+    /// which stays on the same row
+    fn move_page_keep_row(
+        &mut self,
+        scroll: ScrollType,
+        count: Option<usize>,
+    ) -> Result<bool> {
+        let row = self
+            .relative_selection()
+            .saturating_sub(self.scroll_top.get());
+        let moved = self.move_selection(scroll, count)?;
+        self.keep_row.set(Some(row));
+
+        Ok(moved)
+    }
+
+    /// scrolls the selection back to the row paging kept or else
+    /// just far enough to have it visible
+    fn update_scroll_top(&self, height_in_lines: usize) {
+        let selection = self.relative_selection();
+
+        if let Some(row) = self.keep_row.take() {
+            // no empty rows after the last commit
+            let last_top = self
+                .count_total
+                .saturating_sub(height_in_lines)
+                .saturating_sub(self.items.index_offset());
+            self.scroll_top.set(cmp::min(
+                selection.saturating_sub(row),
+                last_top,
+            ));
+        }
+
+        self.scroll_top.set(calc_scroll_top(
+            self.scroll_top.get(),
+            height_in_lines,
+            selection,
+        ));
+    }
+
     fn update_scroll_speed(&mut self) {
         const REPEATED_SCROLL_THRESHOLD_MILLIS: u128 = 300;
         const SCROLL_SPEED_START: f32 = 0.1_f32;
@@ -185,9 +572,11 @@ impl CommitList {
     fn get_entry_to_add<'a>(
         e: &'a LogEntry,
         selected: bool,
-        tags: Option<String>,
+        marker: Option<Span<'a>>,
+        refs: Vec<Span<'a>>,
         theme: &Theme,
-        width: usize,
+        columns: Columns,
+        color_authors: bool,
     ) -> Spans<'a> {
         let mut txt: Vec<Span> = Vec::new();
         txt.reserve(ELEMENTS_PER_LINE);
@@ -196,43 +585,47 @@ impl CommitList {
         let splitter =
             Span::styled(splitter_txt, theme.text(true, selected));
 
+        // marker (only if anything is marked or in range at all)
+        if let Some(marker) = marker {
+            txt.push(marker);
+        }
+
         // commit hash
-        txt.push(Span::styled(
-            Cow::from(e.hash_short.as_str()),
-            theme.commit_hash(selected),
-        ));
+        let hash = if columns.hash == FULL_HASH_WIDTH {
+            Cow::from(e.id.to_string())
+        } else {
+            Cow::from(e.hash_short.as_str())
+        };
+        txt.push(Span::styled(hash, theme.commit_hash(selected)));
 
         txt.push(splitter.clone());
 
-        // commit timestamp
-        txt.push(Span::styled(
-            Cow::from(e.time.as_str()),
-            theme.commit_time(selected),
-        ));
+        if let Some(author_width) = columns.author {
+            // commit timestamp
+            txt.push(Span::styled(
+                Cow::from(e.time.as_str()),
+                theme.commit_time(selected),
+            ));
 
-        txt.push(splitter.clone());
+            txt.push(splitter.clone());
 
-        let author_width =
-            (width.saturating_sub(19) / 3).max(3).min(20);
-        let author = string_width_align(&e.author, author_width);
+            let author = string_width_align(&e.author, author_width);
 
-        // commit author
-        txt.push(Span::styled::<String>(
-            author,
-            theme.commit_author(selected),
-        ));
+            // commit author
+            txt.push(Span::styled::<String>(
+                author,
+                if color_authors {
+                    theme.author(&e.author, selected)
+                } else {
+                    theme.commit_author(selected)
+                },
+            ));
 
-        txt.push(splitter.clone());
+            txt.push(splitter.clone());
+        }
 
-        // commit tags
-        txt.push(Span::styled(
-            Cow::from(if let Some(tags) = tags {
-                format!(" {}", tags)
-            } else {
-                String::from("")
-            }),
-            theme.tags(selected),
-        ));
+        // commit tags or decorations
+        txt.extend(refs);
 
         txt.push(splitter);
 
@@ -246,9 +639,18 @@ impl CommitList {
 
     fn get_text(&self, height: usize, width: usize) -> Vec<Spans> {
         let selection = self.relative_selection();
+        let range = self.range();
+        let show_marker = !self.marked.is_empty() || range.is_some();
 
         let mut txt: Vec<Spans> = Vec::with_capacity(height);
 
+        let marker_width = if show_marker { 2 } else { 0 };
+        let columns = Columns::new(
+            width.saturating_sub(marker_width),
+            self.view.contains(ViewModes::FULL_HASH),
+            self.view.contains(ViewModes::ONELINE),
+        );
+
         for (idx, e) in self
             .items
             .iter()
@@ -256,23 +658,300 @@ impl CommitList {
             .take(height)
             .enumerate()
         {
-            let tags = self
-                .tags
-                .as_ref()
-                .and_then(|t| t.get(&e.id))
-                .map(|tags| tags.join(" "));
-            txt.push(Self::get_entry_to_add(
+            let selected = idx + self.scroll_top.get() == selection;
+            let position = idx
+                + self.scroll_top.get()
+                + self.items.index_offset();
+            let marker = if !show_marker {
+                None
+            } else if self.marked.contains(&e.id) {
+                Some(Span::styled(
+                    Cow::from("\u{2713} "),
+                    self.theme.tags(selected),
+                ))
+            } else if range.is_some_and(|(start, end)| {
+                (start..=end).contains(&position)
+            }) {
+                Some(Span::styled(
+                    Cow::from("\u{2503} "),
+                    self.theme.log_range(selected),
+                ))
+            } else {
+                Some(Span::styled(
+                    Cow::from("  "),
+                    self.theme.text(true, selected),
+                ))
+            };
+            let mut refs = self.get_refs(&e.id, selected);
+            refs.extend(self.get_revert_badges(&e.id, selected));
+            let mut entry = Self::get_entry_to_add(
                 e,
-                idx + self.scroll_top.get() == selection,
-                tags,
+                selected,
+                marker,
+                refs,
                 &self.theme,
-                width,
-            ));
+                columns,
+                self.view.contains(ViewModes::COLOR_AUTHORS),
+            );
+            fit_message(&mut entry, width, &self.ellipsis);
+            txt.push(entry);
+
+            if position + 1 == self.count_total
+                && self.is_shallow_boundary(e.id)
+            {
+                txt.push(Spans::from(Span::styled(
+                    Cow::from(strings::log_shallow_boundary()),
+                    self.theme.text(false, false),
+                )));
+            }
+
+            if let Some(window) = &self.window {
+                if position + 1 == self.count_total
+                    && !self.walk.contains(WalkModes::REVERSED)
+                {
+                    txt.push(Spans::from(Span::styled(
+                        Cow::from(strings::log_window_end(
+                            &self.key_config,
+                            window,
+                        )),
+                        self.theme.text(false, false),
+                    )));
+                }
+            }
         }
 
         txt
     }
 
+    /// `(HEAD -> master, origin/master, tag: v1)` with decorations,
+    /// otherwise the plain tags
+    fn get_refs(&self, id: &CommitId, selected: bool) -> Vec<Span> {
+        if let Some(decorations) = &self.decorations {
+            return decorations
+                .get(id)
+                .map(|decorations| {
+                    self.get_decoration_spans(decorations, selected)
+                })
+                .unwrap_or_default();
+        }
+
+        self.tags
+            .as_ref()
+            .and_then(|t| t.get(id))
+            .map(|tags| {
+                vec![Span::styled(
+                    Cow::from(format!(" {}", tags.join(" "))),
+                    self.theme.tags(selected),
+                )]
+            })
+            .unwrap_or_default()
+    }
+
+    fn get_revert_badges(
+        &self,
+        id: &CommitId,
+        selected: bool,
+    ) -> Vec<Span> {
+        let mut badges = Vec::new();
+        if self.reverts.reverts(id).is_some() {
+            badges.push(Span::styled(
+                Cow::from(strings::log_revert_badge()),
+                self.theme.log_revert(false, selected),
+            ));
+        }
+        if !self.reverts.reverted_by(id).is_empty() {
+            badges.push(Span::styled(
+                Cow::from(strings::log_reverted_badge()),
+                self.theme.log_revert(true, selected),
+            ));
+        }
+
+        badges
+    }
+
+    fn get_decoration_spans(
+        &self,
+        decorations: &[Decoration],
+        selected: bool,
+    ) -> Vec<Span> {
+        let text = self.theme.text(true, selected);
+        let mut spans = vec![Span::styled(Cow::from(" ("), text)];
+        for (idx, decoration) in decorations.iter().enumerate() {
+            if idx > 0 {
+                spans.push(Span::styled(Cow::from(", "), text));
+            }
+            spans.push(Span::styled(
+                Cow::from(decoration_text(decoration)),
+                self.theme.decoration(decoration, selected),
+            ));
+        }
+        spans.push(Span::styled(Cow::from(")"), text));
+
+        spans
+    }
+
+    /// off, colored authors, colored authors with the legend
+    fn cycle_author_colors(&mut self) {
+        let colors = ViewModes::COLOR_AUTHORS;
+        let with_legend = colors | ViewModes::AUTHOR_LEGEND;
+        let next = match self.view & with_legend {
+            current if current == colors => with_legend,
+            current if current == with_legend => ViewModes::empty(),
+            _ => colors,
+        };
+        self.view = (self.view - with_legend) | next;
+    }
+
+    /// authors of the `height` commits on screen
+    fn legend(&self, height: usize) -> Vec<(&str, usize)> {
+        author_legend(
+            self.items
+                .iter()
+                .skip(self.scroll_top.get())
+                .take(height),
+        )
+    }
+
+    fn draw_legend<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        area: Rect,
+        height: usize,
+    ) {
+        let legend = self.legend(height);
+        let count_width = legend
+            .iter()
+            .map(|(_, count)| count.to_string().len())
+            .max()
+            .unwrap_or(0);
+        // border, color square and the spaces around the name
+        let name_width =
+            usize::from(area.width).saturating_sub(count_width + 5);
+
+        let txt: Vec<Spans> = legend
+            .into_iter()
+            .map(|(author, count)| {
+                Spans::from(vec![
+                    Span::styled(
+                        Cow::from("\u{25a0} "),
+                        self.theme.author(author, false),
+                    ),
+                    Span::styled(
+                        Cow::from(format!(
+                            "{} {:>w$}",
+                            string_width_align(author, name_width),
+                            count,
+                            w = count_width
+                        )),
+                        self.theme.text(true, false),
+                    ),
+                ])
+            })
+            .collect();
+
+        f.render_widget(
+            Paragraph::new(txt).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(Span::styled(
+                        strings::log_author_legend_title(),
+                        self.theme.title(true),
+                    ))
+                    .border_style(self.theme.block(true)),
+            ),
+            area,
+        );
+    }
+
+    /// position, branch and the active view modes of the list
+    fn get_title(&self) -> String {
+        let branch_post_fix =
+            self.branch.as_ref().map(|b| format!("- {{{}}}", b));
+
+        // counted from the oldest commit either way
+        let number = if self.walk.contains(WalkModes::REVERSED) {
+            cmp::min(self.selection + 1, self.count_total)
+        } else {
+            self.count_total.saturating_sub(self.selection)
+        };
+        let title = format!(
+            "{} {}/{} {}{}{}{}{}{}",
+            self.title,
+            number,
+            self.count_total,
+            branch_post_fix.as_deref().unwrap_or(""),
+            if self.walk.contains(WalkModes::REVERSED) {
+                format!(
+                    " {}",
+                    strings::log_title_oldest_first(&self.key_config)
+                )
+            } else {
+                String::new()
+            },
+            if self.walk.contains(WalkModes::TOPO_ORDER) {
+                format!(
+                    " {}",
+                    strings::log_title_topo_order(&self.key_config)
+                )
+            } else {
+                String::new()
+            },
+            if self.walk.contains(WalkModes::ALL_REFS) {
+                format!(
+                    " {}",
+                    strings::log_title_all_refs(&self.key_config)
+                )
+            } else {
+                String::new()
+            },
+            self.window.as_ref().map_or_else(String::new, |window| {
+                format!(
+                    " {}",
+                    strings::log_title_window(
+                        &self.key_config,
+                        window
+                    )
+                )
+            }),
+            if self.searching {
+                format!(" {}", strings::log_searching())
+            } else {
+                String::new()
+            },
+        );
+        let title = match self.range() {
+            Some((start, end)) => format!(
+                "{} {}",
+                title,
+                strings::log_title_range(
+                    &self.key_config,
+                    end - start + 1
+                )
+            ),
+            None => title,
+        };
+        let title = if self.marked.is_empty() {
+            title
+        } else {
+            format!(
+                "{} {}",
+                title,
+                strings::log_title_marked(
+                    &self.key_config,
+                    self.marked.len()
+                )
+            )
+        };
+        match self.pending_count() {
+            Some(count) => format!(
+                "{} {}",
+                title,
+                strings::log_title_count(&self.key_config, count)
+            ),
+            None => title,
+        }
+    }
+
     #[allow(clippy::missing_const_for_fn)]
     fn relative_selection(&self) -> usize {
         self.selection.saturating_sub(self.items.index_offset())
@@ -285,6 +964,25 @@ impl DrawableComponent for CommitList {
         f: &mut Frame<B>,
         area: Rect,
     ) -> Result<()> {
+        let (area, legend_area) =
+            if self.view.contains(ViewModes::AUTHOR_LEGEND)
+                && area.width >= LEGEND_MIN_LIST_WIDTH + LEGEND_WIDTH
+            {
+                let chunks = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(
+                        [
+                            Constraint::Min(0),
+                            Constraint::Length(LEGEND_WIDTH),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(area);
+                (chunks[0], Some(chunks[1]))
+            } else {
+                (area, None)
+            };
+
         let current_size = (
             area.width.saturating_sub(2),
             area.height.saturating_sub(2),
@@ -292,24 +990,9 @@ impl DrawableComponent for CommitList {
         self.current_size.set(current_size);
 
         let height_in_lines = self.current_size.get().1 as usize;
-        let selection = self.relative_selection();
+        self.update_scroll_top(height_in_lines);
 
-        self.scroll_top.set(calc_scroll_top(
-            self.scroll_top.get(),
-            height_in_lines,
-            selection,
-        ));
-
-        let branch_post_fix =
-            self.branch.as_ref().map(|b| format!("- {{{}}}", b));
-
-        let title = format!(
-            "{} {}/{} {}",
-            self.title,
-            self.count_total.saturating_sub(self.selection),
-            self.count_total,
-            branch_post_fix.as_deref().unwrap_or(""),
-        );
+        let title = self.get_title();
 
         f.render_widget(
             Paragraph::new(
@@ -331,6 +1014,10 @@ impl DrawableComponent for CommitList {
             area,
         );
 
+        if let Some(legend_area) = legend_area {
+            self.draw_legend(f, legend_area, height_in_lines);
+        }
+
         Ok(())
     }
 }
@@ -338,22 +1025,46 @@ impl DrawableComponent for CommitList {
 impl Component for CommitList {
     fn event(&mut self, ev: Event) -> Result<bool> {
         if let Event::Key(k) = ev {
+            if self.push_count_key(k) {
+                return Ok(true);
+            }
+            let count = self.take_count();
+
             let selection_changed = if k == self.key_config.move_up {
-                self.move_selection(ScrollType::Up)?
+                self.move_selection(ScrollType::Up, count)?
             } else if k == self.key_config.move_down {
-                self.move_selection(ScrollType::Down)?
+                self.move_selection(ScrollType::Down, count)?
             } else if k == self.key_config.shift_up
                 || k == self.key_config.home
             {
-                self.move_selection(ScrollType::Home)?
+                self.move_selection(ScrollType::Home, None)?
             } else if k == self.key_config.shift_down
                 || k == self.key_config.end
             {
-                self.move_selection(ScrollType::End)?
+                self.move_selection(ScrollType::End, None)?
             } else if k == self.key_config.page_up {
-                self.move_selection(ScrollType::PageUp)?
+                self.move_selection(ScrollType::PageUp, count)?
             } else if k == self.key_config.page_down {
-                self.move_selection(ScrollType::PageDown)?
+                self.move_selection(ScrollType::PageDown, count)?
+            } else if k == self.key_config.log_page_up_keep_row {
+                self.move_page_keep_row(ScrollType::PageUp, count)?
+            } else if k == self.key_config.log_page_down_keep_row {
+                self.move_page_keep_row(ScrollType::PageDown, count)?
+            } else if k == self.key_config.log_mark_commit {
+                self.mark();
+                true
+            } else if k == self.key_config.log_visual_mode {
+                self.toggle_range();
+                true
+            } else if k == self.key_config.log_toggle_oneline {
+                self.view.toggle(ViewModes::ONELINE);
+                true
+            } else if k == self.key_config.log_toggle_full_hash {
+                self.view.toggle(ViewModes::FULL_HASH);
+                true
+            } else if k == self.key_config.log_author_colors {
+                self.cycle_author_colors();
+                true
             } else {
                 false
             };
@@ -373,10 +1084,68 @@ impl Component for CommitList {
             self.selected_entry().is_some(),
             true,
         ));
+        out.push(CommandInfo::new(
+            strings::commands::log_page_up_keep_row(&self.key_config),
+            self.selected_entry().is_some(),
+            true,
+        ));
+        out.push(CommandInfo::new(
+            strings::commands::log_page_down_keep_row(
+                &self.key_config,
+            ),
+            self.selected_entry().is_some(),
+            true,
+        ));
+        out.push(CommandInfo::new(
+            strings::commands::log_mark_commit(&self.key_config),
+            self.selected_entry().is_some(),
+            true,
+        ));
+        out.push(CommandInfo::new(
+            strings::commands::log_visual_mode(&self.key_config),
+            self.selected_entry().is_some(),
+            true,
+        ));
+        out.push(CommandInfo::new(
+            strings::commands::log_toggle_oneline(&self.key_config),
+            true,
+            true,
+        ));
+        out.push(CommandInfo::new(
+            strings::commands::log_toggle_full_hash(&self.key_config),
+            true,
+            true,
+        ));
+        out.push(CommandInfo::new(
+            strings::commands::log_author_colors(&self.key_config),
+            true,
+            true,
+        ));
         CommandBlocking::PassingOn
     }
 }
 
+/// authors of `entries` with how many of them each one wrote,
+/// most commits first
+fn author_legend<'a>(
+    entries: impl Iterator<Item = &'a LogEntry>,
+) -> Vec<(&'a str, usize)> {
+    let mut legend: Vec<(&str, usize)> = Vec::new();
+
+    for e in entries {
+        match legend
+            .iter_mut()
+            .find(|(author, _)| *author == e.author)
+        {
+            Some((_, count)) => *count += 1,
+            None => legend.push((&e.author, 1)),
+        }
+    }
+
+    legend.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    legend
+}
+
 #[inline]
 fn string_width_align(s: &str, width: usize) -> String {
     static POSTFIX: &str = "..";
@@ -395,14 +1164,63 @@ fn string_width_align(s: &str, width: usize) -> String {
     }
 }
 
+/// cuts the message (the last span) of a log line so the line is
+/// at most `width` wide, ending it with `ellipsis` if cut
+fn fit_message(line: &mut Spans, width: usize, ellipsis: &str) {
+    if let Some((msg, front)) = line.0.split_last_mut() {
+        let available = width.saturating_sub(
+            front.iter().map(Span::width).sum::<usize>(),
+        );
+
+        if msg.width() > available {
+            let ellipsis = if ellipsis.width() <= available {
+                ellipsis
+            } else {
+                ""
+            };
+            let cut = prefix_with_width(
+                &msg.content,
+                available - ellipsis.width(),
+            );
+            msg.content = Cow::from(format!("{}{}", cut, ellipsis));
+        }
+    }
+}
+
+/// the longest start of `s` at most `width` columns wide
+fn prefix_with_width(s: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (idx, c) in s.char_indices() {
+        used += c.width().unwrap_or(0);
+        if used > width {
+            return &s[..idx];
+        }
+    }
+    s
+}
+
 #[inline]
 fn find_truncate_point(s: &str, chars: usize) -> usize {
     s.chars().take(chars).map(char::len_utf8).sum()
 }
 
+/// a ref the way `git log --decorate` shows it
+fn decoration_text(decoration: &Decoration) -> String {
+    match decoration {
+        Decoration::Head(Some(branch)) => {
+            format!("HEAD -> {}", branch)
+        }
+        Decoration::Head(None) => String::from("HEAD"),
+        Decoration::LocalBranch(name)
+        | Decoration::RemoteBranch(name) => name.clone(),
+        Decoration::Tag(name) => format!("tag: {}", name),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use asyncgit::sync::CommitInfo;
 
     #[test]
     fn test_string_width_align() {
@@ -413,6 +1231,437 @@ mod tests {
         assert_eq!(string_width_align("1234556", 4), "12..");
     }
 
+    #[test]
+    fn test_decorations() {
+        let id = CommitId::default();
+        let mut list = CommitList::new(
+            "",
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        let text = |list: &CommitList| {
+            list.get_refs(&id, false)
+                .iter()
+                .map(|span| span.content.to_string())
+                .collect::<String>()
+        };
+
+        let mut tags = Tags::new();
+        tags.insert(id, vec![String::from("v1"), String::from("v2")]);
+        list.set_tags(tags);
+        assert_eq!(text(&list), " v1 v2");
+
+        let mut decorations = Decorations::new();
+        decorations.insert(
+            id,
+            vec![
+                Decoration::Head(Some(String::from("master"))),
+                Decoration::RemoteBranch(String::from(
+                    "origin/master",
+                )),
+                Decoration::Tag(String::from("v1")),
+            ],
+        );
+        list.set_decorations(decorations);
+        assert_eq!(
+            text(&list),
+            " (HEAD -> master, origin/master, tag: v1)"
+        );
+    }
+
+    #[test]
+    fn test_revert_badges() {
+        let id = CommitId::default();
+        let mut list = CommitList::new(
+            "",
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        let text = |list: &CommitList| {
+            list.get_revert_badges(&id, false)
+                .iter()
+                .map(|span| span.content.to_string())
+                .collect::<String>()
+        };
+
+        assert_eq!(text(&list), "");
+
+        // reverting itself makes it both
+        let mut reverts = Reverts::default();
+        reverts.insert(id, id);
+        list.set_reverts(reverts);
+        assert_eq!(
+            text(&list),
+            format!(
+                "{}{}",
+                strings::log_revert_badge(),
+                strings::log_reverted_badge()
+            )
+        );
+    }
+
+    #[test]
+    fn test_oneline_drops_author_and_time() {
+        let e = LogEntry {
+            time: String::from("2020-10-16"),
+            author: String::from("author"),
+            msg: String::from("msg"),
+            hash_short: String::from("1234567"),
+            id: CommitId::default(),
+        };
+        let theme = Theme::default();
+        let text = |oneline| {
+            CommitList::get_entry_to_add(
+                &e,
+                false,
+                None,
+                Vec::new(),
+                &theme,
+                Columns::new(80, false, oneline),
+                false,
+            )
+            .0
+            .iter()
+            .map(|span| span.content.to_string())
+            .collect::<String>()
+        };
+
+        let full = text(false);
+        assert!(full.contains("author"));
+        assert!(full.contains("2020-10-16"));
+
+        let oneline = text(true);
+        assert!(!oneline.contains("author"));
+        assert!(!oneline.contains("2020-10-16"));
+        assert!(oneline.starts_with("1234567 "));
+        assert!(oneline.ends_with("msg"));
+    }
+
+    #[test]
+    fn test_fit_message() {
+        let fit = |msg: &'static str, width, ellipsis| {
+            let mut line = Spans::from(vec![
+                Span::raw("1234567 "),
+                Span::raw(msg),
+            ]);
+            fit_message(&mut line, width, ellipsis);
+            line.0[1].content.to_string()
+        };
+
+        assert_eq!(fit("fits", 12, "\u{2026}"), "fits");
+        assert_eq!(fit("cut off", 12, "\u{2026}"), "cut\u{2026}");
+        assert_eq!(fit("cut off", 12, "..."), "c...");
+        assert_eq!(fit("cut off", 12, ""), "cut ");
+        assert_eq!(fit("cut off", 9, "..."), "c");
+        assert_eq!(fit("cut off", 5, "\u{2026}"), "");
+        // wide chars are not split
+        assert_eq!(fit("里里里", 13, "\u{2026}"), "里里\u{2026}");
+        assert_eq!(fit("里里里", 12, "\u{2026}"), "里\u{2026}");
+    }
+
+    #[test]
+    fn test_columns_width_budget() {
+        // short hash and date leave the author column as before
+        for width in 0..200 {
+            assert_eq!(
+                Columns::new(width, false, false).author,
+                Some((width.saturating_sub(19) / 3).clamp(3, 20))
+            );
+        }
+
+        for width in 0..200 {
+            let columns = Columns::new(width, true, false);
+            if columns.hash == FULL_HASH_WIDTH {
+                assert!(columns.width() + MIN_MSG_WIDTH <= width);
+            }
+            assert!(columns.author.is_some());
+        }
+
+        let wide = Columns::new(120, true, false);
+        assert_eq!(wide.hash, FULL_HASH_WIDTH);
+        assert_eq!(wide.width(), 40 + 1 + 10 + 1 + 20 + 1);
+
+        // too narrow for the full hash next to the date
+        let narrow = Columns::new(80, true, false);
+        assert_eq!(narrow.hash, SHORT_HASH_WIDTH);
+        assert_eq!(
+            Columns::new(80, true, true).hash,
+            FULL_HASH_WIDTH
+        );
+
+        let e = LogEntry {
+            time: String::from("2020-10-16"),
+            author: String::from("author"),
+            msg: String::from("msg"),
+            hash_short: String::from("0000000"),
+            id: CommitId::default(),
+        };
+        let text = CommitList::get_entry_to_add(
+            &e,
+            false,
+            None,
+            Vec::new(),
+            &Theme::default(),
+            wide,
+            false,
+        )
+        .0
+        .iter()
+        .map(|span| span.content.to_string())
+        .collect::<String>();
+        assert!(text
+            .starts_with(&format!("{} 2020-10-16", "0".repeat(40))));
+        assert_eq!(
+            UnicodeWidthStr::width(text.as_str()),
+            wide.width() + 1 + "msg".len()
+        );
+    }
+
+    #[test]
+    fn test_range_selection() {
+        let mut list = CommitList::new(
+            "",
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        list.items().set_items(
+            0,
+            (0..10)
+                .map(|_| CommitInfo {
+                    message: String::from("msg"),
+                    time: 0,
+                    author: String::from("author"),
+                    id: CommitId::default(),
+                })
+                .collect(),
+        );
+        list.set_count_total(10);
+        list.select_entry(5);
+
+        assert_eq!(
+            list.log_selection(),
+            Some(LogSelection::Single(CommitId::default()))
+        );
+
+        list.toggle_range();
+        list.select_entry(2);
+        assert_eq!(
+            list.log_selection(),
+            Some(LogSelection::Range(2, 5))
+        );
+        list.select_entry(7);
+        assert_eq!(
+            list.log_selection(),
+            Some(LogSelection::Range(5, 7))
+        );
+
+        // the log shrinking keeps the range within it
+        list.set_count_total(4);
+        assert_eq!(list.range(), Some((3, 3)));
+
+        list.clear_range();
+        assert_eq!(list.range(), None);
+    }
+
+    #[test]
+    fn test_page_keep_row() {
+        let key_config = SharedKeyConfig::default();
+        let page_down = Event::Key(key_config.page_down);
+        let keep_down = Event::Key(key_config.log_page_down_keep_row);
+        let keep_up = Event::Key(key_config.log_page_up_keep_row);
+        let mut list =
+            CommitList::new("", SharedTheme::default(), key_config);
+        list.set_count_total(100);
+        list.current_size.set((80, 10));
+
+        // a full page
+        list.event(page_down).unwrap();
+        assert_eq!(list.selection(), 10);
+        list.update_scroll_top(10);
+        assert_eq!(list.scroll_top.get(), 1);
+
+        list.select_entry(13);
+        list.set_scroll_top(10);
+        list.event(keep_down).unwrap();
+        list.update_scroll_top(10);
+        assert_eq!(
+            (list.selection(), list.scroll_top.get()),
+            (23, 20)
+        );
+
+        // the last page stays full
+        for _ in 0..10 {
+            list.event(keep_down).unwrap();
+            list.update_scroll_top(10);
+        }
+        assert_eq!(
+            (list.selection(), list.scroll_top.get()),
+            (99, 90)
+        );
+
+        list.event(keep_up).unwrap();
+        list.update_scroll_top(10);
+        assert_eq!(
+            (list.selection(), list.scroll_top.get()),
+            (89, 80)
+        );
+        assert_eq!(list.selection_row(), 9);
+    }
+
+    #[test]
+    fn test_push_count_digit() {
+        assert_eq!(push_count_digit(None, 0), None);
+        assert_eq!(push_count_digit(None, 1), Some(1));
+        assert_eq!(push_count_digit(Some(1), 0), Some(10));
+        assert_eq!(push_count_digit(Some(10), 5), Some(105));
+        assert_eq!(
+            push_count_digit(Some(MAX_COUNT), 9),
+            Some(MAX_COUNT)
+        );
+    }
+
+    #[test]
+    fn test_count_prefix_movement() {
+        let key = |c| {
+            Event::Key(KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::empty(),
+            })
+        };
+        let key_config = SharedKeyConfig::default();
+        let down = Event::Key(key_config.move_down);
+        let up = Event::Key(key_config.move_up);
+        let mut list =
+            CommitList::new("", SharedTheme::default(), key_config)
+                .with_count_prefix(true);
+        list.set_count_total(100);
+
+        assert!(list.event(key('1')).unwrap());
+        assert!(list.event(key('2')).unwrap());
+        assert_eq!(list.pending_count(), Some(12));
+        assert!(list.event(down).unwrap());
+        assert_eq!(list.selection(), 12);
+        assert_eq!(list.pending_count(), None);
+
+        list.event(key('5')).unwrap();
+        list.event(up).unwrap();
+        assert_eq!(list.selection(), 7);
+
+        // clamped to the log
+        list.event(key('5')).unwrap();
+        list.event(key('0')).unwrap();
+        list.event(key('0')).unwrap();
+        list.event(down).unwrap();
+        assert_eq!(list.selection(), 99);
+
+        // other keys drop the count
+        list.event(key('3')).unwrap();
+        assert!(!list.event(key('x')).unwrap());
+        assert_eq!(list.pending_count(), None);
+
+        // and so does waiting too long
+        list.pending_count = Some((
+            30,
+            Instant::now().checked_sub(COUNT_TIMEOUT).unwrap(),
+        ));
+        list.event(up).unwrap();
+        assert_eq!(list.selection(), 98);
+
+        // a leading zero is no count, nor are digits without the option
+        assert!(!list.event(key('0')).unwrap());
+        list.count_prefix = false;
+        assert!(!list.event(key('1')).unwrap());
+    }
+
+    #[test]
+    fn test_shallow_boundary_marker() {
+        let mut list = CommitList::new(
+            "",
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        list.items().set_items(
+            0,
+            (0..3)
+                .map(|_| CommitInfo {
+                    message: String::from("msg"),
+                    time: 0,
+                    author: String::from("author"),
+                    id: CommitId::default(),
+                })
+                .collect(),
+        );
+        list.set_count_total(3);
+        let last_line = |list: &CommitList| {
+            list.get_text(10, 80)
+                .last()
+                .map(|spans| {
+                    spans
+                        .0
+                        .iter()
+                        .map(|span| span.content.to_string())
+                        .collect::<String>()
+                })
+                .unwrap()
+        };
+
+        assert_ne!(last_line(&list), strings::log_shallow_boundary());
+
+        list.set_shallow_boundary(
+            std::iter::once(CommitId::default()).collect(),
+        );
+        assert_eq!(list.get_text(10, 80).len(), 4);
+        assert_eq!(last_line(&list), strings::log_shallow_boundary());
+
+        // not at the end of the log (yet)
+        list.set_count_total(4);
+        assert_eq!(list.get_text(10, 80).len(), 3);
+
+        list.set_count_total(3);
+        list.set_window(Some(String::from("2 weeks")));
+        assert_eq!(list.get_text(10, 80).len(), 5);
+        assert_eq!(
+            last_line(&list),
+            strings::log_window_end(&list.key_config, "2 weeks")
+        );
+    }
+
+    #[test]
+    fn test_author_legend() {
+        let mut list = CommitList::new(
+            "",
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        list.items().set_items(
+            0,
+            ["b", "a", "c", "a", "b", "a", "d"]
+                .iter()
+                .map(|author| CommitInfo {
+                    message: String::from("msg"),
+                    time: 0,
+                    author: (*author).to_string(),
+                    id: CommitId::default(),
+                })
+                .collect(),
+        );
+
+        assert_eq!(
+            list.legend(6),
+            vec![("a", 3), ("b", 2), ("c", 1)]
+        );
+
+        // only what is on screen
+        list.scroll_top.set(5);
+        assert_eq!(list.legend(6), vec![("a", 1), ("d", 1)]);
+
+        let theme = Theme::default();
+        assert_eq!(
+            theme.author("a", false),
+            theme.author("a", false)
+        );
+    }
+
     #[test]
     fn test_string_width_align_unicode() {
         assert_eq!(string_width_align("äste", 3), "ä..");
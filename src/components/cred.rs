@@ -2,7 +2,10 @@ use anyhow::Result;
 use crossterm::event::Event;
 use tui::{backend::Backend, layout::Rect, Frame};
 
-use asyncgit::sync::cred::BasicAuthCredential;
+use asyncgit::sync::cred::{
+    extract_ssh_passphrase, extract_username_password,
+    need_ssh_passphrase, need_username_password, BasicAuthCredential,
+};
 
 use crate::components::{InputType, TextInputComponent};
 use crate::{
@@ -15,12 +18,24 @@ use crate::{
     ui::style::SharedTheme,
 };
 
+/// how connecting to a remote goes on
+pub enum CredState {
+    /// connect with these, `None` if the remote needs none
+    Ready(Option<BasicAuthCredential>),
+    /// the popup asks for what is missing, connect once it is
+    /// complete
+    Asking,
+}
+
 ///
 pub struct CredComponent {
     visible: bool,
     key_config: SharedKeyConfig,
     input_username: TextInputComponent,
     input_password: TextInputComponent,
+    input_passphrase: TextInputComponent,
+    /// asks for the passphrase of an ssh key instead
+    passphrase: bool,
     cred: BasicAuthCredential,
 }
 
@@ -40,12 +55,20 @@ impl CredComponent {
             )
             .with_input_type(InputType::Singleline),
             input_password: TextInputComponent::new(
-                theme,
+                theme.clone(),
                 key_config.clone(),
                 &strings::password_popup_title(&key_config),
                 &strings::password_popup_msg(&key_config),
             )
             .with_input_type(InputType::Password),
+            input_passphrase: TextInputComponent::new(
+                theme,
+                key_config.clone(),
+                &strings::passphrase_popup_title(&key_config),
+                &strings::passphrase_popup_msg(&key_config),
+            )
+            .with_input_type(InputType::Password),
+            passphrase: false,
             key_config,
             cred: BasicAuthCredential::new(None, None),
         }
@@ -58,6 +81,73 @@ impl CredComponent {
     pub const fn get_cred(&self) -> &BasicAuthCredential {
         &self.cred
     }
+
+    /// asks for the passphrase of the ssh key
+    pub fn show_passphrase(&mut self) -> Result<()> {
+        self.cred = BasicAuthCredential::new(None, None);
+        self.passphrase = true;
+        self.show()
+    }
+
+    /// the credentials to fetch from `remote` of the repository at
+    /// `repo_path` with, remembered ones included. asks for the
+    /// missing ones, like `PushComponent` does for pushing
+    pub fn fetch_credential(
+        &mut self,
+        repo_path: &str,
+        remote: &str,
+    ) -> Result<CredState> {
+        if need_username_password(repo_path, remote)? {
+            let cred = extract_username_password(repo_path, remote)
+                .unwrap_or_else(|_| {
+                    BasicAuthCredential::new(None, None)
+                });
+            if cred.is_complete() {
+                return Ok(CredState::Ready(Some(cred)));
+            }
+            self.set_cred(cred);
+            self.show()?;
+        } else if need_ssh_passphrase(repo_path, remote)? {
+            match extract_ssh_passphrase(repo_path, remote)? {
+                Some(cred) => {
+                    return Ok(CredState::Ready(Some(cred)))
+                }
+                None => self.show_passphrase()?,
+            }
+        } else {
+            return Ok(CredState::Ready(None));
+        }
+
+        Ok(CredState::Asking)
+    }
+
+    /// asks again after fetching from `remote` failed to
+    /// authenticate, `false` if there is nothing to ask for
+    pub fn ask_again(
+        &mut self,
+        repo_path: &str,
+        remote: &str,
+    ) -> Result<bool> {
+        if need_username_password(repo_path, remote)? {
+            self.set_cred(BasicAuthCredential::new(None, None));
+            self.show()?;
+        } else if need_ssh_passphrase(repo_path, remote)? {
+            self.show_passphrase()?;
+        } else {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    /// everything asked for was typed in
+    pub fn is_complete(&self) -> bool {
+        if self.passphrase {
+            self.cred.is_passphrase()
+        } else {
+            self.cred.is_complete()
+        }
+    }
 }
 
 impl DrawableComponent for CredComponent {
@@ -69,6 +159,7 @@ impl DrawableComponent for CredComponent {
         if self.visible {
             self.input_username.draw(f, rect)?;
             self.input_password.draw(f, rect)?;
+            self.input_passphrase.draw(f, rect)?;
         }
         Ok(())
     }
@@ -106,10 +197,23 @@ impl Component for CredComponent {
                 }
                 if self.input_username.event(ev)?
                     || self.input_password.event(ev)?
+                    || self.input_passphrase.event(ev)?
                 {
                     return Ok(true);
                 } else if e == self.key_config.enter {
-                    if self.input_username.is_visible() {
+                    if self.input_passphrase.is_visible() {
+                        self.cred = BasicAuthCredential::new(
+                            None,
+                            Some(
+                                self.input_passphrase
+                                    .get_text()
+                                    .to_owned(),
+                            ),
+                        );
+                        self.input_passphrase.hide();
+                        self.input_passphrase.clear();
+                        return Ok(false);
+                    } else if self.input_username.is_visible() {
                         self.cred = BasicAuthCredential::new(
                             Some(
                                 self.input_username
@@ -148,12 +252,15 @@ impl Component for CredComponent {
 
     fn hide(&mut self) {
         self.cred = BasicAuthCredential::new(None, None);
+        self.passphrase = false;
         self.visible = false;
     }
 
     fn show(&mut self) -> Result<()> {
         self.visible = true;
-        if self.cred.username.is_none() {
+        if self.passphrase {
+            self.input_passphrase.show()
+        } else if self.cred.username.is_none() {
             self.input_username.show()
         } else if self.cred.password.is_none() {
             self.input_password.show()
@@ -267,7 +267,7 @@ impl DiffComponent {
 
             try_or_popup!(
                 self,
-                "copy to clipboard error:",
+                strings::copy_to_clipboard_error(),
                 crate::clipboard::copy_string(
                     &lines_to_copy.join("\n")
                 )
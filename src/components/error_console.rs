@@ -0,0 +1,370 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent, ScrollType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    strings,
+    ui::{self, calc_scroll_top, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{take_background_errors, AsyncNotification};
+use chrono::{DateTime, Local};
+use crossterm::event::Event;
+use std::{cell::Cell, cmp, collections::VecDeque};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// entries kept, the oldest get dropped first
+const CONSOLE_CAPACITY: usize = 200;
+
+/// rows moved by page up/down
+const SCROLL_PAGE: usize = 10;
+
+/// how bad a console entry is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    ///
+    Warning,
+    ///
+    Error,
+}
+
+struct ConsoleEntry {
+    time: DateTime<Local>,
+    severity: Severity,
+    message: String,
+}
+
+/// recent recoverable problems (failing background jobs, invalid
+/// options..) collected without interrupting with a popup,
+/// newest first
+pub struct ErrorConsoleComponent {
+    entries: VecDeque<ConsoleEntry>,
+    /// entries added since the console was looked at
+    unread: usize,
+    visible: bool,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ErrorConsoleComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 60);
+            const MIN_SIZE: Size = Size::new(60, 10);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height_in_lines =
+                (area.height as usize).saturating_sub(2);
+
+            self.scroll_top.set(calc_scroll_top(
+                self.scroll_top.get(),
+                height_in_lines,
+                self.selection,
+            ));
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height_in_lines))
+                    .block(
+                        Block::default()
+                            .title(Span::styled(
+                                strings::error_console_title(
+                                    &self.key_config,
+                                    self.entries.len(),
+                                ),
+                                self.theme.title(true),
+                            ))
+                            .border_type(BorderType::Thick)
+                            .borders(Borders::ALL)
+                            .border_style(self.theme.block(true)),
+                    )
+                    .alignment(Alignment::Left),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for ErrorConsoleComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::error_console_clear(
+                    &self.key_config,
+                ),
+                !self.entries.is_empty(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup
+                    || e == self.key_config.open_error_console
+                {
+                    self.hide()
+                } else if e == self.key_config.error_console_clear {
+                    self.clear();
+                } else if e == self.key_config.move_down {
+                    self.move_selection(ScrollType::Down);
+                } else if e == self.key_config.move_up {
+                    self.move_selection(ScrollType::Up);
+                } else if e == self.key_config.page_down {
+                    self.move_selection(ScrollType::PageDown);
+                } else if e == self.key_config.page_up {
+                    self.move_selection(ScrollType::PageUp);
+                } else if e == self.key_config.home {
+                    self.move_selection(ScrollType::Home);
+                } else if e == self.key_config.end {
+                    self.move_selection(ScrollType::End);
+                }
+            }
+
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        self.unread = 0;
+        self.selection = 0;
+        self.scroll_top.set(0);
+
+        Ok(())
+    }
+}
+
+impl ErrorConsoleComponent {
+    ///
+    pub const fn new(
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            unread: 0,
+            visible: false,
+            selection: 0,
+            scroll_top: Cell::new(0),
+            theme,
+            key_config,
+        }
+    }
+
+    /// adds `message` at the top, unread unless the console is open
+    pub fn push(&mut self, severity: Severity, message: &str) {
+        log::warn!("console: {}", message);
+
+        self.entries.push_front(ConsoleEntry {
+            time: Local::now(),
+            severity,
+            message: message.to_string(),
+        });
+        self.entries.truncate(CONSOLE_CAPACITY);
+
+        if self.visible {
+            // stay on the entry that was selected
+            if self.selection > 0 {
+                self.selection = cmp::min(
+                    self.selection + 1,
+                    self.entries.len() - 1,
+                );
+            }
+        } else {
+            self.unread =
+                cmp::min(self.unread + 1, self.entries.len());
+        }
+    }
+
+    /// entries added since the console was opened the last time
+    pub const fn unread(&self) -> usize {
+        self.unread
+    }
+
+    /// picks up the errors background jobs reported
+    pub fn update_git(&mut self, ev: AsyncNotification) {
+        if ev == AsyncNotification::BackgroundError {
+            for error in take_background_errors() {
+                self.push(
+                    Severity::Error,
+                    &format!("{}: {}", error.job, error.message),
+                );
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.unread = 0;
+        self.selection = 0;
+        self.scroll_top.set(0);
+    }
+
+    fn move_selection(&mut self, scroll: ScrollType) {
+        let max = self.entries.len().saturating_sub(1);
+
+        let new_selection = match scroll {
+            ScrollType::Up => self.selection.saturating_sub(1),
+            ScrollType::Down => self.selection.saturating_add(1),
+            ScrollType::PageUp => {
+                self.selection.saturating_sub(SCROLL_PAGE)
+            }
+            ScrollType::PageDown => {
+                self.selection.saturating_add(SCROLL_PAGE)
+            }
+            ScrollType::Home => 0,
+            ScrollType::End => max,
+        };
+
+        self.selection = cmp::min(new_selection, max);
+    }
+
+    fn get_text(&self, height: usize) -> Text {
+        if self.entries.is_empty() {
+            return Text::from(strings::error_console_empty());
+        }
+
+        let txt = self
+            .entries
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(i, entry)| {
+                let selected = i == self.selection;
+                let severity_style = match entry.severity {
+                    Severity::Error => self.theme.text_danger(),
+                    Severity::Warning => {
+                        self.theme.text(true, selected)
+                    }
+                };
+
+                Spans::from(vec![
+                    Span::styled(
+                        format!("{} ", entry.time.format("%H:%M:%S")),
+                        self.theme.commit_time(selected),
+                    ),
+                    Span::styled(
+                        strings::error_console_severity(
+                            entry.severity,
+                        ),
+                        severity_style,
+                    ),
+                    Span::styled(
+                        // one line per entry
+                        entry.message.replace('\n', " "),
+                        self.theme.text(true, selected),
+                    ),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        Text::from(txt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(console: &ErrorConsoleComponent) -> Vec<&str> {
+        console
+            .entries
+            .iter()
+            .map(|entry| entry.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_push_unread_and_clear() {
+        let mut console = ErrorConsoleComponent::new(
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+
+        for i in 0..CONSOLE_CAPACITY + 5 {
+            console.push(Severity::Error, &i.to_string());
+        }
+        assert_eq!(console.entries.len(), CONSOLE_CAPACITY);
+        assert_eq!(console.unread(), CONSOLE_CAPACITY);
+        assert_eq!(
+            messages(&console)[0],
+            (CONSOLE_CAPACITY + 4).to_string()
+        );
+
+        console.show().unwrap();
+        assert_eq!(console.unread(), 0);
+
+        // the selected entry stays selected while the console is open
+        console.move_selection(ScrollType::Down);
+        console.push(Severity::Warning, "new");
+        assert_eq!(console.unread(), 0);
+        assert_eq!(
+            console.entries[console.selection].message,
+            (CONSOLE_CAPACITY + 3).to_string()
+        );
+
+        console.hide();
+        console.push(Severity::Warning, "unseen");
+        assert_eq!(console.unread(), 1);
+
+        console.clear();
+        assert_eq!(messages(&console), Vec::<&str>::new());
+        assert_eq!(console.unread(), 0);
+    }
+}
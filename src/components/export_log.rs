@@ -0,0 +1,182 @@
+use super::{
+    textinput::{InputType, TextInputComponent},
+    utils::log_export::{
+        export_commit_list, DEFAULT_EXPORT_TEMPLATE,
+    },
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::sync::CommitId;
+use crossterm::event::Event;
+use std::{path::Path, sync::Arc};
+use tui::{backend::Backend, layout::Rect, Frame};
+
+/// file commits are exported to unless another one is typed
+const DEFAULT_EXPORT_FILE: &str = "commits.txt";
+
+/// asks for the file to write the commits of the log (or the
+/// filter results) to
+pub struct ExportLogComponent {
+    input: TextInputComponent,
+    repo_path: Arc<str>,
+    commits: Vec<CommitId>,
+    template: String,
+    queue: Queue,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for ExportLogComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        self.input.draw(f, rect)?;
+
+        Ok(())
+    }
+}
+
+impl Component for ExportLogComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            self.input.commands(out, force_all);
+
+            out.push(CommandInfo::new(
+                strings::commands::export_log_confirm(
+                    &self.key_config,
+                ),
+                !self.input.get_text().trim().is_empty(),
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.is_visible() {
+            if self.input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter {
+                    self.export();
+                }
+
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.input.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.input.hide()
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.input.show()?;
+
+        Ok(())
+    }
+}
+
+impl ExportLogComponent {
+    ///
+    pub fn new(
+        repo_path: Arc<str>,
+        queue: Queue,
+        template: Option<String>,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            queue,
+            input: TextInputComponent::new(
+                theme,
+                key_config.clone(),
+                "",
+                &strings::export_log_popup_msg(&key_config),
+            )
+            .with_input_type(InputType::Singleline),
+            repo_path,
+            commits: Vec::new(),
+            template: template.unwrap_or_else(|| {
+                DEFAULT_EXPORT_TEMPLATE.to_string()
+            }),
+            key_config,
+        }
+    }
+
+    /// asks where to write `commits` (the results of a filter if
+    /// `filtered`), keeps the path typed the last time
+    pub fn open(
+        &mut self,
+        commits: Vec<CommitId>,
+        filtered: bool,
+    ) -> Result<()> {
+        self.input.set_title(strings::export_log_popup_title(
+            &self.key_config,
+            commits.len(),
+            filtered,
+        ));
+        if self.input.get_text().trim().is_empty() {
+            self.input.set_text(DEFAULT_EXPORT_FILE.to_string());
+        }
+        self.commits = commits;
+        self.show()?;
+
+        Ok(())
+    }
+
+    fn export(&mut self) {
+        let path = self.input.get_text().trim().to_string();
+        if path.is_empty() {
+            return;
+        }
+
+        let event = match export_commit_list(
+            &self.repo_path,
+            &self.commits,
+            &self.template,
+            Path::new(&path),
+        ) {
+            Ok(()) => {
+                InternalEvent::ShowInfoMsg(strings::log_exported(
+                    &self.key_config,
+                    self.commits.len(),
+                    &path,
+                ))
+            }
+            Err(e) => {
+                log::error!("export log: {}", e);
+                InternalEvent::ShowErrorMsg(
+                    strings::export_log_error_msg(
+                        &self.key_config,
+                        &e.to_string(),
+                    ),
+                )
+            }
+        };
+
+        self.queue.borrow_mut().push_back(event);
+        self.commits.clear();
+        self.hide();
+    }
+}
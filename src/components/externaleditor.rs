@@ -1,5 +1,6 @@
 use crate::{
     components::{
+        utils::difftool::render_difftool_command,
         visibility_blocking, CommandBlocking, CommandInfo, Component,
         DrawableComponent,
     },
@@ -8,7 +9,13 @@ use crate::{
     ui::{self, style::SharedTheme},
 };
 use anyhow::{anyhow, bail, Result};
-use asyncgit::{sync::utils::repo_work_dir, CWD};
+use asyncgit::{
+    sync::{
+        self, utils::repo_work_dir, CommitId, MessageCleanup,
+        MessageConfig,
+    },
+    CWD,
+};
 use crossterm::{
     event::Event,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen},
@@ -16,7 +23,7 @@ use crossterm::{
 };
 use scopeguard::defer;
 use std::ffi::OsStr;
-use std::{env, io, path::Path, process::Command};
+use std::{env, fs, io, path::Path, process::Command};
 use tui::{
     backend::Backend,
     layout::Rect,
@@ -25,6 +32,18 @@ use tui::{
     Frame,
 };
 
+/// what gets the ui out of the way until it is back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Suspend {
+    /// a shell in the repository, until it exits
+    Shell,
+    /// stops gitui like ctrl+z does in other programs, until `fg`
+    Process,
+}
+
+/// what a root commit is compared to in the difftool
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
 ///
 pub struct ExternalEditorComponent {
     visible: bool,
@@ -59,40 +78,232 @@ impl ExternalEditorComponent {
             bail!("file not found: {:?}", path);
         }
 
-        io::stdout().execute(LeaveAlternateScreen)?;
+        run_editor(&path, &work_dir, sync::get_core_editor(CWD)?)
+    }
+
+    /// lets a message be written in the editor, starting out with
+    /// `message` and the `comments` (given the comment char) below
+    /// it, in the file `file_name` of the git dir (like
+    /// `COMMIT_EDITMSG`). the result is cleaned up as
+    /// `commit.cleanup` asks for, `None` if nothing is left of it
+    pub fn edit_message(
+        config: &MessageConfig,
+        file_name: &str,
+        message: &str,
+        comments: impl Fn(char) -> String,
+    ) -> Result<Option<String>> {
+        let comments = comments(config.comment_char);
+        let work_dir = repo_work_dir(CWD)?;
+        let path = config.git_dir.join(file_name);
+
+        let comment = |line: &str| {
+            if line.is_empty() {
+                format!("{}\n", config.comment_char)
+            } else {
+                format!("{} {}\n", config.comment_char, line)
+            }
+        };
+        // the other modes keep comments, so there are none
+        let comments: String = match config.cleanup {
+            MessageCleanup::Strip => {
+                comments.lines().map(comment).collect()
+            }
+            MessageCleanup::Scissors => {
+                format!(
+                    "{}\n",
+                    sync::scissors_line(config.comment_char)
+                ) + &comments.lines().map(comment).collect::<String>()
+            }
+            MessageCleanup::Whitespace | MessageCleanup::Verbatim => {
+                String::new()
+            }
+        };
+
+        fs::write(
+            &path,
+            format!("{}\n\n{}", message.trim_end(), comments),
+        )?;
         defer! {
-            io::stdout().execute(EnterAlternateScreen).expect("reset terminal");
+            fs::remove_file(&path).ok();
         }
 
-        let editor = env::var("GIT_EDITOR")
-            .ok()
-            .or_else(|| env::var("VISUAL").ok())
-            .or_else(|| env::var("EDITOR").ok())
-            .unwrap_or_else(|| String::from("vi"));
+        run_editor(&path, &work_dir, config.editor.clone())?;
 
-        // TODO: proper handling arguments containing whitespaces
-        // This does not do the right thing if the input is `editor --something "with spaces"`
-        let mut editor = editor.split_whitespace();
+        let message = sync::cleanup_message(
+            &fs::read_to_string(&path)?,
+            config.cleanup,
+            config.comment_char,
+        );
 
-        let command = editor.next().ok_or_else(|| {
-            anyhow!("unable to read editor command")
-        })?;
+        Ok((!message.is_empty()).then_some(message))
+    }
 
-        let mut editor: Vec<&OsStr> =
-            editor.map(|s| OsStr::new(s)).collect();
+    /// compares commit `new` to `old` (its first parent if `None`)
+    /// with `command` (see `render_difftool_command`), or with
+    /// `git difftool` if there is none, run in the work dir
+    pub fn open_commit_in_difftool(
+        repo_path: &str,
+        old: Option<CommitId>,
+        new: CommitId,
+        command: Option<&str>,
+    ) -> Result<()> {
+        if command.is_none()
+            && sync::get_difftool(repo_path)?.is_none()
+        {
+            bail!(strings::difftool_not_configured());
+        }
+
+        let old = match old {
+            Some(old) => old.to_string(),
+            None => sync::get_commit_details(repo_path, new)?
+                .parents
+                .first()
+                .map_or_else(
+                    || EMPTY_TREE.to_string(),
+                    CommitId::to_string,
+                ),
+        };
+        let new = new.to_string();
 
-        editor.push(path.as_os_str());
+        let args = match command {
+            Some(command) => {
+                render_difftool_command(command, &old, &new)?
+            }
+            None => ["git", "difftool", "--no-prompt", &old, &new]
+                .iter()
+                .map(|arg| (*arg).to_string())
+                .collect(),
+        };
+
+        // tools like `difft` print to the terminal, which has to
+        // be out of raw mode for that
+        crate::shutdown_terminal()?;
+        defer! {
+            crate::setup_terminal().expect("reset terminal");
+        }
 
-        Command::new(command)
-            .current_dir(work_dir)
-            .args(editor)
+        // the log of a bare repo works without one
+        let dir = repo_work_dir(repo_path)
+            .unwrap_or_else(|_| repo_path.to_string());
+
+        let program = &args[0];
+        let status = Command::new(program)
+            .current_dir(dir)
+            .args(&args[1..])
             .status()
-            .map_err(|e| anyhow!("\"{}\": {}", command, e))?;
+            .map_err(|e| {
+                if e.kind() == io::ErrorKind::NotFound {
+                    anyhow!(strings::difftool_not_found(program))
+                } else {
+                    anyhow!("\"{}\": {}", program, e)
+                }
+            })?;
+
+        // what a tool printed would be gone right away
+        println!("{}", strings::difftool_wait_hint());
+        io::stdin().read_line(&mut String::new())?;
+
+        if !status.success() {
+            bail!("\"{}\" failed: {}", program, status);
+        }
 
         Ok(())
     }
 }
 
+/// the editor git would start: `$GIT_EDITOR`, `core_editor`,
+/// `$VISUAL`, `$EDITOR` or `vi`, whichever is set first
+fn editor_command(core_editor: Option<String>) -> String {
+    env::var("GIT_EDITOR")
+        .ok()
+        .or(core_editor)
+        .or_else(|| env::var("VISUAL").ok())
+        .or_else(|| env::var("EDITOR").ok())
+        .unwrap_or_else(|| String::from("vi"))
+}
+
+/// edits `path` in the editor, outside of the alternate screen
+fn run_editor(
+    path: &Path,
+    work_dir: &str,
+    core_editor: Option<String>,
+) -> Result<()> {
+    io::stdout().execute(LeaveAlternateScreen)?;
+    defer! {
+        io::stdout().execute(EnterAlternateScreen).expect("reset terminal");
+    }
+
+    let editor = editor_command(core_editor);
+
+    // TODO: proper handling arguments containing whitespaces
+    // This does not do the right thing if the input is `editor --something "with spaces"`
+    let mut editor = editor.split_whitespace();
+
+    let command = editor
+        .next()
+        .ok_or_else(|| anyhow!("unable to read editor command"))?;
+
+    let mut editor: Vec<&OsStr> = editor.map(OsStr::new).collect();
+
+    editor.push(path.as_os_str());
+
+    Command::new(command)
+        .current_dir(work_dir)
+        .args(editor)
+        .status()
+        .map_err(|e| anyhow!("\"{}\": {}", command, e))?;
+
+    Ok(())
+}
+
+impl Suspend {
+    /// restores the terminal for the shell gitui runs in until
+    /// it is back, even if that fails
+    pub fn run(self) -> Result<()> {
+        crate::shutdown_terminal()?;
+        defer! {
+            crate::setup_terminal().expect("reset terminal");
+        }
+
+        match self {
+            Self::Shell => open_shell(),
+            Self::Process => stop_process(),
+        }
+    }
+}
+
+/// `$SHELL` (the `COMSPEC` one on windows) in the work dir
+fn open_shell() -> Result<()> {
+    let dir = repo_work_dir(CWD).unwrap_or_else(|_| CWD.to_string());
+    let shell = if cfg!(windows) {
+        env::var("COMSPEC").unwrap_or_else(|_| String::from("cmd"))
+    } else {
+        env::var("SHELL").unwrap_or_else(|_| String::from("sh"))
+    };
+
+    println!("{}", strings::shell_hint());
+
+    Command::new(&shell)
+        .current_dir(dir)
+        .status()
+        .map_err(|e| anyhow!("\"{}\": {}", shell, e))?;
+
+    Ok(())
+}
+
+/// returns once the process got continued (`fg`)
+#[cfg(unix)]
+fn stop_process() -> Result<()> {
+    // `SIGTSTP` is caught (see `main`) while `SIGSTOP` can not be
+    signal_hook::low_level::raise(signal_hook::consts::SIGSTOP)
+        .map_err(|e| anyhow!("sending SIGSTOP failed: {}", e))
+}
+
+#[cfg(not(unix))]
+fn stop_process() -> Result<()> {
+    bail!("suspending is only supported on unix")
+}
+
 impl DrawableComponent for ExternalEditorComponent {
     fn draw<B: Backend>(
         &self,
@@ -0,0 +1,556 @@
+use super::{
+    textinput::{InputType, TextInputComponent},
+    utils::log_filter::{
+        filter_breadcrumb, filter_fields_to_string, parse_filter,
+        split_line_range, split_scan_limit, MAX_FILTER_LEN,
+    },
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    options::SharedOptions,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::{self, style::SharedTheme},
+};
+use anyhow::Result;
+use asyncgit::sync::FilterBy;
+use crossterm::event::Event;
+use std::{
+    convert::TryFrom,
+    time::{Duration, Instant},
+};
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    widgets::{Clear, Paragraph, Wrap},
+    Frame,
+};
+
+/// idle time after the last edit before a live filter starts
+const LIVE_FILTER_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// size of the single line input popup
+const INPUT_WIDTH: u16 = 32;
+const INPUT_HEIGHT: u16 = 3;
+
+/// lines the breadcrumb of the query may wrap onto
+const BREADCRUMB_HEIGHT: u16 = 3;
+
+/// progress of the filter applied while typing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LiveFilter {
+    /// nothing applied since the popup opened
+    Idle,
+    /// input changed at this instant, waiting for the debounce
+    Pending(Instant),
+    /// applied, the log is still being filtered
+    Running,
+    /// applied and done
+    Applied,
+}
+
+/// where the filter applied to the log is at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterStatus {
+    /// still walking the log
+    Running { matches: usize, scanned: usize },
+    /// walked the whole log
+    Done { matches: usize },
+    /// the walk stopped with this error
+    Failed(String),
+}
+
+pub struct FindCommitComponent {
+    input: TextInputComponent,
+    search: bool,
+    live: bool,
+    live_filter: LiveFilter,
+    status: Option<FilterStatus>,
+    /// what is wrong with the query typed so far
+    syntax_error: Option<String>,
+    /// the query typed so far as parsed, see `filter_breadcrumb`
+    breadcrumb: Option<String>,
+    show_breadcrumb: bool,
+    /// what terms without fields search
+    everywhere: FilterBy,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for FindCommitComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        self.input.draw(f, rect)?;
+
+        if self.is_visible() {
+            let line = match &self.syntax_error {
+                Some(error) => Some((
+                    strings::filter_syntax_error(
+                        &self.key_config,
+                        error,
+                    ),
+                    self.theme.text_danger(),
+                )),
+                None if self.search => None,
+                None => {
+                    self.status.as_ref().map(|status| match status {
+                        FilterStatus::Running {
+                            matches,
+                            scanned,
+                        } => (
+                            strings::filter_status_running(
+                                &self.key_config,
+                                *matches,
+                                *scanned,
+                            ),
+                            self.theme.text(true, false),
+                        ),
+                        FilterStatus::Done { matches } => (
+                            strings::filter_status_done(
+                                &self.key_config,
+                                *matches,
+                            ),
+                            self.theme.text(true, false),
+                        ),
+                        FilterStatus::Failed(error) => (
+                            strings::filter_status_failed(
+                                &self.key_config,
+                                error,
+                            ),
+                            self.theme.text_danger(),
+                        ),
+                    })
+                }
+            };
+
+            // the lines right below the input popup
+            let input = ui::centered_rect_absolute(
+                INPUT_WIDTH,
+                INPUT_HEIGHT,
+                f.size(),
+            );
+            let mut y = input.bottom();
+
+            if let Some((text, style)) = line {
+                let area = Rect::new(input.x, y, input.width, 1)
+                    .intersection(f.size());
+                y = y.saturating_add(1);
+
+                f.render_widget(Clear, area);
+                f.render_widget(
+                    Paragraph::new(text).style(style),
+                    area,
+                );
+            }
+
+            if let Some(breadcrumb) = &self.breadcrumb {
+                let width = usize::from(input.width.max(1));
+                let lines = u16::try_from(
+                    breadcrumb.chars().count().div_ceil(width),
+                )
+                .unwrap_or(BREADCRUMB_HEIGHT);
+                let area = Rect::new(
+                    input.x,
+                    y,
+                    input.width,
+                    lines.min(BREADCRUMB_HEIGHT),
+                )
+                .intersection(f.size());
+
+                f.render_widget(Clear, area);
+                f.render_widget(
+                    Paragraph::new(breadcrumb.as_str())
+                        .style(self.theme.text(false, false))
+                        .wrap(Wrap { trim: true }),
+                    area,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for FindCommitComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            self.input.commands(out, force_all);
+
+            out.push(CommandInfo::new(
+                if self.search {
+                    strings::commands::search_commit_confirm_msg(
+                        &self.key_config,
+                    )
+                } else {
+                    strings::commands::find_commit_confirm_msg(
+                        &self.key_config,
+                    )
+                },
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.is_visible() {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    if matches!(
+                        self.live_filter,
+                        LiveFilter::Running | LiveFilter::Applied
+                    ) {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::FilterLog(String::new()),
+                        );
+                    }
+                    self.set_live_filter(LiveFilter::Idle);
+                }
+            }
+
+            let text = self.input.get_text().clone();
+            if self.input.event(ev)? {
+                if *self.input.get_text() != text {
+                    self.check_syntax();
+                    if self.is_live() {
+                        self.set_live_filter(LiveFilter::Pending(
+                            Instant::now(),
+                        ));
+                    }
+                }
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter {
+                    self.set_live_filter(LiveFilter::Idle);
+                    let text = self.input.get_text().clone();
+                    self.queue.borrow_mut().push_back(
+                        if self.search {
+                            InternalEvent::SearchLog(text)
+                        } else {
+                            InternalEvent::FilterLog(text)
+                        },
+                    );
+                    self.hide();
+                }
+
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.input.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.input.hide()
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.input.show()?;
+
+        Ok(())
+    }
+}
+
+impl FindCommitComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        options: &SharedOptions,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        let everywhere = options
+            .filter_everywhere()
+            .unwrap_or(FilterBy::EVERYWHERE);
+
+        Self {
+            queue,
+            search: false,
+            live: options.find.live,
+            live_filter: LiveFilter::Idle,
+            status: None,
+            syntax_error: None,
+            breadcrumb: None,
+            show_breadcrumb: options.find.breadcrumb,
+            everywhere,
+            input: TextInputComponent::new(
+                theme.clone(),
+                key_config.clone(),
+                &strings::find_commit_popup_title(&key_config),
+                &strings::find_commit_popup_msg(
+                    &key_config,
+                    &filter_fields_to_string(everywhere),
+                ),
+            )
+            .with_input_type(InputType::Singleline)
+            .with_max_len(MAX_FILTER_LEN),
+            theme,
+            key_config,
+        }
+    }
+
+    /// shows the popup to filter the log or, with `search`,
+    /// to jump between matching commits
+    pub fn open(&mut self, search: bool) -> Result<()> {
+        self.search = search;
+        self.set_live_filter(LiveFilter::Idle);
+        self.check_syntax();
+
+        self.show()
+    }
+
+    /// starts the live filter once the input was left alone long
+    /// enough, `true` if it did
+    pub fn update_live(&mut self) -> bool {
+        if let LiveFilter::Pending(changed) = self.live_filter {
+            if changed.elapsed() >= LIVE_FILTER_DEBOUNCE {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::FilterLog(
+                        self.input.get_text().clone(),
+                    ),
+                );
+                self.set_live_filter(LiveFilter::Running);
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// progress of the filter on the log, `None` if the log is
+    /// not filtered
+    pub fn set_filter_status(
+        &mut self,
+        status: Option<FilterStatus>,
+    ) {
+        let running =
+            matches!(status, Some(FilterStatus::Running { .. }));
+        if self.live_filter == LiveFilter::Running && !running {
+            self.set_live_filter(LiveFilter::Applied);
+        }
+
+        self.status = status;
+    }
+
+    ///
+    pub const fn any_work_pending(&self) -> bool {
+        matches!(self.live_filter, LiveFilter::Pending(_))
+    }
+
+    /// looks for syntax errors in the query typed so far, an
+    /// empty one is fine
+    fn check_syntax(&mut self) {
+        let (_, query) = split_scan_limit(self.input.get_text());
+        let (_, query) = split_line_range(query);
+
+        self.syntax_error = None;
+        self.breadcrumb = None;
+
+        if !query.trim().is_empty() {
+            match parse_filter(query, self.everywhere) {
+                Ok(query) => {
+                    self.breadcrumb = self
+                        .show_breadcrumb
+                        .then(|| filter_breadcrumb(&query));
+                }
+                Err(e) => self.syntax_error = Some(e.to_string()),
+            }
+        }
+    }
+
+    const fn is_live(&self) -> bool {
+        self.live && !self.search
+    }
+
+    fn set_live_filter(&mut self, live_filter: LiveFilter) {
+        self.live_filter = live_filter;
+        self.input.set_title(match live_filter {
+            _ if self.search => {
+                strings::search_commit_popup_title(&self.key_config)
+            }
+            LiveFilter::Pending(_) => {
+                strings::find_commit_popup_title_pending(
+                    &self.key_config,
+                )
+            }
+            LiveFilter::Running => {
+                strings::find_commit_popup_title_running(
+                    &self.key_config,
+                )
+            }
+            LiveFilter::Idle | LiveFilter::Applied => {
+                strings::find_commit_popup_title(&self.key_config)
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{FilterOptions, FindOptions, Options};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::thread;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::empty()))
+    }
+
+    fn filters(queue: &Queue) -> Vec<String> {
+        queue
+            .borrow_mut()
+            .drain(..)
+            .filter_map(|ev| match ev {
+                InternalEvent::FilterLog(filter) => Some(filter),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_live_filter_debounce() {
+        let queue = Queue::default();
+        let mut find = FindCommitComponent::new(
+            queue.clone(),
+            &SharedOptions::new(Options {
+                find: FindOptions {
+                    live: true,
+                    ..FindOptions::default()
+                },
+                ..Options::default()
+            }),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        find.open(false).unwrap();
+
+        find.event(key(KeyCode::Char('a'))).unwrap();
+        find.event(key(KeyCode::Char('b'))).unwrap();
+        assert!(find.any_work_pending());
+        assert!(!find.update_live());
+        assert!(filters(&queue).is_empty());
+
+        thread::sleep(LIVE_FILTER_DEBOUNCE);
+        assert!(find.update_live());
+        assert!(!find.any_work_pending());
+        assert_eq!(filters(&queue), vec![String::from("ab")]);
+
+        // moving the cursor does not re-filter
+        find.event(key(KeyCode::Left)).unwrap();
+        assert!(!find.any_work_pending());
+
+        find.set_filter_status(Some(FilterStatus::Running {
+            matches: 1,
+            scanned: 10,
+        }));
+        assert_eq!(find.live_filter, LiveFilter::Running);
+        find.set_filter_status(Some(FilterStatus::Done {
+            matches: 1,
+        }));
+        assert_eq!(find.live_filter, LiveFilter::Applied);
+
+        // escape clears what was applied while typing
+        find.event(key(KeyCode::Esc)).unwrap();
+        assert!(!find.is_visible());
+        assert_eq!(filters(&queue), vec![String::new()]);
+    }
+
+    #[test]
+    fn test_filter_status_text() {
+        let key_config = SharedKeyConfig::default();
+
+        assert_eq!(
+            strings::filter_status_running(&key_config, 87, 45210),
+            "87 matches of 45,210 scanned"
+        );
+        assert_eq!(
+            strings::filter_status_running(&key_config, 0, 999),
+            "0 matches of 999 scanned"
+        );
+        assert_eq!(
+            strings::filter_status_done(&key_config, 1_234_567),
+            "1,234,567 matches"
+        );
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        let mut find = FindCommitComponent::new(
+            Queue::default(),
+            &SharedOptions::new(Options::default()),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        find.open(false).unwrap();
+
+        for c in ":top 5 (a".chars() {
+            find.event(key(KeyCode::Char(c))).unwrap();
+        }
+        assert_eq!(
+            find.syntax_error.as_deref(),
+            Some("`(` without `)`")
+        );
+
+        find.event(key(KeyCode::Char(')'))).unwrap();
+        assert_eq!(find.syntax_error, None);
+    }
+
+    #[test]
+    fn test_breadcrumb() {
+        let open = |breadcrumb| {
+            let mut find = FindCommitComponent::new(
+                Queue::default(),
+                &SharedOptions::new(Options {
+                    find: FindOptions {
+                        breadcrumb,
+                        ..FindOptions::default()
+                    },
+                    filter: FilterOptions {
+                        default_fields: vec![String::from("author")],
+                        ..FilterOptions::default()
+                    },
+                    ..Options::default()
+                }),
+                SharedTheme::default(),
+                SharedKeyConfig::default(),
+            );
+            find.open(false).unwrap();
+            find
+        };
+
+        let mut find = open(true);
+        for c in ":top 5 foo || :m".chars() {
+            find.event(key(KeyCode::Char(c))).unwrap();
+        }
+        assert_eq!(find.breadcrumb, None);
+
+        for c in " bar".chars() {
+            find.event(key(KeyCode::Char(c))).unwrap();
+        }
+        assert_eq!(
+            find.breadcrumb.as_deref(),
+            Some("(author:foo) OR (msg:bar)")
+        );
+
+        let mut find = open(false);
+        find.event(key(KeyCode::Char('a'))).unwrap();
+        assert_eq!(find.breadcrumb, None);
+    }
+}
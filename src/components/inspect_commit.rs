@@ -179,6 +179,17 @@ impl InspectCommitComponent {
         }
     }
 
+    /// highlight message lines longer than these, 0 for no limit
+    pub fn with_message_limits(
+        mut self,
+        subject: usize,
+        body: usize,
+    ) -> Self {
+        self.details =
+            self.details.with_message_limits(subject, body);
+        self
+    }
+
     ///
     pub fn open(
         &mut self,
@@ -1,45 +1,75 @@
+mod author_stats;
 mod changes;
+mod cherry_pick;
 mod command;
 mod commit;
 mod commit_details;
 mod commitlist;
+mod compare_commits;
 mod create_branch;
 mod cred;
 mod diff;
+mod error_console;
+mod export_log;
 mod externaleditor;
 mod filetree;
+mod find_commit;
 mod help;
 mod inspect_commit;
 mod msg;
 mod push;
+mod push_url;
+mod reflog;
 mod rename_branch;
 mod reset;
 mod select_branch;
 mod stashmsg;
 mod tag_commit;
 mod textinput;
+mod undo;
 mod utils;
+mod worktrees;
 
+pub use author_stats::AuthorStatsComponent;
 pub use changes::ChangesComponent;
+pub use cherry_pick::CherryPickComponent;
 pub use command::{CommandInfo, CommandText};
 pub use commit::CommitComponent;
 pub use commit_details::CommitDetailsComponent;
-pub use commitlist::CommitList;
+pub use commitlist::{
+    CommitList, LogSelection, WalkModes, DEFAULT_ELLIPSIS,
+};
+pub use compare_commits::CompareCommitsComponent;
 pub use create_branch::CreateBranchComponent;
+pub use cred::{CredComponent, CredState};
 pub use diff::DiffComponent;
-pub use externaleditor::ExternalEditorComponent;
+pub use error_console::{ErrorConsoleComponent, Severity};
+pub use export_log::ExportLogComponent;
+pub use externaleditor::{ExternalEditorComponent, Suspend};
 pub use filetree::FileTreeComponent;
+pub use find_commit::{FilterStatus, FindCommitComponent};
 pub use help::HelpComponent;
 pub use inspect_commit::InspectCommitComponent;
 pub use msg::MsgComponent;
 pub use push::PushComponent;
+pub use push_url::PushUrlComponent;
+pub use reflog::ReflogComponent;
 pub use rename_branch::RenameBranchComponent;
 pub use reset::ResetComponent;
 pub use select_branch::SelectBranchComponent;
 pub use stashmsg::StashMsgComponent;
 pub use tag_commit::TagCommitComponent;
 pub use textinput::{InputType, TextInputComponent};
+pub use undo::UndoComponent;
+pub use utils::commit_json::commit_to_json;
 pub use utils::filetree::FileTreeItemKind;
+pub use utils::log_filter::{
+    get_what_to_filter_by, parse_filter_fields, split_line_range,
+    split_scan_limit,
+};
+pub use utils::log_window::{next_log_window, parse_log_window};
+pub use utils::rebase_todo::commits_to_rebase_todo;
+pub use worktrees::WorktreesComponent;
 
 use crate::ui::style::Theme;
 use anyhow::Result;
@@ -58,16 +88,16 @@ use tui::{
 /// we always enumerate all components in both getter functions
 #[macro_export]
 macro_rules! accessors {
-    ($self:ident, [$($element:ident),+]) => {
+    ($self:ident, [$($($element:ident).+),+]) => {
         fn components(& $self) -> Vec<&dyn Component> {
             vec![
-                $(&$self.$element,)+
+                $(&$self.$($element).+,)+
             ]
         }
 
         fn components_mut(&mut $self) -> Vec<&mut dyn Component> {
             vec![
-                $(&mut $self.$element,)+
+                $(&mut $self.$($element).+,)+
             ]
         }
     };
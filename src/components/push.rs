@@ -4,19 +4,21 @@ use crate::{
         CommandInfo, Component, DrawableComponent,
     },
     keys::SharedKeyConfig,
-    queue::{InternalEvent, Queue},
+    queue::{Action, InternalEvent, Queue},
     strings,
     ui::{self, style::SharedTheme},
 };
 use anyhow::Result;
 use asyncgit::{
     sync::cred::{
-        extract_username_password, need_username_password,
-        BasicAuthCredential,
+        extract_ssh_passphrase_for_push,
+        extract_username_password_for_push,
+        need_ssh_passphrase_for_push,
+        need_username_password_for_push, BasicAuthCredential,
     },
-    sync::DEFAULT_REMOTE_NAME,
+    sync::{self, RemoteError, DEFAULT_REMOTE_NAME},
     AsyncNotification, AsyncPush, PushProgress, PushProgressState,
-    PushRequest,
+    PushRequest, RemoteOp, RemoteOps, CWD,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
@@ -29,6 +31,17 @@ use tui::{
     Frame,
 };
 
+/// what the popup pushes
+#[derive(PartialEq)]
+enum PushKind {
+    Branch,
+    /// the tag pushed right after creating it
+    Tag(String),
+    /// only previews pushing the branch, confirming it pushes
+    /// for real
+    DryRun,
+}
+
 ///
 pub struct PushComponent {
     visible: bool,
@@ -36,6 +49,8 @@ pub struct PushComponent {
     progress: Option<PushProgress>,
     pending: bool,
     branch: String,
+    kind: PushKind,
+    run_pre_push_hook: bool,
     queue: Queue,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
@@ -47,15 +62,19 @@ impl PushComponent {
     pub fn new(
         queue: &Queue,
         sender: &Sender<AsyncNotification>,
+        remote_ops: &RemoteOps,
+        run_pre_push_hook: bool,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
     ) -> Self {
         Self {
             queue: queue.clone(),
+            run_pre_push_hook,
             pending: false,
             visible: false,
             branch: String::new(),
-            git_push: AsyncPush::new(sender),
+            kind: PushKind::Branch,
+            git_push: AsyncPush::new(sender, remote_ops),
             progress: None,
             input_cred: CredComponent::new(
                 theme.clone(),
@@ -69,18 +88,52 @@ impl PushComponent {
     ///
     pub fn push(&mut self, branch: String) -> Result<()> {
         self.branch = branch;
+        self.kind = PushKind::Branch;
+        self.start()
+    }
+
+    /// shows what pushing `branch` would do and asks to confirm
+    /// the push unless the remote would reject it
+    pub fn push_dry_run(&mut self, branch: String) -> Result<()> {
+        self.branch = branch;
+        self.kind = PushKind::DryRun;
+        self.start()
+    }
+
+    /// pushes the just created tag `tag` alone, reporting both
+    /// its creation and the push
+    pub fn push_tag(&mut self, tag: String) -> Result<()> {
+        self.branch = format!("refs/tags/{}", tag);
+        self.kind = PushKind::Tag(tag);
+        self.start()
+    }
+
+    fn start(&mut self) -> Result<()> {
         self.show()?;
-        if need_username_password(DEFAULT_REMOTE_NAME)? {
-            let cred = extract_username_password(DEFAULT_REMOTE_NAME)
-                .unwrap_or_else(|_| {
-                    BasicAuthCredential::new(None, None)
-                });
+        if need_username_password_for_push(CWD, DEFAULT_REMOTE_NAME)?
+        {
+            let cred = extract_username_password_for_push(
+                CWD,
+                DEFAULT_REMOTE_NAME,
+            )
+            .unwrap_or_else(|_| BasicAuthCredential::new(None, None));
             if cred.is_complete() {
                 self.push_to_remote(Some(cred))
             } else {
                 self.input_cred.set_cred(cred);
                 self.input_cred.show()
             }
+        } else if need_ssh_passphrase_for_push(
+            CWD,
+            DEFAULT_REMOTE_NAME,
+        )? {
+            match extract_ssh_passphrase_for_push(
+                CWD,
+                DEFAULT_REMOTE_NAME,
+            )? {
+                Some(cred) => self.push_to_remote(Some(cred)),
+                None => self.input_cred.show_passphrase(),
+            }
         } else {
             self.push_to_remote(None)
         }
@@ -97,6 +150,8 @@ impl PushComponent {
             remote: String::from(DEFAULT_REMOTE_NAME),
             branch: self.branch.clone(),
             basic_credential: cred,
+            run_pre_push_hook: self.run_pre_push_hook,
+            dry_run: self.kind == PushKind::DryRun,
         })?;
         Ok(())
     }
@@ -121,23 +176,116 @@ impl PushComponent {
         self.progress = self.git_push.progress()?;
 
         if !self.pending {
-            if let Some(err) = self.git_push.last_result()? {
-                self.queue.borrow_mut().push_back(
-                    InternalEvent::ShowErrorMsg(format!(
-                        "push failed:\n{}",
-                        err
-                    )),
-                );
+            match self.git_push.last_result()? {
+                Some(RemoteError::Auth)
+                    if need_username_password_for_push(
+                        CWD,
+                        DEFAULT_REMOTE_NAME,
+                    )? =>
+                {
+                    // keep the popup open and ask again
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowRemoteError {
+                            action: self.action(),
+                            retry: self.key_config.enter,
+                            error: RemoteError::Auth,
+                        },
+                    );
+                    self.input_cred.show()?;
+                }
+                Some(RemoteError::Auth)
+                    if need_ssh_passphrase_for_push(
+                        CWD,
+                        DEFAULT_REMOTE_NAME,
+                    )? =>
+                {
+                    // wrong passphrase, ask again
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowRemoteError {
+                            action: self.action(),
+                            retry: self.key_config.enter,
+                            error: RemoteError::Auth,
+                        },
+                    );
+                    self.input_cred.show_passphrase()?;
+                }
+                Some(RemoteError::Cancelled) => self.hide(),
+                Some(err) => {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowRemoteError {
+                            action: self.action(),
+                            retry: if self.kind == PushKind::DryRun {
+                                self.key_config.push_dry_run
+                            } else {
+                                self.key_config.push
+                            },
+                            error: err,
+                        },
+                    );
+                    self.hide();
+                }
+                None if self.kind == PushKind::DryRun => {
+                    let updates = self
+                        .git_push
+                        .last_preview()?
+                        .unwrap_or_default();
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ConfirmAction(Action::Push(
+                            self.branch.clone(),
+                            Self::push_url(),
+                            updates,
+                        )),
+                    );
+                    self.hide()
+                }
+                None => {
+                    if let PushKind::Tag(tag) = &self.kind {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowInfoMsg(
+                                strings::tag_pushed_msg(tag),
+                            ),
+                        );
+                    }
+                    self.hide()
+                }
             }
-            self.hide();
         }
 
         Ok(())
     }
 
+    /// what failed, a new tag was created before
+    fn action(&self) -> &'static str {
+        match self.kind {
+            PushKind::Branch => strings::push_action(),
+            PushKind::Tag(_) => strings::push_tag_action(),
+            PushKind::DryRun => strings::push_dry_run_action(),
+        }
+    }
+
+    /// where `origin` pushes to
+    fn push_url() -> String {
+        sync::get_remotes_info(CWD)
+            .ok()
+            .and_then(|remotes| {
+                remotes
+                    .into_iter()
+                    .find(|r| r.name == DEFAULT_REMOTE_NAME)
+            })
+            .and_then(|r| r.effective_push_url().map(String::from))
+            .unwrap_or_default()
+    }
+
     fn get_progress(&self) -> (String, u8) {
+        if let Some(op) = self.waiting_for() {
+            return (
+                strings::remote_op_waiting_msg(&self.key_config, op),
+                0,
+            );
+        }
+
         self.progress.as_ref().map_or(
-            (strings::PUSH_POPUP_PROGRESS_NONE.into(), 0),
+            (strings::push_popup_progress_none().into(), 0),
             |progress| {
                 (
                     Self::progress_state_name(&progress.state),
@@ -147,16 +295,23 @@ impl PushComponent {
         )
     }
 
+    fn waiting_for(&self) -> Option<RemoteOp> {
+        self.git_push.waiting_for().ok().flatten()
+    }
+
     fn progress_state_name(state: &PushProgressState) -> String {
         match state {
             PushProgressState::PackingAddingObject => {
-                strings::PUSH_POPUP_STATES_ADDING
+                strings::push_popup_states_adding()
             }
             PushProgressState::PackingDeltafiction => {
-                strings::PUSH_POPUP_STATES_DELTAS
+                strings::push_popup_states_deltas()
             }
             PushProgressState::Pushing => {
-                strings::PUSH_POPUP_STATES_PUSHING
+                strings::push_popup_states_pushing()
+            }
+            PushProgressState::ResolvingDeltas => {
+                strings::push_popup_states_resolving_deltas()
             }
         }
         .into()
@@ -181,7 +336,17 @@ impl DrawableComponent for PushComponent {
                     .block(
                         Block::default()
                             .title(Span::styled(
-                                strings::PUSH_POPUP_MSG,
+                                match self.kind {
+                                    PushKind::Branch => {
+                                        strings::push_popup_msg()
+                                    }
+                                    PushKind::Tag(_) => {
+                                        strings::push_tag_popup_msg()
+                                    }
+                                    PushKind::DryRun => {
+                                        strings::push_dry_run_popup_msg()
+                                    }
+                                },
                                 self.theme.title(true),
                             ))
                             .borders(Borders::ALL)
@@ -222,6 +387,13 @@ impl Component for PushComponent {
                 !self.pending,
                 self.visible,
             ));
+            out.push(CommandInfo::new(
+                strings::commands::cancel_queued_push(
+                    &self.key_config,
+                ),
+                true,
+                self.visible && self.waiting_for().is_some(),
+            ));
             visibility_blocking(self)
         }
     }
@@ -230,13 +402,15 @@ impl Component for PushComponent {
         if self.visible {
             if let Event::Key(e) = ev {
                 if e == self.key_config.exit_popup {
+                    // a push still waiting for its turn is dropped
+                    self.git_push.cancel()?;
                     self.hide();
                 }
                 if self.input_cred.event(ev)? {
                     return Ok(true);
                 } else if e == self.key_config.enter {
                     if self.input_cred.is_visible()
-                        && self.input_cred.get_cred().is_complete()
+                        && self.input_cred.is_complete()
                     {
                         self.push_to_remote(Some(
                             self.input_cred.get_cred().clone(),
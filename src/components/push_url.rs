@@ -0,0 +1,150 @@
+use super::{
+    textinput::{InputType, TextInputComponent},
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{InternalEvent, Queue},
+    strings,
+    ui::style::SharedTheme,
+};
+use anyhow::Result;
+use asyncgit::{sync, CWD};
+use crossterm::event::Event;
+use tui::{backend::Backend, layout::Rect, Frame};
+
+pub struct PushUrlComponent {
+    input: TextInputComponent,
+    remote: Option<String>,
+    queue: Queue,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for PushUrlComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        self.input.draw(f, rect)?;
+
+        Ok(())
+    }
+}
+
+impl Component for PushUrlComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.is_visible() || force_all {
+            self.input.commands(out, force_all);
+
+            out.push(CommandInfo::new(
+                strings::commands::push_url_confirm_msg(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.is_visible() {
+            if self.input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter {
+                    self.save();
+                }
+
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.input.is_visible()
+    }
+
+    fn hide(&mut self) {
+        self.input.hide()
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.input.show()?;
+
+        Ok(())
+    }
+}
+
+impl PushUrlComponent {
+    ///
+    pub fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            queue,
+            input: TextInputComponent::new(
+                theme,
+                key_config.clone(),
+                "",
+                &strings::push_url_popup_msg(&key_config),
+            )
+            .with_input_type(InputType::Singleline),
+            remote: None,
+            key_config,
+        }
+    }
+
+    ///
+    pub fn open(&mut self, remote: String) -> Result<()> {
+        let push_url = sync::get_remotes_info(CWD)?
+            .into_iter()
+            .find(|r| r.name == remote)
+            .and_then(|r| r.push_url);
+
+        self.input.set_title(strings::push_url_popup_title(
+            &self.key_config,
+            &remote,
+        ));
+        self.input.set_text(push_url.unwrap_or_default());
+        self.remote = Some(remote);
+        self.show()?;
+
+        Ok(())
+    }
+
+    fn save(&mut self) {
+        if let Some(remote) = &self.remote {
+            let url = self.input.get_text().trim();
+            let url = if url.is_empty() { None } else { Some(url) };
+
+            if let Err(e) =
+                sync::set_remote_push_url(CWD, remote, url)
+            {
+                log::error!("set push url: {}", e);
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(
+                        strings::push_url_error_msg(
+                            &self.key_config,
+                            &e.to_string(),
+                        ),
+                    ),
+                );
+            }
+        }
+
+        self.hide();
+    }
+}
@@ -19,6 +19,8 @@ use tui::{backend::Backend, layout::Rect, Frame};
 pub struct RenameBranchComponent {
     input: TextInputComponent,
     branch_ref: Option<String>,
+    /// opened from the branch list, go back there when done
+    to_branches: bool,
     queue: Queue,
     key_config: SharedKeyConfig,
 }
@@ -104,6 +106,7 @@ impl RenameBranchComponent {
                 &strings::rename_branch_popup_msg(&key_config),
             ),
             branch_ref: None,
+            to_branches: false,
             key_config,
         }
     }
@@ -113,9 +116,10 @@ impl RenameBranchComponent {
         &mut self,
         branch_ref: String,
         cur_name: String,
+        to_branches: bool,
     ) -> Result<()> {
-        self.branch_ref = None;
         self.branch_ref = Some(branch_ref);
+        self.to_branches = to_branches;
         self.input.set_text(cur_name);
         self.show()?;
 
@@ -137,12 +141,14 @@ impl RenameBranchComponent {
                         InternalEvent::Update(NeedsUpdate::ALL),
                     );
                     self.hide();
-                    self.queue
-                        .borrow_mut()
-                        .push_back(InternalEvent::SelectBranch);
+                    if self.to_branches {
+                        self.queue
+                            .borrow_mut()
+                            .push_back(InternalEvent::SelectBranch);
+                    }
                 }
                 Err(e) => {
-                    log::error!("create branch: {}", e,);
+                    log::error!("rename branch: {}", e);
                     self.queue.borrow_mut().push_back(
                         InternalEvent::ShowErrorMsg(format!(
                             "rename branch error:\n{}",
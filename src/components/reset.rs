@@ -58,7 +58,7 @@ impl Component for ResetComponent {
     ) -> CommandBlocking {
         out.push(CommandInfo::new(
             strings::commands::reset_confirm(&self.key_config),
-            true,
+            self.can_confirm(),
             self.visible,
         ));
         out.push(CommandInfo::new(
@@ -75,7 +75,9 @@ impl Component for ResetComponent {
             if let Event::Key(e) = ev {
                 if e == self.key_config.exit_popup {
                     self.hide();
-                } else if e == self.key_config.enter {
+                } else if e == self.key_config.enter
+                    && self.can_confirm()
+                {
                     self.confirm();
                 }
 
@@ -134,6 +136,16 @@ impl ResetComponent {
         self.hide();
     }
 
+    /// a push the remote would reject is only shown
+    fn can_confirm(&self) -> bool {
+        match &self.target {
+            Some(Action::Push(_, _, updates)) => {
+                updates.iter().all(|u| u.fast_forward)
+            }
+            _ => true,
+        }
+    }
+
     fn get_text(&self) -> (String, String) {
         if let Some(ref a) = self.target {
             return match a {
@@ -160,9 +172,92 @@ impl ResetComponent {
                         branch_ref,
                     ),
                 ),
+                Action::ResetHard(id) => (
+                    strings::confirm_title_reset_hard(
+                        &self.key_config,
+                    ),
+                    strings::confirm_msg_reset_hard(
+                        &self.key_config,
+                        &id.get_short_string(),
+                    ),
+                ),
+                Action::RestoreRef {
+                    refname,
+                    id,
+                    pushed,
+                } => (
+                    strings::confirm_title_restore_ref(
+                        &self.key_config,
+                    ),
+                    strings::confirm_msg_restore_ref(
+                        &self.key_config,
+                        refname,
+                        &id.get_short_string(),
+                        *pushed,
+                    ),
+                ),
+                Action::StashApplyOnto(id) => (
+                    strings::confirm_title_stash_apply_onto(
+                        &self.key_config,
+                    ),
+                    strings::confirm_msg_stash_apply_onto(
+                        &self.key_config,
+                        &id.get_short_string(),
+                    ),
+                ),
+                Action::AbortCherryPick => (
+                    strings::confirm_title_cherry_pick_abort(
+                        &self.key_config,
+                    ),
+                    strings::confirm_msg_cherry_pick_abort(
+                        &self.key_config,
+                    ),
+                ),
+                a => self.get_remote_text(a),
             };
         }
 
         (String::new(), String::new())
     }
+
+    /// texts of the host key, signing and push confirmations
+    fn get_remote_text(&self, a: &Action) -> (String, String) {
+        match a {
+            Action::TrustHostKey { host, fingerprint } => (
+                strings::confirm_title_trust_host_key(
+                    &self.key_config,
+                ),
+                strings::confirm_msg_trust_host_key(
+                    &self.key_config,
+                    host,
+                    fingerprint,
+                ),
+            ),
+            Action::CommitUnsigned { problem, .. } => (
+                strings::confirm_title_unsigned(&self.key_config),
+                strings::confirm_msg_unsigned(
+                    &self.key_config,
+                    "commit",
+                    problem,
+                ),
+            ),
+            Action::TagUnsigned { problem, .. } => (
+                strings::confirm_title_unsigned(&self.key_config),
+                strings::confirm_msg_unsigned(
+                    &self.key_config,
+                    "tag",
+                    problem,
+                ),
+            ),
+            Action::Push(_, url, updates) => (
+                strings::confirm_title_push_dry_run(&self.key_config),
+                strings::confirm_msg_push_dry_run(
+                    &self.key_config,
+                    url,
+                    updates,
+                ),
+            ),
+            _ => (String::new(), String::new()),
+        }
+    }
 }
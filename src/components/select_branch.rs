@@ -1,6 +1,6 @@
 use super::{
     visibility_blocking, CommandBlocking, CommandInfo, Component,
-    DrawableComponent,
+    CredComponent, CredState, DrawableComponent,
 };
 use crate::{
     components::ScrollType,
@@ -11,10 +11,14 @@ use crate::{
 };
 use asyncgit::{
     sync::{
-        checkout_branch, get_branches_to_display, BranchForDisplay,
+        checkout_branch, cred::BasicAuthCredential,
+        get_branches_to_display, is_bare_repo, BranchForDisplay,
+        CommitId, RemoteError, DEFAULT_REMOTE_NAME,
     },
+    AsyncLsRemote, AsyncNotification, LsRemoteRequest, RemoteOps,
     CWD,
 };
+use crossbeam_channel::Sender;
 use crossterm::event::Event;
 use std::{cell::Cell, convert::TryInto};
 use tui::{
@@ -29,12 +33,19 @@ use crate::ui::Size;
 use anyhow::Result;
 use ui::style::SharedTheme;
 
+const IS_HEAD_STAR_LENGTH: usize = 3; // "*  "
+const THREE_DOTS_LENGTH: usize = 3; // "..."
+
 ///
 pub struct SelectBranchComponent {
     branch_names: Vec<BranchForDisplay>,
+    remote_branches: Vec<(String, CommitId)>,
+    git_ls_remote: AsyncLsRemote,
+    input_cred: CredComponent,
     visible: bool,
     selection: u16,
     scroll_top: Cell<usize>,
+    bare: bool,
     queue: Queue,
     theme: SharedTheme,
     key_config: SharedKeyConfig,
@@ -77,7 +88,11 @@ impl DrawableComponent for SelectBranchComponent {
                 )?)
                 .block(
                     Block::default()
-                        .title(strings::SELECT_BRANCH_POPUP_MSG)
+                        .title(if self.git_ls_remote.is_pending() {
+                            strings::select_branch_popup_refreshing_msg()
+                        } else {
+                            strings::select_branch_popup_msg()
+                        })
                         .border_type(BorderType::Thick)
                         .borders(Borders::ALL),
                 )
@@ -89,9 +104,11 @@ impl DrawableComponent for SelectBranchComponent {
                 f,
                 area,
                 &self.theme,
-                self.branch_names.len(),
+                self.entries_count(),
                 self.scroll_top.get(),
             );
+
+            self.input_cred.draw(f, rect)?;
         }
 
         Ok(())
@@ -104,6 +121,10 @@ impl Component for SelectBranchComponent {
         out: &mut Vec<CommandInfo>,
         force_all: bool,
     ) -> CommandBlocking {
+        if self.input_cred.is_visible() {
+            return self.input_cred.commands(out, force_all);
+        }
+
         if self.visible || force_all {
             out.clear();
 
@@ -131,7 +152,8 @@ impl Component for SelectBranchComponent {
                 strings::commands::delete_branch_popup(
                     &self.key_config,
                 ),
-                !self.selection_is_cur_branch(),
+                self.selection_is_local()
+                    && !self.selection_is_cur_branch(),
                 true,
             ));
 
@@ -139,7 +161,23 @@ impl Component for SelectBranchComponent {
                 strings::commands::rename_branch_popup(
                     &self.key_config,
                 ),
+                self.selection_is_local(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::branch_show_in_log(
+                    &self.key_config,
+                ),
+                true,
                 true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::refresh_remote_branches(
+                    &self.key_config,
+                ),
+                !self.git_ls_remote.is_pending(),
                 true,
             ));
         }
@@ -148,6 +186,10 @@ impl Component for SelectBranchComponent {
 
     fn event(&mut self, ev: Event) -> Result<bool> {
         if self.visible {
+            if self.input_cred.is_visible() {
+                return self.cred_event(ev);
+            }
+
             if let Event::Key(e) = ev {
                 if e == self.key_config.exit_popup {
                     self.hide()
@@ -155,7 +197,10 @@ impl Component for SelectBranchComponent {
                     return self.move_selection(ScrollType::Up);
                 } else if e == self.key_config.move_up {
                     return self.move_selection(ScrollType::Down);
-                } else if e == self.key_config.enter {
+                } else if e == self.key_config.enter
+                    && self.selection_is_local()
+                    && !self.bare
+                {
                     if let Err(e) = self.switch_to_selected_branch() {
                         log::error!("switch branch error: {}", e);
                         self.queue.borrow_mut().push_back(
@@ -171,17 +216,21 @@ impl Component for SelectBranchComponent {
                         .borrow_mut()
                         .push_back(InternalEvent::CreateBranch);
                     self.hide();
-                } else if e == self.key_config.rename_branch {
+                } else if e == self.key_config.rename_branch
+                    && self.selection_is_local()
+                {
                     let cur_branch =
                         &self.branch_names[self.selection as usize];
                     self.queue.borrow_mut().push_back(
                         InternalEvent::RenameBranch(
                             cur_branch.reference.clone(),
                             cur_branch.name.clone(),
+                            true,
                         ),
                     );
                     self.hide();
                 } else if e == self.key_config.delete_branch
+                    && self.selection_is_local()
                     && !self.selection_is_cur_branch()
                 {
                     self.queue.borrow_mut().push_back(
@@ -194,6 +243,16 @@ impl Component for SelectBranchComponent {
                             ),
                         ),
                     );
+                } else if e == self.key_config.branch_show_in_log {
+                    if let Some(id) = self.selected_commit() {
+                        self.queue.borrow_mut().push_back(
+                            InternalEvent::ShowCommitInLog(id),
+                        );
+                        self.hide();
+                    }
+                } else if e == self.key_config.refresh_remote_branches
+                {
+                    self.refresh_remote()?;
                 }
             }
 
@@ -221,14 +280,23 @@ impl Component for SelectBranchComponent {
 impl SelectBranchComponent {
     pub fn new(
         queue: Queue,
+        sender: &Sender<AsyncNotification>,
+        remote_ops: &RemoteOps,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
     ) -> Self {
         Self {
             branch_names: Vec::new(),
+            remote_branches: Vec::new(),
+            git_ls_remote: AsyncLsRemote::new(sender, remote_ops),
+            input_cred: CredComponent::new(
+                theme.clone(),
+                key_config.clone(),
+            ),
             visible: false,
             selection: 0,
             scroll_top: Cell::new(0),
+            bare: false,
             queue,
             theme,
             key_config,
@@ -241,6 +309,7 @@ impl SelectBranchComponent {
 
     ///
     pub fn open(&mut self) -> Result<()> {
+        self.bare = is_bare_repo(CWD)?;
         self.update_branches()?;
         self.show()?;
 
@@ -253,6 +322,130 @@ impl SelectBranchComponent {
         Ok(())
     }
 
+    ///
+    pub fn update_git(
+        &mut self,
+        ev: AsyncNotification,
+    ) -> Result<()> {
+        if ev != AsyncNotification::LsRemote {
+            return Ok(());
+        }
+
+        let result = self.git_ls_remote.last_result()?;
+        if matches!(result, Some(Err(RemoteError::Auth)))
+            && self.input_cred.ask_again(CWD, DEFAULT_REMOTE_NAME)?
+        {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowRemoteError {
+                    action: strings::ls_remote_action(),
+                    retry: self.key_config.enter,
+                    error: RemoteError::Auth,
+                },
+            );
+            return Ok(());
+        }
+
+        match result {
+            Some(Ok(refs)) => {
+                self.remote_branches = refs
+                    .into_iter()
+                    .filter_map(|(name, id)| {
+                        name.strip_prefix("refs/heads/")
+                            .map(|name| (name.to_string(), id))
+                    })
+                    .collect();
+                self.clamp_selection();
+            }
+            Some(Err(e)) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowRemoteError {
+                        action: strings::ls_remote_action(),
+                        retry: self
+                            .key_config
+                            .refresh_remote_branches,
+                        error: e,
+                    },
+                );
+            }
+            None => (),
+        }
+
+        Ok(())
+    }
+
+    /// queries the default remote for the branches it has right
+    /// now, asking for missing credentials first
+    fn refresh_remote(&mut self) -> Result<()> {
+        match self
+            .input_cred
+            .fetch_credential(CWD, DEFAULT_REMOTE_NAME)?
+        {
+            CredState::Ready(cred) => self.ls_remote(cred),
+            CredState::Asking => Ok(()),
+        }
+    }
+
+    fn ls_remote(
+        &mut self,
+        cred: Option<BasicAuthCredential>,
+    ) -> Result<()> {
+        self.git_ls_remote.request(LsRemoteRequest {
+            remote: DEFAULT_REMOTE_NAME.to_string(),
+            basic_credential: cred,
+        })?;
+
+        Ok(())
+    }
+
+    /// lists the remote branches once the credentials asked for
+    /// are typed in
+    fn cred_event(&mut self, ev: Event) -> Result<bool> {
+        if self.input_cred.event(ev)? {
+            return Ok(true);
+        }
+
+        if let Event::Key(e) = ev {
+            if e == self.key_config.enter
+                && self.input_cred.is_visible()
+                && self.input_cred.is_complete()
+            {
+                self.ls_remote(Some(
+                    self.input_cred.get_cred().clone(),
+                ))?;
+                self.input_cred.hide();
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn selected_commit(&self) -> Option<CommitId> {
+        let selection = self.selection as usize;
+        self.branch_names.get(selection).map_or_else(
+            || {
+                self.remote_branches
+                    .get(selection - self.branch_names.len())
+                    .map(|(_, id)| *id)
+            },
+            |b| Some(b.top_commit),
+        )
+    }
+
+    const fn entries_count(&self) -> usize {
+        self.branch_names.len() + self.remote_branches.len()
+    }
+
+    const fn selection_is_local(&self) -> bool {
+        (self.selection as usize) < self.branch_names.len()
+    }
+
+    fn clamp_selection(&mut self) {
+        let max = self.entries_count().saturating_sub(1);
+        if self.selection as usize > max {
+            self.selection = max.try_into().unwrap_or_default();
+        }
+    }
+
     ///
     pub fn selection_is_cur_branch(&self) -> bool {
         self.branch_names
@@ -267,7 +460,7 @@ impl SelectBranchComponent {
 
     ///
     fn move_selection(&mut self, scroll: ScrollType) -> Result<bool> {
-        let num_branches: u16 = self.branch_names.len().try_into()?;
+        let num_branches: u16 = self.entries_count().try_into()?;
         let num_branches = num_branches.saturating_sub(1);
 
         let mut new_selection = match scroll {
@@ -293,8 +486,6 @@ impl SelectBranchComponent {
         height: usize,
     ) -> Result<Text> {
         const COMMIT_HASH_LENGTH: usize = 8;
-        const IS_HEAD_STAR_LENGTH: usize = 3; // "*  "
-        const THREE_DOTS_LENGTH: usize = 3; // "..."
 
         // branch name = 30% of area size
         let branch_name_length: usize =
@@ -376,9 +567,67 @@ impl SelectBranchComponent {
             ]));
         }
 
+        let remote = self.get_remote_text(
+            theme,
+            branch_name_length,
+            height.saturating_sub(txt.len()),
+        );
+        txt.extend(remote);
+
         Ok(Text::from(txt))
     }
 
+    /// remote-only branches listed below the local ones
+    fn get_remote_text(
+        &self,
+        theme: &SharedTheme,
+        branch_name_length: usize,
+        height: usize,
+    ) -> Vec<Spans> {
+        let mut txt = Vec::new();
+        let local_count = self.branch_names.len();
+        for (i, (name, id)) in self
+            .remote_branches
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get().saturating_sub(local_count))
+            .take(height)
+        {
+            let mut branch_name =
+                format!("{}/{}", DEFAULT_REMOTE_NAME, name);
+            if branch_name.len() > branch_name_length {
+                branch_name.truncate(
+                    branch_name_length
+                        .saturating_sub(THREE_DOTS_LENGTH),
+                );
+                branch_name += "...";
+            }
+
+            let selected = self.selection as usize == local_count + i;
+
+            txt.push(Spans::from(vec![
+                Span::styled(
+                    " ".repeat(IS_HEAD_STAR_LENGTH),
+                    theme.commit_author(selected),
+                ),
+                Span::styled(
+                    format!(
+                        "{:w$} ",
+                        branch_name,
+                        w = branch_name_length
+                    ),
+                    theme.branch(selected, false),
+                ),
+                Span::styled(
+                    id.get_short_string(),
+                    theme.commit_hash(selected),
+                ),
+            ]));
+        }
+
+        txt
+    }
+
     ///
     fn switch_to_selected_branch(&self) -> Result<()> {
         checkout_branch(
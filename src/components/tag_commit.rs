@@ -1,24 +1,41 @@
 use super::{
-    textinput::TextInputComponent, visibility_blocking,
-    CommandBlocking, CommandInfo, Component, DrawableComponent,
+    textinput::TextInputComponent,
+    utils::tag_template::{
+        load_tag_templates, render_tag_template, TagTemplate,
+    },
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent, ExternalEditorComponent,
 };
 use crate::{
     keys::SharedKeyConfig,
-    queue::{InternalEvent, NeedsUpdate, Queue},
+    queue::{
+        Action, InternalEvent, MessageEditor, NeedsUpdate, Queue,
+    },
     strings,
     ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
-    sync::{self, CommitId},
-    CWD,
+    sync::{self, CommitId, Tags},
+    Error, CWD,
 };
 use crossterm::event::Event;
 use tui::{backend::Backend, layout::Rect, Frame};
 
+/// amount of commits since the previous tag listed in the message
+const TEMPLATE_COMMITS: usize = 100;
+
 pub struct TagCommitComponent {
     input: TextInputComponent,
+    message: TextInputComponent,
     commit_id: Option<CommitId>,
+    tags: Tags,
+    /// template names and the messages rendered from them
+    templates: Vec<(String, String)>,
+    template: usize,
+    /// push the tag to `origin` once it is created
+    push: bool,
+    push_default: bool,
     queue: Queue,
     key_config: SharedKeyConfig,
 }
@@ -30,6 +47,7 @@ impl DrawableComponent for TagCommitComponent {
         rect: Rect,
     ) -> Result<()> {
         self.input.draw(f, rect)?;
+        self.message.draw(f, rect)?;
 
         Ok(())
     }
@@ -42,23 +60,79 @@ impl Component for TagCommitComponent {
         force_all: bool,
     ) -> CommandBlocking {
         if self.is_visible() || force_all {
-            self.input.commands(out, force_all);
+            if self.message.is_visible() {
+                self.message.commands(out, force_all);
 
-            out.push(CommandInfo::new(
-                strings::commands::tag_commit_confirm_msg(
-                    &self.key_config,
-                ),
-                true,
-                true,
-            ));
+                out.push(CommandInfo::new(
+                    strings::commands::tag_template_next(
+                        &self.key_config,
+                    ),
+                    self.templates.len() > 1,
+                    true,
+                ));
+
+                out.push(CommandInfo::new(
+                    strings::commands::tag_open_editor(
+                        &self.key_config,
+                    ),
+                    true,
+                    true,
+                ));
+
+                out.push(CommandInfo::new(
+                    strings::commands::tag_push_toggle(
+                        &self.key_config,
+                        self.push,
+                    ),
+                    true,
+                    true,
+                ));
+
+                out.push(CommandInfo::new(
+                    strings::commands::tag_message_confirm_msg(
+                        &self.key_config,
+                    ),
+                    true,
+                    true,
+                ));
+            } else {
+                self.input.commands(out, force_all);
+
+                out.push(CommandInfo::new(
+                    strings::commands::tag_commit_confirm_msg(
+                        &self.key_config,
+                    ),
+                    !self.input.get_text().is_empty(),
+                    true,
+                ));
+            }
         }
 
         visibility_blocking(self)
     }
 
     fn event(&mut self, ev: Event) -> Result<bool> {
-        if self.is_visible() {
-            if self.input.event(ev)? {
+        if self.message.is_visible() {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.tag_template_next {
+                    self.select_template(self.template + 1);
+                    return Ok(true);
+                } else if e == self.key_config.tag_push_toggle {
+                    self.push = !self.push;
+                    self.update_title();
+                    return Ok(true);
+                } else if e == self.key_config.open_commit_editor {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::EditMessage(
+                            MessageEditor::Tag,
+                        ),
+                    );
+                    self.message.hide();
+                    return Ok(true);
+                }
+            }
+
+            if self.message.event(ev)? {
                 return Ok(true);
             }
 
@@ -67,6 +141,20 @@ impl Component for TagCommitComponent {
                     self.tag()
                 }
 
+                return Ok(true);
+            }
+        } else if self.input.is_visible() {
+            if self.input.event(ev)? {
+                return Ok(true);
+            }
+
+            if let Event::Key(e) = ev {
+                if e == self.key_config.enter
+                    && !self.input.get_text().is_empty()
+                {
+                    self.open_message()?;
+                }
+
                 return Ok(true);
             }
         }
@@ -74,11 +162,12 @@ impl Component for TagCommitComponent {
     }
 
     fn is_visible(&self) -> bool {
-        self.input.is_visible()
+        self.input.is_visible() || self.message.is_visible()
     }
 
     fn hide(&mut self) {
-        self.input.hide()
+        self.input.hide();
+        self.message.hide();
     }
 
     fn show(&mut self) -> Result<()> {
@@ -92,53 +181,272 @@ impl TagCommitComponent {
     ///
     pub fn new(
         queue: Queue,
+        push_after_create: bool,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
     ) -> Self {
         Self {
             queue,
             input: TextInputComponent::new(
-                theme,
+                theme.clone(),
                 key_config.clone(),
                 &strings::tag_commit_popup_title(&key_config),
                 &strings::tag_commit_popup_msg(&key_config),
             ),
+            message: TextInputComponent::new(
+                theme,
+                key_config.clone(),
+                "",
+                &strings::tag_message_popup_msg(&key_config),
+            ),
             commit_id: None,
+            tags: Tags::new(),
+            templates: Vec::new(),
+            template: 0,
+            push: push_after_create,
+            push_default: push_after_create,
             key_config,
         }
     }
 
-    ///
-    pub fn open(&mut self, id: CommitId) -> Result<()> {
+    /// `tags` as already loaded by the log, read from the repo
+    /// if they are not available yet
+    pub fn open(
+        &mut self,
+        id: CommitId,
+        tags: Option<Tags>,
+    ) -> Result<()> {
         self.commit_id = Some(id);
+        self.push = self.push_default;
+        self.tags = match tags {
+            Some(tags) => tags,
+            None => sync::get_tags(CWD)?,
+        };
         self.show()?;
 
         Ok(())
     }
 
+    /// asks for the tag message, pre-filled from the first template
+    fn open_message(&mut self) -> Result<()> {
+        if let Some(commit_id) = self.commit_id {
+            let tag = self.input.get_text();
+            let since = sync::get_commits_since_tag(
+                CWD,
+                commit_id,
+                &self.tags,
+                TEMPLATE_COMMITS,
+            )?;
+            let commits =
+                sync::get_commits_info(CWD, &since.commits, 100)?;
+            let date = chrono::Local::now().format("%Y-%m-%d");
+
+            let templates = load_tag_templates()
+                .into_iter()
+                .map(|TagTemplate { name, text }| {
+                    let message = render_tag_template(
+                        &text,
+                        tag,
+                        &date.to_string(),
+                        since.tag.as_deref(),
+                        &commits,
+                    );
+                    (name, message)
+                })
+                .collect();
+            self.set_templates(templates);
+            self.input.hide();
+            self.message.show()?;
+        }
+
+        Ok(())
+    }
+
+    fn set_templates(&mut self, templates: Vec<(String, String)>) {
+        self.templates = templates;
+        self.select_template(0);
+    }
+
+    /// replaces the message with the one of template `idx`,
+    /// wrapping around after the last one
+    fn select_template(&mut self, idx: usize) {
+        if self.templates.is_empty() {
+            return;
+        }
+
+        self.template = idx % self.templates.len();
+        self.message
+            .set_text(self.templates[self.template].1.clone());
+        self.update_title();
+    }
+
+    fn update_title(&mut self) {
+        let template = self
+            .templates
+            .get(self.template)
+            .map_or("", |(name, _)| name.as_str());
+
+        self.message.set_title(strings::tag_message_popup_title(
+            &self.key_config,
+            self.input.get_text(),
+            template,
+            self.push,
+        ));
+    }
+
+    /// lets the message be edited in the editor, an empty one
+    /// aborts the tag
+    pub fn show_editor(&mut self) -> Result<()> {
+        let config = sync::get_message_config(CWD)?;
+        let message = ExternalEditorComponent::edit_message(
+            &config,
+            "TAG_EDITMSG",
+            self.message.get_text(),
+            |comment_char| {
+                strings::tag_editor_msg(
+                    &self.key_config,
+                    self.input.get_text(),
+                    comment_char,
+                )
+            },
+        )?;
+
+        if let Some(message) = message {
+            self.message.set_text(message);
+            self.message.show()?;
+        } else {
+            self.input.clear();
+            self.message.clear();
+            self.hide();
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowInfoMsg(
+                    strings::tag_aborted_empty().to_string(),
+                ),
+            );
+        }
+
+        Ok(())
+    }
+
     ///
     pub fn tag(&mut self) {
         if let Some(commit_id) = self.commit_id {
-            match sync::tag(CWD, &commit_id, self.input.get_text()) {
-                Ok(_) => {
-                    self.input.clear();
-                    self.hide();
+            let tag = self.input.get_text().clone();
+            let message = self.message.get_text().clone();
 
-                    self.queue.borrow_mut().push_back(
-                        InternalEvent::Update(NeedsUpdate::ALL),
-                    );
-                }
-                Err(e) => {
+            match sync::tag(CWD, &commit_id, &tag, &message) {
+                Ok(_) => self.tagged(tag, self.push),
+                Err(Error::Sign(problem)) => {
                     self.hide();
-                    log::error!("e: {}", e,);
                     self.queue.borrow_mut().push_back(
-                        InternalEvent::ShowErrorMsg(format!(
-                            "tag error:\n{}",
-                            e,
-                        )),
+                        InternalEvent::ConfirmAction(
+                            Action::TagUnsigned {
+                                commit: commit_id,
+                                tag,
+                                message,
+                                push: self.push,
+                                problem,
+                            },
+                        ),
                     );
                 }
+                Err(e) => self.tag_failed(&e),
             }
         }
     }
+
+    /// creates the tag without signing it after that failed
+    pub fn tag_unsigned(
+        &mut self,
+        commit_id: CommitId,
+        tag: String,
+        message: &str,
+        push: bool,
+    ) {
+        match sync::tag_unsigned(CWD, &commit_id, &tag, message) {
+            Ok(_) => self.tagged(tag, push),
+            Err(e) => self.tag_failed(&e),
+        }
+    }
+
+    fn tagged(&mut self, tag: String, push: bool) {
+        if push {
+            // the push reports that the tag got created
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::PushTag(tag));
+        }
+
+        self.input.clear();
+        self.message.clear();
+        self.hide();
+
+        self.queue
+            .borrow_mut()
+            .push_back(InternalEvent::Update(NeedsUpdate::ALL));
+    }
+
+    fn tag_failed(&mut self, e: &Error) {
+        self.hide();
+        log::error!("e: {}", e);
+        self.queue.borrow_mut().push_back(
+            InternalEvent::ShowErrorMsg(format!("tag error:\n{}", e)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_template() {
+        let mut tag = TagCommitComponent::new(
+            Queue::default(),
+            false,
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        tag.input.set_text(String::from("v1"));
+        tag.set_templates(vec![
+            (String::from("release"), String::from("v1 released")),
+            (String::from("blank"), String::new()),
+        ]);
+        tag.message.show().unwrap();
+        assert_eq!(tag.message.get_text(), "v1 released");
+
+        tag.event(Event::Key(tag.key_config.tag_template_next))
+            .unwrap();
+        assert_eq!(tag.message.get_text(), "");
+
+        tag.event(Event::Key(tag.key_config.tag_template_next))
+            .unwrap();
+        assert_eq!(tag.message.get_text(), "v1 released");
+    }
+
+    #[test]
+    fn test_push_toggle() {
+        let mut tag = TagCommitComponent::new(
+            Queue::default(),
+            true,
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        tag.input.set_text(String::from("v1"));
+        tag.set_templates(vec![(
+            String::from("blank"),
+            String::new(),
+        )]);
+        tag.message.show().unwrap();
+        assert!(tag.push);
+
+        tag.event(Event::Key(tag.key_config.tag_push_toggle))
+            .unwrap();
+        assert!(!tag.push);
+        assert_eq!(tag.message.get_text(), "");
+
+        // every new tag starts out with the configured default
+        tag.open(CommitId::default(), Some(Tags::new())).unwrap();
+        assert!(tag.push);
+    }
 }
@@ -34,6 +34,8 @@ pub struct TextInputComponent {
     key_config: SharedKeyConfig,
     cursor_position: usize,
     input_type: InputType,
+    max_len: Option<usize>,
+    truncated: bool,
 }
 
 impl TextInputComponent {
@@ -53,9 +55,17 @@ impl TextInputComponent {
             default_msg: default_msg.to_string(),
             cursor_position: 0,
             input_type: InputType::Multiline,
+            max_len: None,
+            truncated: false,
         }
     }
 
+    /// input beyond `max_len` chars is dropped
+    pub const fn with_max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
     pub const fn with_input_type(
         mut self,
         input_type: InputType,
@@ -68,6 +78,7 @@ impl TextInputComponent {
     pub fn clear(&mut self) {
         self.msg.clear();
         self.cursor_position = 0;
+        self.truncated = false;
     }
 
     /// Get the `msg`.
@@ -118,6 +129,46 @@ impl TextInputComponent {
     pub fn set_text(&mut self, msg: String) {
         self.msg = msg;
         self.cursor_position = 0;
+        self.truncated = false;
+
+        if let Some(max_len) = self.max_len {
+            if let Some((pos, _)) =
+                self.msg.char_indices().nth(max_len)
+            {
+                self.msg.truncate(pos);
+                self.truncated = true;
+            }
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.max_len.is_some_and(|max_len| {
+            self.msg.chars().count() >= max_len
+        })
+    }
+
+    fn insert_char(&mut self, c: char) {
+        if self.is_full() {
+            self.truncated = true;
+        } else {
+            self.msg.insert(self.cursor_position, c);
+            self.incr_cursor();
+        }
+    }
+
+    fn get_title(&self) -> String {
+        if self.truncated && self.is_full() {
+            format!(
+                "{} {}",
+                self.title,
+                strings::text_input_truncated(
+                    &self.key_config,
+                    self.max_len.unwrap_or_default()
+                )
+            )
+        } else {
+            self.title.clone()
+        }
     }
 
     /// Set the `title`.
@@ -213,7 +264,7 @@ impl DrawableComponent for TextInputComponent {
             f.render_widget(Clear, area);
             f.render_widget(
                 popup_paragraph(
-                    self.title.as_str(),
+                    self.get_title().as_str(),
                     txt,
                     &self.theme,
                     true,
@@ -256,8 +307,7 @@ impl Component for TextInputComponent {
 
                 match e.code {
                     KeyCode::Char(c) if !is_ctrl => {
-                        self.msg.insert(self.cursor_position, c);
-                        self.incr_cursor();
+                        self.insert_char(c);
                         return Ok(true);
                     }
                     KeyCode::Delete => {
@@ -311,6 +361,7 @@ impl Component for TextInputComponent {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crossterm::event::KeyEvent;
     use tui::style::Style;
 
     #[test]
@@ -333,6 +384,36 @@ mod tests {
         assert_eq!(comp.cursor_position, 0);
     }
 
+    #[test]
+    fn test_max_len() {
+        let mut comp = TextInputComponent::new(
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+            "title",
+            "",
+        )
+        .with_max_len(3);
+        comp.show().unwrap();
+
+        for c in "abcde".chars() {
+            comp.event(Event::Key(KeyEvent::new(
+                KeyCode::Char(c),
+                KeyModifiers::empty(),
+            )))
+            .unwrap();
+        }
+
+        assert_eq!(comp.get_text(), "abc");
+        assert_eq!(comp.get_title(), "title (truncated to 3 chars)");
+
+        comp.backspace();
+        assert_eq!(comp.get_title(), "title");
+
+        comp.set_text(String::from("äöüß"));
+        assert_eq!(comp.get_text(), "äöü");
+        assert_eq!(comp.get_title(), "title (truncated to 3 chars)");
+    }
+
     #[test]
     fn text_cursor_initial_position() {
         let mut comp = TextInputComponent::new(
@@ -0,0 +1,299 @@
+use super::{
+    utils::time_to_string, visibility_blocking, CommandBlocking,
+    CommandInfo, Component, DrawableComponent, ScrollType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    queue::{Action, InternalEvent, Queue},
+    strings,
+    ui::{self, calc_scroll_top, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{
+        is_bare_repo, is_ref_pushed, reflog_entries, ReflogEntry,
+    },
+    CWD,
+};
+use crossterm::event::Event;
+use std::{cell::Cell, cmp};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+/// amount of ref updates listed
+const UNDO_ENTRIES: usize = 100;
+
+/// latest updates of `HEAD` and all local branches,
+/// each can be undone by restoring the ref to its previous commit
+pub struct UndoComponent {
+    entries: Vec<ReflogEntry>,
+    visible: bool,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    page_size: Cell<usize>,
+    bare: bool,
+    queue: Queue,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for UndoComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(80, 50);
+            const MIN_SIZE: Size = Size::new(60, 20);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height_in_lines =
+                (area.height as usize).saturating_sub(2);
+            self.page_size.set(height_in_lines);
+
+            self.scroll_top.set(calc_scroll_top(
+                self.scroll_top.get(),
+                height_in_lines,
+                self.selection,
+            ));
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height_in_lines))
+                    .block(
+                        Block::default()
+                            .title(strings::undo_popup_msg())
+                            .border_type(BorderType::Thick)
+                            .borders(Borders::ALL),
+                    )
+                    .alignment(Alignment::Left),
+                area,
+            );
+
+            ui::draw_scrollbar(
+                f,
+                area,
+                &self.theme,
+                self.entries.len(),
+                self.scroll_top.get(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for UndoComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(
+                CommandInfo::new(
+                    strings::commands::undo_restore(&self.key_config),
+                    !self.entries.is_empty(),
+                    true,
+                )
+                .needs_worktree(self.bare),
+            );
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide()
+                } else if e == self.key_config.move_down {
+                    self.move_selection(ScrollType::Down);
+                } else if e == self.key_config.move_up {
+                    self.move_selection(ScrollType::Up);
+                } else if e == self.key_config.page_down {
+                    self.move_selection(ScrollType::PageDown);
+                } else if e == self.key_config.page_up {
+                    self.move_selection(ScrollType::PageUp);
+                } else if e == self.key_config.home {
+                    self.move_selection(ScrollType::Home);
+                } else if e == self.key_config.end {
+                    self.move_selection(ScrollType::End);
+                } else if e == self.key_config.enter && !self.bare {
+                    self.restore_selected()?;
+                }
+            }
+
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl UndoComponent {
+    ///
+    pub const fn new(
+        queue: Queue,
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            entries: Vec::new(),
+            visible: false,
+            selection: 0,
+            scroll_top: Cell::new(0),
+            page_size: Cell::new(0),
+            bare: false,
+            queue,
+            theme,
+            key_config,
+        }
+    }
+
+    /// reads the reflogs and shows them, latest update selected
+    pub fn open(&mut self) -> Result<()> {
+        self.entries = reflog_entries(CWD, UNDO_ENTRIES)?;
+        self.bare = is_bare_repo(CWD)?;
+        self.selection = 0;
+        self.scroll_top.set(0);
+        self.show()?;
+
+        Ok(())
+    }
+
+    /// asks to move the ref of the selected entry back to where
+    /// it was before that update
+    fn restore_selected(&self) -> Result<()> {
+        if let Some(entry) = self.entries.get(self.selection) {
+            let pushed = is_ref_pushed(CWD, &entry.refname)?;
+
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ConfirmAction(Action::RestoreRef {
+                    refname: entry.refname.clone(),
+                    id: entry.previous,
+                    pushed,
+                }),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn move_selection(&mut self, scroll: ScrollType) {
+        let max = self.entries.len().saturating_sub(1);
+        let page = self.page_size.get().saturating_sub(1).max(1);
+
+        let new_selection = match scroll {
+            ScrollType::Up => self.selection.saturating_sub(1),
+            ScrollType::Down => self.selection.saturating_add(1),
+            ScrollType::PageUp => self.selection.saturating_sub(page),
+            ScrollType::PageDown => {
+                self.selection.saturating_add(page)
+            }
+            ScrollType::Home => 0,
+            ScrollType::End => max,
+        };
+
+        self.selection = cmp::min(new_selection, max);
+    }
+
+    fn get_text(&self, height: usize) -> Text {
+        let ref_width = self
+            .entries
+            .iter()
+            .map(|entry| short_refname(&entry.refname).len())
+            .max()
+            .unwrap_or_default();
+
+        let txt = self
+            .entries
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(i, entry)| {
+                let selected = i == self.selection;
+
+                Spans::from(vec![
+                    Span::styled(
+                        format!(
+                            "{:<w$} ",
+                            short_refname(&entry.refname),
+                            w = ref_width
+                        ),
+                        self.theme.commit_author(selected),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{} ",
+                            time_to_string(entry.time, true)
+                        ),
+                        self.theme.commit_time(selected),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{}..{} ",
+                            entry.previous.get_short_string(),
+                            entry.id.get_short_string()
+                        ),
+                        self.theme.commit_hash(selected),
+                    ),
+                    Span::styled(
+                        entry.message.clone(),
+                        self.theme.text(true, selected),
+                    ),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        Text::from(txt)
+    }
+}
+
+fn short_refname(refname: &str) -> &str {
+    refname.strip_prefix("refs/heads/").unwrap_or(refname)
+}
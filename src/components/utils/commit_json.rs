@@ -0,0 +1,92 @@
+use anyhow::Result;
+use asyncgit::sync::{CommitDetails, CommitSignature};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct SignatureJson<'a> {
+    name: &'a str,
+    email: &'a str,
+    time: i64,
+}
+
+impl<'a> From<&'a CommitSignature> for SignatureJson<'a> {
+    fn from(s: &'a CommitSignature) -> Self {
+        Self {
+            name: s.name.as_str(),
+            email: s.email.as_str(),
+            time: s.time,
+        }
+    }
+}
+
+/// field order here is the order in the resulting json
+#[derive(Serialize)]
+struct CommitJson<'a> {
+    sha: &'a str,
+    author: SignatureJson<'a>,
+    committer: SignatureJson<'a>,
+    subject: &'a str,
+    parents: Vec<String>,
+}
+
+/// serializes commit metadata into a (pretty) json object
+pub fn commit_to_json(details: &CommitDetails) -> Result<String> {
+    let json = CommitJson {
+        sha: details.hash.as_str(),
+        author: SignatureJson::from(&details.author),
+        committer: SignatureJson::from(
+            details.committer.as_ref().unwrap_or(&details.author),
+        ),
+        subject: details
+            .message
+            .as_ref()
+            .map_or("", |msg| msg.subject.as_str()),
+        parents: details
+            .parents
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    };
+
+    Ok(serde_json::to_string_pretty(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asyncgit::sync::CommitMessage;
+
+    #[test]
+    fn test_commit_to_json() {
+        let details = CommitDetails {
+            author: CommitSignature {
+                name: String::from("name"),
+                email: String::from("name@mail.com"),
+                time: 1,
+            },
+            committer: None,
+            message: Some(CommitMessage::from("subject\nbody")),
+            hash: String::from("1234567"),
+            parents: Vec::new(),
+        };
+
+        assert_eq!(
+            commit_to_json(&details).unwrap(),
+            r#"{
+  "sha": "1234567",
+  "author": {
+    "name": "name",
+    "email": "name@mail.com",
+    "time": 1
+  },
+  "committer": {
+    "name": "name",
+    "email": "name@mail.com",
+    "time": 1
+  },
+  "subject": "subject",
+  "parents": []
+}"#
+        );
+    }
+}
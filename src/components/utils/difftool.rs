@@ -0,0 +1,144 @@
+use anyhow::{bail, Result};
+
+/// splits `command` into words like a shell would: whitespace
+/// separates them unless quoted, `'..'` keeps everything as is,
+/// `".."` and unquoted text take `\` as escape
+pub fn split_command(command: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    // a word was started, even if it is still empty (`""`)
+    let mut in_word = false;
+    let mut chars = command.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => bail!("unclosed ' in `{}`", command),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) if c == '"' || c == '\\' => {
+                                word.push(c)
+                            }
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => {
+                                bail!("unclosed \" in `{}`", command)
+                            }
+                        },
+                        Some(c) => word.push(c),
+                        None => bail!("unclosed \" in `{}`", command),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    Some(c) => word.push(c),
+                    None => bail!("trailing \\ in `{}`", command),
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+
+    Ok(words)
+}
+
+/// the words of the difftool `template` with `{old}` and `{new}`
+/// replaced by the revisions compared and `{rev}` by the newer one,
+/// a revision with spaces stays one word
+#[allow(clippy::literal_string_with_formatting_args)]
+pub fn render_difftool_command(
+    template: &str,
+    old: &str,
+    new: &str,
+) -> Result<Vec<String>> {
+    let words = split_command(template)?;
+    if words.is_empty() {
+        bail!("empty difftool command");
+    }
+
+    Ok(words
+        .into_iter()
+        .map(|word| {
+            word.replace("{old}", old)
+                .replace("{new}", new)
+                .replace("{rev}", new)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_command() {
+        let split = |command| split_command(command).unwrap();
+
+        assert_eq!(split("difft  a\tb "), vec!["difft", "a", "b"]);
+        assert_eq!(
+            split(r#"meld "my dir" 'it''s' a\ b """#),
+            vec!["meld", "my dir", "its", "a b", ""]
+        );
+        assert_eq!(
+            split(r#""say \"hi\" \n" '\n'"#),
+            vec![r#"say "hi" \n"#, r"\n"]
+        );
+        assert_eq!(split("  "), Vec::<String>::new());
+
+        assert!(split_command("difft 'a").is_err());
+        assert!(split_command("difft \"a").is_err());
+        assert!(split_command("difft a\\").is_err());
+    }
+
+    #[test]
+    fn test_render_difftool_command() {
+        assert_eq!(
+            render_difftool_command(
+                "git difftool -d {rev}^ {rev}",
+                "a",
+                "b"
+            )
+            .unwrap(),
+            vec!["git", "difftool", "-d", "b^", "b"]
+        );
+        assert_eq!(
+            render_difftool_command(
+                "'/opt/my tools/difft' {old} {new}",
+                "a b",
+                "c"
+            )
+            .unwrap(),
+            vec!["/opt/my tools/difft", "a b", "c"]
+        );
+        assert!(render_difftool_command(" ", "a", "b").is_err());
+    }
+}
@@ -0,0 +1,100 @@
+use super::time_to_string;
+use anyhow::Result;
+use asyncgit::sync::{self, CommitId, CommitInfo};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// one line per commit unless `log.export_template` is set
+pub const DEFAULT_EXPORT_TEMPLATE: &str = "{sha} {subject}";
+
+/// commits looked up at once while exporting
+const EXPORT_CHUNK: usize = 1000;
+
+/// `template` filled in for `commit`: `{sha}`, `{short}`,
+/// `{author}`, `{date}` and `{subject}`
+fn export_row(template: &str, commit: &CommitInfo) -> String {
+    template
+        .replace("{sha}", &commit.id.to_string())
+        .replace("{short}", &commit.id.get_short_string())
+        .replace("{author}", &commit.author)
+        .replace("{date}", &time_to_string(commit.time, true))
+        .replace(
+            "{subject}",
+            commit.message.lines().next().unwrap_or_default(),
+        )
+}
+
+/// writes a line per commit of `commits` to `out`
+pub fn write_commit_list<W: Write>(
+    out: &mut W,
+    commits: &[CommitInfo],
+    template: &str,
+) -> Result<()> {
+    for commit in commits {
+        writeln!(out, "{}", export_row(template, commit))?;
+    }
+
+    Ok(())
+}
+
+/// writes the commits `ids` (in this order) to the file `path`,
+/// replacing it
+pub fn export_commit_list(
+    repo_path: &str,
+    ids: &[CommitId],
+    template: &str,
+    path: &Path,
+) -> Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+
+    for chunk in ids.chunks(EXPORT_CHUNK) {
+        let commits = sync::get_commits_info(repo_path, chunk, 1000)?;
+        write_commit_list(&mut out, &commits, template)?;
+    }
+
+    out.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(message: &str, author: &str) -> CommitInfo {
+        CommitInfo {
+            message: message.to_string(),
+            time: 0,
+            author: author.to_string(),
+            id: CommitId::default(),
+        }
+    }
+
+    #[test]
+    fn test_write_commit_list() {
+        let commits = [info("fix\n\nbody", "bob"), info("", "alice")];
+        let sha = CommitId::default().to_string();
+        let write = |template| {
+            let mut out = Vec::new();
+            write_commit_list(&mut out, &commits, template).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        assert_eq!(
+            write(DEFAULT_EXPORT_TEMPLATE),
+            format!("{} fix\n{} \n", sha, sha)
+        );
+        assert_eq!(
+            write("{short}\t{author}: {subject} {unknown}"),
+            format!(
+                "{}\tbob: fix {{unknown}}\n{}\talice:  {{unknown}}\n",
+                &sha[..7],
+                &sha[..7]
+            )
+        );
+        assert_eq!(write("").lines().count(), 2);
+    }
+}
@@ -0,0 +1,1185 @@
+use crate::strings;
+use anyhow::{anyhow, bail, Result};
+use asyncgit::sync::{FilterBy, FilterQuery, FilterTerm, LineRange};
+
+/// find queries are cut off after this many chars, far more than
+/// any hand written query needs while keeping parsing cheap
+pub const MAX_FILTER_LEN: usize = 1000;
+
+/// names of the fields terms without `:` prefix can search
+const FILTER_FIELDS: [(&str, FilterBy); 6] = [
+    ("sha", FilterBy::SHA),
+    ("author", FilterBy::AUTHOR),
+    ("committer", FilterBy::COMMITTER),
+    ("message", FilterBy::MESSAGE),
+    ("body", FilterBy::BODY),
+    ("tags", FilterBy::TAGS),
+];
+
+/// `:name` terms matching commits whose subject starts with the
+/// prefix git uses for them, `:!name` for the other commits
+const SUBJECT_PREFIXES: [(&str, &str); 3] = [
+    ("revert", "Revert "),
+    ("fixup", "fixup! "),
+    ("squash", "squash! "),
+];
+
+/// `:name` terms matching commits by their signature (negated
+/// if the flag is set), `:!name` for the other commits
+const SIGNATURE_TERMS: [(&str, FilterBy, bool); 3] = [
+    ("signed", FilterBy::SIGNED, false),
+    ("unsigned", FilterBy::SIGNED, true),
+    ("verified", FilterBy::VERIFIED, false),
+];
+
+/// groups nested deeper than this are flattened into their parent,
+/// keeping their negation
+const MAX_GROUP_DEPTH: usize = 32;
+
+/// names of the fields in the breadcrumb of a query
+const BREADCRUMB_FIELDS: [(&str, FilterBy); 10] = [
+    ("sha", FilterBy::SHA),
+    ("author", FilterBy::AUTHOR),
+    ("committer", FilterBy::COMMITTER),
+    ("msg", FilterBy::MESSAGE),
+    ("body", FilterBy::BODY),
+    ("tags", FilterBy::TAGS),
+    ("trailer", FilterBy::TRAILER),
+    ("prefix", FilterBy::SUBJECT_PREFIX),
+    ("parent", FilterBy::PARENT),
+    ("long", FilterBy::SUBJECT_LONGER),
+];
+
+/// `OR`ed groups the breadcrumb lists before cutting off
+const MAX_BREADCRUMB_CLAUSES: usize = 16;
+
+/// terms of an `AND` group, negated if the flag is set
+type Clause<'a> = Vec<(&'a FilterTerm, bool)>;
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    And,
+    Or,
+    Open,
+    NotOpen,
+    Close,
+    Term(&'a str),
+}
+
+/// collapses runs of whitespace into single spaces,
+/// input beyond `MAX_FILTER_LEN` chars is ignored
+fn pre_process_string(s: &str) -> String {
+    let s = s
+        .char_indices()
+        .nth(MAX_FILTER_LEN)
+        .map_or(s, |(pos, _)| &s[..pos]);
+
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// the fields named in `names` (see `FILTER_FIELDS`),
+/// fails on unknown names or if there are none
+pub fn parse_filter_fields<S: AsRef<str>>(
+    names: &[S],
+) -> Result<FilterBy> {
+    let mut by = FilterBy::empty();
+
+    for name in names {
+        let name = name.as_ref().trim();
+        match FILTER_FIELDS.iter().find(|(n, _)| *n == name) {
+            Some((_, field)) => by |= *field,
+            None => bail!(strings::unknown_filter_field_msg(
+                name,
+                &filter_fields_to_string(FilterBy::all())
+            )),
+        }
+    }
+
+    if by.is_empty() {
+        bail!(strings::no_filter_fields_msg());
+    }
+
+    Ok(by)
+}
+
+/// names of the fields in `by`, separated by `, `
+pub fn filter_fields_to_string(by: FilterBy) -> String {
+    FILTER_FIELDS
+        .iter()
+        .filter(|(_, field)| by.contains(*field))
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// splits a leading `:top N` off a find query, restricting the
+/// filter to the `N` most recent commits.
+/// without a positive number after `:top` the query is returned as is
+pub fn split_scan_limit(
+    filter_by_str: &str,
+) -> (Option<usize>, &str) {
+    let trimmed = filter_by_str.trim_start();
+    let mut parts = trimmed.splitn(3, char::is_whitespace);
+
+    if parts.next() == Some(":top") {
+        if let Some(limit) = parts
+            .next()
+            .and_then(|n| n.parse::<usize>().ok())
+            .filter(|n| *n > 0)
+        {
+            return (Some(limit), parts.next().unwrap_or_default());
+        }
+    }
+
+    (None, filter_by_str)
+}
+
+/// splits a leading `:L start,end:path` (or `:L start,+count:path`)
+/// off a find query, restricting the filter to commits that changed
+/// these lines of `path` (like `git log -L`).
+/// without a valid range the query is returned as is
+pub fn split_line_range(
+    filter_by_str: &str,
+) -> (Option<LineRange>, &str) {
+    let trimmed = filter_by_str.trim_start();
+    let mut parts = trimmed.splitn(3, char::is_whitespace);
+
+    if parts.next() == Some(":L") {
+        if let Some(range) = parts.next().and_then(parse_line_range) {
+            return (Some(range), parts.next().unwrap_or_default());
+        }
+    }
+
+    (None, filter_by_str)
+}
+
+fn parse_line_range(s: &str) -> Option<LineRange> {
+    let (lines, path) = s.split_once(':')?;
+    let (start, end) = lines.split_once(',')?;
+    let start = start.parse::<usize>().ok().filter(|n| *n > 0)?;
+    let end = match end.strip_prefix('+') {
+        Some(count) => {
+            let count =
+                count.parse::<usize>().ok().filter(|n| *n > 0)?;
+            start.checked_add(count)?.checked_sub(1)?
+        }
+        None => end.parse::<usize>().ok()?,
+    };
+
+    (!path.is_empty() && end >= start).then(|| LineRange {
+        path: path.to_string(),
+        start,
+        end,
+    })
+}
+
+/// parses a find query, `None` if it contains no terms.
+/// whatever is malformed (unbalanced parens, operators without
+/// a term..) is skipped, `parse_filter` tells what it was
+///
+/// terms are joined by `&&` and `||` (binding weaker) and can be
+/// grouped in `(..)`, `:!(..)` negates a whole group.
+/// a term can be prefixed with `:` followed by the fields to search
+/// (`s`ha, `a`uthor, c`o`mmitter, `p`erson for author or committer,
+/// `m`essage subject, whole message `b`ody, `t`ag) and modifiers
+/// (`!` negate, `c` case sensitive, `=` exact author/committer),
+/// e.g. `:a= bob && :!(:m wip || :t nightly)`.
+/// `:trailer key` and `:trailer key=value` match message trailers
+/// like `Signed-off-by`, modifiers follow directly (`:trailer! ..`).
+/// `:T key=value` is the same, `:T value` looks at all trailer values.
+/// `:signed`, `:unsigned` and `:verified` match by the signature of
+/// a commit, `:verified` has to run `git verify-commit` for each.
+/// `:parent sha` matches the children of a commit, the sha can be
+/// abbreviated (`:parent! sha` for all other commits).
+/// `:long 50` matches subjects longer than 50 chars
+/// (`:long! 50` for the others)
+///
+/// terms without `:` prefix are matched against the fields
+/// in `everywhere` (`FilterBy::EVERYWHERE` unless configured
+/// otherwise, see `parse_filter_fields`)
+///
+/// only the first `MAX_FILTER_LEN` chars are considered
+pub fn get_what_to_filter_by(
+    filter_by_str: &str,
+    everywhere: FilterBy,
+) -> Option<FilterQuery> {
+    parse_query(filter_by_str, everywhere).0
+}
+
+/// parses a find query (see `get_what_to_filter_by`) into its
+/// tree of terms, failing on the first syntax error or if it
+/// contains no terms instead of making the best of it
+pub fn parse_filter(
+    filter_by_str: &str,
+    everywhere: FilterBy,
+) -> Result<FilterQuery> {
+    let (query, problem) = parse_query(filter_by_str, everywhere);
+    if let Some(problem) = problem {
+        bail!(problem);
+    }
+
+    query.ok_or_else(|| anyhow!("no terms to look for"))
+}
+
+/// the query and the first syntax problem skipped parsing it
+fn parse_query(
+    filter_by_str: &str,
+    everywhere: FilterBy,
+) -> (Option<FilterQuery>, Option<String>) {
+    let filter_by_str = pre_process_string(filter_by_str);
+    let mut parser = Parser {
+        tokens: tokenize(&filter_by_str),
+        pos: 0,
+        depth: 0,
+        everywhere,
+        problem: None,
+    };
+
+    let mut queries = Vec::new();
+    while parser.pos < parser.tokens.len() {
+        queries.extend(parser.or());
+        if parser.pos < parser.tokens.len() {
+            parser.report("`)` without `(`".to_string());
+        }
+        // skip unbalanced `)`
+        parser.pos += 1;
+    }
+
+    (combine(queries, FilterQuery::And), parser.problem)
+}
+
+fn tokenize(s: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = s.trim_start();
+
+    while !rest.is_empty() {
+        let (token, len) = if rest.starts_with("&&") {
+            (Token::And, 2)
+        } else if rest.starts_with("||") {
+            (Token::Or, 2)
+        } else if rest.starts_with(":!(") {
+            (Token::NotOpen, 3)
+        } else if rest.starts_with('(') {
+            (Token::Open, 1)
+        } else if rest.starts_with(')') {
+            (Token::Close, 1)
+        } else {
+            let len = ["&&", "||", ")"]
+                .iter()
+                .filter_map(|sep| rest.find(sep))
+                .min()
+                .unwrap_or(rest.len());
+            (Token::Term(rest[..len].trim()), len)
+        };
+
+        tokens.push(token);
+        rest = rest[len..].trim_start();
+    }
+
+    tokens
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+    depth: usize,
+    everywhere: FilterBy,
+    /// the first thing skipped as malformed
+    problem: Option<String>,
+}
+
+impl Parser<'_> {
+    fn or(&mut self) -> Option<FilterQuery> {
+        let mut queries = Vec::new();
+        loop {
+            queries.extend(self.and());
+            if !self.consume(&Token::Or) {
+                break;
+            }
+        }
+        combine(queries, FilterQuery::Or)
+    }
+
+    fn and(&mut self) -> Option<FilterQuery> {
+        let mut queries = Vec::new();
+        loop {
+            queries.extend(self.primary());
+            if !self.consume(&Token::And) {
+                break;
+            }
+        }
+        combine(queries, FilterQuery::And)
+    }
+
+    fn primary(&mut self) -> Option<FilterQuery> {
+        let negated =
+            self.depth >= MAX_GROUP_DEPTH && self.skip_deep_groups();
+        let query = self.operand();
+
+        if negated {
+            query.map(|query| FilterQuery::Not(Box::new(query)))
+        } else {
+            query
+        }
+    }
+
+    /// skips the groups opened here to bound the recursion, their
+    /// `)` are skipped as unbalanced later.
+    /// `true` if an odd number of them was negated
+    fn skip_deep_groups(&mut self) -> bool {
+        let mut nested = false;
+        let mut negated = false;
+        loop {
+            if self.consume(&Token::NotOpen) {
+                negated = !negated;
+            } else if !self.consume(&Token::Open) {
+                break;
+            }
+            nested = true;
+        }
+        if nested {
+            self.report(format!(
+                "groups nested deeper than {}",
+                MAX_GROUP_DEPTH
+            ));
+        }
+        negated
+    }
+
+    fn operand(&mut self) -> Option<FilterQuery> {
+        if self.consume(&Token::Open) {
+            self.group()
+        } else if self.consume(&Token::NotOpen) {
+            self.group()
+                .map(|query| FilterQuery::Not(Box::new(query)))
+        } else if let Some(Token::Term(term)) =
+            self.tokens.get(self.pos)
+        {
+            self.pos += 1;
+            let query = parse_term(term, self.everywhere)
+                .map(FilterQuery::Term);
+            if query.is_none() {
+                self.report(format!(
+                    "`{}` has nothing to look for",
+                    term
+                ));
+            }
+            query
+        } else {
+            self.report(
+                match self.tokens.get(self.pos) {
+                    Some(Token::And) => {
+                        "`&&` without a term before it"
+                    }
+                    Some(Token::Or) => {
+                        "`||` without a term before it"
+                    }
+                    Some(Token::Close) => {
+                        "`)` without a term before it"
+                    }
+                    _ => "a term is missing at the end",
+                }
+                .to_string(),
+            );
+            None
+        }
+    }
+
+    fn group(&mut self) -> Option<FilterQuery> {
+        self.depth += 1;
+        let query = self.or();
+        self.depth -= 1;
+        if !self.consume(&Token::Close) {
+            self.report("`(` without `)`".to_string());
+        }
+        query
+    }
+
+    /// keeps `problem` unless there was one before
+    fn report(&mut self, problem: String) {
+        self.problem.get_or_insert(problem);
+    }
+
+    fn consume(&mut self, token: &Token) -> bool {
+        let found = self.tokens.get(self.pos) == Some(token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+}
+
+fn combine(
+    mut queries: Vec<FilterQuery>,
+    f: fn(Vec<FilterQuery>) -> FilterQuery,
+) -> Option<FilterQuery> {
+    if queries.len() > 1 {
+        Some(f(queries))
+    } else {
+        queries.pop()
+    }
+}
+
+fn parse_term(
+    term: &str,
+    everywhere: FilterBy,
+) -> Option<FilterTerm> {
+    if let Some(term) = parse_subject_prefix(term)
+        .or_else(|| parse_signature_term(term))
+        .or_else(|| parse_parent_term(term))
+    {
+        return Some(term);
+    }
+    // a missing or invalid length is not a search for `ong`
+    if term.starts_with(":long") {
+        return parse_long_term(term);
+    }
+
+    let (by, text) =
+        term.strip_prefix(':').map_or((everywhere, term), |rest| {
+            let (flags, text) = rest.split_at(
+                rest.find(char::is_whitespace).unwrap_or(rest.len()),
+            );
+            let by = flags
+                .strip_prefix("trailer")
+                .or_else(|| flags.strip_prefix('T'))
+                .map_or_else(
+                    || parse_flags(flags, everywhere),
+                    |modifiers| {
+                        parse_flags(modifiers, everywhere)
+                            & (FilterBy::NOT
+                                | FilterBy::CASE_SENSITIVE
+                                | FilterBy::EXACT)
+                            | FilterBy::TRAILER
+                    },
+                );
+            (by, text.trim())
+        });
+
+    if text.is_empty() {
+        None
+    } else if term.starts_with(":T") && !text.contains('=') {
+        // an empty key matches any trailer
+        Some(FilterTerm::new(&format!("={}", text), by))
+    } else {
+        Some(FilterTerm::new(text, by))
+    }
+}
+
+/// `:revert`, `:!fixup` and the like (see `SUBJECT_PREFIXES`)
+fn parse_subject_prefix(term: &str) -> Option<FilterTerm> {
+    let name = term.strip_prefix(':')?;
+    let (name, not) = name
+        .strip_prefix('!')
+        .map_or((name, FilterBy::empty()), |name| {
+            (name, FilterBy::NOT)
+        });
+
+    SUBJECT_PREFIXES.iter().find(|(n, _)| *n == name).map(
+        |(_, prefix)| {
+            FilterTerm::new(
+                prefix,
+                FilterBy::SUBJECT_PREFIX
+                    | FilterBy::CASE_SENSITIVE
+                    | not,
+            )
+        },
+    )
+}
+
+/// `:signed`, `:!verified` and the like (see `SIGNATURE_TERMS`)
+fn parse_signature_term(term: &str) -> Option<FilterTerm> {
+    let name = term.strip_prefix(':')?;
+    let (name, not) = name
+        .strip_prefix('!')
+        .map_or((name, false), |name| (name, true));
+
+    SIGNATURE_TERMS.iter().find(|(n, _, _)| *n == name).map(
+        |(_, by, negated)| {
+            FilterTerm::new(
+                "",
+                if not == *negated {
+                    *by
+                } else {
+                    *by | FilterBy::NOT
+                },
+            )
+        },
+    )
+}
+
+/// `:parent sha` and `:parent! sha`
+fn parse_parent_term(term: &str) -> Option<FilterTerm> {
+    let rest = term.strip_prefix(":parent")?;
+    let (not, sha) = rest
+        .strip_prefix('!')
+        .map_or((FilterBy::empty(), rest), |sha| {
+            (FilterBy::NOT, sha)
+        });
+    let sha = sha.strip_prefix(char::is_whitespace)?.trim();
+
+    (!sha.is_empty())
+        .then(|| FilterTerm::new(sha, FilterBy::PARENT | not))
+}
+
+/// `:long n` and `:long! n`
+fn parse_long_term(term: &str) -> Option<FilterTerm> {
+    let rest = term.strip_prefix(":long")?;
+    let (not, limit) = rest
+        .strip_prefix('!')
+        .map_or((FilterBy::empty(), rest), |limit| {
+            (FilterBy::NOT, limit)
+        });
+    let limit = limit
+        .strip_prefix(char::is_whitespace)?
+        .trim()
+        .parse::<usize>()
+        .ok()?;
+
+    Some(FilterTerm::new(
+        &limit.to_string(),
+        FilterBy::SUBJECT_LONGER | not,
+    ))
+}
+
+fn parse_flags(flags: &str, everywhere: FilterBy) -> FilterBy {
+    let mut by = flags.chars().fold(FilterBy::empty(), |by, c| {
+        by | match c {
+            's' => FilterBy::SHA,
+            'a' => FilterBy::AUTHOR,
+            'o' => FilterBy::COMMITTER,
+            'p' => FilterBy::AUTHOR | FilterBy::COMMITTER,
+            'm' => FilterBy::MESSAGE,
+            'b' => FilterBy::BODY,
+            't' => FilterBy::TAGS,
+            '!' => FilterBy::NOT,
+            'c' => FilterBy::CASE_SENSITIVE,
+            '=' => FilterBy::EXACT,
+            _ => FilterBy::empty(),
+        }
+    });
+
+    if !by.intersects(
+        FilterBy::EVERYWHERE | FilterBy::COMMITTER | FilterBy::BODY,
+    ) {
+        by |= everywhere;
+    }
+
+    by
+}
+
+/// the query in disjunctive normal form, e.g.
+/// `(author:foo AND msg:bar) OR (sha:baz)`, negations are moved
+/// down to the terms and only the first `MAX_BREADCRUMB_CLAUSES`
+/// groups are listed
+pub fn filter_breadcrumb(query: &FilterQuery) -> String {
+    let clauses = to_dnf(query, false);
+    let render = |clause: &Clause| {
+        clause
+            .iter()
+            .map(|(term, negated)| term_breadcrumb(term, *negated))
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    };
+
+    if clauses.len() == 1 {
+        return render(&clauses[0]);
+    }
+
+    let mut groups = clauses
+        .iter()
+        .take(MAX_BREADCRUMB_CLAUSES)
+        .map(|clause| format!("({})", render(clause)))
+        .collect::<Vec<_>>();
+    if clauses.len() > MAX_BREADCRUMB_CLAUSES {
+        groups.push(String::from("\u{2026}"));
+    }
+
+    groups.join(" OR ")
+}
+
+/// `OR`ed `AND` groups equal to `query` (negated if `negated`),
+/// at most one more than `MAX_BREADCRUMB_CLAUSES` to tell there
+/// are too many
+fn to_dnf(query: &FilterQuery, negated: bool) -> Vec<Clause> {
+    match (query, negated) {
+        (FilterQuery::Term(term), _) => vec![vec![(term, negated)]],
+        (FilterQuery::Not(query), _) => to_dnf(query, !negated),
+        (FilterQuery::Or(queries), false)
+        | (FilterQuery::And(queries), true) => queries
+            .iter()
+            .flat_map(|query| to_dnf(query, negated))
+            .take(MAX_BREADCRUMB_CLAUSES + 1)
+            .collect(),
+        (FilterQuery::And(queries), false)
+        | (FilterQuery::Or(queries), true) => {
+            queries.iter().fold(vec![Vec::new()], |clauses, query| {
+                let other = to_dnf(query, negated);
+                clauses
+                    .iter()
+                    .flat_map(|clause| {
+                        other.iter().map(move |terms| {
+                            [clause.as_slice(), terms].concat()
+                        })
+                    })
+                    .take(MAX_BREADCRUMB_CLAUSES + 1)
+                    .collect()
+            })
+        }
+    }
+}
+
+/// `author:foo`, `NOT msg:wip`, `author=Bob [case]`..
+fn term_breadcrumb(term: &FilterTerm, negated: bool) -> String {
+    let negated = negated != term.by.contains(FilterBy::NOT);
+    let not = if negated { "NOT " } else { "" };
+
+    if term.by.contains(FilterBy::SIGNED) {
+        return format!("{}signed", not);
+    }
+    if term.by.contains(FilterBy::VERIFIED) {
+        return format!("{}verified", not);
+    }
+
+    let fields = BREADCRUMB_FIELDS
+        .iter()
+        .filter(|(_, field)| term.by.contains(*field))
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join("|");
+
+    format!(
+        "{}{}{}{}{}",
+        not,
+        fields,
+        if term.by.contains(FilterBy::EXACT) {
+            "="
+        } else {
+            ":"
+        },
+        term.text,
+        if term.by.contains(FilterBy::CASE_SENSITIVE) {
+            " [case]"
+        } else {
+            ""
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Option<FilterQuery> {
+        get_what_to_filter_by(s, FilterBy::EVERYWHERE)
+    }
+
+    fn term(text: &str, by: FilterBy) -> FilterQuery {
+        FilterQuery::Term(FilterTerm::new(text, by))
+    }
+
+    fn any(text: &str) -> FilterQuery {
+        term(text, FilterBy::EVERYWHERE)
+    }
+
+    fn not(query: FilterQuery) -> FilterQuery {
+        FilterQuery::Not(Box::new(query))
+    }
+
+    #[test]
+    fn test_plain_term() {
+        assert_eq!(parse("  Foo   bar "), Some(any("foo bar")));
+        assert_eq!(parse("  "), None);
+        assert_eq!(parse("&& ||"), None);
+    }
+
+    #[test]
+    fn test_scan_limit() {
+        assert_eq!(
+            split_scan_limit(" :top 5000 foo && :a bob"),
+            (Some(5000), "foo && :a bob")
+        );
+        assert_eq!(split_scan_limit(":top 10"), (Some(10), ""));
+        assert_eq!(
+            parse(split_scan_limit(":top 10 foo").1),
+            Some(any("foo"))
+        );
+
+        // not a directive, `:top` stays a term prefix
+        assert_eq!(split_scan_limit(":top foo"), (None, ":top foo"));
+        assert_eq!(
+            split_scan_limit(":top 0 foo"),
+            (None, ":top 0 foo")
+        );
+        assert_eq!(
+            split_scan_limit("foo :top 5"),
+            (None, "foo :top 5")
+        );
+    }
+
+    #[test]
+    fn test_filter_fields() {
+        let by =
+            parse_filter_fields(&["author", " message "]).unwrap();
+        assert_eq!(by, FilterBy::AUTHOR | FilterBy::MESSAGE);
+        assert_eq!(filter_fields_to_string(by), "author, message");
+        assert_eq!(
+            parse_filter_fields(&[
+                "sha", "author", "message", "tags"
+            ])
+            .unwrap(),
+            FilterBy::EVERYWHERE
+        );
+
+        // hex fragments no longer match shas
+        assert_eq!(
+            get_what_to_filter_by("cafe", by),
+            Some(term("cafe", by))
+        );
+        // explicit fields are not affected
+        assert_eq!(
+            get_what_to_filter_by(":s cafe", by),
+            Some(term("cafe", FilterBy::SHA))
+        );
+
+        let err = parse_filter_fields(&["author", "shaa"])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("`shaa`"));
+        assert!(err.contains("sha, author, committer"));
+        assert!(parse_filter_fields::<&str>(&[]).is_err());
+    }
+
+    #[test]
+    fn test_line_range() {
+        let range = |path: &str, start, end| LineRange {
+            path: path.to_string(),
+            start,
+            end,
+        };
+
+        assert_eq!(
+            split_line_range(" :L 10,20:src/foo.rs :a bob"),
+            (Some(range("src/foo.rs", 10, 20)), ":a bob")
+        );
+        assert_eq!(
+            split_line_range(":L 10,+5:foo.rs"),
+            (Some(range("foo.rs", 10, 14)), "")
+        );
+        assert_eq!(
+            split_line_range(":L 3,3:foo.rs"),
+            (Some(range("foo.rs", 3, 3)), "")
+        );
+
+        // not a valid range, `:L` stays a term prefix
+        for query in &[
+            ":L foo",
+            ":L 0,3:foo.rs",
+            ":L 5,3:foo.rs",
+            ":L 3,+0:foo.rs",
+            ":L 1,+18446744073709551615:file",
+            ":L 3,5:",
+            ":L 3,5 foo.rs",
+            "foo :L 1,2:foo.rs",
+        ] {
+            assert_eq!(split_line_range(query), (None, *query));
+        }
+    }
+
+    #[test]
+    fn test_and_or() {
+        assert_eq!(
+            parse(":a bob && :m fix || :s abc"),
+            Some(FilterQuery::Or(vec![
+                FilterQuery::And(vec![
+                    term("bob", FilterBy::AUTHOR),
+                    term("fix", FilterBy::MESSAGE),
+                ]),
+                term("abc", FilterBy::SHA),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_groups() {
+        assert_eq!(
+            parse("a && (b || c)"),
+            Some(FilterQuery::And(vec![
+                any("a"),
+                FilterQuery::Or(vec![any("b"), any("c")]),
+            ]))
+        );
+        // unbalanced parens are tolerated
+        assert_eq!(
+            parse("(a || b"),
+            Some(FilterQuery::Or(vec![any("a"), any("b")]))
+        );
+        assert_eq!(
+            parse("a) && b"),
+            Some(FilterQuery::And(vec![any("a"), any("b")]))
+        );
+    }
+
+    #[test]
+    fn test_negated_groups() {
+        assert_eq!(
+            parse(":!(foo || bar)"),
+            Some(not(FilterQuery::Or(vec![any("foo"), any("bar")])))
+        );
+        assert_eq!(
+            parse("x && :!(foo || :!(:a bar))"),
+            Some(FilterQuery::And(vec![
+                any("x"),
+                not(FilterQuery::Or(vec![
+                    any("foo"),
+                    not(term("bar", FilterBy::AUTHOR)),
+                ])),
+            ]))
+        );
+        assert_eq!(parse(":!()"), None);
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        let strict = |s| {
+            parse_filter(s, FilterBy::EVERYWHERE)
+                .map_err(|e| e.to_string())
+        };
+
+        assert_eq!(
+            strict(":!(a || :a b) && c").unwrap(),
+            FilterQuery::And(vec![
+                not(FilterQuery::Or(vec![
+                    any("a"),
+                    term("b", FilterBy::AUTHOR),
+                ])),
+                any("c"),
+            ])
+        );
+        assert_eq!(strict(" a ").unwrap(), any("a"));
+
+        let error = |s| strict(s).unwrap_err();
+        assert_eq!(error("  "), "no terms to look for");
+        assert_eq!(error("(a || b"), "`(` without `)`");
+        assert_eq!(error("a) && b"), "`)` without `(`");
+        assert_eq!(
+            error("a && || b"),
+            "`||` without a term before it"
+        );
+        assert_eq!(error("&& a"), "`&&` without a term before it");
+        assert_eq!(error("(a ||)"), "`)` without a term before it");
+        assert_eq!(error("a &&"), "a term is missing at the end");
+        assert_eq!(error("a && :a"), "`:a` has nothing to look for");
+        assert_eq!(
+            error(&format!(
+                "{}a{}",
+                "(".repeat(MAX_GROUP_DEPTH + 1),
+                ")".repeat(MAX_GROUP_DEPTH + 1)
+            )),
+            format!("groups nested deeper than {}", MAX_GROUP_DEPTH)
+        );
+
+        // the lenient parser makes the best of the same input
+        assert_eq!(
+            parse("a && || b"),
+            Some(FilterQuery::Or(vec![any("a"), any("b")]))
+        );
+    }
+
+    #[test]
+    fn test_long_input() {
+        let long = "a".repeat(MAX_FILTER_LEN * 2);
+
+        assert_eq!(parse(&long), Some(any(&long[..MAX_FILTER_LEN])));
+    }
+
+    #[test]
+    fn test_deep_nesting() {
+        let depth = MAX_FILTER_LEN / 2 - 1;
+        let nested =
+            format!("{}a{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(parse(&nested), Some(any("a")));
+
+        let negated = format!(
+            "{}a{}",
+            ":!(".repeat(MAX_GROUP_DEPTH + 1),
+            ")".repeat(MAX_GROUP_DEPTH + 1)
+        );
+        let mut expected = any("a");
+        for _ in 0..=MAX_GROUP_DEPTH {
+            expected = not(expected);
+        }
+        assert_eq!(parse(&negated), Some(expected));
+
+        // two negations past the limit cancel out
+        let twice = format!(
+            "{}a{}",
+            ":!(".repeat(MAX_GROUP_DEPTH + 2),
+            ")".repeat(MAX_GROUP_DEPTH + 2)
+        );
+        let mut expected = any("a");
+        for _ in 0..MAX_GROUP_DEPTH {
+            expected = not(expected);
+        }
+        assert_eq!(parse(&twice), Some(expected));
+    }
+
+    #[test]
+    fn test_trailer() {
+        assert_eq!(
+            parse(":trailer Signed-off-by=Alice"),
+            Some(term("signed-off-by=alice", FilterBy::TRAILER))
+        );
+        assert_eq!(
+            parse(":trailer Co-authored-by"),
+            Some(term("co-authored-by", FilterBy::TRAILER))
+        );
+        assert_eq!(
+            parse(":trailer!= Acked-by=bob && fix"),
+            Some(FilterQuery::And(vec![
+                term(
+                    "acked-by=bob",
+                    FilterBy::TRAILER
+                        | FilterBy::NOT
+                        | FilterBy::EXACT
+                ),
+                any("fix"),
+            ]))
+        );
+        assert_eq!(parse(":trailer"), None);
+
+        assert_eq!(
+            parse(":T Reviewed-by=Alice"),
+            Some(term("reviewed-by=alice", FilterBy::TRAILER))
+        );
+        assert_eq!(
+            parse(":T! alice"),
+            Some(term("=alice", FilterBy::TRAILER | FilterBy::NOT))
+        );
+        assert_eq!(parse(":T"), None);
+    }
+
+    #[test]
+    fn test_subject_prefix() {
+        let prefix =
+            FilterBy::SUBJECT_PREFIX | FilterBy::CASE_SENSITIVE;
+
+        assert_eq!(parse(":revert"), Some(term("Revert ", prefix)));
+        assert_eq!(parse(":fixup"), Some(term("fixup! ", prefix)));
+        assert_eq!(parse(":squash"), Some(term("squash! ", prefix)));
+        assert_eq!(
+            parse(":!fixup && :!squash"),
+            Some(FilterQuery::And(vec![
+                term("fixup! ", prefix | FilterBy::NOT),
+                term("squash! ", prefix | FilterBy::NOT),
+            ]))
+        );
+        assert_eq!(
+            parse(":revert || :a bob"),
+            Some(FilterQuery::Or(vec![
+                term("Revert ", prefix),
+                term("bob", FilterBy::AUTHOR),
+            ]))
+        );
+
+        // without `:` it is just text
+        assert_eq!(parse("revert"), Some(any("revert")));
+    }
+
+    #[test]
+    fn test_signature_terms() {
+        let signed = FilterBy::SIGNED;
+        let unsigned = FilterBy::SIGNED | FilterBy::NOT;
+
+        assert_eq!(parse(":signed"), Some(term("", signed)));
+        assert_eq!(parse(":unsigned"), Some(term("", unsigned)));
+        assert_eq!(parse(":!signed"), Some(term("", unsigned)));
+        assert_eq!(parse(":!unsigned"), Some(term("", signed)));
+        assert_eq!(
+            parse(":verified"),
+            Some(term("", FilterBy::VERIFIED))
+        );
+        assert_eq!(
+            parse(":unsigned && :a bob || :!verified"),
+            Some(FilterQuery::Or(vec![
+                FilterQuery::And(vec![
+                    term("", unsigned),
+                    term("bob", FilterBy::AUTHOR),
+                ]),
+                term("", FilterBy::VERIFIED | FilterBy::NOT),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_parent() {
+        let parent = FilterBy::PARENT;
+
+        assert_eq!(
+            parse(":parent abc123"),
+            Some(term("abc123", parent))
+        );
+        assert_eq!(
+            parse(":parent ABC123"),
+            Some(term("abc123", parent))
+        );
+        assert_eq!(
+            parse(":parent! abc123"),
+            Some(term("abc123", parent | FilterBy::NOT))
+        );
+        assert_eq!(
+            parse(":parent abc || :parent def && :a bob"),
+            Some(FilterQuery::Or(vec![
+                term("abc", parent),
+                FilterQuery::And(vec![
+                    term("def", parent),
+                    term("bob", FilterBy::AUTHOR),
+                ]),
+            ]))
+        );
+        // `:p` still searches authors and committers
+        assert_eq!(
+            parse(":p bob"),
+            Some(term("bob", FilterBy::AUTHOR | FilterBy::COMMITTER))
+        );
+        assert!(
+            parse_filter(":parent", FilterBy::EVERYWHERE).is_err()
+        );
+    }
+
+    #[test]
+    fn test_long() {
+        let long = FilterBy::SUBJECT_LONGER;
+
+        assert_eq!(parse(":long 50"), Some(term("50", long)));
+        assert_eq!(parse(":long 072"), Some(term("72", long)));
+        assert_eq!(
+            parse(":long! 50 && :a bob"),
+            Some(FilterQuery::And(vec![
+                term("50", long | FilterBy::NOT),
+                term("bob", FilterBy::AUTHOR),
+            ]))
+        );
+        for invalid in
+            &[":long", ":long fifty", ":long -1", ":longer 5"]
+        {
+            assert!(
+                parse_filter(invalid, FilterBy::EVERYWHERE).is_err()
+            );
+        }
+    }
+
+    #[test]
+    fn test_person() {
+        assert_eq!(
+            parse(":p bob@example.com"),
+            Some(term(
+                "bob@example.com",
+                FilterBy::AUTHOR | FilterBy::COMMITTER
+            ))
+        );
+        assert_eq!(
+            parse(":p= Bob"),
+            Some(term(
+                "bob",
+                FilterBy::AUTHOR
+                    | FilterBy::COMMITTER
+                    | FilterBy::EXACT
+            ))
+        );
+    }
+
+    #[test]
+    fn test_modifiers() {
+        assert_eq!(
+            parse(":a= Bob"),
+            Some(term("bob", FilterBy::AUTHOR | FilterBy::EXACT))
+        );
+        assert_eq!(
+            parse(":o=c Bob"),
+            Some(term(
+                "Bob",
+                FilterBy::COMMITTER
+                    | FilterBy::EXACT
+                    | FilterBy::CASE_SENSITIVE
+            ))
+        );
+        assert_eq!(
+            parse(":! wip"),
+            Some(term("wip", FilterBy::EVERYWHERE | FilterBy::NOT))
+        );
+    }
+
+    #[test]
+    fn test_subject_and_body() {
+        assert_eq!(
+            parse(":m fix && :b lexer"),
+            Some(FilterQuery::And(vec![
+                term("fix", FilterBy::MESSAGE),
+                term("lexer", FilterBy::BODY),
+            ]))
+        );
+        assert_eq!(
+            parse(":ab bob"),
+            Some(term("bob", FilterBy::AUTHOR | FilterBy::BODY))
+        );
+
+        let everywhere = FilterBy::EVERYWHERE | FilterBy::BODY;
+        assert_eq!(
+            get_what_to_filter_by("fix", everywhere),
+            Some(term("fix", everywhere))
+        );
+        assert_eq!(
+            get_what_to_filter_by(":! wip", everywhere),
+            Some(term("wip", everywhere | FilterBy::NOT))
+        );
+        assert_eq!(
+            get_what_to_filter_by(":m fix", everywhere),
+            Some(term("fix", FilterBy::MESSAGE))
+        );
+    }
+
+    #[test]
+    fn test_filter_breadcrumb() {
+        let crumb = |s: &str| filter_breadcrumb(&parse(s).unwrap());
+
+        assert_eq!(crumb(":a foo"), "author:foo");
+        assert_eq!(crumb("foo"), "sha|author|msg|tags:foo");
+        assert_eq!(
+            crumb(":a foo && :m bar || :s baz"),
+            "(author:foo AND msg:bar) OR (sha:baz)"
+        );
+        // `AND` over `OR` is multiplied out
+        assert_eq!(
+            crumb(":a foo && (:m bar || :m baz)"),
+            "(author:foo AND msg:bar) OR (author:foo AND msg:baz)"
+        );
+        // negations end up at the terms
+        assert_eq!(
+            crumb(":!(:m wip || :t nightly) && :a= Bob"),
+            "NOT msg:wip AND NOT tags:nightly AND author=bob"
+        );
+        assert_eq!(
+            crumb(":!(:m a && :!m b)"),
+            "(NOT msg:a) OR (msg:b)"
+        );
+        assert_eq!(
+            crumb(":ac Bob && :unsigned && :trailer! fixes=1"),
+            "author:Bob [case] AND NOT signed AND NOT trailer:fixes=1"
+        );
+        assert_eq!(crumb(":parent abc"), "parent:abc");
+        assert_eq!(crumb(":long! 50"), "NOT long:50");
+
+        // too many groups are cut off
+        let many = (0..10)
+            .map(|i| format!("(:a a{0} || :a b{0})", i))
+            .collect::<Vec<_>>()
+            .join(" && ");
+        let crumb = crumb(&many);
+        assert_eq!(
+            crumb.matches(" OR ").count(),
+            MAX_BREADCRUMB_CLAUSES
+        );
+        assert!(crumb.ends_with(" OR \u{2026}"));
+    }
+}
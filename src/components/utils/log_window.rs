@@ -0,0 +1,132 @@
+use anyhow::{bail, Result};
+use asyncgit::sync::LogWindow;
+
+/// windows `log_cycle_window` goes through in this order,
+/// the full history follows the last one
+pub const LOG_WINDOW_PRESETS: [&str; 4] =
+    ["last 1000", "2 weeks", "3 months", "1 year"];
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+/// units of `N <unit>` windows in seconds, months and years
+/// are rounded to 30 and 365 days
+const UNITS: [(&str, i64); 4] = [
+    ("day", DAY_SECS),
+    ("week", 7 * DAY_SECS),
+    ("month", 30 * DAY_SECS),
+    ("year", 365 * DAY_SECS),
+];
+
+/// parses a log window like `last 500` (or just `500`),
+/// `2 weeks` (`since 2 weeks ago` works as well) or `since v1.0`
+/// (any revision), relative times count back from `now`
+pub fn parse_log_window(text: &str, now: i64) -> Result<LogWindow> {
+    let text = text.trim();
+    let rest = text.strip_prefix("since ").unwrap_or(text).trim();
+    let words: Vec<&str> = rest.split_whitespace().collect();
+
+    match words.as_slice() {
+        [] => bail!("empty log window"),
+        ["last", count] | [count]
+            if count.chars().all(|c| c.is_ascii_digit()) =>
+        {
+            match count.parse::<usize>() {
+                Ok(count) if count > 0 => Ok(LogWindow::Last(count)),
+                _ => bail!("invalid commit count in `{}`", text),
+            }
+        }
+        [count, unit] | [count, unit, "ago"] => {
+            let count: i64 = match count.parse() {
+                Ok(count) => count,
+                Err(_) => bail!("invalid amount in `{}`", text),
+            };
+            let unit = unit.strip_suffix('s').unwrap_or(unit);
+
+            match UNITS.iter().find(|(name, _)| *name == unit) {
+                Some((_, secs)) => Ok(LogWindow::Since(
+                    now.saturating_sub(count.saturating_mul(*secs)),
+                )),
+                None => bail!(
+                    "unknown unit in `{}`, expected days, weeks, months or years",
+                    text
+                ),
+            }
+        }
+        [rev] => Ok(LogWindow::SinceRev((*rev).to_string())),
+        _ => bail!("invalid log window `{}`", text),
+    }
+}
+
+/// the preset following `current` (the first one unless it is
+/// a preset), `None` after the last one
+pub fn next_log_window(
+    current: Option<&str>,
+) -> Option<&'static str> {
+    let next = current.map_or(0, |current| {
+        LOG_WINDOW_PRESETS
+            .iter()
+            .position(|preset| *preset == current)
+            .map_or(0, |idx| idx + 1)
+    });
+
+    LOG_WINDOW_PRESETS.get(next).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_window() {
+        let now = 100 * DAY_SECS;
+        let parse = |text| parse_log_window(text, now).ok();
+
+        assert_eq!(parse("last 500"), Some(LogWindow::Last(500)));
+        assert_eq!(parse(" 20 "), Some(LogWindow::Last(20)));
+        assert_eq!(parse("last 0"), None);
+
+        assert_eq!(
+            parse("2 weeks"),
+            Some(LogWindow::Since(now - 14 * DAY_SECS))
+        );
+        assert_eq!(
+            parse("since 1 day ago"),
+            Some(LogWindow::Since(now - DAY_SECS))
+        );
+        assert_eq!(
+            parse("3 months"),
+            Some(LogWindow::Since(now - 90 * DAY_SECS))
+        );
+        assert_eq!(parse("2 fortnights"), None);
+        assert_eq!(parse("a week"), None);
+
+        assert_eq!(
+            parse("since v2.3.0"),
+            Some(LogWindow::SinceRev("v2.3.0".into()))
+        );
+        assert_eq!(
+            parse("HEAD~10"),
+            Some(LogWindow::SinceRev("HEAD~10".into()))
+        );
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("since a b c d"), None);
+
+        for preset in &LOG_WINDOW_PRESETS {
+            assert!(parse(preset).is_some());
+        }
+    }
+
+    #[test]
+    fn test_next_log_window() {
+        assert_eq!(next_log_window(None), Some("last 1000"));
+        assert_eq!(
+            next_log_window(Some("last 1000")),
+            Some("2 weeks")
+        );
+        assert_eq!(next_log_window(Some("1 year")), None);
+        assert_eq!(
+            next_log_window(Some("since v1")),
+            Some("last 1000")
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use super::time_to_string;
+use crate::strings;
 use asyncgit::sync::{CommitId, CommitInfo};
 use std::slice::Iter;
 
@@ -24,6 +25,22 @@ impl From<CommitInfo> for LogEntry {
     }
 }
 
+/// a commit that failed to load shows up as placeholder
+impl From<Result<CommitInfo, CommitId>> for LogEntry {
+    fn from(c: Result<CommitInfo, CommitId>) -> Self {
+        c.map_or_else(
+            |id| Self {
+                author: String::new(),
+                msg: String::from(strings::log_unreadable_commit()),
+                time: String::new(),
+                hash_short: id.get_short_string(),
+                id,
+            },
+            Self::from,
+        )
+    }
+}
+
 ///
 #[derive(Default)]
 pub struct ItemBatch {
@@ -52,13 +69,13 @@ impl ItemBatch {
     }
 
     /// insert new batch of items
-    pub fn set_items(
+    pub fn set_items<T: Into<LogEntry>>(
         &mut self,
         start_index: usize,
-        commits: Vec<CommitInfo>,
+        commits: Vec<T>,
     ) {
         self.items.clear();
-        self.items.extend(commits.into_iter().map(LogEntry::from));
+        self.items.extend(commits.into_iter().map(Into::into));
         self.index_offset = start_index;
     }
 
@@ -1,14 +1,21 @@
 use chrono::{DateTime, Local, NaiveDateTime, Utc};
 
+pub mod commit_json;
+pub mod difftool;
 pub mod filetree;
+pub mod log_export;
+pub mod log_filter;
+pub mod log_window;
 pub mod logitems;
+pub mod rebase_todo;
 pub mod statustree;
+pub mod tag_template;
 
 /// macro to simplify running code that might return Err.
 /// It will show a popup in that case
 #[macro_export]
 macro_rules! try_or_popup {
-    ($self:ident, $msg:literal, $e:expr) => {
+    ($self:ident, $msg:expr, $e:expr) => {
         if let Err(err) = $e {
             $self.queue.borrow_mut().push_back(
                 InternalEvent::ShowErrorMsg(format!(
@@ -0,0 +1,47 @@
+use asyncgit::sync::CommitInfo;
+
+/// `git rebase -i` todo list picking `commits`,
+/// expects them newest first (log order) and lists the oldest first
+pub fn commits_to_rebase_todo(commits: &[CommitInfo]) -> String {
+    commits
+        .iter()
+        .rev()
+        .map(|c| {
+            format!(
+                "pick {} {}\n",
+                c.id.get_short_string(),
+                c.message.lines().next().unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asyncgit::sync::CommitId;
+
+    fn info(message: &str) -> CommitInfo {
+        CommitInfo {
+            message: message.to_string(),
+            time: 0,
+            author: String::from("bob"),
+            id: CommitId::default(),
+        }
+    }
+
+    #[test]
+    fn test_rebase_todo() {
+        let commits =
+            [info("third"), info("second\n\nbody"), info("first")];
+
+        assert_eq!(
+            commits_to_rebase_todo(&commits),
+            "pick 0000000 first\n\
+             pick 0000000 second\n\
+             pick 0000000 third\n"
+        );
+        assert_eq!(commits_to_rebase_todo(&[]), "");
+    }
+}
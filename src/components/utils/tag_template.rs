@@ -0,0 +1,181 @@
+use crate::{get_app_config_path, strings};
+use asyncgit::sync::CommitInfo;
+use std::{fs, path::Path};
+
+/// used unless the config dir contains tag templates
+pub const DEFAULT_TAG_TEMPLATE: &str =
+    "{tag} ({date})\n\nchanges since {previous}:\n{commits}";
+
+/// a tag message template and the name it is picked by
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagTemplate {
+    pub name: String,
+    pub text: String,
+}
+
+impl TagTemplate {
+    fn new(name: &str, text: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            text: text.to_string(),
+        }
+    }
+}
+
+/// templates of the config dir, see `load_tag_templates_from`
+pub fn load_tag_templates() -> Vec<TagTemplate> {
+    get_app_config_path().map_or_else(
+        |_| load_tag_templates_from(Path::new("")),
+        |path| load_tag_templates_from(&path),
+    )
+}
+
+/// the `tag_template` file and every file in `tag_templates`
+/// (by name) of `dir`, the default if there is none,
+/// always followed by a blank one
+pub fn load_tag_templates_from(dir: &Path) -> Vec<TagTemplate> {
+    let mut templates = Vec::new();
+
+    if let Ok(text) = fs::read_to_string(dir.join("tag_template")) {
+        templates.push(TagTemplate::new("tag_template", &text));
+    }
+
+    if let Ok(entries) = fs::read_dir(dir.join("tag_templates")) {
+        let mut files = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let name = path.file_name()?.to_str()?.to_string();
+                let text = fs::read_to_string(&path).ok()?;
+                Some(TagTemplate { name, text })
+            })
+            .collect::<Vec<_>>();
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+        templates.extend(files);
+    }
+
+    if templates.is_empty() {
+        templates.push(TagTemplate::new(
+            strings::tag_template_default_name(),
+            DEFAULT_TAG_TEMPLATE,
+        ));
+    }
+    templates.push(TagTemplate::new(
+        strings::tag_template_blank_name(),
+        "",
+    ));
+
+    templates
+}
+
+/// fills in `{tag}`, `{date}`, `{previous}` (the tag before) and
+/// `{commits}` (a `- <sha> <subject>` line per commit)
+#[allow(clippy::literal_string_with_formatting_args)]
+pub fn render_tag_template(
+    template: &str,
+    tag: &str,
+    date: &str,
+    previous: Option<&str>,
+    commits: &[CommitInfo],
+) -> String {
+    let commits = commits
+        .iter()
+        .map(|c| {
+            format!(
+                "- {} {}\n",
+                c.id.get_short_string(),
+                c.message.lines().next().unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .concat();
+
+    template
+        .replace("{tag}", tag)
+        .replace("{date}", date)
+        .replace(
+            "{previous}",
+            previous.unwrap_or_else(|| {
+                strings::tag_template_no_previous()
+            }),
+        )
+        .replace("{commits}", &commits)
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asyncgit::sync::CommitId;
+    use tempfile::TempDir;
+
+    fn info(message: &str) -> CommitInfo {
+        CommitInfo {
+            message: message.to_string(),
+            time: 0,
+            author: String::from("bob"),
+            id: CommitId::default(),
+        }
+    }
+
+    #[test]
+    fn test_default_template() {
+        let commits = [info("fix parser\n\nbody"), info("add docs")];
+
+        assert_eq!(
+            render_tag_template(
+                DEFAULT_TAG_TEMPLATE,
+                "v1.1",
+                "2020-12-24",
+                Some("v1.0"),
+                &commits
+            ),
+            "v1.1 (2020-12-24)\n\n\
+             changes since v1.0:\n\
+             - 0000000 fix parser\n\
+             - 0000000 add docs"
+        );
+        assert_eq!(
+            render_tag_template(
+                "{tag}: {previous}",
+                "v1",
+                "",
+                None,
+                &[]
+            ),
+            format!("v1: {}", strings::tag_template_no_previous())
+        );
+    }
+
+    #[test]
+    fn test_load_templates() {
+        let dir = TempDir::new().unwrap();
+        let names = |templates: Vec<TagTemplate>| {
+            templates.into_iter().map(|t| t.name).collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            load_tag_templates_from(dir.path()),
+            vec![
+                TagTemplate::new("default", DEFAULT_TAG_TEMPLATE),
+                TagTemplate::new("blank", ""),
+            ]
+        );
+
+        fs::create_dir(dir.path().join("tag_templates")).unwrap();
+        fs::write(dir.path().join("tag_templates/release"), "{tag}")
+            .unwrap();
+        fs::write(dir.path().join("tag_templates/hotfix"), "fix")
+            .unwrap();
+        assert_eq!(
+            names(load_tag_templates_from(dir.path())),
+            ["hotfix", "release", "blank"]
+        );
+
+        fs::write(dir.path().join("tag_template"), "{tag}").unwrap();
+        assert_eq!(
+            names(load_tag_templates_from(dir.path())),
+            ["tag_template", "hotfix", "release", "blank"]
+        );
+    }
+}
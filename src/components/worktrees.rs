@@ -0,0 +1,232 @@
+use super::{
+    visibility_blocking, CommandBlocking, CommandInfo, Component,
+    DrawableComponent, ScrollType,
+};
+use crate::{
+    keys::SharedKeyConfig,
+    strings,
+    ui::{self, calc_scroll_top, style::SharedTheme, Size},
+};
+use anyhow::Result;
+use asyncgit::{
+    sync::{get_worktrees, WorktreeInfo},
+    CWD,
+};
+use crossterm::event::Event;
+use std::{cell::Cell, cmp};
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    text::{Span, Spans, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+///
+pub struct WorktreesComponent {
+    worktrees: Vec<WorktreeInfo>,
+    visible: bool,
+    selection: usize,
+    scroll_top: Cell<usize>,
+    theme: SharedTheme,
+    key_config: SharedKeyConfig,
+}
+
+impl DrawableComponent for WorktreesComponent {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        rect: Rect,
+    ) -> Result<()> {
+        if self.visible {
+            const PERCENT_SIZE: Size = Size::new(70, 40);
+            const MIN_SIZE: Size = Size::new(60, 10);
+
+            let area = ui::centered_rect(
+                PERCENT_SIZE.width,
+                PERCENT_SIZE.height,
+                f.size(),
+            );
+            let area =
+                ui::rect_inside(MIN_SIZE, f.size().into(), area);
+            let area = area.intersection(rect);
+
+            let height_in_lines =
+                (area.height as usize).saturating_sub(2);
+
+            self.scroll_top.set(calc_scroll_top(
+                self.scroll_top.get(),
+                height_in_lines,
+                self.selection,
+            ));
+
+            f.render_widget(Clear, area);
+            f.render_widget(
+                Paragraph::new(self.get_text(height_in_lines))
+                    .block(
+                        Block::default()
+                            .title(strings::worktrees_popup_msg())
+                            .border_type(BorderType::Thick)
+                            .borders(Borders::ALL),
+                    )
+                    .alignment(Alignment::Left),
+                area,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for WorktreesComponent {
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            out.clear();
+
+            out.push(CommandInfo::new(
+                strings::commands::scroll(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::close_popup(&self.key_config),
+                true,
+                true,
+            ));
+        }
+
+        visibility_blocking(self)
+    }
+
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            if let Event::Key(e) = ev {
+                if e == self.key_config.exit_popup {
+                    self.hide()
+                } else if e == self.key_config.move_down {
+                    self.move_selection(ScrollType::Down);
+                } else if e == self.key_config.move_up {
+                    self.move_selection(ScrollType::Up);
+                } else if e == self.key_config.home {
+                    self.move_selection(ScrollType::Home);
+                } else if e == self.key_config.end {
+                    self.move_selection(ScrollType::End);
+                }
+            }
+
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+
+        Ok(())
+    }
+}
+
+impl WorktreesComponent {
+    ///
+    pub const fn new(
+        theme: SharedTheme,
+        key_config: SharedKeyConfig,
+    ) -> Self {
+        Self {
+            worktrees: Vec::new(),
+            visible: false,
+            selection: 0,
+            scroll_top: Cell::new(0),
+            theme,
+            key_config,
+        }
+    }
+
+    /// lists all worktrees, the current one selected
+    pub fn open(&mut self) -> Result<()> {
+        self.worktrees = get_worktrees(CWD)?;
+        self.selection = self
+            .worktrees
+            .iter()
+            .position(|wt| wt.is_current)
+            .unwrap_or_default();
+        self.scroll_top.set(0);
+        self.show()?;
+
+        Ok(())
+    }
+
+    fn move_selection(&mut self, scroll: ScrollType) {
+        let max = self.worktrees.len().saturating_sub(1);
+
+        let new_selection = match scroll {
+            ScrollType::Up => self.selection.saturating_sub(1),
+            ScrollType::Down => self.selection.saturating_add(1),
+            ScrollType::Home => 0,
+            _ => max,
+        };
+
+        self.selection = cmp::min(new_selection, max);
+    }
+
+    fn get_text(&self, height: usize) -> Text {
+        let branch_width = self
+            .worktrees
+            .iter()
+            .map(|wt| Self::branch_label(wt).len())
+            .max()
+            .unwrap_or_default();
+
+        let txt = self
+            .worktrees
+            .iter()
+            .enumerate()
+            .skip(self.scroll_top.get())
+            .take(height)
+            .map(|(i, wt)| {
+                let selected = i == self.selection;
+
+                Spans::from(vec![
+                    Span::styled(
+                        if wt.is_current { "* " } else { "  " },
+                        self.theme.text(true, selected),
+                    ),
+                    Span::styled(
+                        format!(
+                            "{:<w$} ",
+                            Self::branch_label(wt),
+                            w = branch_width
+                        ),
+                        self.theme.commit_author(selected),
+                    ),
+                    Span::styled(
+                        wt.path.clone(),
+                        self.theme.text(true, selected),
+                    ),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        Text::from(txt)
+    }
+
+    fn branch_label(wt: &WorktreeInfo) -> String {
+        wt.branch.clone().unwrap_or_else(|| {
+            String::from(strings::worktree_detached())
+        })
+    }
+}
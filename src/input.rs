@@ -49,23 +49,33 @@ impl Input {
                 if !arc_current.load(Ordering::Relaxed) {
                     log::info!("input polling resumed");
 
-                    tx.send(InputEvent::State(InputState::Polling))
-                        .expect("send state failed");
+                    if tx
+                        .send(InputEvent::State(InputState::Polling))
+                        .is_err()
+                    {
+                        // the app quit
+                        break;
+                    }
                 }
                 arc_current.store(true, Ordering::Relaxed);
 
                 if let Some(e) = Self::poll(POLL_DURATION)
                     .expect("failed to pull events.")
                 {
-                    tx.send(InputEvent::Input(e))
-                        .expect("send input failed");
+                    if tx.send(InputEvent::Input(e)).is_err() {
+                        break;
+                    }
                 }
             } else {
                 if arc_current.load(Ordering::Relaxed) {
                     log::info!("input polling suspended");
 
-                    tx.send(InputEvent::State(InputState::Paused))
-                        .expect("send state failed");
+                    if tx
+                        .send(InputEvent::State(InputState::Paused))
+                        .is_err()
+                    {
+                        break;
+                    }
                 }
 
                 arc_current.store(false, Ordering::Relaxed);
@@ -32,6 +32,10 @@ pub struct KeyConfig {
     pub focus_below: KeyEvent,
     pub exit: KeyEvent,
     pub exit_popup: KeyEvent,
+    pub suspend: KeyEvent,
+    pub open_shell: KeyEvent,
+    pub open_error_console: KeyEvent,
+    pub error_console_clear: KeyEvent,
     pub open_commit: KeyEvent,
     pub open_commit_editor: KeyEvent,
     pub open_help: KeyEvent,
@@ -57,18 +61,72 @@ pub struct KeyConfig {
     pub stash_drop: KeyEvent,
     pub cmd_bar_toggle: KeyEvent,
     pub log_tag_commit: KeyEvent,
+    pub log_copy_json: KeyEvent,
+    pub log_mark_commit: KeyEvent,
+    pub log_toggle_oneline: KeyEvent,
+    pub log_toggle_full_hash: KeyEvent,
+    pub log_toggle_reverse: KeyEvent,
+    pub log_goto_parent: KeyEvent,
+    pub log_goto_revert: KeyEvent,
+    pub log_toggle_unpushed: KeyEvent,
+    pub log_stash_apply_onto: KeyEvent,
+    pub log_filter_range: KeyEvent,
+    pub log_compare_commits: KeyEvent,
+    pub log_copy_rebase_todo: KeyEvent,
+    pub log_find_commit: KeyEvent,
+    pub log_search: KeyEvent,
+    pub log_search_next: KeyEvent,
+    pub log_search_prev: KeyEvent,
+    pub log_sort_relevance: KeyEvent,
+    pub log_visual_mode: KeyEvent,
+    pub log_copy_subjects: KeyEvent,
+    pub log_export_patches: KeyEvent,
+    pub log_export_list: KeyEvent,
+    pub log_cherry_pick: KeyEvent,
+    pub log_copy_diff: KeyEvent,
+    pub log_copy_describe: KeyEvent,
+    pub log_open_difftool: KeyEvent,
+    pub log_copy_filter: KeyEvent,
+    pub log_author_summary: KeyEvent,
+    pub log_rename_branch: KeyEvent,
+    pub log_cycle_window: KeyEvent,
+    pub log_full_history: KeyEvent,
+    pub log_author_colors: KeyEvent,
+    pub log_author_stats: KeyEvent,
+    pub log_toggle_topo_order: KeyEvent,
+    pub log_toggle_all_refs: KeyEvent,
+    pub log_page_up_keep_row: KeyEvent,
+    pub log_page_down_keep_row: KeyEvent,
+    pub cherry_pick_abort: KeyEvent,
     pub commit_amend: KeyEvent,
+    pub tag_template_next: KeyEvent,
+    pub tag_push_toggle: KeyEvent,
+    pub commit_revert_file: KeyEvent,
+    pub log_details_scroll_up: KeyEvent,
+    pub log_details_scroll_down: KeyEvent,
+    pub log_details_page_up: KeyEvent,
+    pub log_details_page_down: KeyEvent,
     pub copy: KeyEvent,
     pub create_branch: KeyEvent,
     pub rename_branch: KeyEvent,
     pub select_branch: KeyEvent,
     pub delete_branch: KeyEvent,
+    pub refresh_remote_branches: KeyEvent,
+    pub branch_show_in_log: KeyEvent,
+    pub open_reflog: KeyEvent,
+    pub open_worktrees: KeyEvent,
+    pub open_undo: KeyEvent,
     pub push: KeyEvent,
+    pub push_dry_run: KeyEvent,
     pub fetch: KeyEvent,
+    pub fetch_upstream: KeyEvent,
+    pub edit_push_url: KeyEvent,
 }
 
 #[rustfmt::skip]
 impl Default for KeyConfig {
+    // one line per key, kept flat to mirror key_config.ron
+    #[allow(clippy::too_many_lines)]
     fn default() -> Self {
         Self {
 			tab_status: KeyEvent { code: KeyCode::Char('1'), modifiers: KeyModifiers::empty()},
@@ -86,6 +144,10 @@ impl Default for KeyConfig {
 			focus_below: KeyEvent { code: KeyCode::Down, modifiers: KeyModifiers::empty()},
 			exit: KeyEvent { code: KeyCode::Char('c'), modifiers: KeyModifiers::CONTROL},
 			exit_popup: KeyEvent { code: KeyCode::Esc, modifiers: KeyModifiers::empty()},
+			suspend: KeyEvent { code: KeyCode::Char('z'), modifiers: KeyModifiers::CONTROL},
+			open_shell: KeyEvent { code: KeyCode::Char('!'), modifiers: KeyModifiers::empty()},
+			open_error_console: KeyEvent { code: KeyCode::Char('~'), modifiers: KeyModifiers::empty()},
+			error_console_clear: KeyEvent { code: KeyCode::Char('D'), modifiers: KeyModifiers::SHIFT},
 			open_commit: KeyEvent { code: KeyCode::Char('c'), modifiers: KeyModifiers::empty()},
 			open_commit_editor: KeyEvent { code: KeyCode::Char('e'), modifiers:KeyModifiers::CONTROL},
 			open_help: KeyEvent { code: KeyCode::Char('h'), modifiers: KeyModifiers::empty()},
@@ -111,14 +173,66 @@ impl Default for KeyConfig {
 			stash_drop: KeyEvent { code: KeyCode::Char('D'), modifiers: KeyModifiers::SHIFT},
 			cmd_bar_toggle: KeyEvent { code: KeyCode::Char('.'), modifiers: KeyModifiers::empty()},
 			log_tag_commit: KeyEvent { code: KeyCode::Char('t'), modifiers: KeyModifiers::empty()},
+			log_copy_json: KeyEvent { code: KeyCode::Char('Y'), modifiers: KeyModifiers::SHIFT},
+			log_mark_commit: KeyEvent { code: KeyCode::Char(' '), modifiers: KeyModifiers::empty()},
+			log_toggle_oneline: KeyEvent { code: KeyCode::Char('o'), modifiers: KeyModifiers::empty()},
+			log_toggle_full_hash: KeyEvent { code: KeyCode::Char('#'), modifiers: KeyModifiers::empty()},
+			log_toggle_reverse: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::empty()},
+			log_goto_parent: KeyEvent { code: KeyCode::Char('^'), modifiers: KeyModifiers::empty()},
+			log_goto_revert: KeyEvent { code: KeyCode::Char('V'), modifiers: KeyModifiers::SHIFT},
+			log_toggle_unpushed: KeyEvent { code: KeyCode::Char('u'), modifiers: KeyModifiers::empty()},
+			log_stash_apply_onto: KeyEvent { code: KeyCode::Char('A'), modifiers: KeyModifiers::SHIFT},
+			log_filter_range: KeyEvent { code: KeyCode::Char('R'), modifiers: KeyModifiers::SHIFT},
+			log_compare_commits: KeyEvent { code: KeyCode::Char('='), modifiers: KeyModifiers::empty()},
+			log_copy_rebase_todo: KeyEvent { code: KeyCode::Char('T'), modifiers: KeyModifiers::SHIFT},
+			log_find_commit: KeyEvent { code: KeyCode::Char('/'), modifiers: KeyModifiers::empty()},
+			log_search: KeyEvent { code: KeyCode::Char('?'), modifiers: KeyModifiers::empty()},
+			log_search_next: KeyEvent { code: KeyCode::Char('n'), modifiers: KeyModifiers::empty()},
+			log_search_prev: KeyEvent { code: KeyCode::Char('N'), modifiers: KeyModifiers::SHIFT},
+			log_sort_relevance: KeyEvent { code: KeyCode::Char('S'), modifiers: KeyModifiers::SHIFT},
+			log_visual_mode: KeyEvent { code: KeyCode::Char('v'), modifiers: KeyModifiers::empty()},
+			log_copy_subjects: KeyEvent { code: KeyCode::Char('M'), modifiers: KeyModifiers::SHIFT},
+			log_export_patches: KeyEvent { code: KeyCode::Char('E'), modifiers: KeyModifiers::SHIFT},
+			log_export_list: KeyEvent { code: KeyCode::Char('x'), modifiers: KeyModifiers::CONTROL},
+			log_cherry_pick: KeyEvent { code: KeyCode::Char('C'), modifiers: KeyModifiers::SHIFT},
+			log_copy_diff: KeyEvent { code: KeyCode::Char('d'), modifiers: KeyModifiers::empty()},
+			log_copy_describe: KeyEvent { code: KeyCode::Char('G'), modifiers: KeyModifiers::SHIFT},
+			log_open_difftool: KeyEvent { code: KeyCode::Char('O'), modifiers: KeyModifiers::SHIFT},
+			log_copy_filter: KeyEvent { code: KeyCode::Char('y'), modifiers: KeyModifiers::CONTROL},
+			log_author_summary: KeyEvent { code: KeyCode::Char('a'), modifiers: KeyModifiers::empty()},
+			log_rename_branch: KeyEvent { code: KeyCode::Char('B'), modifiers: KeyModifiers::SHIFT},
+			log_cycle_window: KeyEvent { code: KeyCode::Char('w'), modifiers: KeyModifiers::empty()},
+			log_full_history: KeyEvent { code: KeyCode::Char('L'), modifiers: KeyModifiers::SHIFT},
+			log_author_colors: KeyEvent { code: KeyCode::Char('c'), modifiers: KeyModifiers::empty()},
+			log_author_stats: KeyEvent { code: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL},
+			log_toggle_topo_order: KeyEvent { code: KeyCode::Char('g'), modifiers: KeyModifiers::empty()},
+			log_toggle_all_refs: KeyEvent { code: KeyCode::Char('*'), modifiers: KeyModifiers::empty()},
+			log_page_up_keep_row: KeyEvent { code: KeyCode::Char('u'), modifiers: KeyModifiers::CONTROL},
+			log_page_down_keep_row: KeyEvent { code: KeyCode::Char('d'), modifiers: KeyModifiers::CONTROL},
+			cherry_pick_abort: KeyEvent { code: KeyCode::Char('D'), modifiers: KeyModifiers::SHIFT},
 			commit_amend: KeyEvent { code: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL},
+			tag_template_next: KeyEvent { code: KeyCode::Char('t'), modifiers: KeyModifiers::CONTROL},
+			tag_push_toggle: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::CONTROL},
+			commit_revert_file: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::CONTROL},
+			log_details_scroll_up: KeyEvent { code: KeyCode::Up, modifiers: KeyModifiers::CONTROL},
+			log_details_scroll_down: KeyEvent { code: KeyCode::Down, modifiers: KeyModifiers::CONTROL},
+			log_details_page_up: KeyEvent { code: KeyCode::PageUp, modifiers: KeyModifiers::CONTROL},
+			log_details_page_down: KeyEvent { code: KeyCode::PageDown, modifiers: KeyModifiers::CONTROL},
             copy: KeyEvent { code: KeyCode::Char('y'), modifiers: KeyModifiers::empty()},
             create_branch: KeyEvent { code: KeyCode::Char('c'), modifiers: KeyModifiers::NONE},
             rename_branch: KeyEvent { code: KeyCode::Char('r'), modifiers: KeyModifiers::NONE},
             select_branch: KeyEvent { code: KeyCode::Char('b'), modifiers: KeyModifiers::NONE},
             delete_branch: KeyEvent{code: KeyCode::Char('D'), modifiers: KeyModifiers::SHIFT},
+            refresh_remote_branches: KeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::NONE},
+            branch_show_in_log: KeyEvent { code: KeyCode::Char('L'), modifiers: KeyModifiers::SHIFT},
+            open_reflog: KeyEvent { code: KeyCode::Char('H'), modifiers: KeyModifiers::SHIFT},
+            open_worktrees: KeyEvent { code: KeyCode::Char('W'), modifiers: KeyModifiers::SHIFT},
+            open_undo: KeyEvent { code: KeyCode::Char('Z'), modifiers: KeyModifiers::SHIFT},
             push: KeyEvent { code: KeyCode::Char('p'), modifiers: KeyModifiers::empty()},
+            push_dry_run: KeyEvent { code: KeyCode::Char('P'), modifiers: KeyModifiers::SHIFT},
             fetch: KeyEvent { code: KeyCode::Char('f'), modifiers: KeyModifiers::empty()},
+            fetch_upstream: KeyEvent { code: KeyCode::Char('F'), modifiers: KeyModifiers::SHIFT},
+            edit_push_url: KeyEvent { code: KeyCode::Char('U'), modifiers: KeyModifiers::SHIFT},
         }
     }
 }
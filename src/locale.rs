@@ -0,0 +1,336 @@
+//! message catalogs translating the texts of `strings`, English is
+//! built in and stands in for every text a catalog lacks
+
+use crate::get_app_config_path;
+use anyhow::{anyhow, Result};
+use ron::de::from_bytes;
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, OnceLock,
+    },
+};
+
+/// the catalog of the user's language, set once at startup
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// mark texts without a translation (`--locale-debug`)
+static DEBUG: AtomicBool = AtomicBool::new(false);
+
+/// marked English texts handed out so far, by key
+static MARKED: OnceLock<Mutex<HashMap<&'static str, &'static str>>> =
+    OnceLock::new();
+
+/// around texts without a translation while debugging
+const MISSING_MARKS: (&str, &str) = ("\u{27e6}", "\u{27e7}");
+
+/// environment variables naming the language, in order of priority
+const LANGUAGE_VARS: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// `key` translated, `english` if there is no translation
+///
+/// `tr!("key", "text")` for `tr`, `tr!("key", "{0} of {1}", a, b)`
+/// for `tr_args`
+#[macro_export]
+macro_rules! tr {
+    ($key:expr, $english:expr) => {
+        $crate::locale::tr($key, $english)
+    };
+    ($key:expr, $english:expr, $($arg:expr),+ $(,)?) => {
+        $crate::locale::tr_args(
+            $key,
+            $english,
+            &[$(&$arg as &dyn std::fmt::Display),+],
+        )
+    };
+}
+
+/// `tr!` with one text for a `count` of 1 (key `<key>.one`) and
+/// one for all others (key `<key>.other`)
+#[macro_export]
+macro_rules! tr_count {
+    ($key:literal, $one:expr, $other:expr, $count:expr $(, $arg:expr)* $(,)?) => {
+        if $count == 1 {
+            $crate::tr!(concat!($key, ".one"), $one $(, $arg)*)
+        } else {
+            $crate::tr!(concat!($key, ".other"), $other $(, $arg)*)
+        }
+    };
+}
+
+/// translations of one language by key
+#[derive(Debug, Default)]
+pub struct Catalog {
+    messages: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// reads a catalog file, a RON map of keys to texts like
+    /// `{ "tab_log": "Historie [{0}]" }`
+    pub fn read(path: &Path) -> Result<Self> {
+        let data = fs::read(path)?;
+        let messages = from_bytes(&data).map_err(|e| {
+            anyhow!("invalid catalog {}: {}", path.display(), e)
+        })?;
+
+        Ok(Self { messages })
+    }
+
+    /// the translation of `key`
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+}
+
+/// loads the catalog of the `configured` language or else the one
+/// named by the environment, keeps English if there is none
+pub fn init(configured: Option<&str>) -> Result<()> {
+    let Some(language) =
+        language(configured, |var| env::var(var).ok())
+    else {
+        return Ok(());
+    };
+    let dir = get_app_config_path()?.join("locale");
+
+    if let Some(file) = catalog_files(&dir, &language)
+        .into_iter()
+        .find(|file| file.exists())
+    {
+        let catalog = Catalog::read(&file)?;
+        log::info!(
+            "{} translations from {}",
+            catalog.messages.len(),
+            file.display()
+        );
+        CATALOG.set(catalog).ok();
+    }
+
+    Ok(())
+}
+
+/// marks the texts that have no translation from now on
+pub fn set_debug(debug: bool) {
+    DEBUG.store(debug, Ordering::Relaxed);
+}
+
+/// `key` translated into the user's language, `english` if its
+/// catalog does not have it
+pub fn tr(key: &'static str, english: &'static str) -> &'static str {
+    if let Some(text) =
+        CATALOG.get().and_then(|catalog| catalog.get(key))
+    {
+        return text;
+    }
+
+    if DEBUG.load(Ordering::Relaxed) {
+        let mut marked = MARKED
+            .get_or_init(Mutex::default)
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        return marked.entry(key).or_insert_with(|| {
+            Box::leak(mark_missing(english).into_boxed_str())
+        });
+    }
+
+    english
+}
+
+/// `tr` with `{0}`, `{1}`.. replaced by `args`, a translation using
+/// args there are not falls back to `english`
+pub fn tr_args(
+    key: &'static str,
+    english: &'static str,
+    args: &[&dyn Display],
+) -> String {
+    let args: Vec<String> =
+        args.iter().map(ToString::to_string).collect();
+
+    interpolate(tr(key, english), &args).unwrap_or_else(|| {
+        log::warn!("translation of `{}` does not fit its args", key);
+        interpolate(english, &args).unwrap_or_default()
+    })
+}
+
+/// the language `configured` or the first of `LANGUAGE_VARS` set,
+/// without encoding and modifier (`de_DE.UTF-8` -> `de_DE`).
+/// `C` and `POSIX` have none
+fn language<F: Fn(&str) -> Option<String>>(
+    configured: Option<&str>,
+    var: F,
+) -> Option<String> {
+    let language = configured.map(String::from).or_else(|| {
+        LANGUAGE_VARS
+            .iter()
+            .filter_map(|name| var(name))
+            .find(|value| !value.is_empty())
+    })?;
+    let language =
+        language.split(['.', '@']).next().unwrap_or_default().trim();
+
+    (!language.is_empty() && language != "C" && language != "POSIX")
+        .then(|| language.to_string())
+}
+
+/// the catalogs for `language` in `dir` to look for in order,
+/// `de_DE.ron` then `de.ron`
+fn catalog_files(dir: &Path, language: &str) -> Vec<PathBuf> {
+    let mut files = vec![dir.join(format!("{}.ron", language))];
+    if let Some((base, _)) = language.split_once('_') {
+        files.push(dir.join(format!("{}.ron", base)));
+    }
+    files
+}
+
+fn mark_missing(english: &str) -> String {
+    format!("{}{}{}", MISSING_MARKS.0, english, MISSING_MARKS.1)
+}
+
+/// `template` with `{n}` replaced by `args[n]` and `{{`, `}}`
+/// unescaped, `None` for other or missing placeholders
+fn interpolate(template: &str, args: &[String]) -> Option<String> {
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.as_str().starts_with('}') => {
+                chars.next();
+                out.push('}');
+            }
+            '{' => {
+                let rest = chars.as_str();
+                let end = rest.find('}')?;
+                let index: usize = rest[..end].parse().ok()?;
+                out.push_str(args.get(index)?);
+                chars = rest[end + 1..].chars();
+            }
+            '}' => return None,
+            c => out.push(c),
+        }
+    }
+
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_interpolate() {
+        assert_eq!(
+            interpolate("{0} of {1}", &args(&["1", "2"])).as_deref(),
+            Some("1 of 2")
+        );
+        assert_eq!(
+            interpolate("{1} von {0}", &args(&["1", "2"])).as_deref(),
+            Some("2 von 1")
+        );
+        assert_eq!(
+            interpolate("{{0}} {0}", &args(&["x"])).as_deref(),
+            Some("{0} x")
+        );
+        assert_eq!(interpolate("{1}", &args(&["x"])), None);
+        assert_eq!(interpolate("{}", &args(&["x"])), None);
+        assert_eq!(interpolate("{0", &args(&["x"])), None);
+        assert_eq!(interpolate("0}", &args(&["x"])), None);
+    }
+
+    #[test]
+    fn test_language() {
+        let env =
+            |vars: &'static [(&'static str, &'static str)]| {
+                move |name: &str| {
+                    vars.iter()
+                        .find(|(n, _)| *n == name)
+                        .map(|(_, value)| value.to_string())
+                }
+            };
+
+        assert_eq!(
+            language(None, env(&[("LANG", "de_DE.UTF-8")]))
+                .as_deref(),
+            Some("de_DE")
+        );
+        assert_eq!(
+            language(
+                None,
+                env(&[("LANG", "de_DE.UTF-8"), ("LC_ALL", "fr_FR")])
+            )
+            .as_deref(),
+            Some("fr_FR")
+        );
+        assert_eq!(
+            language(
+                None,
+                env(&[("LC_ALL", ""), ("LANG", "sr@latin")])
+            )
+            .as_deref(),
+            Some("sr")
+        );
+        assert_eq!(
+            language(Some("pt_BR"), env(&[("LANG", "de_DE")]))
+                .as_deref(),
+            Some("pt_BR")
+        );
+        assert_eq!(language(None, env(&[("LANG", "C.UTF-8")])), None);
+        assert_eq!(language(None, env(&[("LANG", "POSIX")])), None);
+        assert_eq!(language(None, env(&[])), None);
+    }
+
+    #[test]
+    fn test_catalog() {
+        let dir = TempDir::new().unwrap();
+        let files = catalog_files(dir.path(), "de_DE");
+        assert_eq!(
+            files,
+            vec![
+                dir.path().join("de_DE.ron"),
+                dir.path().join("de.ron")
+            ]
+        );
+        assert_eq!(catalog_files(dir.path(), "de").len(), 1);
+
+        fs::write(
+            dir.path().join("de.ron"),
+            r#"{ "tab_log": "Historie [{0}]", "commands.quit": "Beenden" }"#,
+        )
+        .unwrap();
+        let catalog =
+            Catalog::read(&dir.path().join("de.ron")).unwrap();
+        assert_eq!(catalog.get("tab_log"), Some("Historie [{0}]"));
+        assert_eq!(catalog.get("tab_status"), None);
+
+        fs::write(dir.path().join("fr.ron"), "[1, 2]").unwrap();
+        assert!(Catalog::read(&dir.path().join("fr.ron")).is_err());
+    }
+
+    #[test]
+    fn test_english_fallback() {
+        // no catalog is loaded in tests
+        assert_eq!(tr("tab_log", "Log [{0}]"), "Log [{0}]");
+        assert_eq!(
+            tr_args("tab_log", "Log [{0}]", &[&"2"]),
+            "Log [2]"
+        );
+        assert_eq!(
+            crate::tr_count!("x", "{0} error", "{0} errors", 2, 2),
+            "2 errors"
+        );
+        assert_eq!(mark_missing("Log"), "\u{27e6}Log\u{27e7}");
+    }
+}
@@ -16,7 +16,9 @@ mod cmdbar;
 mod components;
 mod input;
 mod keys;
+mod locale;
 mod notify_mutex;
+mod options;
 mod profiler;
 mod queue;
 mod spinner;
@@ -25,7 +27,7 @@ mod tabs;
 mod ui;
 mod version;
 
-use crate::app::App;
+use crate::{app::App, components::Suspend};
 use anyhow::{anyhow, bail, Result};
 use asyncgit::AsyncNotification;
 use backtrace::Backtrace;
@@ -54,6 +56,10 @@ use std::{
     panic,
     path::PathBuf,
     process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 use tui::{
@@ -74,12 +80,12 @@ pub enum QueueEvent {
 }
 
 fn main() -> Result<()> {
-    process_cmdline()?;
+    let repo_path = process_cmdline()?;
 
     let _profiler = Profiler::new();
 
-    if !valid_path()? {
-        eprintln!("invalid path\nplease run gitui inside of a non-bare git repository");
+    if !valid_path(&repo_path) {
+        eprintln!("invalid path\nplease run gitui inside of a git repository");
         return Ok(());
     }
 
@@ -100,7 +106,8 @@ fn main() -> Result<()> {
     let ticker = tick(TICK_INTERVAL);
     let spinner_ticker = tick(SPINNER_INTERVAL);
 
-    let mut app = App::new(&tx_git, input);
+    let mut app = App::new(repo_path, &tx_git, input)?;
+    let suspend_requested = register_suspend_signal()?;
 
     let mut spinner = Spinner::default();
     let mut first_update = true;
@@ -118,8 +125,16 @@ fn main() -> Result<()> {
             )?
         };
 
+        if suspend_requested.swap(false, Ordering::Relaxed) {
+            app.suspend(Suspend::Process);
+        }
+
         {
             if let QueueEvent::SpinnerUpdate = event {
+                if app.update_debounced()? {
+                    draw(&mut terminal, &app)?;
+                    spinner.set_state(app.any_work_pending());
+                }
                 spinner.update();
                 spinner.draw(&mut terminal)?;
                 continue;
@@ -133,8 +148,15 @@ fn main() -> Result<()> {
                     {
                         //Note: external ed closed, we need to re-hide cursor
                         terminal.hide_cursor()?;
+                        app.event(ev)?;
+
+                        // piled up while the ui was away
+                        for ev in drain_git_events(&rx_git) {
+                            app.update_git(ev)?;
+                        }
+                    } else {
+                        app.event(ev)?
                     }
-                    app.event(ev)?
                 }
                 QueueEvent::Tick => app.update()?,
                 QueueEvent::GitEvent(ev)
@@ -157,6 +179,10 @@ fn main() -> Result<()> {
         }
     }
 
+    // running walks, filters and scans give up instead of
+    // holding up the exit or panicking on the closed channel
+    asyncgit::shutdown();
+
     Ok(())
 }
 
@@ -172,6 +198,41 @@ fn shutdown_terminal() -> Result<()> {
     Ok(())
 }
 
+/// set once `SIGTSTP` (`kill -TSTP`, ctrl+z is a key in raw mode)
+/// arrived, the terminal is restored before gitui stops
+#[cfg(unix)]
+fn register_suspend_signal() -> Result<Arc<AtomicBool>> {
+    let requested = Arc::new(AtomicBool::new(false));
+    signal_hook::flag::register(
+        signal_hook::consts::SIGTSTP,
+        Arc::clone(&requested),
+    )?;
+
+    Ok(requested)
+}
+
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+fn register_suspend_signal() -> Result<Arc<AtomicBool>> {
+    Ok(Arc::new(AtomicBool::new(false)))
+}
+
+/// the pending notifications, each kind only once
+fn drain_git_events(
+    rx_git: &Receiver<AsyncNotification>,
+) -> Vec<AsyncNotification> {
+    let mut events = Vec::new();
+    for ev in rx_git.try_iter() {
+        if ev != AsyncNotification::FinishUnchanged
+            && !events.contains(&ev)
+        {
+            events.push(ev);
+        }
+    }
+
+    events
+}
+
 fn draw<B: Backend>(
     terminal: &mut Terminal<B>,
     app: &App,
@@ -187,9 +248,8 @@ fn draw<B: Backend>(
     })
 }
 
-fn valid_path() -> Result<bool> {
-    Ok(asyncgit::sync::is_repo(asyncgit::CWD)
-        && !asyncgit::sync::is_bare_repo(asyncgit::CWD)?)
+fn valid_path(repo_path: &str) -> bool {
+    asyncgit::sync::is_repo(repo_path)
 }
 
 fn select_event(
@@ -261,7 +321,8 @@ fn setup_logging() -> Result<()> {
     Ok(())
 }
 
-fn process_cmdline() -> Result<()> {
+/// returns the path of the repository to open
+fn process_cmdline() -> Result<Arc<str>> {
     let app = ClapApp::new(crate_name!())
         .author(crate_authors!())
         .version(crate_version!())
@@ -278,6 +339,11 @@ fn process_cmdline() -> Result<()> {
                 .short("d")
                 .long("directory")
                 .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("locale-debug")
+                .help("Marks texts missing in the catalog of the language")
+                .long("locale-debug"),
         );
 
     let arg_matches = app.get_matches();
@@ -285,13 +351,19 @@ fn process_cmdline() -> Result<()> {
         setup_logging()?;
     }
 
+    locale::set_debug(arg_matches.is_present("locale-debug"));
+
     if arg_matches.is_present("directory") {
         let directory =
             arg_matches.value_of("directory").unwrap_or(".");
-        env::set_current_dir(directory)?;
+        // absolute, so it stays valid after changing the cwd
+        let directory = fs::canonicalize(directory)?;
+        env::set_current_dir(&directory)?;
+
+        return Ok(Arc::from(directory.to_string_lossy().as_ref()));
     }
 
-    Ok(())
+    Ok(Arc::from(asyncgit::CWD))
 }
 
 fn set_panic_handlers() -> Result<()> {
@@ -0,0 +1,344 @@
+use crate::{
+    components::{parse_filter_fields, DEFAULT_ELLIPSIS},
+    get_app_config_path,
+};
+use anyhow::{bail, Result};
+use asyncgit::{
+    sync::{FilterBy, DEFAULT_MAX_FIELD_LEN, DEFAULT_REMOTE_TIMEOUT},
+    BusyPolicy, RemoteOp, DEFAULT_LOG_NOTIFY_INTERVAL,
+};
+use ron::{
+    de::from_bytes,
+    ser::{to_string_pretty, PrettyConfig},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    convert::TryFrom,
+    fs::File,
+    io::{Read, Write},
+    path::PathBuf,
+    rc::Rc,
+    time::Duration,
+};
+
+pub type SharedOptions = Rc<Options>;
+
+/// behaviour settings read from `options.ron`,
+/// missing entries fall back to their default
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct Options {
+    pub log: LogOptions,
+    pub find: FindOptions,
+    pub filter: FilterOptions,
+    pub remote: RemoteOptions,
+    /// chars of a commit subject the details show before the rest
+    /// is highlighted as too long, 0 for no limit
+    pub commit_subject_limit: usize,
+    /// same for the lines of the body, which also get a ruler at
+    /// the limit
+    pub commit_body_limit: usize,
+    /// command the log's difftool key runs instead of
+    /// `git difftool`, `{old}` and `{new}` are replaced with the
+    /// commits compared and `{rev}` with the newer one
+    pub difftool_command: Option<String>,
+    /// language of the texts (`de_DE`, `fr`..), picked from
+    /// `LC_ALL`, `LC_MESSAGES` and `LANG` if not set
+    pub locale: Option<String>,
+}
+
+/// refs shown next to the commits in the log
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum LogRefs {
+    /// just the tags
+    Tags,
+    /// `HEAD`, branches, remote branches and tags
+    All,
+}
+
+/// `log` entries of `options.ron`
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct LogOptions {
+    /// select the `HEAD` commit whenever the log is shown
+    pub select_head: bool,
+    /// start the log with the full instead of the short commit hash
+    pub full_hash: bool,
+    /// digits typed in the log are a count for the movement after
+    /// them (`10j`), the tabs can then not be switched to by
+    /// number from the log
+    pub count_prefix: bool,
+    pub refs: LogRefs,
+    /// only walk the log this far back at start, e.g. `2 weeks`,
+    /// `last 1000` or `since v1.0`
+    pub window: Option<String>,
+    /// ends commit messages cut off at the edge of the log, e.g.
+    /// `...` for terminals without `…`, empty to just cut them
+    pub ellipsis: String,
+    /// line written per commit when exporting the log, `{sha}`,
+    /// `{short}`, `{author}`, `{date}` and `{subject}` are
+    /// replaced, `{sha} {subject}` if not set
+    pub export_template: Option<String>,
+    /// milliseconds between two redraws of the log while it is
+    /// still loading, 0 to redraw after every batch
+    pub notify_interval_ms: u64,
+}
+
+/// `find` entries of `options.ron`, the popup the log filter is
+/// typed in
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct FindOptions {
+    /// re-filter the log while typing
+    pub live: bool,
+    /// show the query typed as the `OR`ed groups of terms it is
+    /// parsed into
+    pub breadcrumb: bool,
+}
+
+/// `filter` entries of `options.ron`
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct FilterOptions {
+    /// bytes of each commit field (message, author..) the log
+    /// filter and search look at, the rest is ignored
+    pub max_field_len: usize,
+    /// terms without fields search the whole commit message
+    /// instead of only its subject
+    pub message_body: bool,
+    /// fields terms without fields search (`sha`, `author`,
+    /// `committer`, `message`, `body`, `tags`)
+    pub default_fields: Vec<String>,
+    /// run all log walks on one thread that is kept around,
+    /// dropping walks that got superseded by a newer filter
+    pub warm_worker: bool,
+}
+
+/// `remote` entries of `options.ron`
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct RemoteOptions {
+    /// seconds a push, fetch or ls-remote may go without any
+    /// progress before it is given up
+    pub timeout_secs: u64,
+    /// what a push, fetch or ls-remote does while another one is
+    /// running
+    pub busy_policy: BusyPolicies,
+    /// add the ssh host keys of hosts not in `known_hosts` yet
+    /// without asking (trust on first use), changed keys are
+    /// still refused
+    pub trust_new_host_keys: bool,
+    /// run `.git/hooks/pre-push` before pushing and stop the push
+    /// if it fails
+    pub run_pre_push_hook: bool,
+    /// push a new tag to `origin` right after creating it unless
+    /// toggled off in the tag popup
+    pub tag_push_after_create: bool,
+}
+
+/// what a remote operation does while another one is running
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum WhenBusy {
+    /// wait for the running one to finish
+    Queue,
+    /// fail right away
+    Reject,
+}
+
+impl From<WhenBusy> for BusyPolicy {
+    fn from(when: WhenBusy) -> Self {
+        match when {
+            WhenBusy::Queue => Self::Queue,
+            WhenBusy::Reject => Self::Reject,
+        }
+    }
+}
+
+/// `remote.busy_policy` entries of `options.ron`
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(default)]
+pub struct BusyPolicies {
+    pub fetch: WhenBusy,
+    pub push: WhenBusy,
+    pub ls_remote: WhenBusy,
+}
+
+impl BusyPolicies {
+    /// the policy of each kind of operation
+    pub fn policies(&self) -> [(RemoteOp, BusyPolicy); 3] {
+        [
+            (RemoteOp::Fetch, self.fetch.into()),
+            (RemoteOp::Push, self.push.into()),
+            (RemoteOp::LsRemote, self.ls_remote.into()),
+        ]
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            log: LogOptions::default(),
+            find: FindOptions::default(),
+            filter: FilterOptions::default(),
+            remote: RemoteOptions::default(),
+            commit_subject_limit: 50,
+            commit_body_limit: 72,
+            difftool_command: None,
+            locale: None,
+        }
+    }
+}
+
+impl Default for LogOptions {
+    fn default() -> Self {
+        Self {
+            select_head: false,
+            full_hash: false,
+            count_prefix: false,
+            refs: LogRefs::Tags,
+            window: None,
+            ellipsis: String::from(DEFAULT_ELLIPSIS),
+            export_template: None,
+            notify_interval_ms: u64::try_from(
+                DEFAULT_LOG_NOTIFY_INTERVAL.as_millis(),
+            )
+            .unwrap_or_default(),
+        }
+    }
+}
+
+impl Default for FilterOptions {
+    fn default() -> Self {
+        Self {
+            max_field_len: DEFAULT_MAX_FIELD_LEN,
+            message_body: false,
+            default_fields: ["sha", "author", "message", "tags"]
+                .iter()
+                .map(|name| (*name).to_string())
+                .collect(),
+            warm_worker: false,
+        }
+    }
+}
+
+impl Default for BusyPolicies {
+    fn default() -> Self {
+        Self {
+            fetch: WhenBusy::Queue,
+            push: WhenBusy::Queue,
+            ls_remote: WhenBusy::Reject,
+        }
+    }
+}
+
+impl Default for RemoteOptions {
+    fn default() -> Self {
+        Self {
+            timeout_secs: DEFAULT_REMOTE_TIMEOUT.as_secs(),
+            busy_policy: BusyPolicies::default(),
+            trust_new_host_keys: false,
+            run_pre_push_hook: false,
+            tag_push_after_create: false,
+        }
+    }
+}
+
+impl Options {
+    /// what terms without fields search, fails on an invalid
+    /// `filter.default_fields`
+    pub fn filter_everywhere(&self) -> Result<FilterBy> {
+        let by = parse_filter_fields(&self.filter.default_fields)?;
+
+        Ok(if self.filter.message_body {
+            by | FilterBy::BODY
+        } else {
+            by
+        })
+    }
+
+    /// how long remote operations may go without progress, fails
+    /// on a `remote.timeout_secs` of 0
+    pub fn remote_timeout(&self) -> Result<Duration> {
+        if self.remote.timeout_secs == 0 {
+            bail!("`remote.timeout_secs` must be at least 1");
+        }
+
+        Ok(Duration::from_secs(self.remote.timeout_secs))
+    }
+
+    fn save(&self) -> Result<()> {
+        let options_file = Self::get_options_file()?;
+        let mut file = File::create(options_file)?;
+        let data = to_string_pretty(self, PrettyConfig::default())?;
+        file.write_all(data.as_bytes())?;
+        Ok(())
+    }
+
+    fn get_options_file() -> Result<PathBuf> {
+        let app_home = get_app_config_path()?;
+        Ok(app_home.join("options.ron"))
+    }
+
+    fn read_file(options_file: PathBuf) -> Result<Self> {
+        let mut f = File::open(options_file)?;
+        let mut buffer = Vec::new();
+        f.read_to_end(&mut buffer)?;
+        Ok(from_bytes(&buffer)?)
+    }
+
+    fn init_internal() -> Result<Self> {
+        let file = Self::get_options_file()?;
+        if file.exists() {
+            Ok(Self::read_file(file)?)
+        } else {
+            let def = Self::default();
+            if def.save().is_err() {
+                log::warn!("failed to store default options to disk.")
+            }
+            Ok(def)
+        }
+    }
+
+    pub fn init() -> Self {
+        Self::init_internal().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_groups() {
+        let options: Options = from_bytes(
+            b"(log: (refs: All), remote: (timeout_secs: 5, busy_policy: (push: Reject)))",
+        )
+        .unwrap();
+
+        assert_eq!(options.log.refs, LogRefs::All);
+        assert!(!options.log.select_head);
+        assert_eq!(options.log.ellipsis, DEFAULT_ELLIPSIS);
+        assert_eq!(options.log.notify_interval_ms, 50);
+        assert_eq!(options.remote.timeout_secs, 5);
+        assert_eq!(options.remote.busy_policy.push, WhenBusy::Reject);
+        assert_eq!(options.remote.busy_policy.fetch, WhenBusy::Queue);
+        assert!(!options.remote.trust_new_host_keys);
+        assert_eq!(options.commit_subject_limit, 50);
+    }
+
+    #[test]
+    fn test_remote_timeout() {
+        let mut options = Options::default();
+        assert_eq!(
+            options.remote_timeout().unwrap(),
+            DEFAULT_REMOTE_TIMEOUT
+        );
+
+        options.remote.timeout_secs = 0;
+        assert!(options.remote_timeout().is_err());
+    }
+}
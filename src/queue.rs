@@ -1,6 +1,9 @@
-use crate::tabs::StashingOptions;
-use asyncgit::sync::{CommitId, CommitTags};
+use crate::{components::Severity, tabs::StashingOptions};
+use asyncgit::sync::{
+    CommitId, CommitTags, PushUpdate, RemoteError, Tags,
+};
 use bitflags::bitflags;
+use crossterm::event::KeyEvent;
 use std::{cell::RefCell, collections::VecDeque, rc::Rc};
 
 bitflags! {
@@ -29,6 +32,47 @@ pub enum Action {
     ResetHunk(String, u64),
     StashDrop(CommitId),
     DeleteBranch(String),
+    /// move HEAD to the commit of a reflog entry
+    ResetHard(CommitId),
+    /// checkout the commit detached and apply the latest stash
+    StashApplyOnto(CommitId),
+    /// move a ref back to the commit of a reflog entry,
+    /// `pushed` if its current tip was pushed already
+    RestoreRef {
+        refname: String,
+        id: CommitId,
+        pushed: bool,
+    },
+    /// branch ref to push, url pushed to and the dry-run preview
+    Push(String, String, Vec<PushUpdate>),
+    /// reset to where the branch was before the cherry-picks
+    AbortCherryPick,
+    /// add the ssh host key with this fingerprint to `known_hosts`
+    TrustHostKey {
+        host: String,
+        fingerprint: String,
+    },
+    /// commit (or amend `amend`) unsigned after signing failed
+    CommitUnsigned {
+        amend: Option<CommitId>,
+        msg: String,
+        problem: String,
+    },
+    /// create the tag unsigned after signing failed, `push` it after
+    TagUnsigned {
+        commit: CommitId,
+        tag: String,
+        message: String,
+        push: bool,
+        problem: String,
+    },
+}
+
+/// popups whose message can be written in the editor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageEditor {
+    Commit,
+    Tag,
 }
 
 ///
@@ -40,6 +84,18 @@ pub enum InternalEvent {
     ///
     ShowErrorMsg(String),
     ///
+    ShowInfoMsg(String),
+    /// recoverable problem for the error console, no popup
+    ReportProblem(Severity, String),
+    /// failed remote `action`, offers to trust unknown host keys
+    ShowRemoteError {
+        action: &'static str,
+        retry: KeyEvent,
+        error: RemoteError,
+    },
+    /// commits to apply onto `HEAD`, oldest first
+    CherryPick(Vec<CommitId>),
+    ///
     Update(NeedsUpdate),
     /// open commit msg input
     OpenCommit,
@@ -49,18 +105,55 @@ pub enum InternalEvent {
     TabSwitch,
     ///
     InspectCommit(CommitId, Option<CommitTags>),
+    /// files changed from the first commit to the second
+    CompareCommits(CommitId, CommitId),
+    /// tags known so far, used for the message template
+    TagCommit(CommitId, Option<Tags>),
     ///
-    TagCommit(CommitId),
+    FindCommit,
+    /// open the find popup to search the log without filtering
+    SearchCommit,
+    /// switch to the log tab with this commit selected
+    ShowCommitInLog(CommitId),
+    /// filter the log by a search query (empty clears the filter)
+    FilterLog(String),
+    /// select the next commit matching a search query
+    SearchLog(String),
     ///
     CreateBranch,
-    ///
-    RenameBranch(String, String),
+    /// branch ref, its current name and whether to go back to the
+    /// branch list afterwards
+    RenameBranch(String, String, bool),
     ///
     SelectBranch,
     ///
-    OpenExternalEditor(Option<String>),
+    OpenReflog,
+    ///
+    OpenWorktrees,
+    /// commits to count the authors of and whether they are the
+    /// results of a filter
+    OpenAuthorStats(Vec<CommitId>, bool),
+    /// commits to write to a file and whether they are the results
+    /// of a filter
+    ExportLog(Vec<CommitId>, bool),
+    ///
+    OpenUndo,
+    ///
+    OpenExternalEditor(String),
+    /// suspends the ui to write the message of the popup in the
+    /// editor
+    EditMessage(MessageEditor),
+    /// suspends the ui to compare the second commit to the first
+    /// (its parent if `None`) in the difftool
+    OpenDifftool(Option<CommitId>, CommitId),
     ///
     Push(String),
+    /// previews pushing the branch, see `Action::Push`
+    PushDryRun(String),
+    /// pushes the just created tag `name` to `origin`
+    PushTag(String),
+    /// edit `pushurl` of the remote
+    EditPushUrl(String),
 }
 
 ///
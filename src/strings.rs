@@ -1,702 +1,3120 @@
-use crate::keys::{get_hint, SharedKeyConfig};
+use crate::{
+    components::Severity,
+    keys::{get_hint, SharedKeyConfig},
+    tr, tr_count,
+};
+use asyncgit::{
+    sync::{PushUpdate, RemoteError},
+    RemoteOp,
+};
+use crossterm::event::KeyEvent;
 
 pub mod order {
     pub static NAV: i8 = 1;
 }
 
-pub static PUSH_POPUP_MSG: &str = "Push";
-pub static PUSH_POPUP_PROGRESS_NONE: &str = "preparing...";
-pub static PUSH_POPUP_STATES_ADDING: &str = "adding objects (1/3)";
-pub static PUSH_POPUP_STATES_DELTAS: &str = "deltas (2/3)";
-pub static PUSH_POPUP_STATES_PUSHING: &str = "pushing (3/3)";
+pub fn push_popup_msg() -> &'static str {
+    tr!("push_popup_msg", "Push")
+}
+pub fn push_tag_popup_msg() -> &'static str {
+    tr!("push_tag_popup_msg", "Push Tag")
+}
+pub fn push_dry_run_popup_msg() -> &'static str {
+    tr!("push_dry_run_popup_msg", "Push (dry run)")
+}
+/// the tag got created, only pushing it failed
+pub fn push_action() -> &'static str {
+    tr!("push_action", "push")
+}
+pub fn push_dry_run_action() -> &'static str {
+    tr!("push_dry_run_action", "push preview")
+}
+pub fn fetch_action() -> &'static str {
+    tr!("fetch_action", "fetch")
+}
+pub fn ls_remote_action() -> &'static str {
+    tr!("ls_remote_action", "list remote branches")
+}
+pub fn push_tag_action() -> &'static str {
+    tr!("push_tag_action", "tag created locally, push")
+}
+pub fn push_popup_progress_none() -> &'static str {
+    tr!("push_popup_progress_none", "preparing...")
+}
+pub fn push_popup_states_adding() -> &'static str {
+    tr!("push_popup_states_adding", "adding objects (1/3)")
+}
+pub fn push_popup_states_deltas() -> &'static str {
+    tr!("push_popup_states_deltas", "deltas (2/3)")
+}
+pub fn push_popup_states_pushing() -> &'static str {
+    tr!("push_popup_states_pushing", "pushing (3/3)")
+}
+pub fn push_popup_states_resolving_deltas() -> &'static str {
+    tr!("push_popup_states_resolving_deltas", "resolving deltas")
+}
+
+pub fn fetch_states_preparing() -> &'static str {
+    tr!("fetch_states_preparing", "fetching...")
+}
+pub fn fetch_states_receiving() -> &'static str {
+    tr!("fetch_states_receiving", "receiving objects")
+}
+
+pub fn log_searching() -> &'static str {
+    tr!("log_searching", "searching...")
+}
+pub fn log_author_legend_title() -> &'static str {
+    tr!("log_author_legend_title", "Authors")
+}
+pub fn log_shallow_boundary() -> &'static str {
+    tr!("log_shallow_boundary", "\u{2014} shallow boundary \u{2014}")
+}
+pub fn log_revert_badge() -> &'static str {
+    tr!("log_revert_badge", " \u{21a9}")
+}
+pub fn log_reverted_badge() -> &'static str {
+    tr!("log_reverted_badge", " \u{2298}")
+}
+pub fn log_unreadable_commit() -> &'static str {
+    tr!("log_unreadable_commit", "\u{2039}unreadable commit\u{203a}")
+}
+pub fn log_describe_no_tag() -> &'static str {
+    tr!(
+        "log_describe_no_tag",
+        "no annotated tag to describe the commit with"
+    )
+}
+
+pub fn reflog_popup_msg() -> &'static str {
+    tr!("reflog_popup_msg", "Reflog (HEAD)")
+}
+pub fn tag_template_no_previous() -> &'static str {
+    tr!("tag_template_no_previous", "the first commit")
+}
+pub fn tag_template_default_name() -> &'static str {
+    tr!("tag_template_default_name", "default")
+}
+pub fn tag_template_blank_name() -> &'static str {
+    tr!("tag_template_blank_name", "blank")
+}
+pub fn cherry_pick_popup_msg() -> &'static str {
+    tr!("cherry_pick_popup_msg", "Cherry-pick")
+}
+pub fn undo_popup_msg() -> &'static str {
+    tr!("undo_popup_msg", "Undo (HEAD and branch reflogs)")
+}
+pub fn worktrees_popup_msg() -> &'static str {
+    tr!("worktrees_popup_msg", "Worktrees")
+}
+pub fn author_stats_popup_msg() -> &'static str {
+    tr!("author_stats_popup_msg", "Authors")
+}
+pub fn worktree_detached() -> &'static str {
+    tr!("worktree_detached", "(detached)")
+}
+pub fn needs_worktree() -> &'static str {
+    tr!(
+        "needs_worktree",
+        "not available in a bare repository (needs a worktree)"
+    )
+}
 
-pub static SELECT_BRANCH_POPUP_MSG: &str = "Switch Branch";
+pub fn select_branch_popup_msg() -> &'static str {
+    tr!("select_branch_popup_msg", "Switch Branch")
+}
+pub fn select_branch_popup_refreshing_msg() -> &'static str {
+    tr!(
+        "select_branch_popup_refreshing_msg",
+        "Switch Branch (querying remote...)"
+    )
+}
 
 pub fn title_status(key_config: &SharedKeyConfig) -> String {
-    format!(
-        "Unstaged Changes [{}]",
+    tr!(
+        "title_status",
+        "Unstaged Changes [{0}]",
         get_hint(key_config.focus_workdir)
     )
 }
 pub fn title_diff(_key_config: &SharedKeyConfig) -> String {
-    "Diff: ".to_string()
+    tr!("title_diff", "Diff: ").to_string()
 }
 pub fn title_index(key_config: &SharedKeyConfig) -> String {
-    format!("Staged Changes [{}]", get_hint(key_config.focus_stage))
+    tr!(
+        "title_index",
+        "Staged Changes [{0}]",
+        get_hint(key_config.focus_stage)
+    )
 }
 pub fn tab_status(key_config: &SharedKeyConfig) -> String {
-    format!("Status [{}]", get_hint(key_config.tab_status))
+    tr!(
+        "tab_status",
+        "Status [{0}]",
+        get_hint(key_config.tab_status)
+    )
 }
 pub fn tab_log(key_config: &SharedKeyConfig) -> String {
-    format!("Log [{}]", get_hint(key_config.tab_log))
+    tr!("tab_log", "Log [{0}]", get_hint(key_config.tab_log))
 }
 pub fn tab_stashing(key_config: &SharedKeyConfig) -> String {
-    format!("Stashing [{}]", get_hint(key_config.tab_stashing))
+    tr!(
+        "tab_stashing",
+        "Stashing [{0}]",
+        get_hint(key_config.tab_stashing)
+    )
 }
 pub fn tab_stashes(key_config: &SharedKeyConfig) -> String {
-    format!("Stashes [{}]", get_hint(key_config.tab_stashes))
+    tr!(
+        "tab_stashes",
+        "Stashes [{0}]",
+        get_hint(key_config.tab_stashes)
+    )
 }
 pub fn tab_divider(_key_config: &SharedKeyConfig) -> String {
-    " | ".to_string()
+    tr!("tab_divider", " | ").to_string()
 }
 pub fn cmd_splitter(_key_config: &SharedKeyConfig) -> String {
-    " ".to_string()
+    tr!("cmd_splitter", " ").to_string()
+}
+pub fn error_console_empty() -> &'static str {
+    tr!("error_console_empty", "no errors or warnings")
+}
+pub fn error_console_title(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    tr!("error_console_title", "Errors ({0})", count)
+}
+pub fn error_console_badge(
+    key_config: &SharedKeyConfig,
+    unread: usize,
+) -> String {
+    tr_count!(
+        "error_console_badge",
+        "{0} new error [{1}]",
+        "{0} new errors [{1}]",
+        unread,
+        unread,
+        get_hint(key_config.open_error_console)
+    )
+}
+pub fn error_console_severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Warning => {
+            tr!("error_console_severity.warning", "warning ")
+        }
+        Severity::Error => {
+            tr!("error_console_severity.error", "error   ")
+        }
+    }
+}
+pub fn difftool_not_configured() -> &'static str {
+    tr!(
+        "difftool_not_configured",
+        "no difftool configured, set `diff.tool` in your git config"
+    )
+}
+pub fn difftool_not_found(program: &str) -> String {
+    tr!(
+        "difftool_not_found",
+        "difftool \"{0}\" not found, is it installed and in your PATH? (see `difftool_command` in options.ron)",
+        program
+    )
+}
+pub fn difftool_wait_hint() -> &'static str {
+    tr!("difftool_wait_hint", "press enter to return to gitui")
+}
+pub fn difftool_failed_msg(
+    _key_config: &SharedKeyConfig,
+    error: &str,
+) -> String {
+    tr!("difftool_failed_msg", "failed to run difftool:\n{0}", error)
+}
+pub fn shell_hint() -> &'static str {
+    tr!(
+        "shell_hint",
+        "gitui is waiting for this shell, type `exit` to return"
+    )
+}
+pub fn editor_failed_msg(
+    _key_config: &SharedKeyConfig,
+    error: &str,
+) -> String {
+    tr!("editor_failed_msg", "failed to launch editor:\n{0}", error)
+}
+pub fn suspend_failed_msg(
+    _key_config: &SharedKeyConfig,
+    error: &str,
+) -> String {
+    tr!("suspend_failed_msg", "failed to suspend:\n{0}", error)
 }
 pub fn msg_opening_editor(_key_config: &SharedKeyConfig) -> String {
-    "opening editor...".to_string()
+    tr!("msg_opening_editor", "opening editor...").to_string()
+}
+pub fn staging_error() -> &'static str {
+    tr!("staging_error", "staging error:")
+}
+pub fn copy_file_diff_error() -> &'static str {
+    tr!("copy_file_diff_error", "copy file diff error:")
+}
+pub fn revert_file_error() -> &'static str {
+    tr!("revert_file_error", "revert file error:")
+}
+pub fn copy_to_clipboard_error() -> &'static str {
+    tr!("copy_to_clipboard_error", "copy to clipboard error:")
+}
+pub fn copy_commit_json_error() -> &'static str {
+    tr!("copy_commit_json_error", "copy commit json error:")
+}
+pub fn copy_commit_diff_error() -> &'static str {
+    tr!("copy_commit_diff_error", "copy commit diff error:")
+}
+pub fn copy_describe_error() -> &'static str {
+    tr!("copy_describe_error", "copy describe error:")
+}
+pub fn reverse_log_error() -> &'static str {
+    tr!("reverse_log_error", "reverse log error:")
+}
+pub fn author_summary_error() -> &'static str {
+    tr!("author_summary_error", "author summary error:")
+}
+pub fn author_stats_error() -> &'static str {
+    tr!("author_stats_error", "author stats error:")
+}
+pub fn export_error() -> &'static str {
+    tr!("export_error", "export error:")
+}
+pub fn log_order_error() -> &'static str {
+    tr!("log_order_error", "log order error:")
+}
+pub fn log_refs_error() -> &'static str {
+    tr!("log_refs_error", "log refs error:")
+}
+pub fn log_window_error() -> &'static str {
+    tr!("log_window_error", "log window error:")
+}
+pub fn unpushed_commits_error() -> &'static str {
+    tr!("unpushed_commits_error", "unpushed commits error:")
+}
+pub fn compare_error() -> &'static str {
+    tr!("compare_error", "compare error:")
+}
+pub fn filter_error() -> &'static str {
+    tr!("filter_error", "filter error:")
+}
+pub fn copy_rebase_todo_error() -> &'static str {
+    tr!("copy_rebase_todo_error", "copy rebase todo error:")
+}
+pub fn copy_hash_error() -> &'static str {
+    tr!("copy_hash_error", "copy hash error:")
+}
+pub fn copy_subject_error() -> &'static str {
+    tr!("copy_subject_error", "copy subject error:")
+}
+pub fn export_patch_error() -> &'static str {
+    tr!("export_patch_error", "export patch error:")
+}
+pub fn cherry_pick_error() -> &'static str {
+    tr!("cherry_pick_error", "cherry-pick error:")
+}
+pub fn copy_filter_error() -> &'static str {
+    tr!("copy_filter_error", "copy filter error:")
+}
+pub fn search_error() -> &'static str {
+    tr!("search_error", "search error:")
+}
+pub fn sort_error() -> &'static str {
+    tr!("sort_error", "sort error:")
 }
 pub fn msg_title_error(_key_config: &SharedKeyConfig) -> String {
-    "Error".to_string()
+    tr!("msg_title_error", "Error").to_string()
+}
+pub fn msg_title_info(_key_config: &SharedKeyConfig) -> String {
+    tr!("msg_title_info", "Info").to_string()
 }
 pub fn commit_title(_key_config: &SharedKeyConfig) -> String {
-    "Commit".to_string()
+    tr!("commit_title", "Commit").to_string()
 }
 pub fn commit_title_amend(_key_config: &SharedKeyConfig) -> String {
-    "Commit (Amend)".to_string()
+    tr!("commit_title_amend", "Commit (Amend)").to_string()
 }
 pub fn commit_msg(_key_config: &SharedKeyConfig) -> String {
-    "type commit message..".to_string()
+    tr!("commit_msg", "type commit message..").to_string()
 }
-pub fn commit_editor_msg(_key_config: &SharedKeyConfig) -> String {
-    r##"
-# Edit your commit message
-# Lines starting with '#' will be ignored"##
-        .to_string()
+pub fn commit_editor_msg(
+    _key_config: &SharedKeyConfig,
+    comment_char: char,
+) -> String {
+    tr!(
+        "commit_editor_msg",
+        "Please enter the commit message for your changes. Lines starting\nwith '{0}' will be ignored, and an empty message aborts the commit.",
+        comment_char
+    )
+}
+pub fn tag_editor_msg(
+    _key_config: &SharedKeyConfig,
+    tag: &str,
+    comment_char: char,
+) -> String {
+    tr!(
+        "tag_editor_msg",
+        "\nWrite a message for tag:\n  {0}\nLines starting with '{1}' will be ignored.",
+        tag, comment_char
+    )
+}
+pub fn file_reverted_msg(
+    _key_config: &SharedKeyConfig,
+    path: &str,
+    commit: &str,
+) -> String {
+    tr!(
+        "file_reverted_msg",
+        "reverted the changes of {0} to `{1}`, staged",
+        commit,
+        path
+    )
+}
+pub fn file_revert_conflict_msg(
+    _key_config: &SharedKeyConfig,
+    path: &str,
+    commit: &str,
+) -> String {
+    tr!(
+        "file_revert_conflict_msg",
+        "reverting the changes of {0} to `{1}` conflicts, resolve and stage it in the status tab",
+        commit, path
+    )
+}
+pub fn log_fetch_error(
+    _key_config: &SharedKeyConfig,
+    error: &str,
+) -> String {
+    tr!(
+        "log_fetch_error",
+        "could not load the log, the rows shown may be outdated:\n{0}",
+        error
+    )
+}
+pub fn commit_aborted_empty() -> &'static str {
+    tr!(
+        "commit_aborted_empty",
+        "Aborting commit due to empty commit message."
+    )
+}
+pub fn commit_aborted_template() -> &'static str {
+    tr!(
+        "commit_aborted_template",
+        "Aborting commit; you did not edit the message."
+    )
+}
+pub fn tag_aborted_empty() -> &'static str {
+    tr!(
+        "tag_aborted_empty",
+        "Aborting tag due to empty tag message."
+    )
 }
 pub fn stash_popup_title(_key_config: &SharedKeyConfig) -> String {
-    "Stash".to_string()
+    tr!("stash_popup_title", "Stash").to_string()
 }
 pub fn stash_popup_msg(_key_config: &SharedKeyConfig) -> String {
-    "type name (optional)".to_string()
+    tr!("stash_popup_msg", "type name (optional)").to_string()
 }
 pub fn confirm_title_reset(_key_config: &SharedKeyConfig) -> String {
-    "Reset".to_string()
+    tr!("confirm_title_reset", "Reset").to_string()
 }
 pub fn confirm_title_stashdrop(
     _key_config: &SharedKeyConfig,
 ) -> String {
-    "Drop".to_string()
+    tr!("confirm_title_stashdrop", "Drop").to_string()
 }
 pub fn confirm_msg_reset(_key_config: &SharedKeyConfig) -> String {
-    "confirm file reset?".to_string()
+    tr!("confirm_msg_reset", "confirm file reset?").to_string()
 }
 pub fn confirm_msg_stashdrop(
     _key_config: &SharedKeyConfig,
 ) -> String {
-    "confirm stash drop?".to_string()
+    tr!("confirm_msg_stashdrop", "confirm stash drop?").to_string()
 }
 pub fn confirm_msg_resethunk(
     _key_config: &SharedKeyConfig,
 ) -> String {
-    "confirm reset hunk?".to_string()
+    tr!("confirm_msg_resethunk", "confirm reset hunk?").to_string()
 }
 pub fn confirm_title_delete_branch(
     _key_config: &SharedKeyConfig,
 ) -> String {
-    "Delete Branch".to_string()
+    tr!("confirm_title_delete_branch", "Delete Branch").to_string()
 }
 pub fn confirm_msg_delete_branch(
     _key_config: &SharedKeyConfig,
     branch_ref: &str,
 ) -> String {
-    format!("Confirm deleting branch: '{}' ?", branch_ref)
+    tr!(
+        "confirm_msg_delete_branch",
+        "Confirm deleting branch: '{0}' ?",
+        branch_ref
+    )
 }
-pub fn log_title(_key_config: &SharedKeyConfig) -> String {
-    "Commit".to_string()
+pub fn confirm_title_reset_hard(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("confirm_title_reset_hard", "Reset").to_string()
 }
-pub fn tag_commit_popup_title(
+pub fn confirm_msg_reset_hard(
+    _key_config: &SharedKeyConfig,
+    commit: &str,
+) -> String {
+    tr!(
+        "confirm_msg_reset_hard",
+        "Reset HEAD to '{0}' ?\nuncommitted changes will be lost",
+        commit
+    )
+}
+pub fn confirm_title_restore_ref(
     _key_config: &SharedKeyConfig,
 ) -> String {
-    "Tag".to_string()
+    tr!("confirm_title_restore_ref", "Undo").to_string()
 }
-pub fn tag_commit_popup_msg(_key_config: &SharedKeyConfig) -> String {
-    "type tag".to_string()
+pub fn confirm_msg_restore_ref(
+    _key_config: &SharedKeyConfig,
+    refname: &str,
+    commit: &str,
+    pushed: bool,
+) -> String {
+    if pushed {
+        tr!(
+            "confirm_msg_restore_ref.pushed",
+            "Move '{0}' back to '{1}' ?\nuncommitted changes will be lost\nWARNING: its current commits were pushed already",
+            refname,
+            commit
+        )
+    } else {
+        tr!(
+            "confirm_msg_restore_ref",
+            "Move '{0}' back to '{1}' ?\nuncommitted changes will be lost",
+            refname,
+            commit
+        )
+    }
 }
-pub fn stashlist_title(_key_config: &SharedKeyConfig) -> String {
-    "Stashes".to_string()
+pub fn confirm_title_cherry_pick_abort(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("confirm_title_cherry_pick_abort", "Abort cherry-pick")
+        .to_string()
 }
-pub fn help_title(_key_config: &SharedKeyConfig) -> String {
-    "Help: all commands".to_string()
+pub fn confirm_msg_cherry_pick_abort(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("confirm_msg_cherry_pick_abort", "Drop the picked commits and all changes the stopped pick left and move back to where the branch was before?").to_string()
 }
-pub fn stashing_files_title(_key_config: &SharedKeyConfig) -> String {
-    "Files to Stash".to_string()
+pub fn confirm_title_unsigned(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("confirm_title_unsigned", "Signing failed").to_string()
 }
-pub fn stashing_options_title(
+pub fn confirm_msg_unsigned(
     _key_config: &SharedKeyConfig,
+    what: &str,
+    problem: &str,
 ) -> String {
-    "Options".to_string()
+    tr!(
+        "confirm_msg_unsigned",
+        "{0}\n\nCreate the {1} without a signature?",
+        problem,
+        what
+    )
 }
-pub fn loading_text(_key_config: &SharedKeyConfig) -> String {
-    "Loading ...".to_string()
+pub fn confirm_title_trust_host_key(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("confirm_title_trust_host_key", "Unknown host key")
+        .to_string()
 }
-pub fn create_branch_popup_title(
+pub fn confirm_msg_trust_host_key(
     _key_config: &SharedKeyConfig,
+    host: &str,
+    fingerprint: &str,
 ) -> String {
-    "Branch".to_string()
+    tr!(
+        "confirm_msg_trust_host_key",
+        "The authenticity of '{0}' can't be established.\nkey fingerprint is {1}\n\nOnly continue if it matches the fingerprint published by the host.\nTrust it and add it to known_hosts?",
+        host, fingerprint
+    )
 }
-pub fn create_branch_popup_msg(
+pub fn host_key_store_failed_msg(
     _key_config: &SharedKeyConfig,
+    error: &str,
 ) -> String {
-    "type branch name".to_string()
+    tr!(
+        "host_key_store_failed_msg",
+        "storing the host key failed:\n{0}",
+        error
+    )
 }
-pub fn username_popup_title(_key_config: &SharedKeyConfig) -> String {
-    "Username".to_string()
+pub fn host_key_trusted_msg(
+    _key_config: &SharedKeyConfig,
+    host: &str,
+) -> String {
+    tr!(
+        "host_key_trusted_msg",
+        "added the host key of '{0}' to known_hosts.\nretry the operation",
+        host
+    )
 }
-pub fn username_popup_msg(_key_config: &SharedKeyConfig) -> String {
-    "type username".to_string()
+pub fn cherry_pick_progress(
+    _key_config: &SharedKeyConfig,
+    current: usize,
+    total: usize,
+) -> String {
+    tr!(
+        "cherry_pick_progress",
+        "applying {0} of {1}...",
+        current,
+        total
+    )
 }
-pub fn password_popup_title(_key_config: &SharedKeyConfig) -> String {
-    "Password".to_string()
+pub fn cherry_pick_conflict(
+    key_config: &SharedKeyConfig,
+    applied: usize,
+    total: usize,
+    commit: &str,
+) -> String {
+    tr!(
+        "cherry_pick_conflict",
+        "applied {0} of {1}, conflict on {2}\n\nresolve and stage the conflicts, then continue [{3}] or abort and restore the branch [{4}]",
+        applied,
+        total,
+        commit,
+        get_hint(key_config.enter),
+        get_hint(key_config.cherry_pick_abort)
+    )
 }
-pub fn password_popup_msg(_key_config: &SharedKeyConfig) -> String {
-    "type password".to_string()
+pub fn cherry_pick_failed(
+    key_config: &SharedKeyConfig,
+    applied: usize,
+    total: usize,
+    commit: &str,
+    error: &str,
+) -> String {
+    tr!(
+        "cherry_pick_failed",
+        "applied {0} of {1}, failed on {2}:\n{3}\n\nabort and restore the branch [{4}] or keep the commits applied so far [{5}]",
+        applied,
+        total,
+        commit,
+        error,
+        get_hint(key_config.cherry_pick_abort),
+        get_hint(key_config.exit_popup)
+    )
 }
-
-pub fn rename_branch_popup_title(
+pub fn cherry_pick_done(
     _key_config: &SharedKeyConfig,
+    applied: usize,
+    skipped: &[String],
 ) -> String {
-    "Rename Branch".to_string()
+    if skipped.is_empty() {
+        tr!("cherry_pick_done", "applied {0} commit(s)", applied)
+    } else {
+        tr!(
+            "cherry_pick_done.skipped",
+            "applied {0} commit(s)\nskipped as already applied: {1}",
+            applied,
+            skipped.join(" ")
+        )
+    }
 }
-pub fn rename_branch_popup_msg(
+pub fn cherry_pick_error_msg(
     _key_config: &SharedKeyConfig,
+    error: &str,
 ) -> String {
-    "new branch name".to_string()
+    tr!("cherry_pick_error_msg", "cherry-pick error:\n{0}", error)
 }
+pub fn confirm_title_stash_apply_onto(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("confirm_title_stash_apply_onto", "Apply stash").to_string()
+}
+pub fn confirm_msg_stash_apply_onto(
+    _key_config: &SharedKeyConfig,
+    commit: &str,
+) -> String {
+    tr!(
+        "confirm_msg_stash_apply_onto",
+        "Checkout '{0}' (detached HEAD) and apply the latest stash on top?",
+        commit
+    )
+}
+pub fn confirm_title_push_dry_run(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("confirm_title_push_dry_run", "Push (dry run)").to_string()
+}
+pub fn confirm_msg_push_dry_run(
+    _key_config: &SharedKeyConfig,
+    url: &str,
+    updates: &[PushUpdate],
+) -> String {
+    let lines = updates
+        .iter()
+        .map(|u| {
+            let old = u.old_remote_tip.map_or_else(
+                || {
+                    tr!("confirm_msg_push_dry_run.new", "(new)")
+                        .to_string()
+                },
+                |id| id.get_short_string(),
+            );
+            let state = if u.old_remote_tip == Some(u.new_local_tip) {
+                tr!(
+                    "confirm_msg_push_dry_run.up_to_date",
+                    "up to date"
+                )
+            } else if u.fast_forward {
+                tr!(
+                    "confirm_msg_push_dry_run.fast_forward",
+                    "fast-forward"
+                )
+            } else {
+                tr!(
+                    "confirm_msg_push_dry_run.rejected",
+                    "rejected: non-fast-forward"
+                )
+            };
+            format!(
+                "{}: {} -> {} [{}]",
+                u.refname,
+                old,
+                u.new_local_tip.get_short_string(),
+                state
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
 
-pub mod commit {
-    use crate::keys::SharedKeyConfig;
-    pub fn details_author(_key_config: &SharedKeyConfig) -> String {
-        "Author: ".to_string()
-    }
-    pub fn details_committer(
-        _key_config: &SharedKeyConfig,
-    ) -> String {
-        "Committer: ".to_string()
-    }
-    pub fn details_sha(_key_config: &SharedKeyConfig) -> String {
-        "Sha: ".to_string()
-    }
-    pub fn details_date(_key_config: &SharedKeyConfig) -> String {
-        "Date: ".to_string()
-    }
-    pub fn details_tags(_key_config: &SharedKeyConfig) -> String {
-        "Tags: ".to_string()
-    }
-    pub fn details_info_title(
-        _key_config: &SharedKeyConfig,
-    ) -> String {
-        "Info".to_string()
-    }
-    pub fn details_message_title(
-        _key_config: &SharedKeyConfig,
-    ) -> String {
-        "Message".to_string()
-    }
-    pub fn details_files_title(
-        _key_config: &SharedKeyConfig,
-    ) -> String {
-        "Files:".to_string()
+    if updates.iter().all(|u| u.fast_forward) {
+        tr!(
+            "confirm_msg_push_dry_run",
+            "to {0}\n\n{1}\n\nConfirm push?",
+            url,
+            lines
+        )
+    } else {
+        tr!(
+            "confirm_msg_push_dry_run.refused",
+            "to {0}\n\n{1}\n\nThe remote would reject the push.",
+            url,
+            lines
+        )
     }
 }
-
-pub mod commands {
-    use crate::components::CommandText;
-    use crate::keys::{get_hint, SharedKeyConfig};
-
-    static CMD_GROUP_GENERAL: &str = "-- General --";
-    static CMD_GROUP_DIFF: &str = "-- Diff --";
-    static CMD_GROUP_CHANGES: &str = "-- Changes --";
-    static CMD_GROUP_COMMIT: &str = "-- Commit --";
-    static CMD_GROUP_STASHING: &str = "-- Stashing --";
-    static CMD_GROUP_STASHES: &str = "-- Stashes --";
-    static CMD_GROUP_LOG: &str = "-- Log --";
-
-    pub fn toggle_tabs(key_config: &SharedKeyConfig) -> CommandText {
+pub fn log_title(_key_config: &SharedKeyConfig) -> String {
+    tr!("log_title", "Commit").to_string()
+}
+pub fn log_title_filtering(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    tr!("log_title_filtering", "Commit (filtering.. {0})", count)
+}
+pub fn log_title_filtered(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    tr!(
+        "log_title_filtered",
+        "Commit (filtered, done: {0} matches)",
+        count
+    )
+}
+pub fn log_title_relevance(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    tr!(
+        "log_title_relevance",
+        "Commit (by relevance, done: {0} matches)",
+        count
+    )
+}
+pub fn log_title_range(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    tr!("log_title_range", "[range: {0}]", count)
+}
+pub fn log_title_oldest_first(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("log_title_oldest_first", "[oldest first]").to_string()
+}
+pub fn log_title_topo_order(_key_config: &SharedKeyConfig) -> String {
+    tr!("log_title_topo_order", "[topo order]").to_string()
+}
+pub fn log_title_all_refs(_key_config: &SharedKeyConfig) -> String {
+    tr!("log_title_all_refs", "[all refs]").to_string()
+}
+pub fn log_title_count(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    tr!("log_title_count", "[count: {0}]", count)
+}
+pub fn log_title_window(
+    _key_config: &SharedKeyConfig,
+    window: &str,
+) -> String {
+    tr!("log_title_window", "[window: {0}]", window)
+}
+pub fn log_window_end(
+    key_config: &SharedKeyConfig,
+    window: &str,
+) -> String {
+    tr!(
+        "log_window_end",
+        "\u{2014} older commits are outside the log window `{0}`, [{1}] shows the full history \u{2014}",
+        window,
+        get_hint(key_config.log_full_history),
+    )
+}
+pub fn invalid_log_window_msg(
+    _key_config: &SharedKeyConfig,
+    error: &str,
+) -> String {
+    tr!(
+        "invalid_log_window_msg",
+        "invalid `log.window` option, showing the full history:\n{0}",
+        error
+    )
+}
+pub fn invalid_remote_timeout_msg(
+    _key_config: &SharedKeyConfig,
+    error: &str,
+) -> String {
+    tr!(
+        "invalid_remote_timeout_msg",
+        "invalid `remote.timeout_secs` option, using the default:\n{0}",
+        error
+    )
+}
+pub fn log_title_marked(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    tr!("log_title_marked", "[marked: {0}]", count)
+}
+pub fn patches_exported(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+    dir: &str,
+) -> String {
+    tr!(
+        "patches_exported",
+        "exported {0} patch(es) to:\n{1}",
+        count,
+        dir
+    )
+}
+pub fn log_exported(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+    path: &str,
+) -> String {
+    tr!(
+        "log_exported",
+        "exported {0} commit(s) to:\n{1}",
+        count,
+        path
+    )
+}
+pub fn export_log_popup_title(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+    filtered: bool,
+) -> String {
+    if filtered {
+        tr!(
+            "export_log_popup_title.filtered",
+            "Export {0} matching commit(s) to",
+            with_separators(count)
+        )
+    } else {
+        tr!(
+            "export_log_popup_title",
+            "Export {0} commit(s) to",
+            with_separators(count)
+        )
+    }
+}
+pub fn export_log_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    tr!("export_log_popup_msg", "file to write the commits to")
+        .to_string()
+}
+pub fn export_log_error_msg(
+    _key_config: &SharedKeyConfig,
+    error: &str,
+) -> String {
+    tr!("export_log_error_msg", "export error:\n{0}", error)
+}
+pub fn rebase_todo_not_contiguous(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!(
+        "rebase_todo_not_contiguous",
+        "rebase todo needs a contiguous range of marked commits"
+    )
+    .to_string()
+}
+pub fn log_search_no_match(_key_config: &SharedKeyConfig) -> String {
+    tr!(
+        "log_search_no_match",
+        "no further commit matches the search"
+    )
+    .to_string()
+}
+pub fn log_title_unpushed(
+    _key_config: &SharedKeyConfig,
+    count: usize,
+) -> String {
+    tr!("log_title_unpushed", "Commit (unpushed: {0})", count)
+}
+pub fn tag_commit_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("tag_commit_popup_title", "Tag").to_string()
+}
+pub fn tag_commit_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    tr!("tag_commit_popup_msg", "type tag").to_string()
+}
+pub fn tag_message_popup_title(
+    _key_config: &SharedKeyConfig,
+    tag: &str,
+    template: &str,
+    push: bool,
+) -> String {
+    if push {
+        tr!(
+            "tag_message_popup_title.push",
+            "Message of '{0}' [{1}] (push to origin)",
+            tag,
+            template
+        )
+    } else {
+        tr!(
+            "tag_message_popup_title",
+            "Message of '{0}' [{1}]",
+            tag,
+            template
+        )
+    }
+}
+pub fn tag_pushed_msg(tag: &str) -> String {
+    tr!(
+        "tag_pushed_msg",
+        "tag '{0}' created and pushed to origin",
+        tag
+    )
+}
+pub fn tag_message_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("tag_message_popup_msg", "type tag message").to_string()
+}
+pub fn compare_commits_title(
+    _key_config: &SharedKeyConfig,
+    old: &str,
+    new: &str,
+    files: usize,
+) -> String {
+    tr!(
+        "compare_commits_title",
+        "Files {0}..{1}: {2}",
+        old,
+        new,
+        files
+    )
+}
+pub fn find_commit_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("find_commit_popup_title", "Find Commit").to_string()
+}
+pub fn find_commit_popup_title_pending(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!(
+        "find_commit_popup_title_pending",
+        "Find Commit (starts soon)"
+    )
+    .to_string()
+}
+pub fn find_commit_popup_title_running(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!(
+        "find_commit_popup_title_running",
+        "Find Commit (filtering..)"
+    )
+    .to_string()
+}
+pub fn filter_status_running(
+    _key_config: &SharedKeyConfig,
+    matches: usize,
+    scanned: usize,
+) -> String {
+    tr!(
+        "filter_status_running",
+        "{0} matches of {1} scanned",
+        with_separators(matches),
+        with_separators(scanned)
+    )
+}
+pub fn filter_status_done(
+    _key_config: &SharedKeyConfig,
+    matches: usize,
+) -> String {
+    tr!(
+        "filter_status_done",
+        "{0} matches",
+        with_separators(matches)
+    )
+}
+pub fn filter_status_failed(
+    _key_config: &SharedKeyConfig,
+    error: &str,
+) -> String {
+    tr!("filter_status_failed", "filter failed: {0}", error)
+}
+pub fn filter_syntax_error(
+    _key_config: &SharedKeyConfig,
+    error: &str,
+) -> String {
+    tr!("filter_syntax_error", "syntax: {0}", error)
+}
+pub fn search_commit_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("search_commit_popup_title", "Search Commit").to_string()
+}
+pub fn find_commit_popup_msg(
+    _key_config: &SharedKeyConfig,
+    default_fields: &str,
+) -> String {
+    tr!(
+        "find_commit_popup_msg",
+        "e.g. `:a= bob && :m fix` (plain terms search {0})",
+        default_fields
+    )
+}
+pub fn unknown_filter_field_msg(
+    name: &str,
+    expected: &str,
+) -> String {
+    tr!(
+        "unknown_filter_field_msg",
+        "unknown filter field `{0}`, expected any of: {1}",
+        name,
+        expected
+    )
+}
+pub fn no_filter_fields_msg() -> &'static str {
+    tr!("no_filter_fields_msg", "no filter fields given")
+}
+pub fn invalid_filter_fields_msg(
+    _key_config: &SharedKeyConfig,
+    error: &str,
+) -> String {
+    tr!(
+        "invalid_filter_fields_msg",
+        "invalid `filter.default_fields` in options.ron:\n{0}\n(using the default fields)",
+        error
+    )
+}
+pub fn log_parent_not_fetched(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("log_parent_not_fetched", "this is the shallow boundary, the parents of this commit were not fetched").to_string()
+}
+pub fn log_rename_branch_detached(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!(
+        "log_rename_branch_detached",
+        "no local branch is checked out (detached HEAD)"
+    )
+    .to_string()
+}
+pub fn author_stats_title(
+    _key_config: &SharedKeyConfig,
+    filtered: bool,
+    progress: Option<(usize, usize)>,
+) -> String {
+    format!(
+        "{}{}{}",
+        author_stats_popup_msg(),
+        if filtered {
+            tr!("author_stats_title.filtered", " (filtered)")
+        } else {
+            ""
+        },
+        progress.map_or_else(String::new, |(done, total)| format!(
+            " {}/{}",
+            with_separators(done),
+            with_separators(total)
+        ))
+    )
+}
+pub fn log_author_summary_msg(
+    _key_config: &SharedKeyConfig,
+    counts: &[(String, usize)],
+    max: usize,
+    pending: bool,
+) -> String {
+    let total: usize = counts.iter().map(|(_, count)| count).sum();
+    let mut lines = vec![if pending {
+        tr!(
+            "log_author_summary_msg.pending",
+            "{0} matching commits by {1} authors (still filtering):",
+            with_separators(total),
+            with_separators(counts.len())
+        )
+    } else {
+        tr!(
+            "log_author_summary_msg",
+            "{0} matching commits by {1} authors:",
+            with_separators(total),
+            with_separators(counts.len())
+        )
+    }];
+
+    lines.extend(counts.iter().take(max).map(|(author, count)| {
+        format!("{:>7}  {}", with_separators(*count), author)
+    }));
+    if counts.len() > max {
+        lines.push(tr!(
+            "log_author_summary_msg.more",
+            "+{0} more",
+            counts.len() - max
+        ));
+    }
+
+    lines.join("\n")
+}
+pub fn push_url_popup_title(
+    _key_config: &SharedKeyConfig,
+    remote: &str,
+) -> String {
+    tr!("push_url_popup_title", "Push URL of '{0}'", remote)
+}
+pub fn push_url_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    tr!("push_url_popup_msg", "empty to push to the fetch url")
+        .to_string()
+}
+pub fn push_url_error_msg(
+    _key_config: &SharedKeyConfig,
+    error: &str,
+) -> String {
+    tr!("push_url_error_msg", "push url error:\n{0}", error)
+}
+pub fn stashlist_title(_key_config: &SharedKeyConfig) -> String {
+    tr!("stashlist_title", "Stashes").to_string()
+}
+pub fn help_title(_key_config: &SharedKeyConfig) -> String {
+    tr!("help_title", "Help: all commands").to_string()
+}
+pub fn stashing_files_title(_key_config: &SharedKeyConfig) -> String {
+    tr!("stashing_files_title", "Files to Stash").to_string()
+}
+pub fn stashing_options_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("stashing_options_title", "Options").to_string()
+}
+pub fn loading_text(_key_config: &SharedKeyConfig) -> String {
+    tr!("loading_text", "Loading ...").to_string()
+}
+pub fn create_branch_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("create_branch_popup_title", "Branch").to_string()
+}
+pub fn create_branch_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("create_branch_popup_msg", "type branch name").to_string()
+}
+pub fn username_popup_title(_key_config: &SharedKeyConfig) -> String {
+    tr!("username_popup_title", "Username").to_string()
+}
+pub fn username_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    tr!("username_popup_msg", "type username").to_string()
+}
+pub fn password_popup_title(_key_config: &SharedKeyConfig) -> String {
+    tr!("password_popup_title", "Password").to_string()
+}
+pub fn password_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    tr!("password_popup_msg", "type password").to_string()
+}
+pub fn passphrase_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("passphrase_popup_title", "SSH Key Passphrase").to_string()
+}
+pub fn passphrase_popup_msg(_key_config: &SharedKeyConfig) -> String {
+    tr!(
+        "passphrase_popup_msg",
+        "type the passphrase of your ssh key"
+    )
+    .to_string()
+}
+
+pub fn rename_branch_popup_title(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("rename_branch_popup_title", "Rename Branch").to_string()
+}
+pub fn rename_branch_popup_msg(
+    _key_config: &SharedKeyConfig,
+) -> String {
+    tr!("rename_branch_popup_msg", "new branch name").to_string()
+}
+pub fn text_input_truncated(
+    _key_config: &SharedKeyConfig,
+    max_len: usize,
+) -> String {
+    tr!("text_input_truncated", "(truncated to {0} chars)", max_len)
+}
+
+pub fn remote_op_waiting_msg(
+    _key_config: &SharedKeyConfig,
+    op: RemoteOp,
+) -> String {
+    tr!("remote_op_waiting_msg", "waiting for {0} to finish...", op)
+}
+
+pub fn remote_error_msg(
+    _key_config: &SharedKeyConfig,
+    action: &str,
+    retry: KeyEvent,
+    err: &RemoteError,
+) -> String {
+    match err {
+        RemoteError::Auth => tr!(
+            "remote_error_msg.auth",
+            "{0} failed: authentication rejected.\ncheck your credentials and retry [{1}]",
+            action,
+            get_hint(retry)
+        ),
+        RemoteError::Network { url, message } => tr!(
+            "remote_error_msg.network",
+            "{0} failed: could not reach '{1}':\n{2}\ncheck your connection and retry [{3}]",
+            action,
+            url,
+            message,
+            get_hint(retry)
+        ),
+        RemoteError::Rejected { refname, reason } => tr!(
+            "remote_error_msg.rejected",
+            "{0} failed: '{1}' was rejected by the remote:\n{2}\n(fetch and integrate remote changes first)",
+            action, refname, reason
+        ),
+        RemoteError::NotFound => tr!(
+            "remote_error_msg.not_found",
+            "{0} failed: remote not found.\ncheck the remotes configured for this repository",
+            action
+        ),
+        RemoteError::InvalidRefspec { refspec, reason } => tr!(
+            "remote_error_msg.invalid_refspec",
+            "{0} failed: invalid refspec '{1}':\n{2}",
+            action, refspec, reason
+        ),
+        RemoteError::UnknownHostKey { host, fingerprint } => tr!(
+            "remote_error_msg.unknown_host_key",
+            "{0} failed: the host key of '{1}' is not in known_hosts ({2})",
+            action, host, fingerprint
+        ),
+        RemoteError::HostKeyMismatch { host, fingerprint } => tr!(
+            "remote_error_msg.host_key_mismatch",
+            "{0} failed: the host key of '{1}' ({2}) does not match the one in known_hosts!\nsomeone could be eavesdropping on you, or the host key was changed.\nremove the old key from known_hosts if the change is expected",
+            action, host, fingerprint
+        ),
+        RemoteError::HostKeyRevoked { host, fingerprint } => tr!(
+            "remote_error_msg.host_key_revoked",
+            "{0} failed: the host key of '{1}' ({2}) is marked as revoked in known_hosts!\nsomeone could be eavesdropping on you, do not trust this host",
+            action, host, fingerprint
+        ),
+        RemoteError::RebaseConflict { commit } => tr!(
+            "remote_error_msg.rebase_conflict",
+            "{0} stopped: local commit {1} conflicts with the remote changes.\nresolve the conflicts or abort the rebase",
+            action, commit
+        ),
+        RemoteError::Timeout { secs } => tr!(
+            "remote_error_msg.timeout",
+            "{0} failed: timed out after {1}s with no progress.\nretry [{2}]",
+            action,
+            secs,
+            get_hint(retry)
+        ),
+        RemoteError::HookRejected(output) => tr!(
+            "remote_error_msg.hook_rejected",
+            "{0} stopped by the pre-push hook:\n{1}",
+            action, output
+        ),
+        RemoteError::Busy { running } => tr!(
+            "remote_error_msg.busy",
+            "{0} not started: {1} is still running.\nretry [{2}] once it finished",
+            action,
+            running,
+            get_hint(retry)
+        ),
+        RemoteError::Cancelled => tr!(
+            "remote_error_msg.cancelled",
+            "{0} cancelled before it started",
+            action
+        ),
+        RemoteError::Other(msg) => {
+            tr!("remote_error_msg.other", "{0} failed:\n{1}", action, msg)
+        }
+    }
+}
+pub fn invalid_catalog_msg(
+    _key_config: &SharedKeyConfig,
+    error: &str,
+) -> String {
+    tr!(
+        "invalid_catalog_msg",
+        "invalid message catalog, texts stay English:\n{0}",
+        error
+    )
+}
+
+pub mod commit {
+    use crate::keys::SharedKeyConfig;
+    use crate::tr;
+    pub fn details_author(_key_config: &SharedKeyConfig) -> String {
+        tr!("commit.details_author", "Author: ").to_string()
+    }
+    pub fn details_co_authors(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        tr!("commit.details_co_authors", "Co-authors: ").to_string()
+    }
+    pub fn details_committer(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        tr!("commit.details_committer", "Committer: ").to_string()
+    }
+    pub fn details_sha(_key_config: &SharedKeyConfig) -> String {
+        tr!("commit.details_sha", "Sha: ").to_string()
+    }
+    pub fn details_date(_key_config: &SharedKeyConfig) -> String {
+        tr!("commit.details_date", "Date: ").to_string()
+    }
+    pub fn details_tags(_key_config: &SharedKeyConfig) -> String {
+        tr!("commit.details_tags", "Tags: ").to_string()
+    }
+    pub fn details_parents(_key_config: &SharedKeyConfig) -> String {
+        tr!("commit.details_parents", "Parents: ").to_string()
+    }
+    pub fn details_reverts(_key_config: &SharedKeyConfig) -> String {
+        tr!("commit.details_reverts", "Reverts: ").to_string()
+    }
+    pub fn details_reverted_by(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        tr!("commit.details_reverted_by", "Reverted by: ").to_string()
+    }
+    pub fn details_released(_key_config: &SharedKeyConfig) -> String {
+        tr!("commit.details_released", "First released in: ")
+            .to_string()
+    }
+    pub fn details_merge(_key_config: &SharedKeyConfig) -> String {
+        tr!("commit.details_merge", "(merge)").to_string()
+    }
+    pub fn details_info_title(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        tr!("commit.details_info_title", "Info").to_string()
+    }
+    pub fn details_message_title(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        tr!("commit.details_message_title", "Message").to_string()
+    }
+    pub fn details_files_title(
+        _key_config: &SharedKeyConfig,
+    ) -> String {
+        tr!("commit.details_files_title", "Files:").to_string()
+    }
+}
+
+pub mod commands {
+    use crate::components::CommandText;
+    use crate::keys::{get_hint, SharedKeyConfig};
+    use crate::tr;
+
+    fn cmd_group_general() -> &'static str {
+        tr!("commands.group.general", "-- General --")
+    }
+    fn cmd_group_diff() -> &'static str {
+        tr!("commands.group.diff", "-- Diff --")
+    }
+    fn cmd_group_changes() -> &'static str {
+        tr!("commands.group.changes", "-- Changes --")
+    }
+    fn cmd_group_commit() -> &'static str {
+        tr!("commands.group.commit", "-- Commit --")
+    }
+    fn cmd_group_stashing() -> &'static str {
+        tr!("commands.group.stashing", "-- Stashing --")
+    }
+    fn cmd_group_stashes() -> &'static str {
+        tr!("commands.group.stashes", "-- Stashes --")
+    }
+    fn cmd_group_log() -> &'static str {
+        tr!("commands.group.log", "-- Log --")
+    }
+
+    pub fn toggle_tabs(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.toggle_tabs",
+                "Next [{0}]",
+                get_hint(key_config.tab_toggle)
+            ),
+            tr!("commands.toggle_tabs.desc", "switch to next tab"),
+            cmd_group_general(),
+        )
+    }
+    pub fn toggle_tabs_direct(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.toggle_tabs_direct",
+                "Tab [{0}{1}{2}{3}]",
+                get_hint(key_config.tab_status),
+                get_hint(key_config.tab_log),
+                get_hint(key_config.tab_stashing),
+                get_hint(key_config.tab_stashes),
+            ),
+            tr!(
+                "commands.toggle_tabs_direct.desc",
+                "switch top level tabs directly"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn help_open(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.help_open",
+                "Help [{0}]",
+                get_hint(key_config.open_help)
+            ),
+            tr!("commands.help_open.desc", "open this help screen"),
+            cmd_group_general(),
+        )
+    }
+    pub fn navigate_commit_message(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.navigate_commit_message",
+                "Nav [{0}{1}]",
+                get_hint(key_config.move_up),
+                get_hint(key_config.move_down)
+            ),
+            tr!(
+                "commands.navigate_commit_message.desc",
+                "navigate commit message"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn navigate_tree(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.navigate_tree",
+                "Nav [{0}{1}{2}{3}]",
+                get_hint(key_config.move_up),
+                get_hint(key_config.move_down),
+                get_hint(key_config.move_right),
+                get_hint(key_config.move_left)
+            ),
+            tr!("commands.navigate_tree.desc", "navigate tree view"),
+            cmd_group_general(),
+        )
+    }
+    pub fn scroll(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.scroll",
+                "Scroll [{0}{1}]",
+                get_hint(key_config.focus_above),
+                get_hint(key_config.focus_below)
+            ),
+            tr!(
+                "commands.scroll.desc",
+                "scroll up or down in focused view"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn copy(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.copy",
+                "Copy [{0}]",
+                get_hint(key_config.copy),
+            ),
+            tr!(
+                "commands.copy.desc",
+                "copy selected lines to clipboard"
+            ),
+            cmd_group_diff(),
+        )
+    }
+    pub fn diff_home_end(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.diff_home_end",
+                "Jump up/down [{0},{1},{2},{3}]",
+                get_hint(key_config.home),
+                get_hint(key_config.end),
+                get_hint(key_config.move_up),
+                get_hint(key_config.move_down)
+            ),
+            tr!(
+                "commands.diff_home_end.desc",
+                "scroll to top or bottom of diff"
+            ),
+            cmd_group_diff(),
+        )
+    }
+    pub fn diff_hunk_add(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.diff_hunk_add",
+                "Add hunk [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.diff_hunk_add.desc",
+                "adds selected hunk to stage"
+            ),
+            cmd_group_diff(),
+        )
+    }
+    pub fn diff_hunk_revert(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.diff_hunk_revert",
+                "Revert hunk [{0}]",
+                get_hint(key_config.status_reset_item),
+            ),
+            tr!(
+                "commands.diff_hunk_revert.desc",
+                "reverts selected hunk"
+            ),
+            cmd_group_diff(),
+        )
+    }
+    pub fn diff_hunk_remove(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.diff_hunk_remove",
+                "Remove hunk [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.diff_hunk_remove.desc",
+                "removes selected hunk from stage"
+            ),
+            cmd_group_diff(),
+        )
+    }
+    pub fn close_popup(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.close_popup",
+                "Close [{0}]",
+                get_hint(key_config.exit_popup),
+            ),
+            tr!(
+                "commands.close_popup.desc",
+                "close overlay (e.g commit, help)"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn close_msg(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.close_msg",
+                "Close [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.close_msg.desc",
+                "close msg popup (e.g msg)"
+            ),
+            cmd_group_general(),
+        )
+        .hide_help()
+    }
+    pub fn cancel_queued_push(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.cancel_queued_push",
+                "Cancel [{0}]",
+                get_hint(key_config.exit_popup)
+            ),
+            tr!(
+                "commands.cancel_queued_push.desc",
+                "drop the push waiting for another remote operation"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn validate_msg(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.validate_msg",
+                "Validate [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!("commands.validate_msg.desc", "validate msg"),
+            cmd_group_general(),
+        )
+        .hide_help()
+    }
+    pub fn select_staging(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.select_staging",
+                "To stage [{0}]",
+                get_hint(key_config.focus_stage),
+            ),
+            tr!(
+                "commands.select_staging.desc",
+                "focus/select staging area"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn select_status(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.select_status",
+                "To files [{0},{1}]",
+                get_hint(key_config.tab_status),
+                get_hint(key_config.tab_log),
+            ),
+            tr!(
+                "commands.select_status.desc",
+                "focus/select file tree of staged or unstaged files"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn select_unstaged(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.select_unstaged",
+                "To unstaged [{0}]",
+                get_hint(key_config.focus_workdir),
+            ),
+            tr!(
+                "commands.select_unstaged.desc",
+                "focus/select unstaged area"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn commit_open(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.commit_open",
+                "Commit [{0}]",
+                get_hint(key_config.open_commit),
+            ),
+            tr!(
+                "commands.commit_open.desc",
+                "open commit popup (available in non-empty stage)"
+            ),
+            cmd_group_commit(),
+        )
+    }
+    pub fn commit_open_editor(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.commit_open_editor",
+                "Open editor [{0}]",
+                get_hint(key_config.open_commit_editor),
+            ),
+            tr!(
+                "commands.commit_open_editor.desc",
+                "open commit editor (available in non-empty stage)"
+            ),
+            cmd_group_commit(),
+        )
+    }
+    pub fn commit_enter(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.commit_enter",
+                "Commit [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.commit_enter.desc",
+                "commit (available when commit message is non-empty)"
+            ),
+            cmd_group_commit(),
+        )
+    }
+    pub fn commit_amend(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.commit_amend",
+                "Amend [{0}]",
+                get_hint(key_config.commit_amend),
+            ),
+            tr!("commands.commit_amend.desc", "amend last commit"),
+            cmd_group_commit(),
+        )
+    }
+    pub fn edit_item(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!("commands.edit_item","Edit Item [{0}]", get_hint(key_config.edit_file),),
+            tr!("commands.edit_item.desc", "edit the currently selected file in an external editor"),
+            cmd_group_changes(),
+        )
+    }
+    pub fn stage_item(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.stage_item",
+                "Stage Item [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.stage_item.desc",
+                "stage currently selected file or entire path"
+            ),
+            cmd_group_changes(),
+        )
+    }
+    pub fn stage_all(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.stage_all",
+                "Stage All [{0}]",
+                get_hint(key_config.status_stage_all),
+            ),
+            tr!(
+                "commands.stage_all.desc",
+                "stage all changes (in unstaged files)"
+            ),
+            cmd_group_changes(),
+        )
+    }
+    pub fn unstage_item(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.unstage_item",
+                "Unstage Item [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.unstage_item.desc",
+                "unstage currently selected file or entire path"
+            ),
+            cmd_group_changes(),
+        )
+    }
+    pub fn unstage_all(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.unstage_all",
+                "Unstage all [{0}]",
+                get_hint(key_config.status_stage_all),
+            ),
+            tr!(
+                "commands.unstage_all.desc",
+                "unstage all files (in staged files)"
+            ),
+            cmd_group_changes(),
+        )
+    }
+    pub fn reset_item(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.reset_item",
+                "Reset Item [{0}]",
+                get_hint(key_config.stash_drop),
+            ),
+            tr!(
+                "commands.reset_item.desc",
+                "revert changes in selected file or entire path"
+            ),
+            cmd_group_changes(),
+        )
+    }
+    pub fn ignore_item(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.ignore_item",
+                "Ignore [{0}]",
+                get_hint(key_config.status_ignore_file),
+            ),
+            tr!(
+                "commands.ignore_item.desc",
+                "Add file or path to .gitignore"
+            ),
+            cmd_group_changes(),
+        )
+    }
+
+    pub fn diff_focus_left(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.diff_focus_left",
+                "Back [{0}]",
+                get_hint(key_config.focus_left),
+            ),
+            tr!(
+                "commands.diff_focus_left.desc",
+                "view and select changed files"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn diff_focus_right(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.diff_focus_right",
+                "Diff [{0}]",
+                get_hint(key_config.focus_right),
+            ),
+            tr!(
+                "commands.diff_focus_right.desc",
+                "inspect file diff"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn suspend(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!("commands.suspend","Suspend [{0}]", get_hint(key_config.suspend)),
+            tr!("commands.suspend.desc", "suspend gitui to the shell it runs in, resume it with `fg`"),
+            cmd_group_general(),
+        )
+    }
+    pub fn open_shell(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!("commands.open_shell","Shell [{0}]", get_hint(key_config.open_shell)),
+            tr!("commands.open_shell.desc", "open a shell in the repository, gitui comes back once it exits"),
+            cmd_group_general(),
+        )
+    }
+    pub fn open_error_console(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.open_error_console",
+                "Errors [{0}]",
+                get_hint(key_config.open_error_console)
+            ),
+            tr!("commands.open_error_console.desc", "show recent errors and warnings that did not interrupt"),
+            cmd_group_general(),
+        )
+    }
+    pub fn error_console_clear(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.error_console_clear",
+                "Clear [{0}]",
+                get_hint(key_config.error_console_clear)
+            ),
+            tr!(
+                "commands.error_console_clear.desc",
+                "remove all errors and warnings from the console"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn quit(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.quit",
+                "Quit [{0}]",
+                get_hint(key_config.exit),
+            ),
+            tr!("commands.quit.desc", "quit gitui application"),
+            cmd_group_general(),
+        )
+    }
+    pub fn reset_confirm(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.reset_confirm",
+                "Confirm [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.reset_confirm.desc",
+                "resets the file in question"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn stashing_save(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.stashing_save",
+                "Save [{0}]",
+                get_hint(key_config.stashing_save),
+            ),
+            tr!(
+                "commands.stashing_save.desc",
+                "opens stash name input popup"
+            ),
+            cmd_group_stashing(),
+        )
+    }
+    pub fn stashing_toggle_indexed(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.stashing_toggle_indexed",
+                "Toggle Staged [{0}]",
+                get_hint(key_config.stashing_toggle_index),
+            ),
+            tr!(
+                "commands.stashing_toggle_indexed.desc",
+                "toggle including staged files into stash"
+            ),
+            cmd_group_stashing(),
+        )
+    }
+    pub fn stashing_toggle_untracked(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.stashing_toggle_untracked",
+                "Toggle Untracked [{0}]",
+                get_hint(key_config.stashing_toggle_untracked),
+            ),
+            tr!(
+                "commands.stashing_toggle_untracked.desc",
+                "toggle including untracked files into stash"
+            ),
+            cmd_group_stashing(),
+        )
+    }
+    pub fn stashing_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.stashing_confirm_msg",
+                "Stash [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.stashing_confirm_msg.desc",
+                "save files to stash"
+            ),
+            cmd_group_stashing(),
+        )
+    }
+    pub fn stashlist_apply(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.stashlist_apply",
+                "Apply [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.stashlist_apply.desc",
+                "apply selected stash"
+            ),
+            cmd_group_stashes(),
+        )
+    }
+    pub fn stashlist_drop(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.stashlist_drop",
+                "Drop [{0}]",
+                get_hint(key_config.stash_drop),
+            ),
+            tr!(
+                "commands.stashlist_drop.desc",
+                "drop selected stash"
+            ),
+            cmd_group_stashes(),
+        )
+    }
+    pub fn stashlist_inspect(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.stashlist_inspect",
+                "Inspect [{0}]",
+                get_hint(key_config.focus_right),
+            ),
+            tr!(
+                "commands.stashlist_inspect.desc",
+                "open stash commit details (allows to diff files)"
+            ),
+            cmd_group_stashes(),
+        )
+    }
+    pub fn log_details_toggle(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_details_toggle",
+                "Details [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.log_details_toggle.desc",
+                "open details of selected commit"
+            ),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_details_open(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_details_open",
+                "Inspect [{0}]",
+                get_hint(key_config.focus_right),
+            ),
+            tr!(
+                "commands.log_details_open.desc",
+                "inspect selected commit in detail"
+            ),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_details_scroll(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_details_scroll",
+                "Scroll Details [{0}{1}{2}{3}]",
+                get_hint(key_config.log_details_scroll_up),
+                get_hint(key_config.log_details_scroll_down),
+                get_hint(key_config.log_details_page_up),
+                get_hint(key_config.log_details_page_down),
+            ),
+            tr!("commands.log_details_scroll.desc", "scroll the commit message in the details, keeping the selected commit"),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_tag_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_tag_commit",
+                "Tag [{0}]",
+                get_hint(key_config.log_tag_commit),
+            ),
+            tr!("commands.log_tag_commit.desc", "tag commit"),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_copy_json(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_copy_json",
+                "Copy JSON [{0}]",
+                get_hint(key_config.log_copy_json),
+            ),
+            tr!(
+                "commands.log_copy_json.desc",
+                "copy commit metadata as json to clipboard"
+            ),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_mark_commit(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_mark_commit",
+                "Mark [{0}]",
+                get_hint(key_config.log_mark_commit),
+            ),
+            tr!(
+                "commands.log_mark_commit.desc",
+                "mark or unmark selected commit"
+            ),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_goto_parent(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_goto_parent",
+                "Parent [{0}]",
+                get_hint(key_config.log_goto_parent),
+            ),
+            tr!(
+                "commands.log_goto_parent.desc",
+                "select the (first) parent of the selected commit"
+            ),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_goto_revert(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_goto_revert",
+                "Revert [{0}]",
+                get_hint(key_config.log_goto_revert),
+            ),
+            tr!("commands.log_goto_revert.desc", "select the commit the selected one reverts, or else the (first) commit reverting it"),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_page_up_keep_row(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_page_up_keep_row",
+                "Page Up [{0}]",
+                get_hint(key_config.log_page_up_keep_row),
+            ),
+            tr!("commands.log_page_up_keep_row.desc", "scroll a page up keeping the selection on its row, like a pager"),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_page_down_keep_row(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_page_down_keep_row",
+                "Page Down [{0}]",
+                get_hint(key_config.log_page_down_keep_row),
+            ),
+            tr!("commands.log_page_down_keep_row.desc", "scroll a page down keeping the selection on its row, like a pager"),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_stash_apply_onto(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_stash_apply_onto",
+                "Apply stash here [{0}]",
+                get_hint(key_config.log_stash_apply_onto),
+            ),
+            tr!("commands.log_stash_apply_onto.desc", "checkout selected commit detached and apply the latest stash"),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_toggle_unpushed(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_toggle_unpushed",
+                "Unpushed [{0}]",
+                get_hint(key_config.log_toggle_unpushed),
+            ),
+            tr!("commands.log_toggle_unpushed.desc", "toggle showing only commits not pushed to the upstream"),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_toggle_oneline(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_toggle_oneline",
+                "Oneline [{0}]",
+                get_hint(key_config.log_toggle_oneline),
+            ),
+            tr!(
+                "commands.log_toggle_oneline.desc",
+                "toggle compact layout showing only hash and message"
+            ),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_toggle_full_hash(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Next [{}]", get_hint(key_config.tab_toggle)),
-            "switch to next tab",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.log_toggle_full_hash",
+                "Full hash [{0}]",
+                get_hint(key_config.log_toggle_full_hash),
+            ),
+            tr!(
+                "commands.log_toggle_full_hash.desc",
+                "toggle the full commit hash in the log"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn toggle_tabs_direct(
+    pub fn log_toggle_reverse(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "Tab [{}{}{}{}]",
-                get_hint(key_config.tab_status),
-                get_hint(key_config.tab_log),
-                get_hint(key_config.tab_stashing),
-                get_hint(key_config.tab_stashes),
+            tr!(
+                "commands.log_toggle_reverse",
+                "Oldest first [{0}]",
+                get_hint(key_config.log_toggle_reverse),
             ),
-            "switch top level tabs directly",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.log_toggle_reverse.desc",
+                "toggle showing the log oldest first"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn help_open(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_visual_mode(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Help [{}]", get_hint(key_config.open_help)),
-            "open this help screen",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.log_visual_mode",
+                "Visual [{0}]",
+                get_hint(key_config.log_visual_mode),
+            ),
+            tr!("commands.log_visual_mode.desc", "start or end a range selection, actions apply to the range"),
+            cmd_group_log(),
         )
     }
-    pub fn navigate_commit_message(
+    pub fn log_range_clear(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "Nav [{}{}]",
-                get_hint(key_config.move_up),
-                get_hint(key_config.move_down)
+            tr!(
+                "commands.log_range_clear",
+                "End range [{0}]",
+                get_hint(key_config.exit_popup),
+            ),
+            tr!(
+                "commands.log_range_clear.desc",
+                "end the range selection"
             ),
-            "navigate commit message",
-            CMD_GROUP_GENERAL,
+            cmd_group_log(),
         )
     }
-    pub fn navigate_tree(
+    pub fn log_marks_clear(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "Nav [{}{}{}{}]",
-                get_hint(key_config.move_up),
-                get_hint(key_config.move_down),
-                get_hint(key_config.move_right),
-                get_hint(key_config.move_left)
+            tr!(
+                "commands.log_marks_clear",
+                "Unmark all [{0}]",
+                get_hint(key_config.exit_popup)
             ),
-            "navigate tree view",
-            CMD_GROUP_GENERAL,
+            tr!("commands.log_marks_clear.desc", "clear all marks"),
+            cmd_group_log(),
         )
     }
-    pub fn scroll(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_copy_hashes(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!(
-                "Scroll [{}{}]",
-                get_hint(key_config.focus_above),
-                get_hint(key_config.focus_below)
+            tr!(
+                "commands.log_copy_hashes",
+                "Copy Hash [{0}]",
+                get_hint(key_config.copy)
             ),
-            "scroll up or down in focused view",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.log_copy_hashes.desc",
+                "copy the hashes of the marked (or selected) commits"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn copy(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_copy_subjects(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Copy [{}]", get_hint(key_config.copy),),
-            "copy selected lines to clipboard",
-            CMD_GROUP_DIFF,
+            tr!(
+                "commands.log_copy_subjects",
+                "Copy Subject [{0}]",
+                get_hint(key_config.log_copy_subjects),
+            ),
+            tr!("commands.log_copy_subjects.desc", "copy the subjects of the marked (or selected) commits"),
+            cmd_group_log(),
         )
     }
-    pub fn diff_home_end(
+    pub fn log_export_patches(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "Jump up/down [{},{},{},{}]",
-                get_hint(key_config.home),
-                get_hint(key_config.end),
-                get_hint(key_config.move_up),
-                get_hint(key_config.move_down)
+            tr!(
+                "commands.log_export_patches",
+                "Export Patch [{0}]",
+                get_hint(key_config.log_export_patches),
             ),
-            "scroll to top or bottom of diff",
-            CMD_GROUP_DIFF,
+            tr!("commands.log_export_patches.desc", "write the marked (or selected) commits as patch files into the workdir"),
+            cmd_group_log(),
         )
     }
-    pub fn diff_hunk_add(
+    pub fn log_export_list(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Add hunk [{}]", get_hint(key_config.enter),),
-            "adds selected hunk to stage",
-            CMD_GROUP_DIFF,
+            tr!(
+                "commands.log_export_list",
+                "Export List [{0}]",
+                get_hint(key_config.log_export_list),
+            ),
+            tr!("commands.log_export_list.desc", "write the commits of the log (or the filter results) to a file"),
+            cmd_group_log(),
         )
     }
-    pub fn diff_hunk_revert(
+    pub fn export_log_confirm(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "Revert hunk [{}]",
-                get_hint(key_config.status_reset_item),
+            tr!(
+                "commands.export_log_confirm",
+                "Export [{0}]",
+                get_hint(key_config.enter)
             ),
-            "reverts selected hunk",
-            CMD_GROUP_DIFF,
+            tr!(
+                "commands.export_log_confirm.desc",
+                "write the commits to the file"
+            ),
+            cmd_group_general(),
         )
     }
-    pub fn diff_hunk_remove(
+    pub fn log_copy_diff(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Remove hunk [{}]", get_hint(key_config.enter),),
-            "removes selected hunk from stage",
-            CMD_GROUP_DIFF,
+            tr!(
+                "commands.log_copy_diff",
+                "Copy Diff [{0}]",
+                get_hint(key_config.log_copy_diff),
+            ),
+            tr!("commands.log_copy_diff.desc", "copy the diff of the selected commit to its parent to clipboard"),
+            cmd_group_log(),
         )
     }
-    pub fn close_popup(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_copy_describe(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Close [{}]", get_hint(key_config.exit_popup),),
-            "close overlay (e.g commit, help)",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.log_copy_describe",
+                "Copy Describe [{0}]",
+                get_hint(key_config.log_copy_describe),
+            ),
+            tr!("commands.log_copy_describe.desc", "copy the nearest annotated tag, commits since and hash of the selected commit (like git describe) to clipboard"),
+            cmd_group_log(),
         )
     }
-    pub fn close_msg(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn commit_copy_file_diff(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Close [{}]", get_hint(key_config.enter),),
-            "close msg popup (e.g msg)",
-            CMD_GROUP_GENERAL,
+            tr!("commands.commit_copy_file_diff","Copy File Diff [{0}]", get_hint(key_config.copy)),
+            tr!("commands.commit_copy_file_diff.desc", "copy the diff of the selected file in this commit to clipboard"),
+            cmd_group_log(),
         )
-        .hide_help()
     }
-    pub fn validate_msg(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn commit_revert_file(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Validate [{}]", get_hint(key_config.enter),),
-            "validate msg",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.commit_revert_file",
+                "Revert File [{0}]",
+                get_hint(key_config.commit_revert_file)
+            ),
+            tr!("commands.commit_revert_file.desc", "undo the changes this commit made to the selected file, staged"),
+            cmd_group_log(),
         )
-        .hide_help()
     }
-    pub fn select_staging(
+    pub fn log_copy_filter(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "To stage [{}]",
-                get_hint(key_config.focus_stage),
+            tr!(
+                "commands.log_copy_filter",
+                "Copy Filter [{0}]",
+                get_hint(key_config.log_copy_filter),
             ),
-            "focus/select staging area",
-            CMD_GROUP_GENERAL,
+            tr!("commands.log_copy_filter.desc", "copy the applied filter expression as typed to clipboard"),
+            cmd_group_log(),
         )
     }
-    pub fn select_status(
+    pub fn log_author_summary(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "To files [{},{}]",
-                get_hint(key_config.tab_status),
-                get_hint(key_config.tab_log),
+            tr!(
+                "commands.log_author_summary",
+                "Authors [{0}]",
+                get_hint(key_config.log_author_summary),
             ),
-            "focus/select file tree of staged or unstaged files",
-            CMD_GROUP_GENERAL,
+            tr!("commands.log_author_summary.desc", "show how many of the filtered commits each author wrote"),
+            cmd_group_log(),
         )
     }
-    pub fn select_unstaged(
+    pub fn log_author_stats(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "To unstaged [{}]",
-                get_hint(key_config.focus_workdir),
+            tr!(
+                "commands.log_author_stats",
+                "Author Stats [{0}]",
+                get_hint(key_config.log_author_stats),
             ),
-            "focus/select unstaged area",
-            CMD_GROUP_GENERAL,
+            tr!("commands.log_author_stats.desc", "count the commits of each author in the log (or the filter results)"),
+            cmd_group_log(),
         )
     }
-    pub fn commit_open(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn author_stats_filter(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Commit [{}]", get_hint(key_config.open_commit),),
-            "open commit popup (available in non-empty stage)",
-            CMD_GROUP_COMMIT,
+            tr!("commands.author_stats_filter","Filter [{0}]", get_hint(key_config.enter)),
+            tr!("commands.author_stats_filter.desc", "filter the log to the commits of the selected author"),
+            cmd_group_general(),
         )
     }
-    pub fn commit_open_editor(
+    pub fn log_rename_branch(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "Open editor [{}]",
-                get_hint(key_config.open_commit_editor),
+            tr!(
+                "commands.log_rename_branch",
+                "Rename Branch [{0}]",
+                get_hint(key_config.log_rename_branch),
             ),
-            "open commit editor (available in non-empty stage)",
-            CMD_GROUP_COMMIT,
+            tr!(
+                "commands.log_rename_branch.desc",
+                "rename the checked out local branch"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn commit_enter(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_author_colors(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Commit [{}]", get_hint(key_config.enter),),
-            "commit (available when commit message is non-empty)",
-            CMD_GROUP_COMMIT,
+            tr!(
+                "commands.log_author_colors",
+                "Author Colors [{0}]",
+                get_hint(key_config.log_author_colors),
+            ),
+            tr!("commands.log_author_colors.desc", "color commits by author, then also list the authors on screen with their commit count"),
+            cmd_group_log(),
         )
     }
-    pub fn commit_amend(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_toggle_topo_order(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Amend [{}]", get_hint(key_config.commit_amend),),
-            "amend last commit",
-            CMD_GROUP_COMMIT,
+            tr!(
+                "commands.log_toggle_topo_order",
+                "Topo Order [{0}]",
+                get_hint(key_config.log_toggle_topo_order),
+            ),
+            tr!("commands.log_toggle_topo_order.desc", "toggle walking the log in topological order (lines of history kept together) or by date"),
+            cmd_group_log(),
         )
     }
-    pub fn edit_item(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_toggle_all_refs(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Edit Item [{}]", get_hint(key_config.edit_file),),
-            "edit the currently selected file in an external editor",
-            CMD_GROUP_CHANGES,
+            tr!(
+                "commands.log_toggle_all_refs",
+                "All Refs [{0}]",
+                get_hint(key_config.log_toggle_all_refs),
+            ),
+            tr!("commands.log_toggle_all_refs.desc", "toggle walking the history of all branches and tags (like `git log --all`) or just of HEAD"),
+            cmd_group_log(),
         )
     }
-    pub fn stage_item(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_cycle_window(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Stage Item [{}]", get_hint(key_config.enter),),
-            "stage currently selected file or entire path",
-            CMD_GROUP_CHANGES,
+            tr!(
+                "commands.log_cycle_window",
+                "Window [{0}]",
+                get_hint(key_config.log_cycle_window),
+            ),
+            tr!("commands.log_cycle_window.desc", "only show recent commits, cycles through a few windows and the full history"),
+            cmd_group_log(),
         )
     }
-    pub fn stage_all(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_full_history(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!(
-                "Stage All [{}]",
-                get_hint(key_config.status_stage_all),
+            tr!(
+                "commands.log_full_history",
+                "Full History [{0}]",
+                get_hint(key_config.log_full_history),
             ),
-            "stage all changes (in unstaged files)",
-            CMD_GROUP_CHANGES,
+            tr!(
+                "commands.log_full_history.desc",
+                "drop the log window and load the full history"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn unstage_item(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_open_difftool(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Unstage Item [{}]", get_hint(key_config.enter),),
-            "unstage currently selected file or entire path",
-            CMD_GROUP_CHANGES,
+            tr!(
+                "commands.log_open_difftool",
+                "Difftool [{0}]",
+                get_hint(key_config.log_open_difftool),
+            ),
+            tr!("commands.log_open_difftool.desc", "open the selected (or the two marked) commits in the configured difftool"),
+            cmd_group_log(),
         )
     }
-    pub fn unstage_all(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_cherry_pick(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!(
-                "Unstage all [{}]",
-                get_hint(key_config.status_stage_all),
+            tr!(
+                "commands.log_cherry_pick",
+                "Cherry-pick [{0}]",
+                get_hint(key_config.log_cherry_pick),
             ),
-            "unstage all files (in staged files)",
-            CMD_GROUP_CHANGES,
+            tr!("commands.log_cherry_pick.desc", "apply the marked (or selected) commits onto HEAD, oldest first"),
+            cmd_group_log(),
         )
     }
-    pub fn reset_item(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn cherry_pick_continue(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!(
-                "Reset Item [{}]",
-                get_hint(key_config.stash_drop),
+            tr!(
+                "commands.cherry_pick_continue",
+                "Continue [{0}]",
+                get_hint(key_config.enter)
+            ),
+            tr!(
+                "commands.cherry_pick_continue.desc",
+                "commit the resolved conflict and pick the rest"
             ),
-            "revert changes in selected file or entire path",
-            CMD_GROUP_CHANGES,
+            cmd_group_general(),
         )
     }
-    pub fn ignore_item(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn cherry_pick_abort(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!(
-                "Ignore [{}]",
-                get_hint(key_config.status_ignore_file),
+            tr!(
+                "commands.cherry_pick_abort",
+                "Abort [{0}]",
+                get_hint(key_config.cherry_pick_abort)
+            ),
+            tr!(
+                "commands.cherry_pick_abort.desc",
+                "abort and restore the branch"
             ),
-            "Add file or path to .gitignore",
-            CMD_GROUP_CHANGES,
+            cmd_group_general(),
         )
     }
-
-    pub fn diff_focus_left(
+    pub fn log_copy_rebase_todo(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Back [{}]", get_hint(key_config.focus_left),),
-            "view and select changed files",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.log_copy_rebase_todo",
+                "Copy rebase todo [{0}]",
+                get_hint(key_config.log_copy_rebase_todo),
+            ),
+            tr!("commands.log_copy_rebase_todo.desc", "copy the marked commits as `git rebase -i` todo list"),
+            cmd_group_log(),
         )
     }
-    pub fn diff_focus_right(
+    pub fn log_filter_range(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Diff [{}]", get_hint(key_config.focus_right),),
-            "inspect file diff",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.log_filter_range",
+                "Filter range [{0}]",
+                get_hint(key_config.log_filter_range),
+            ),
+            tr!(
+                "commands.log_filter_range.desc",
+                "show only commits between the two marked commits"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn quit(key_config: &SharedKeyConfig) -> CommandText {
+    pub fn log_compare_commits(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
         CommandText::new(
-            format!("Quit [{}]", get_hint(key_config.exit),),
-            "quit gitui application",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.log_compare_commits",
+                "Compare [{0}]",
+                get_hint(key_config.log_compare_commits),
+            ),
+            tr!("commands.log_compare_commits.desc", "list the files changed between the two marked commits"),
+            cmd_group_log(),
         )
     }
-    pub fn reset_confirm(
+    pub fn log_filter_clear(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Confirm [{}]", get_hint(key_config.enter),),
-            "resets the file in question",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.log_filter_clear",
+                "Clear filter [{0}]",
+                get_hint(key_config.exit_popup),
+            ),
+            tr!(
+                "commands.log_filter_clear.desc",
+                "show the full log again"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn stashing_save(
+    pub fn log_find_commit(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Save [{}]", get_hint(key_config.stashing_save),),
-            "opens stash name input popup",
-            CMD_GROUP_STASHING,
+            tr!(
+                "commands.log_find_commit",
+                "Find [{0}]",
+                get_hint(key_config.log_find_commit),
+            ),
+            tr!(
+                "commands.log_find_commit.desc",
+                "filter the log by sha, author, message or tag"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn stashing_toggle_indexed(
+    pub fn log_sort_relevance(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "Toggle Staged [{}]",
-                get_hint(key_config.stashing_toggle_index),
+            tr!(
+                "commands.log_sort_relevance",
+                "Relevance [{0}]",
+                get_hint(key_config.log_sort_relevance),
             ),
-            "toggle including staged files into stash",
-            CMD_GROUP_STASHING,
+            tr!("commands.log_sort_relevance.desc", "order the filtered commits by how many terms they match"),
+            cmd_group_log(),
         )
     }
-    pub fn stashing_toggle_untracked(
+    pub fn log_search(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.log_search",
+                "Search [{0}]",
+                get_hint(key_config.log_search)
+            ),
+            tr!(
+                "commands.log_search.desc",
+                "jump to commits matching a find query"
+            ),
+            cmd_group_log(),
+        )
+    }
+    pub fn log_search_next_prev(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "Toggle Untracked [{}]",
-                get_hint(key_config.stashing_toggle_untracked),
+            tr!(
+                "commands.log_search_next_prev",
+                "Next/Prev Match [{0}{1}]",
+                get_hint(key_config.log_search_next),
+                get_hint(key_config.log_search_prev),
             ),
-            "toggle including untracked files into stash",
-            CMD_GROUP_STASHING,
+            tr!("commands.log_search_next_prev.desc", "jump to the next (older) or previous matching commit"),
+            cmd_group_log(),
         )
     }
-    pub fn stashing_confirm_msg(
+    pub fn log_search_cancel(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Stash [{}]", get_hint(key_config.enter),),
-            "save files to stash",
-            CMD_GROUP_STASHING,
+            tr!(
+                "commands.log_search_cancel",
+                "Cancel search [{0}]",
+                get_hint(key_config.exit_popup),
+            ),
+            tr!(
+                "commands.log_search_cancel.desc",
+                "stop looking for the next match"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn stashlist_apply(
+    pub fn status_edit_push_url(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Apply [{}]", get_hint(key_config.enter),),
-            "apply selected stash",
-            CMD_GROUP_STASHES,
+            tr!(
+                "commands.status_edit_push_url",
+                "Push URL [{0}]",
+                get_hint(key_config.edit_push_url),
+            ),
+            tr!(
+                "commands.status_edit_push_url.desc",
+                "set or clear the separate push url of the remote"
+            ),
+            cmd_group_general(),
         )
     }
-    pub fn stashlist_drop(
+    pub fn push_url_confirm_msg(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.push_url_confirm_msg",
+                "Save [{0}]",
+                get_hint(key_config.enter)
+            ),
+            tr!(
+                "commands.push_url_confirm_msg.desc",
+                "save push url"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn find_commit_confirm_msg(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Drop [{}]", get_hint(key_config.stash_drop),),
-            "drop selected stash",
-            CMD_GROUP_STASHES,
+            tr!(
+                "commands.find_commit_confirm_msg",
+                "Find [{0}]",
+                get_hint(key_config.enter)
+            ),
+            tr!(
+                "commands.find_commit_confirm_msg.desc",
+                "apply filter"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn stashlist_inspect(
+    pub fn search_commit_confirm_msg(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Inspect [{}]", get_hint(key_config.focus_right),),
-            "open stash commit details (allows to diff files)",
-            CMD_GROUP_STASHES,
+            tr!(
+                "commands.search_commit_confirm_msg",
+                "Search [{0}]",
+                get_hint(key_config.enter)
+            ),
+            tr!(
+                "commands.search_commit_confirm_msg.desc",
+                "jump to the first match"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn log_details_toggle(
+    pub fn tag_message_confirm_msg(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Details [{}]", get_hint(key_config.enter),),
-            "open details of selected commit",
-            CMD_GROUP_LOG,
+            tr!(
+                "commands.tag_message_confirm_msg",
+                "Tag [{0}]",
+                get_hint(key_config.enter)
+            ),
+            tr!(
+                "commands.tag_message_confirm_msg.desc",
+                "create the annotated tag"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn log_details_open(
+    pub fn tag_open_editor(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.tag_open_editor",
+                "Open editor [{0}]",
+                get_hint(key_config.open_commit_editor),
+            ),
+            tr!(
+                "commands.tag_open_editor.desc",
+                "write the tag message in the editor"
+            ),
+            cmd_group_log(),
+        )
+    }
+    pub fn tag_template_next(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Inspect [{}]", get_hint(key_config.focus_right),),
-            "inspect selected commit in detail",
-            CMD_GROUP_LOG,
+            tr!(
+                "commands.tag_template_next",
+                "Next template [{0}]",
+                get_hint(key_config.tag_template_next),
+            ),
+            tr!(
+                "commands.tag_template_next.desc",
+                "replace the message with the next tag template"
+            ),
+            cmd_group_log(),
         )
     }
-    pub fn log_tag_commit(
+    pub fn tag_push_toggle(
         key_config: &SharedKeyConfig,
+        push: bool,
     ) -> CommandText {
         CommandText::new(
-            format!("Tag [{}]", get_hint(key_config.log_tag_commit),),
-            "tag commit",
-            CMD_GROUP_LOG,
+            if push {
+                tr!(
+                    "commands.tag_push_toggle.off",
+                    "Don't push [{0}]",
+                    get_hint(key_config.tag_push_toggle),
+                )
+            } else {
+                tr!(
+                    "commands.tag_push_toggle.on",
+                    "Push to origin [{0}]",
+                    get_hint(key_config.tag_push_toggle),
+                )
+            },
+            tr!(
+                "commands.tag_push_toggle.desc",
+                "toggle pushing the tag to origin after creating it"
+            ),
+            cmd_group_log(),
         )
     }
     pub fn tag_commit_confirm_msg(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Tag [{}]", get_hint(key_config.enter),),
-            "tag commit",
-            CMD_GROUP_LOG,
+            tr!(
+                "commands.tag_commit_confirm_msg",
+                "Tag [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.tag_commit_confirm_msg.desc",
+                "continue with the tag message"
+            ),
+            cmd_group_log(),
         )
     }
     pub fn create_branch_confirm_msg(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Create Branch [{}]", get_hint(key_config.enter),),
-            "create branch",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.create_branch_confirm_msg",
+                "Create Branch [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.create_branch_confirm_msg.desc",
+                "create branch"
+            ),
+            cmd_group_general(),
         )
     }
     pub fn open_branch_create_popup(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "Create [{}]",
+            tr!(
+                "commands.open_branch_create_popup",
+                "Create [{0}]",
                 get_hint(key_config.create_branch),
             ),
-            "open create branch popup",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.open_branch_create_popup.desc",
+                "open create branch popup"
+            ),
+            cmd_group_general(),
         )
     }
     pub fn rename_branch_confirm_msg(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!("Rename Branch [{}]", get_hint(key_config.enter),),
-            "rename branch",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.rename_branch_confirm_msg",
+                "Rename Branch [{0}]",
+                get_hint(key_config.enter),
+            ),
+            tr!(
+                "commands.rename_branch_confirm_msg.desc",
+                "rename branch"
+            ),
+            cmd_group_general(),
         )
     }
     pub fn rename_branch_popup(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "Rename Branch [{}]",
+            tr!(
+                "commands.rename_branch_popup",
+                "Rename Branch [{0}]",
                 get_hint(key_config.rename_branch),
             ),
-            "rename branch",
-            CMD_GROUP_GENERAL,
+            tr!("commands.rename_branch_popup.desc", "rename branch"),
+            cmd_group_general(),
         )
     }
     pub fn delete_branch_popup(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "Delete [{}]",
+            tr!(
+                "commands.delete_branch_popup",
+                "Delete [{0}]",
                 get_hint(key_config.delete_branch),
             ),
-            "delete a branch",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.delete_branch_popup.desc",
+                "delete a branch"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn refresh_remote_branches(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.refresh_remote_branches",
+                "Refresh remote [{0}]",
+                get_hint(key_config.refresh_remote_branches),
+            ),
+            tr!(
+                "commands.refresh_remote_branches.desc",
+                "list the branches that exist on the remote server"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn branch_show_in_log(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.branch_show_in_log",
+                "Show in log [{0}]",
+                get_hint(key_config.branch_show_in_log),
+            ),
+            tr!(
+                "commands.branch_show_in_log.desc",
+                "select the top commit of the branch in the log"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn open_reflog(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.open_reflog",
+                "Reflog [{0}]",
+                get_hint(key_config.open_reflog)
+            ),
+            tr!(
+                "commands.open_reflog.desc",
+                "show where HEAD pointed to before"
+            ),
+            cmd_group_log(),
+        )
+    }
+    pub fn open_worktrees(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.open_worktrees",
+                "Worktrees [{0}]",
+                get_hint(key_config.open_worktrees)
+            ),
+            tr!(
+                "commands.open_worktrees.desc",
+                "list all worktrees of the repository"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn open_undo(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!("commands.open_undo","Undo [{0}]", get_hint(key_config.open_undo)),
+            tr!("commands.open_undo.desc", "list recent updates of HEAD and branches to undo them"),
+            cmd_group_general(),
+        )
+    }
+    pub fn undo_restore(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.undo_restore",
+                "Undo [{0}]",
+                get_hint(key_config.enter)
+            ),
+            tr!(
+                "commands.undo_restore.desc",
+                "move the ref back to where it was before"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn reflog_reset(key_config: &SharedKeyConfig) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.reflog_reset",
+                "Reset to [{0}]",
+                get_hint(key_config.enter)
+            ),
+            tr!(
+                "commands.reflog_reset.desc",
+                "reset HEAD (hard) to the selected entry"
+            ),
+            cmd_group_general(),
         )
     }
     pub fn open_branch_select_popup(
         key_config: &SharedKeyConfig,
     ) -> CommandText {
         CommandText::new(
-            format!(
-                "Branches [{}]",
+            tr!(
+                "commands.open_branch_select_popup",
+                "Branches [{0}]",
                 get_hint(key_config.select_branch),
             ),
-            "open select branch popup",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.open_branch_select_popup.desc",
+                "open select branch popup"
+            ),
+            cmd_group_general(),
         )
     }
 
     pub fn status_push(key_config: &SharedKeyConfig) -> CommandText {
         CommandText::new(
-            format!("Push [{}]", get_hint(key_config.push),),
-            "push to origin",
-            CMD_GROUP_GENERAL,
+            tr!(
+                "commands.status_push",
+                "Push [{0}]",
+                get_hint(key_config.push),
+            ),
+            tr!("commands.status_push.desc", "push to origin"),
+            cmd_group_general(),
+        )
+    }
+    pub fn status_push_dry_run(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.status_push_dry_run",
+                "Push preview [{0}]",
+                get_hint(key_config.push_dry_run),
+            ),
+            tr!(
+                "commands.status_push_dry_run.desc",
+                "show what a push would update before pushing"
+            ),
+            cmd_group_general(),
+        )
+    }
+    pub fn status_fetch_upstream(
+        key_config: &SharedKeyConfig,
+    ) -> CommandText {
+        CommandText::new(
+            tr!(
+                "commands.status_fetch_upstream",
+                "Fetch upstream [{0}]",
+                get_hint(key_config.fetch_upstream),
+            ),
+            tr!(
+                "commands.status_fetch_upstream.desc",
+                "fetch the upstream tracked by the current branch"
+            ),
+            cmd_group_general(),
         )
     }
 }
+
+/// `45210` -> `45,210`
+fn with_separators(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() * 4 / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out
+}
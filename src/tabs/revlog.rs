@@ -1,23 +1,39 @@
 use crate::{
+    clipboard::copy_string,
     components::{
-        visibility_blocking, CommandBlocking, CommandInfo,
-        CommitDetailsComponent, CommitList, Component,
-        DrawableComponent,
+        commit_to_json, commits_to_rebase_todo,
+        get_what_to_filter_by, next_log_window, parse_log_window,
+        split_line_range, split_scan_limit, visibility_blocking,
+        CommandBlocking, CommandInfo, CommitDetailsComponent,
+        CommitList, Component, DrawableComponent, FilterStatus,
+        LogSelection, ScrollType, Severity, WalkModes,
     },
     keys::SharedKeyConfig,
-    queue::{InternalEvent, Queue},
-    strings,
+    options::{LogRefs, SharedOptions},
+    queue::{Action, InternalEvent, Queue},
+    strings, try_or_popup,
     ui::style::SharedTheme,
 };
 use anyhow::Result;
 use asyncgit::{
     cached,
-    sync::{self, CommitId},
-    AsyncLog, AsyncNotification, AsyncTags, FetchStatus, CWD,
+    sync::{
+        self, CommitId, CommitInfo, FilterBy, FilterQuery, LineRange,
+        LogWalkerFilter, LogWindow,
+    },
+    AsyncDecorations, AsyncLog, AsyncNotification, AsyncReverts,
+    AsyncTags, FetchStatus, LogWorker, WalkGenerations,
 };
+use bitflags::bitflags;
+use chrono::Utc;
 use crossbeam_channel::Sender;
-use crossterm::event::Event;
-use std::time::Duration;
+use crossterm::event::{Event, KeyEvent};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
 use sync::CommitTags;
 use tui::{
     backend::Backend,
@@ -26,72 +42,300 @@ use tui::{
 };
 
 const SLICE_SIZE: usize = 1200;
+/// commits checked per step of a search before yielding to the ui
+const SEARCH_STEP: usize = 1000;
+/// authors listed by the author summary, the rest is counted
+const AUTHOR_SUMMARY_MAX: usize = 15;
+/// ticks a fetch of commits is retried while the repo is locked
+const MAX_FETCH_RETRIES: usize = 3;
+
+bitflags! {
+    /// what the shown commits are narrowed down to and how they
+    /// are ordered
+    struct FilterModes: u8 {
+        /// a filter is applied
+        const FILTERED = 0b0001;
+        /// the matches get ordered by relevance once walked..
+        const BY_RELEVANCE = 0b0010;
+        /// ..which happened since the last walk
+        const RELEVANCE_SORTED = 0b0100;
+        /// only the commits not pushed yet
+        const UNPUSHED = 0b1000;
+    }
+}
+
+/// progress of a search looking for the next match
+#[derive(Clone, Copy)]
+struct SearchScan {
+    /// index in the log checked next
+    next: usize,
+    /// towards older commits
+    forward: bool,
+}
+
+/// where the log was left when it got hidden
+#[derive(Clone, Copy)]
+struct ViewState {
+    selected: CommitId,
+    /// rows between the top of the list and the selection
+    row: usize,
+}
 
 ///
 pub struct Revlog {
+    repo_path: Arc<str>,
     commit_details: CommitDetailsComponent,
     list: CommitList,
     git_log: AsyncLog,
+    /// only with `options.filter.warm_worker`
+    log_worker: Option<LogWorker>,
+    /// a new `git_log` stops the walk of the one it replaced
+    log_generations: WalkGenerations,
     git_tags: AsyncTags,
+    git_reverts: AsyncReverts,
+    /// log entries handed to `git_reverts` so far
+    reverts_scanned: usize,
+    /// only with `options.log.refs` set to `All`
+    git_decorations: Option<AsyncDecorations>,
     queue: Queue,
     visible: bool,
     branch_name: cached::BranchName,
+    filter_modes: FilterModes,
+    filter_query: Option<FilterQuery>,
+    filter_line_range: Option<LineRange>,
+    /// the applied filter as typed
+    filter_string: String,
+    filter_scan_limit: Option<usize>,
+    walk: WalkModes,
+    /// between two notifications of a running walk
+    notify_interval: Duration,
+    /// only commits within this window are walked,
+    /// see `parse_log_window`
+    window: Option<String>,
+    bare: bool,
+    select_head: bool,
+    filter_max_field_len: usize,
+    /// fields searched by terms without a `:` prefix
+    filter_everywhere: FilterBy,
+    select_on_show: Option<CommitId>,
+    restore_view: Option<ViewState>,
+    search: Option<LogWalkerFilter>,
+    search_scan: Option<SearchScan>,
+    /// failed fetches of a locked repo so far, retried on the
+    /// next update
+    fetch_retry: Option<usize>,
+    /// the last fetch error reported, not shown again until a
+    /// fetch succeeds
+    fetch_error: Option<String>,
+    sender: Sender<AsyncNotification>,
     key_config: SharedKeyConfig,
 }
 
 impl Revlog {
     ///
     pub fn new(
+        repo_path: Arc<str>,
+        bare: bool,
         queue: &Queue,
         sender: &Sender<AsyncNotification>,
+        options: &SharedOptions,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
     ) -> Self {
-        Self {
+        let filter_everywhere =
+            options.filter_everywhere().unwrap_or_else(|e| {
+                report_warning(
+                    queue,
+                    strings::invalid_filter_fields_msg(
+                        &key_config,
+                        &e.to_string(),
+                    ),
+                );
+                FilterBy::EVERYWHERE
+            });
+
+        let window = options.log.window.clone().filter(|window| {
+            resolve_window(&repo_path, window)
+                .map_err(|e| {
+                    report_warning(
+                        queue,
+                        strings::invalid_log_window_msg(
+                            &key_config,
+                            &e.to_string(),
+                        ),
+                    );
+                })
+                .is_ok()
+        });
+
+        let log_worker =
+            options.filter.warm_worker.then(LogWorker::new);
+        let log_generations = WalkGenerations::new();
+        let notify_interval =
+            Duration::from_millis(options.log.notify_interval_ms);
+        let git_log = window_log(
+            &repo_path,
+            sender,
+            None,
+            &log_generations,
+            log_worker.as_ref(),
+            window.as_deref(),
+            notify_interval,
+        );
+
+        let mut revlog = Self {
             queue: queue.clone(),
             commit_details: CommitDetailsComponent::new(
                 queue,
                 sender,
                 theme.clone(),
                 key_config.clone(),
+            )
+            .with_message_limits(
+                options.commit_subject_limit,
+                options.commit_body_limit,
             ),
             list: CommitList::new(
                 &strings::log_title(&key_config),
                 theme,
                 key_config.clone(),
-            ),
-            git_log: AsyncLog::new(sender),
+            )
+            .with_full_hash(options.log.full_hash)
+            .with_ellipsis(options.log.ellipsis.clone())
+            .with_count_prefix(options.log.count_prefix),
+            git_log,
+            log_worker,
+            log_generations,
             git_tags: AsyncTags::new(sender),
+            git_reverts: AsyncReverts::new(
+                Arc::clone(&repo_path),
+                sender,
+            ),
+            reverts_scanned: 0,
+            git_decorations: (options.log.refs == LogRefs::All).then(
+                || {
+                    AsyncDecorations::new(
+                        Arc::clone(&repo_path),
+                        sender,
+                    )
+                },
+            ),
             visible: false,
-            branch_name: cached::BranchName::new(CWD),
+            branch_name: cached::BranchName::new(&repo_path),
+            filter_modes: FilterModes::empty(),
+            filter_query: None,
+            filter_line_range: None,
+            filter_string: String::new(),
+            filter_scan_limit: None,
+            walk: WalkModes::empty(),
+            notify_interval,
+            window,
+            bare,
+            select_head: options.log.select_head,
+            filter_max_field_len: options.filter.max_field_len,
+            filter_everywhere,
+            select_on_show: None,
+            restore_view: None,
+            search: None,
+            search_scan: None,
+            fetch_retry: None,
+            fetch_error: None,
+            sender: sender.clone(),
             key_config,
-        }
+            repo_path,
+        };
+
+        revlog.list.set_window(revlog.window.clone());
+
+        revlog
+    }
+
+    /// stops the running log walk, filter and search, called
+    /// when the app quits
+    pub fn stop_filter(&mut self) {
+        self.git_log.stop();
+        self.stop_search();
     }
 
     ///
     pub fn any_work_pending(&self) -> bool {
         self.git_log.is_pending()
+            || self.search_scan.is_some()
             || self.git_tags.is_pending()
+            || self.git_reverts.is_pending()
+            || self
+                .git_decorations
+                .as_ref()
+                .is_some_and(AsyncDecorations::is_pending)
             || self.commit_details.any_work_pending()
+            || self.fetch_retry.is_some()
+    }
+
+    /// progress of the filter walk, `None` if not filtered
+    pub fn filter_status(&self) -> Result<Option<FilterStatus>> {
+        if !self.filter_modes.contains(FilterModes::FILTERED) {
+            return Ok(None);
+        }
+
+        let matches = self.git_log.count()?;
+        Ok(Some(match self.git_log.error()? {
+            Some(error) => FilterStatus::Failed(error),
+            None if self.git_log.is_pending() => {
+                FilterStatus::Running {
+                    matches,
+                    scanned: self.git_log.scanned(),
+                }
+            }
+            None => FilterStatus::Done { matches },
+        }))
     }
 
     ///
     pub fn update(&mut self) -> Result<()> {
         if self.visible {
-            let log_changed =
+            let mut log_changed =
                 self.git_log.fetch()? == FetchStatus::Started;
 
-            self.list.set_count_total(self.git_log.count()?);
+            if log_changed {
+                self.filter_modes
+                    .remove(FilterModes::RELEVANCE_SORTED);
+                self.list.set_shallow_boundary(
+                    sync::get_shallow_boundary(&self.repo_path)?,
+                );
+            }
+            log_changed |= self.sort_by_relevance()?;
+
+            let count = self.git_log.count()?;
+            log_changed |= self.keep_reversed_selection(count)?;
+            self.list.set_count_total(count);
+            self.scan_reverts(log_changed, count)?;
+            if self.filter_modes.contains(FilterModes::FILTERED)
+                && !self.filter_modes.contains(FilterModes::UNPUSHED)
+            {
+                self.list.set_title(&filtered_title(
+                    &self.key_config,
+                    self.filter_modes
+                        .contains(FilterModes::BY_RELEVANCE),
+                    !self.git_log.is_pending(),
+                    count,
+                ));
+            }
+            self.select_pending_commit()?;
+            self.continue_search()?;
 
             let selection = self.list.selection();
             let selection_max = self.list.selection_max();
             if self.list.items().needs_data(selection, selection_max)
                 || log_changed
+                || self.fetch_retry.is_some()
             {
-                self.fetch_commits()?;
+                self.fetch_commits();
             }
 
             self.git_tags.request(Duration::from_secs(3), false)?;
+            if let Some(decorations) = &mut self.git_decorations {
+                decorations.request(Duration::from_secs(3), false)?;
+            }
 
             self.list.set_branch(
                 self.branch_name.lookup().map(Some).unwrap_or(None),
@@ -102,12 +346,42 @@ impl Revlog {
                 let tags = self.selected_commit_tags(&commit);
 
                 self.commit_details.set_commit(commit, tags)?;
+                if let Some(id) = commit {
+                    let reverts = self.list.reverts();
+                    self.commit_details.set_reverts(
+                        reverts.reverts(&id),
+                        reverts.reverted_by(&id),
+                    );
+                }
             }
         }
 
         Ok(())
     }
 
+    /// hands the log entries loaded since the last call to
+    /// `git_reverts`, all of them again if the log got replaced
+    fn scan_reverts(
+        &mut self,
+        log_changed: bool,
+        count: usize,
+    ) -> Result<()> {
+        if log_changed || count < self.reverts_scanned {
+            self.reverts_scanned = 0;
+        }
+
+        if count > self.reverts_scanned {
+            let ids = self.git_log.get_slice(
+                self.reverts_scanned,
+                count - self.reverts_scanned,
+            )?;
+            self.git_reverts.scan(&ids);
+            self.reverts_scanned = count;
+        }
+
+        Ok(())
+    }
+
     ///
     pub fn update_git(
         &mut self,
@@ -116,13 +390,30 @@ impl Revlog {
         if self.visible {
             match ev {
                 AsyncNotification::CommitFiles
-                | AsyncNotification::Log => self.update()?,
+                | AsyncNotification::Log
+                | AsyncNotification::Fetch => self.update()?,
                 AsyncNotification::Tags => {
                     if let Some(tags) = self.git_tags.last()? {
                         self.list.set_tags(tags);
                         self.update()?;
                     }
                 }
+                AsyncNotification::Reverts => {
+                    self.list.set_reverts(self.git_reverts.last()?);
+                    self.update()?;
+                }
+                AsyncNotification::Decorations => {
+                    if let Some(decorations) = self
+                        .git_decorations
+                        .as_mut()
+                        .map(AsyncDecorations::last)
+                        .transpose()?
+                        .flatten()
+                    {
+                        self.list.set_decorations(decorations);
+                        self.update()?;
+                    }
+                }
                 _ => (),
             }
         }
@@ -130,172 +421,2506 @@ impl Revlog {
         Ok(())
     }
 
-    fn fetch_commits(&mut self) -> Result<()> {
-        let want_min =
-            self.list.selection().saturating_sub(SLICE_SIZE / 2);
+    /// select (and scroll to) `id` the next time the tab is shown,
+    /// stays at the top if it is not part of the log
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn select_commit_on_show(&mut self, id: CommitId) {
+        self.select_on_show = Some(id);
+    }
 
-        let commits = sync::get_commits_info(
-            CWD,
-            &self.git_log.get_slice(want_min, SLICE_SIZE)?,
-            self.list.current_size().0.into(),
-        );
+    fn select_pending_commit(&mut self) -> Result<()> {
+        if let Some(id) = self.select_on_show {
+            let view = self.restore_view.filter(|v| v.selected == id);
 
-        if let Ok(commits) = commits {
-            self.list.items().set_items(want_min, commits);
+            if let Some(position) = self.git_log.position(id)? {
+                let position = self.flip_index(position)?;
+                self.list.select_entry(position);
+                if let Some(view) = view {
+                    self.list.set_scroll_top(
+                        position.saturating_sub(view.row),
+                    );
+                }
+                self.select_on_show = None;
+                self.restore_view = None;
+            } else if !self.git_log.is_pending() {
+                self.list.select_entry(0);
+                self.list.set_scroll_top(0);
+                self.select_on_show = None;
+                self.restore_view = None;
+            }
         }
 
         Ok(())
     }
 
-    fn selected_commit(&self) -> Option<CommitId> {
-        self.list.selected_entry().map(|e| e.id)
-    }
+    /// selects the first parent of the selected commit
+    fn goto_parent(&mut self) -> Result<()> {
+        if let Some(id) = self.selected_commit() {
+            if self.list.is_shallow_boundary(id) {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowInfoMsg(
+                        strings::log_parent_not_fetched(
+                            &self.key_config,
+                        ),
+                    ),
+                );
+                return Ok(());
+            }
+        }
 
-    fn selected_commit_tags(
-        &self,
-        commit: &Option<CommitId>,
-    ) -> Option<CommitTags> {
-        let tags = self.list.tags();
+        let parent = self.selected_commit().and_then(|id| {
+            sync::get_commit_details(&self.repo_path, id)
+                .ok()
+                .and_then(|details| details.parents.first().copied())
+        });
 
-        commit.and_then(|commit| {
-            tags.and_then(|tags| tags.get(&commit).cloned())
+        if let Some(parent) = parent {
+            if let Some(position) = self.git_log.position(parent)? {
+                self.list.select_entry(self.flip_index(position)?);
+                self.update()?;
+            } else if self.git_log.is_pending() {
+                self.select_commit_on_show(parent);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// the commit the selected one reverts, else the first one
+    /// reverting it
+    fn selected_revert_relation(&self) -> Option<CommitId> {
+        self.selected_commit().and_then(|id| {
+            let reverts = self.list.reverts();
+            reverts
+                .reverts(&id)
+                .or_else(|| reverts.reverted_by(&id).first().copied())
         })
     }
-}
 
-impl DrawableComponent for Revlog {
-    fn draw<B: Backend>(
-        &self,
-        f: &mut Frame<B>,
-        area: Rect,
-    ) -> Result<()> {
-        let chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints(
-                [
-                    Constraint::Percentage(60),
-                    Constraint::Percentage(40),
-                ]
-                .as_ref(),
+    /// jumps from a revert to the commit it reverts and from a
+    /// reverted commit to its revert
+    fn goto_revert(&mut self) -> Result<()> {
+        if let Some(target) = self.selected_revert_relation() {
+            if let Some(position) = self.git_log.position(target)? {
+                self.list.select_entry(self.flip_index(position)?);
+                self.update()?;
+            } else if self.git_log.is_pending() {
+                self.select_commit_on_show(target);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fetch_commits(&mut self) {
+        let want_min =
+            self.list.selection().saturating_sub(SLICE_SIZE / 2);
+
+        let commits = self.fetch_ids(want_min).and_then(|ids| {
+            // a corrupt commit only replaces its own row
+            sync::get_commits_info_partial(
+                &self.repo_path,
+                &ids,
+                self.list.current_size().0.into(),
             )
-            .split(area);
+        });
 
-        if self.commit_details.is_visible() {
-            self.list.draw(f, chunks[0])?;
-            self.commit_details.draw(f, chunks[1])?;
+        self.commits_fetched(want_min, commits);
+    }
+
+    /// ids of the rows from `want_min` on
+    fn fetch_ids(
+        &self,
+        want_min: usize,
+    ) -> asyncgit::Result<Vec<CommitId>> {
+        if self.walk.contains(WalkModes::REVERSED) {
+            // rows from `want_min` on, counted from the end
+            let end = self.git_log.count()?.saturating_sub(want_min);
+            let start = end.saturating_sub(SLICE_SIZE);
+            let mut ids =
+                self.git_log.get_slice(start, end - start)?;
+            ids.reverse();
+            Ok(ids)
         } else {
-            self.list.draw(f, area)?;
+            self.git_log.get_slice(want_min, SLICE_SIZE)
         }
-
-        Ok(())
     }
-}
 
-impl Component for Revlog {
-    fn event(&mut self, ev: Event) -> Result<bool> {
-        if self.visible {
-            let event_used = self.list.event(ev)?;
+    /// shows the fetched rows from `want_min` on. while the repo is
+    /// locked the fetch is retried a few times, other errors are
+    /// reported (once) and the rows stay as they are
+    fn commits_fetched(
+        &mut self,
+        want_min: usize,
+        commits: asyncgit::Result<
+            Vec<std::result::Result<CommitInfo, CommitId>>,
+        >,
+    ) {
+        match commits {
+            Ok(commits) => {
+                self.list.items().set_items(want_min, commits);
+                self.fetch_retry = None;
+                self.fetch_error = None;
+            }
+            Err(e)
+                if e.is_locked()
+                    && self.fetch_retry.unwrap_or_default()
+                        < MAX_FETCH_RETRIES =>
+            {
+                log::warn!("fetch commits, retrying: {}", e);
+                self.fetch_retry =
+                    Some(self.fetch_retry.unwrap_or_default() + 1);
+            }
+            Err(e) => {
+                self.fetch_retry = None;
 
-            if event_used {
-                self.update()?;
-                return Ok(true);
-            } else if let Event::Key(k) = ev {
-                if k == self.key_config.enter {
-                    self.commit_details.toggle_visible()?;
-                    self.update()?;
-                    return Ok(true);
-                } else if k == self.key_config.log_tag_commit {
-                    return self.selected_commit().map_or(
-                        Ok(false),
-                        |id| {
-                            self.queue.borrow_mut().push_back(
-                                InternalEvent::TagCommit(id),
-                            );
-                            Ok(true)
-                        },
-                    );
-                } else if k == self.key_config.focus_right
-                    && self.commit_details.is_visible()
-                {
-                    return self.selected_commit().map_or(
-                        Ok(false),
-                        |id| {
-                            self.queue.borrow_mut().push_back(
-                                InternalEvent::InspectCommit(
-                                    id,
-                                    self.selected_commit_tags(&Some(
-                                        id,
-                                    )),
-                                ),
-                            );
-                            Ok(true)
-                        },
+                let error = e.to_string();
+                if self.fetch_error.as_ref() != Some(&error) {
+                    log::error!("fetch commits: {}", error);
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::ShowErrorMsg(
+                            strings::log_fetch_error(
+                                &self.key_config,
+                                &error,
+                            ),
+                        ),
                     );
-                } else if k == self.key_config.select_branch {
-                    self.queue
-                        .borrow_mut()
-                        .push_back(InternalEvent::SelectBranch);
-                    return Ok(true);
+                    self.fetch_error = Some(error);
                 }
             }
         }
+    }
 
-        Ok(false)
+    /// shows only commits matching `filter_by`
+    /// (see `get_what_to_filter_by`), empty clears the filter.
+    /// a leading `:top N` only looks at the `N` most recent commits,
+    /// `:L start,end:path` after it only at those changing the lines
+    pub fn filter(&mut self, filter_by: &str) -> Result<()> {
+        let (scan_limit, query) = split_scan_limit(filter_by);
+        let (line_range, query) = split_line_range(query);
+        self.set_filter_query(
+            get_what_to_filter_by(query, self.filter_everywhere),
+            line_range,
+            scan_limit,
+        )?;
+        self.filter_string = if self.filter_query.is_some()
+            || self.filter_line_range.is_some()
+        {
+            filter_by.to_string()
+        } else {
+            String::new()
+        };
+
+        Ok(())
     }
 
-    fn commands(
-        &self,
-        out: &mut Vec<CommandInfo>,
-        force_all: bool,
-    ) -> CommandBlocking {
-        if self.visible || force_all {
-            self.list.commands(out, force_all);
+    /// `true` if the log is narrowed down by a typed filter
+    const fn is_filtering(&self) -> bool {
+        self.filter_modes.contains(FilterModes::FILTERED)
+            && !self.filter_string.is_empty()
+    }
+
+    /// the raw expression `copy_filter` puts in the clipboard
+    fn filter_to_copy(&self) -> Option<&str> {
+        self.is_filtering().then_some(self.filter_string.as_str())
+    }
+
+    fn copy_filter(&self) -> Result<()> {
+        if let Some(filter) = self.filter_to_copy() {
+            copy_string(filter)?;
         }
 
-        out.push(CommandInfo::new(
-            strings::commands::log_details_toggle(&self.key_config),
-            true,
-            self.visible,
-        ));
+        Ok(())
+    }
 
-        out.push(CommandInfo::new(
-            strings::commands::log_details_open(&self.key_config),
-            true,
-            (self.visible && self.commit_details.is_visible())
-                || force_all,
-        ));
+    fn set_filter_query(
+        &mut self,
+        query: Option<FilterQuery>,
+        line_range: Option<LineRange>,
+        scan_limit: Option<usize>,
+    ) -> Result<()> {
+        let tags = self.list.tags().cloned().unwrap_or_default();
+        let filter = query.clone().map(|query| {
+            sync::commit_filter(
+                query,
+                tags,
+                self.filter_max_field_len,
+            )
+        });
 
-        out.push(CommandInfo::new(
-            strings::commands::log_tag_commit(&self.key_config),
-            true,
-            self.visible || force_all,
-        ));
+        self.set_filter_with_range(filter, line_range, scan_limit)?;
+        self.filter_query = query;
 
-        out.push(CommandInfo::new(
-            strings::commands::open_branch_select_popup(
-                &self.key_config,
+        Ok(())
+    }
+
+    /// switches the filtered view between log order and
+    /// ordering by relevance
+    fn toggle_relevance(&mut self) -> Result<()> {
+        if self.filter_modes.contains(FilterModes::BY_RELEVANCE) {
+            self.set_filter_query(
+                self.filter_query.clone(),
+                self.filter_line_range.clone(),
+                self.filter_scan_limit,
+            )
+        } else {
+            self.filter_modes.insert(FilterModes::BY_RELEVANCE);
+            self.update()
+        }
+    }
+
+    /// sorts the filtered commits once the walk is done,
+    /// `true` if it did
+    fn sort_by_relevance(&mut self) -> Result<bool> {
+        if !self.filter_modes.contains(FilterModes::BY_RELEVANCE)
+            || self
+                .filter_modes
+                .contains(FilterModes::RELEVANCE_SORTED)
+            || self.git_log.is_pending()
+        {
+            return Ok(false);
+        }
+
+        if let Some(query) = &self.filter_query {
+            let count = self.git_log.count()?;
+            let ids = self.git_log.get_slice(0, count)?;
+            let tags = self.list.tags().cloned().unwrap_or_default();
+
+            self.git_log.set_commits(sync::sort_by_relevance(
+                &self.repo_path,
+                &ids,
+                query,
+                &tags,
+                self.filter_max_field_len,
+            )?)?;
+        }
+        self.filter_modes.insert(FilterModes::RELEVANCE_SORTED);
+        self.list.clear_range();
+
+        Ok(true)
+    }
+
+    /// like `filter` but blocks up to `timeout` for the walk to
+    /// finish, returns the amount of matches unless it timed out
+    #[allow(dead_code)]
+    pub fn filter_and_count(
+        &mut self,
+        filter_by: &str,
+        timeout: Duration,
+    ) -> Result<Option<usize>> {
+        self.filter(filter_by)?;
+        self.git_log.fetch()?;
+
+        Ok(self.git_log.wait(timeout)?)
+    }
+
+    /// selects the first commit from the selection on matching
+    /// `query` (see `get_what_to_filter_by`) without filtering,
+    /// `search_next` continues from there
+    pub fn search(&mut self, query: &str) -> Result<()> {
+        let tags = self.list.tags().cloned().unwrap_or_default();
+        self.search =
+            get_what_to_filter_by(query, self.filter_everywhere).map(
+                |query| {
+                    sync::commit_filter(
+                        query,
+                        tags,
+                        self.filter_max_field_len,
+                    )
+                },
+            );
+
+        let selection = self.flip_index(self.list.selection())?;
+        self.start_search(
+            selection,
+            !self.walk.contains(WalkModes::REVERSED),
+        );
+        self.update()
+    }
+
+    /// selects the next match further down (`forward`) or up the list
+    fn search_next(&mut self, forward: bool) -> Result<()> {
+        let selection = self.flip_index(self.list.selection())?;
+
+        if forward != self.walk.contains(WalkModes::REVERSED) {
+            self.start_search(selection + 1, true);
+        } else if let Some(next) = selection.checked_sub(1) {
+            self.start_search(next, false);
+        } else {
+            self.search_no_match();
+        }
+
+        self.update()
+    }
+
+    fn start_search(&mut self, next: usize, forward: bool) {
+        self.search_scan = self
+            .search
+            .as_ref()
+            .map(|_| SearchScan { next, forward });
+        self.list.set_searching(self.search_scan.is_some());
+    }
+
+    const fn stop_search(&mut self) {
+        self.search_scan = None;
+        self.list.set_searching(false);
+    }
+
+    fn search_no_match(&mut self) {
+        self.stop_search();
+        self.queue.borrow_mut().push_back(
+            InternalEvent::ShowErrorMsg(
+                strings::log_search_no_match(&self.key_config),
             ),
-            true,
-            self.visible || force_all,
-        ));
+        );
+    }
 
-        visibility_blocking(self)
+    /// checks the next `SEARCH_STEP` commits of a running search,
+    /// waits for the walk when it reaches the end of what is loaded
+    fn continue_search(&mut self) -> Result<()> {
+        let (mut scan, filter) =
+            match (self.search_scan, &self.search) {
+                (Some(scan), Some(filter)) => {
+                    (scan, Arc::clone(filter))
+                }
+                _ => return Ok(()),
+            };
+
+        // the count is final once the walk is no longer pending
+        let walk_pending = self.git_log.is_pending();
+        let count = self.git_log.count()?;
+
+        let (found, done) = if scan.forward {
+            let ids =
+                self.git_log.get_slice(scan.next, SEARCH_STEP)?;
+            let found = sync::find_first_match(
+                &self.repo_path,
+                &ids,
+                &filter,
+            )?
+            .map(|index| scan.next + index);
+            scan.next += ids.len();
+
+            (found, !walk_pending && scan.next >= count)
+        } else {
+            let start = scan.next.saturating_sub(SEARCH_STEP - 1);
+            let mut ids = self
+                .git_log
+                .get_slice(start, scan.next + 1 - start)?;
+            ids.reverse();
+            let found = sync::find_first_match(
+                &self.repo_path,
+                &ids,
+                &filter,
+            )?
+            .map(|index| scan.next - index);
+            scan.next = start.saturating_sub(1);
+
+            (found, start == 0)
+        };
+
+        if let Some(position) = found {
+            self.stop_search();
+            self.list.select_entry(self.flip_index(position)?);
+        } else if done {
+            self.search_no_match();
+        } else {
+            self.search_scan = Some(scan);
+
+            // otherwise the walk notifies once it loaded more
+            if !scan.forward || scan.next < count {
+                self.sender.send(AsyncNotification::Log)?;
+            }
+        }
+
+        Ok(())
     }
 
-    fn is_visible(&self) -> bool {
-        self.visible
+    fn set_filter(
+        &mut self,
+        filter: Option<LogWalkerFilter>,
+    ) -> Result<()> {
+        self.filter_string.clear();
+        self.set_filter_with_range(filter, None, None)
     }
 
-    fn hide(&mut self) {
-        self.visible = false;
-        self.git_log.set_background();
+    /// like `set_filter`, also leaving out the commits not changing
+    /// `line_range`, the walk stops after `scan_limit` commits
+    fn set_filter_with_range(
+        &mut self,
+        filter: Option<LogWalkerFilter>,
+        line_range: Option<LineRange>,
+        scan_limit: Option<usize>,
+    ) -> Result<()> {
+        let filtered = filter.is_some() || line_range.is_some();
+
+        self.stop_search();
+        self.list.clear_range();
+        self.filter_modes = if filtered {
+            FilterModes::FILTERED
+        } else {
+            FilterModes::empty()
+        };
+        self.filter_query = None;
+        self.filter_line_range = line_range;
+        self.filter_scan_limit = scan_limit.filter(|_| filtered);
+        self.git_log = self.new_log(filter);
+        if !self.filter_modes.contains(FilterModes::FILTERED) {
+            self.list
+                .set_title(&strings::log_title(&self.key_config));
+        }
+        self.list.clear();
+        self.update()
     }
 
-    fn show(&mut self) -> Result<()> {
-        self.visible = true;
+    /// a log walking `filter` within the window (on the worker),
+    /// its walks supersede the ones of the logs before
+    fn new_log(&self, filter: Option<LogWalkerFilter>) -> AsyncLog {
+        let mut log = window_log(
+            &self.repo_path,
+            &self.sender,
+            filter,
+            &self.log_generations,
+            self.log_worker.as_ref(),
+            self.window.as_deref(),
+            self.notify_interval,
+        );
+        log.set_line_range(self.filter_line_range.clone());
+        log.set_scan_limit(self.filter_scan_limit);
+        log.set_topo_order(self.walk.contains(WalkModes::TOPO_ORDER));
+        log.set_all_refs(self.walk.contains(WalkModes::ALL_REFS));
+
+        log
+    }
+
+    /// walks the log again within `window` (all of it if `None`),
+    /// keeping the filter
+    fn set_window(&mut self, window: Option<String>) -> Result<()> {
+        self.stop_search();
+        self.list.clear_range();
+        self.filter_modes.remove(FilterModes::RELEVANCE_SORTED);
+        self.window = window;
+        self.git_log = self.new_log(self.git_log.filter());
+        self.list.set_window(self.window.clone());
         self.list.clear();
-        self.update()?;
+        self.update()
+    }
 
-        Ok(())
+    /// walks the log again in topological order or by date,
+    /// keeping the filter, the selection goes back to the top
+    fn toggle_topo_order(&mut self) -> Result<()> {
+        self.stop_search();
+        self.list.clear_range();
+        self.filter_modes.remove(FilterModes::RELEVANCE_SORTED);
+        self.walk.toggle(WalkModes::TOPO_ORDER);
+        self.git_log = self.new_log(self.git_log.filter());
+        self.list.set_walk_modes(self.walk);
+        self.list.clear();
+        self.list.select_entry(0);
+        self.update()
+    }
+
+    /// walks the log again from all refs or just from `HEAD`,
+    /// keeping the filter and the selected commit if it is still
+    /// part of the log
+    fn toggle_all_refs(&mut self) -> Result<()> {
+        let selected = self.selected_commit();
+
+        self.stop_search();
+        self.list.clear_range();
+        self.filter_modes.remove(FilterModes::RELEVANCE_SORTED);
+        self.walk.toggle(WalkModes::ALL_REFS);
+        self.git_log = self.new_log(self.git_log.filter());
+        self.list.set_walk_modes(self.walk);
+        self.list.clear();
+        self.list.select_entry(0);
+        if let Some(id) = selected {
+            self.select_commit_on_show(id);
+        }
+        self.update()
+    }
+
+    /// goes on to the next of the `LOG_WINDOW_PRESETS`
+    fn cycle_window(&mut self) -> Result<()> {
+        let next = next_log_window(self.window.as_deref());
+        self.set_window(next.map(String::from))
+    }
+
+    /// the two marked commits (older first) if there are two,
+    /// else the selected one compared to its parent
+    fn difftool_commits(
+        &self,
+    ) -> Result<Option<(Option<CommitId>, CommitId)>> {
+        if let [a, b] = *self.list.marked() {
+            let older_first = match (
+                self.git_log.position(a)?,
+                self.git_log.position(b)?,
+            ) {
+                (Some(a), Some(b)) => a > b,
+                _ => true,
+            };
+
+            return Ok(Some(if older_first {
+                (Some(a), b)
+            } else {
+                (Some(b), a)
+            }));
+        }
+
+        Ok(self.selected_commit().map(|id| (None, id)))
+    }
+
+    /// lists the files changed between the two marked commits
+    fn compare_marked(&self) -> Result<()> {
+        if let Some((Some(old), new)) = self.difftool_commits()? {
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::CompareCommits(old, new));
+        }
+
+        Ok(())
+    }
+
+    fn filter_marked_range(&mut self) -> Result<()> {
+        if let [a, b] = *self.list.marked() {
+            let commits: HashSet<CommitId> =
+                sync::get_commits_between(&self.repo_path, a, b)?
+                    .into_iter()
+                    .collect();
+
+            self.list.clear_marked();
+            self.set_filter(Some(Arc::new(Box::new(
+                move |_repo, id| Ok(commits.contains(id)),
+            ))))?;
+        }
+
+        Ok(())
+    }
+
+    /// copies the marked commits as rebase todo list,
+    /// they have to be next to each other in the log
+    fn copy_rebase_todo(&mut self) -> Result<()> {
+        let mut positions = Vec::new();
+        for id in self.list.marked() {
+            if let Some(position) = self.git_log.position(*id)? {
+                positions.push((position, *id));
+            }
+        }
+        positions.sort_unstable();
+
+        let contiguous = positions.len() == self.list.marked().len()
+            && positions.windows(2).all(|w| w[0].0 + 1 == w[1].0);
+
+        if contiguous {
+            let ids = positions
+                .into_iter()
+                .map(|(_, id)| id)
+                .collect::<Vec<_>>();
+            let commits =
+                sync::get_commits_info(&self.repo_path, &ids, 1000)?;
+
+            copy_string(&commits_to_rebase_todo(&commits))?;
+            self.list.clear_marked();
+        } else {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowErrorMsg(
+                    strings::rebase_todo_not_contiguous(
+                        &self.key_config,
+                    ),
+                ),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// restricts the log to `@{u}..HEAD` or clears that again
+    fn toggle_unpushed(&mut self) -> Result<()> {
+        if self.filter_modes.contains(FilterModes::UNPUSHED) {
+            return self.set_filter(None);
+        }
+
+        let commits: HashSet<CommitId> =
+            sync::get_unpushed_commits(&self.repo_path)?
+                .into_iter()
+                .collect();
+        let count = commits.len();
+
+        self.set_filter(Some(Arc::new(Box::new(
+            move |_repo, id| Ok(commits.contains(id)),
+        ))))?;
+        self.filter_modes.insert(FilterModes::UNPUSHED);
+        self.list.set_title(&strings::log_title_unpushed(
+            &self.key_config,
+            count,
+        ));
+
+        Ok(())
+    }
+
+    /// the marked commits, else the ones of the range selection
+    /// (or the one under the cursor), oldest first
+    fn selected_commits(&self) -> Result<Vec<CommitId>> {
+        if !self.list.marked().is_empty() {
+            let mut positions = Vec::new();
+            for id in self.list.marked() {
+                if let Some(position) = self.git_log.position(*id)? {
+                    positions.push((position, *id));
+                }
+            }
+            positions.sort_unstable_by_key(|p| std::cmp::Reverse(*p));
+
+            return Ok(positions
+                .into_iter()
+                .map(|(_, id)| id)
+                .collect());
+        }
+
+        let mut ids = match self.list.log_selection() {
+            Some(LogSelection::Single(id)) => vec![id],
+            Some(LogSelection::Range(start, end)) => {
+                let (start, end) = if self
+                    .walk
+                    .contains(WalkModes::REVERSED)
+                {
+                    (self.flip_index(end)?, self.flip_index(start)?)
+                } else {
+                    (start, end)
+                };
+                self.git_log.get_slice(start, end - start + 1)?
+            }
+            None => Vec::new(),
+        };
+        ids.reverse();
+
+        Ok(ids)
+    }
+
+    /// cherry-picks the marked commits, the range or the one
+    /// under the cursor, oldest first
+    fn cherry_pick(&mut self) -> Result<()> {
+        let commits = self.selected_commits()?;
+
+        if !commits.is_empty() {
+            self.list.clear_marked();
+            self.list.clear_range();
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::CherryPick(commits));
+        }
+
+        Ok(())
+    }
+
+    /// copies the (short) hashes of the selected commits,
+    /// one per line
+    fn copy_hashes(&mut self) -> Result<()> {
+        let hashes = self
+            .selected_commits()?
+            .iter()
+            .map(CommitId::to_string)
+            .collect::<Vec<_>>();
+
+        copy_string(&hashes.join("\n"))?;
+        self.list.clear_range();
+
+        Ok(())
+    }
+
+    /// opens the author stats of the commits loaded so far (the
+    /// filter results while filtering)
+    fn open_author_stats(&self) -> Result<()> {
+        let count = self.git_log.count()?;
+        let ids = self.git_log.get_slice(0, count)?;
+
+        self.queue.borrow_mut().push_back(
+            InternalEvent::OpenAuthorStats(ids, self.is_filtering()),
+        );
+
+        Ok(())
+    }
+
+    /// shows how many of the filtered commits (found so far) each
+    /// author wrote
+    fn show_author_summary(&mut self) -> Result<()> {
+        let count = self.git_log.count()?;
+        let ids = self.git_log.get_slice(0, count)?;
+        let commits =
+            sync::get_commits_info(&self.repo_path, &ids, 0)?;
+
+        self.queue.borrow_mut().push_back(
+            InternalEvent::ShowInfoMsg(
+                strings::log_author_summary_msg(
+                    &self.key_config,
+                    &author_counts(&commits),
+                    AUTHOR_SUMMARY_MAX,
+                    self.git_log.is_pending(),
+                ),
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// asks for a new name of the checked out local branch
+    fn rename_head_branch(&mut self) {
+        let event = match self.branch_name.lookup() {
+            Ok(name) => InternalEvent::RenameBranch(
+                format!("refs/heads/{}", name),
+                name,
+                false,
+            ),
+            Err(_) => InternalEvent::ShowErrorMsg(
+                strings::log_rename_branch_detached(&self.key_config),
+            ),
+        };
+
+        self.queue.borrow_mut().push_back(event);
+    }
+
+    /// list row of log index `idx` and the other way round,
+    /// they only differ when the log is shown oldest first
+    fn flip_index(&self, idx: usize) -> Result<usize> {
+        Ok(if self.walk.contains(WalkModes::REVERSED) {
+            self.git_log.count()?.saturating_sub(idx + 1)
+        } else {
+            idx
+        })
+    }
+
+    /// shows the log oldest first or newest first again,
+    /// staying on the selected commit
+    fn toggle_reverse(&mut self) -> Result<()> {
+        let selected = self.selected_commit();
+
+        self.walk.toggle(WalkModes::REVERSED);
+        self.list.set_walk_modes(self.walk);
+        self.list.clear_range();
+
+        let position = match selected {
+            Some(id) => self.git_log.position(id)?,
+            None => None,
+        };
+        self.list
+            .select_entry(self.flip_index(position.unwrap_or(0))?);
+        self.fetch_commits();
+
+        Ok(())
+    }
+
+    /// oldest first the rows of a log still being walked move down
+    /// as it grows, keeps the selection on its commit.
+    /// `true` if the rows moved
+    fn keep_reversed_selection(
+        &mut self,
+        count: usize,
+    ) -> Result<bool> {
+        if !self.walk.contains(WalkModes::REVERSED)
+            || count == self.list.count_total()
+        {
+            return Ok(false);
+        }
+
+        if let Some(id) = self.selected_commit() {
+            if let Some(position) = self.git_log.position(id)? {
+                self.list.set_count_total(count);
+                self.list.select_entry(self.flip_index(position)?);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// asks where to write the commits loaded so far (the filter
+    /// results while filtering), in log order
+    fn open_export_list(&self) -> Result<()> {
+        let count = self.git_log.count()?;
+        let ids = self.git_log.get_slice(0, count)?;
+
+        self.queue.borrow_mut().push_back(InternalEvent::ExportLog(
+            ids,
+            self.is_filtering(),
+        ));
+
+        Ok(())
+    }
+
+    /// copies the subjects of the selected commits, one per line
+    fn copy_subjects(&mut self) -> Result<()> {
+        let ids = self.selected_commits()?;
+        let subjects =
+            sync::get_commits_info(&self.repo_path, &ids, 1000)?
+                .into_iter()
+                .map(|c| {
+                    c.message
+                        .lines()
+                        .next()
+                        .unwrap_or_default()
+                        .to_string()
+                })
+                .collect::<Vec<_>>();
+
+        copy_string(&subjects.join("\n"))?;
+        self.list.clear_range();
+
+        Ok(())
+    }
+
+    /// writes the selected commits as numbered patch files into
+    /// the root of the workdir, like `git format-patch` does
+    fn export_patches(&mut self) -> Result<()> {
+        let ids = self.selected_commits()?;
+        let dir = sync::utils::repo_work_dir(&self.repo_path)?;
+        let files = sync::export_patches(
+            &self.repo_path,
+            &ids,
+            Path::new(&dir),
+        )?;
+
+        self.list.clear_range();
+        self.queue.borrow_mut().push_back(
+            InternalEvent::ShowInfoMsg(strings::patches_exported(
+                &self.key_config,
+                files.len(),
+                &dir,
+            )),
+        );
+
+        Ok(())
+    }
+
+    fn copy_commit_diff(&self, id: CommitId) -> Result<()> {
+        copy_string(&sync::get_commit_diff_text(&self.repo_path, id)?)
+    }
+
+    fn copy_commit_describe(&self, id: CommitId) -> Result<()> {
+        if let Some(describe) =
+            sync::describe_commit(&self.repo_path, id)?
+        {
+            copy_string(&describe)?;
+        } else {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowInfoMsg(
+                    strings::log_describe_no_tag().to_string(),
+                ),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn copy_commit_json(&self, id: CommitId) -> Result<()> {
+        let details = sync::get_commit_details(&self.repo_path, id)?;
+        copy_string(&commit_to_json(&details)?)
+    }
+
+    fn selected_commit(&self) -> Option<CommitId> {
+        self.list.selected_entry().map(|e| e.id)
+    }
+
+    fn selected_commit_tags(
+        &self,
+        commit: &Option<CommitId>,
+    ) -> Option<CommitTags> {
+        let tags = self.list.tags();
+
+        commit.and_then(|commit| {
+            tags.and_then(|tags| tags.get(&commit).cloned())
+        })
+    }
+
+    /// keys showing and inspecting the commit details
+    fn details_keys(&mut self, k: KeyEvent) -> Result<bool> {
+        if k == self.key_config.enter {
+            self.commit_details.toggle_visible()?;
+            self.update()?;
+            return Ok(true);
+        } else if k == self.key_config.focus_right
+            && self.commit_details.is_visible()
+        {
+            return Ok(self.selected_commit().is_some_and(|id| {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::InspectCommit(
+                        id,
+                        self.selected_commit_tags(&Some(id)),
+                    ),
+                );
+                true
+            }));
+        }
+
+        Ok(false)
+    }
+
+    /// keys acting on the selected commit
+    fn commit_keys(&mut self, k: KeyEvent) -> Result<bool> {
+        if k == self.key_config.log_open_difftool {
+            return Ok(self.difftool_commits()?.is_some_and(
+                |(old, new)| {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::OpenDifftool(old, new),
+                    );
+                    true
+                },
+            ));
+        } else if k == self.key_config.log_rename_branch && !self.bare
+        {
+            self.rename_head_branch();
+            return Ok(true);
+        } else if k == self.key_config.log_goto_parent {
+            self.goto_parent()?;
+            return Ok(true);
+        } else if k == self.key_config.log_goto_revert {
+            self.goto_revert()?;
+            return Ok(true);
+        }
+
+        Ok(self
+            .selected_commit()
+            .is_some_and(|id| self.selected_commit_keys(k, id)))
+    }
+
+    /// keys acting on `id`, the selected commit
+    fn selected_commit_keys(
+        &self,
+        k: KeyEvent,
+        id: CommitId,
+    ) -> bool {
+        if k == self.key_config.log_tag_commit {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::TagCommit(
+                    id,
+                    self.list.tags().cloned(),
+                ),
+            );
+        } else if k == self.key_config.log_copy_json {
+            try_or_popup!(
+                self,
+                strings::copy_commit_json_error(),
+                self.copy_commit_json(id)
+            );
+        } else if k == self.key_config.log_copy_diff {
+            try_or_popup!(
+                self,
+                strings::copy_commit_diff_error(),
+                self.copy_commit_diff(id)
+            );
+        } else if k == self.key_config.log_copy_describe {
+            try_or_popup!(
+                self,
+                strings::copy_describe_error(),
+                self.copy_commit_describe(id)
+            );
+        } else if k == self.key_config.log_stash_apply_onto
+            && !self.bare
+        {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ConfirmAction(Action::StashApplyOnto(
+                    id,
+                )),
+            );
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// keys changing which commits the log walks and how they show
+    fn view_keys(&mut self, k: KeyEvent) -> bool {
+        if k == self.key_config.log_toggle_reverse {
+            try_or_popup!(
+                self,
+                strings::reverse_log_error(),
+                self.toggle_reverse()
+            );
+        } else if k == self.key_config.log_author_summary
+            && self.is_filtering()
+        {
+            try_or_popup!(
+                self,
+                strings::author_summary_error(),
+                self.show_author_summary()
+            );
+        } else if k == self.key_config.log_author_stats {
+            try_or_popup!(
+                self,
+                strings::author_stats_error(),
+                self.open_author_stats()
+            );
+        } else if k == self.key_config.log_export_list {
+            try_or_popup!(
+                self,
+                strings::export_error(),
+                self.open_export_list()
+            );
+        } else if k == self.key_config.log_toggle_topo_order {
+            try_or_popup!(
+                self,
+                strings::log_order_error(),
+                self.toggle_topo_order()
+            );
+        } else if k == self.key_config.log_toggle_all_refs {
+            try_or_popup!(
+                self,
+                strings::log_refs_error(),
+                self.toggle_all_refs()
+            );
+        } else if k == self.key_config.log_cycle_window {
+            try_or_popup!(
+                self,
+                strings::log_window_error(),
+                self.cycle_window()
+            );
+        } else if k == self.key_config.log_full_history
+            && self.window.is_some()
+        {
+            try_or_popup!(
+                self,
+                strings::log_window_error(),
+                self.set_window(None)
+            );
+        } else if k == self.key_config.log_toggle_unpushed {
+            try_or_popup!(
+                self,
+                strings::unpushed_commits_error(),
+                self.toggle_unpushed()
+            );
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// keys opening popups
+    fn popup_keys(&self, k: KeyEvent) -> bool {
+        let event = if k == self.key_config.open_reflog {
+            InternalEvent::OpenReflog
+        } else if k == self.key_config.open_worktrees {
+            InternalEvent::OpenWorktrees
+        } else if k == self.key_config.open_undo {
+            InternalEvent::OpenUndo
+        } else if k == self.key_config.log_find_commit {
+            InternalEvent::FindCommit
+        } else if k == self.key_config.log_search {
+            InternalEvent::SearchCommit
+        } else if k == self.key_config.select_branch {
+            InternalEvent::SelectBranch
+        } else {
+            return false;
+        };
+
+        self.queue.borrow_mut().push_back(event);
+        true
+    }
+
+    /// keys acting on the marked commits or the visual range
+    fn mark_keys(&mut self, k: KeyEvent) -> bool {
+        let marked = self.list.marked().len();
+        let selection = self.list.log_selection().is_some();
+
+        if k == self.key_config.log_compare_commits && marked == 2 {
+            try_or_popup!(
+                self,
+                strings::compare_error(),
+                self.compare_marked()
+            );
+        } else if k == self.key_config.log_filter_range && marked == 2
+        {
+            try_or_popup!(
+                self,
+                strings::filter_error(),
+                self.filter_marked_range()
+            );
+        } else if k == self.key_config.log_copy_rebase_todo
+            && marked > 0
+        {
+            try_or_popup!(
+                self,
+                strings::copy_rebase_todo_error(),
+                self.copy_rebase_todo()
+            );
+        } else if k == self.key_config.copy && selection {
+            try_or_popup!(
+                self,
+                strings::copy_hash_error(),
+                self.copy_hashes()
+            );
+        } else if k == self.key_config.log_copy_subjects && selection
+        {
+            try_or_popup!(
+                self,
+                strings::copy_subject_error(),
+                self.copy_subjects()
+            );
+        } else if k == self.key_config.log_export_patches
+            && selection
+            && !self.bare
+        {
+            try_or_popup!(
+                self,
+                strings::export_patch_error(),
+                self.export_patches()
+            );
+        } else if k == self.key_config.log_cherry_pick
+            && selection
+            && !self.bare
+        {
+            try_or_popup!(
+                self,
+                strings::cherry_pick_error(),
+                self.cherry_pick()
+            );
+        } else if k == self.key_config.exit_popup
+            && self.list.range().is_some()
+        {
+            self.list.clear_range();
+        } else if k == self.key_config.exit_popup && marked > 0 {
+            self.list.clear_marked();
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// keys about the filter and the search
+    fn filter_keys(&mut self, k: KeyEvent) -> Result<bool> {
+        if k == self.key_config.log_copy_filter && self.is_filtering()
+        {
+            try_or_popup!(
+                self,
+                strings::copy_filter_error(),
+                self.copy_filter()
+            );
+        } else if (k == self.key_config.log_search_next
+            || k == self.key_config.log_search_prev)
+            && self.search.is_some()
+        {
+            try_or_popup!(
+                self,
+                strings::search_error(),
+                self.search_next(
+                    k == self.key_config.log_search_next
+                )
+            );
+        } else if k == self.key_config.log_sort_relevance
+            && self.filter_query.is_some()
+        {
+            try_or_popup!(
+                self,
+                strings::sort_error(),
+                self.toggle_relevance()
+            );
+        } else if k == self.key_config.exit_popup
+            && self.search_scan.is_some()
+        {
+            self.stop_search();
+        } else if k == self.key_config.exit_popup
+            && self.filter_modes.contains(FilterModes::FILTERED)
+        {
+            self.set_filter(None)?;
+        } else {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    /// commands the commit details
+    fn details_commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) {
+        out.push(CommandInfo::new(
+            strings::commands::log_details_toggle(&self.key_config),
+            true,
+            self.visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_details_open(&self.key_config),
+            true,
+            (self.visible && self.commit_details.is_visible())
+                || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_details_scroll(&self.key_config),
+            true,
+            (self.visible && self.commit_details.is_visible())
+                || force_all,
+        ));
+    }
+
+    /// commands acting on the selected commit
+    fn commit_commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) {
+        let visible = self.visible || force_all;
+
+        out.push(CommandInfo::new(
+            strings::commands::log_tag_commit(&self.key_config),
+            true,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_copy_json(&self.key_config),
+            self.selected_commit().is_some(),
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_copy_diff(&self.key_config),
+            self.selected_commit().is_some(),
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_copy_describe(&self.key_config),
+            self.selected_commit().is_some(),
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_open_difftool(&self.key_config),
+            self.selected_commit().is_some(),
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_goto_parent(&self.key_config),
+            self.selected_commit().is_some(),
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_goto_revert(&self.key_config),
+            self.selected_revert_relation().is_some(),
+            visible,
+        ));
+
+        out.push(
+            CommandInfo::new(
+                strings::commands::log_stash_apply_onto(
+                    &self.key_config,
+                ),
+                self.selected_commit().is_some(),
+                visible,
+            )
+            .needs_worktree(self.bare),
+        );
+
+        out.push(CommandInfo::new(
+            strings::commands::log_rename_branch(&self.key_config),
+            !self.bare,
+            visible,
+        ));
+    }
+
+    /// commands changing which commits the log walks and how they show
+    fn view_commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) {
+        let visible = self.visible || force_all;
+
+        out.push(CommandInfo::new(
+            strings::commands::log_toggle_reverse(&self.key_config),
+            true,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_author_summary(&self.key_config),
+            self.is_filtering(),
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_author_stats(&self.key_config),
+            true,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_export_list(&self.key_config),
+            self.list.count_total() > 0,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_toggle_topo_order(
+                &self.key_config,
+            ),
+            true,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_toggle_all_refs(&self.key_config),
+            true,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_cycle_window(&self.key_config),
+            true,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_full_history(&self.key_config),
+            self.window.is_some(),
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_toggle_unpushed(&self.key_config),
+            true,
+            visible,
+        ));
+    }
+
+    /// commands opening popups
+    fn popup_commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) {
+        let visible = self.visible || force_all;
+
+        out.push(CommandInfo::new(
+            strings::commands::open_reflog(&self.key_config),
+            true,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::open_worktrees(&self.key_config),
+            true,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::open_undo(&self.key_config),
+            true,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_find_commit(&self.key_config),
+            true,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search(&self.key_config),
+            true,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::open_branch_select_popup(
+                &self.key_config,
+            ),
+            true,
+            visible,
+        ));
+    }
+
+    /// commands acting on the marked commits or the visual range
+    fn mark_commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) {
+        let visible = self.visible || force_all;
+
+        out.push(CommandInfo::new(
+            strings::commands::log_compare_commits(&self.key_config),
+            self.list.marked().len() == 2,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_filter_range(&self.key_config),
+            self.list.marked().len() == 2,
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_copy_rebase_todo(&self.key_config),
+            !self.list.marked().is_empty(),
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_copy_hashes(&self.key_config),
+            self.list.log_selection().is_some(),
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_copy_subjects(&self.key_config),
+            self.list.log_selection().is_some(),
+            visible,
+        ));
+
+        out.push(
+            CommandInfo::new(
+                strings::commands::log_export_patches(
+                    &self.key_config,
+                ),
+                self.list.log_selection().is_some(),
+                visible,
+            )
+            .needs_worktree(self.bare),
+        );
+
+        out.push(
+            CommandInfo::new(
+                strings::commands::log_cherry_pick(&self.key_config),
+                self.list.log_selection().is_some(),
+                visible,
+            )
+            .needs_worktree(self.bare),
+        );
+
+        out.push(CommandInfo::new(
+            strings::commands::log_range_clear(&self.key_config),
+            true,
+            (self.visible && self.list.range().is_some())
+                || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_marks_clear(&self.key_config),
+            true,
+            (self.visible
+                && self.list.range().is_none()
+                && !self.list.marked().is_empty())
+                || force_all,
+        ));
+    }
+
+    /// commands about the filter and the search
+    fn filter_commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) {
+        let visible = self.visible || force_all;
+
+        out.push(CommandInfo::new(
+            strings::commands::log_copy_filter(&self.key_config),
+            self.is_filtering(),
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search_next_prev(&self.key_config),
+            self.search.is_some(),
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_search_cancel(&self.key_config),
+            true,
+            (self.visible
+                && self.list.range().is_none()
+                && self.search_scan.is_some())
+                || force_all,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_sort_relevance(&self.key_config),
+            self.filter_query.is_some(),
+            visible,
+        ));
+
+        out.push(CommandInfo::new(
+            strings::commands::log_filter_clear(&self.key_config),
+            true,
+            (self.visible
+                && self.filter_modes.contains(FilterModes::FILTERED)
+                && self.list.range().is_none()
+                && self.search_scan.is_none())
+                || force_all,
+        ));
+    }
+
+    /// how `key` scrolls the details while they are shown
+    fn details_scroll(&self, key: KeyEvent) -> Option<ScrollType> {
+        if !self.commit_details.is_visible() {
+            None
+        } else if key == self.key_config.log_details_scroll_up {
+            Some(ScrollType::Up)
+        } else if key == self.key_config.log_details_scroll_down {
+            Some(ScrollType::Down)
+        } else if key == self.key_config.log_details_page_up {
+            Some(ScrollType::PageUp)
+        } else if key == self.key_config.log_details_page_down {
+            Some(ScrollType::PageDown)
+        } else {
+            None
+        }
+    }
+}
+
+impl DrawableComponent for Revlog {
+    fn draw<B: Backend>(
+        &self,
+        f: &mut Frame<B>,
+        area: Rect,
+    ) -> Result<()> {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(
+                [
+                    Constraint::Percentage(60),
+                    Constraint::Percentage(40),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        if self.commit_details.is_visible() {
+            self.list.draw(f, chunks[0])?;
+            self.commit_details.draw(f, chunks[1])?;
+        } else {
+            self.list.draw(f, area)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Component for Revlog {
+    fn event(&mut self, ev: Event) -> Result<bool> {
+        if self.visible {
+            // the details take their scroll keys before the list
+            if let Event::Key(k) = ev {
+                if let Some(scroll) = self.details_scroll(k) {
+                    self.commit_details.scroll_details(scroll);
+                    return Ok(true);
+                }
+            }
+
+            if self.list.event(ev)? {
+                self.update()?;
+                return Ok(true);
+            } else if let Event::Key(k) = ev {
+                return Ok(self.details_keys(k)?
+                    || self.commit_keys(k)?
+                    || self.view_keys(k)
+                    || self.popup_keys(k)
+                    || self.mark_keys(k)
+                    || self.filter_keys(k)?);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn commands(
+        &self,
+        out: &mut Vec<CommandInfo>,
+        force_all: bool,
+    ) -> CommandBlocking {
+        if self.visible || force_all {
+            self.list.commands(out, force_all);
+        }
+
+        self.details_commands(out, force_all);
+        self.commit_commands(out, force_all);
+        self.view_commands(out, force_all);
+        self.popup_commands(out, force_all);
+        self.mark_commands(out, force_all);
+        self.filter_commands(out, force_all);
+
+        visibility_blocking(self)
+    }
+
+    fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn hide(&mut self) {
+        self.visible = false;
+        self.git_log.set_background();
+        self.restore_view =
+            self.selected_commit().map(|selected| ViewState {
+                selected,
+                row: self.list.selection_row(),
+            });
+    }
+
+    fn show(&mut self) -> Result<()> {
+        self.visible = true;
+        self.list.clear();
+
+        if self.select_head && self.select_on_show.is_none() {
+            self.select_on_show =
+                sync::get_head(&self.repo_path).ok();
+        }
+
+        match self.restore_view {
+            Some(view) if self.select_on_show.is_none() => {
+                self.select_on_show = Some(view.selected);
+            }
+            _ => self.restore_view = None,
+        }
+
+        self.update()?;
+
+        Ok(())
+    }
+}
+
+/// title of the filtered log, tells a walk that may still find
+/// more matches apart from a finished one
+fn filtered_title(
+    key_config: &SharedKeyConfig,
+    by_relevance: bool,
+    finished: bool,
+    count: usize,
+) -> String {
+    if !finished {
+        strings::log_title_filtering(key_config, count)
+    } else if by_relevance {
+        strings::log_title_relevance(key_config, count)
+    } else {
+        strings::log_title_filtered(key_config, count)
+    }
+}
+
+/// amount of commits per author, most first (then by name)
+fn author_counts(commits: &[CommitInfo]) -> Vec<(String, usize)> {
+    let mut by_author: HashMap<&str, usize> = HashMap::new();
+    for c in commits {
+        *by_author.entry(c.author.as_str()).or_default() += 1;
+    }
+
+    let mut counts: Vec<(String, usize)> = by_author
+        .into_iter()
+        .map(|(author, count)| (author.to_string(), count))
+        .collect();
+    counts.sort_by(|(a, a_count), (b, b_count)| {
+        b_count.cmp(a_count).then_with(|| a.cmp(b))
+    });
+
+    counts
+}
+
+/// a log walking `filter` within `window` (on `worker`), sharing
+/// the walk `generations` with the other logs of the tab and
+/// notifying at most once per `notify_interval` while running
+fn window_log(
+    repo_path: &Arc<str>,
+    sender: &Sender<AsyncNotification>,
+    filter: Option<LogWalkerFilter>,
+    generations: &WalkGenerations,
+    worker: Option<&LogWorker>,
+    window: Option<&str>,
+    notify_interval: Duration,
+) -> AsyncLog {
+    let mut log =
+        AsyncLog::new(Arc::clone(repo_path), sender, filter);
+    log.set_generations(generations.clone());
+    log.set_notify_interval(notify_interval);
+    if let Some(worker) = worker {
+        log.set_worker(worker.clone());
+    }
+    log.set_window(window.and_then(|window| {
+        parse_log_window(window, Utc::now().timestamp()).ok()
+    }));
+
+    log
+}
+
+/// shows `msg` in the error console
+fn report_warning(queue: &Queue, msg: String) {
+    queue.borrow_mut().push_back(InternalEvent::ReportProblem(
+        Severity::Warning,
+        msg,
+    ));
+}
+
+/// parses `window` and checks the revision it names exists
+fn resolve_window(
+    repo_path: &str,
+    window: &str,
+) -> Result<LogWindow> {
+    let window = parse_log_window(window, Utc::now().timestamp())?;
+    if let LogWindow::SinceRev(rev) = &window {
+        sync::resolve_rev(repo_path, rev)?;
+    }
+
+    Ok(window)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{LogOptions, Options};
+    use crossbeam_channel::unbounded;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(path: &str, args: &[&str]) {
+        assert!(Command::new("git")
+            .args(args)
+            .current_dir(path)
+            .status()
+            .unwrap()
+            .success());
+    }
+
+    #[test]
+    fn test_filtered_title() {
+        let key_config = SharedKeyConfig::default();
+
+        assert_eq!(
+            filtered_title(&key_config, false, false, 120),
+            "Commit (filtering.. 120)"
+        );
+        assert_eq!(
+            filtered_title(&key_config, true, false, 3),
+            "Commit (filtering.. 3)"
+        );
+        assert_eq!(
+            filtered_title(&key_config, false, true, 120),
+            "Commit (filtered, done: 120 matches)"
+        );
+        assert_eq!(
+            filtered_title(&key_config, true, true, 0),
+            "Commit (by relevance, done: 0 matches)"
+        );
+    }
+
+    #[test]
+    fn test_fetch_error_reported() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+
+        let queue = Queue::default();
+        let (sender, _receiver) = unbounded();
+        let mut revlog = Revlog::new(
+            Arc::from(path),
+            false,
+            &queue,
+            &sender,
+            &SharedOptions::default(),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        let errors = || {
+            queue
+                .borrow_mut()
+                .drain(..)
+                .filter(|ev| {
+                    matches!(ev, InternalEvent::ShowErrorMsg(_))
+                })
+                .count()
+        };
+        let locked = || {
+            Err(asyncgit::Error::Io(std::io::Error::from(
+                std::io::ErrorKind::WouldBlock,
+            )))
+        };
+        let broken =
+            || Err(asyncgit::Error::Generic(String::from("broken")));
+
+        // a locked repo is retried quietly for a while
+        for _ in 0..MAX_FETCH_RETRIES {
+            revlog.commits_fetched(0, locked());
+            assert_eq!(errors(), 0);
+            assert!(revlog.any_work_pending());
+        }
+        revlog.commits_fetched(0, locked());
+        assert_eq!(errors(), 1);
+        assert_eq!(revlog.fetch_retry, None);
+
+        // other errors right away, once until a fetch succeeds
+        revlog.commits_fetched(0, broken());
+        assert_eq!(errors(), 1);
+        revlog.commits_fetched(0, broken());
+        assert_eq!(errors(), 0);
+        revlog.commits_fetched(0, Ok(Vec::new()));
+        revlog.commits_fetched(0, broken());
+        assert_eq!(errors(), 1);
+    }
+
+    #[test]
+    fn test_restore_view_on_show() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+        git(path, &["config", "user.name", "name"]);
+        git(path, &["config", "user.email", "email"]);
+
+        let ids = ["a", "b", "c", "d"]
+            .iter()
+            .map(|msg| sync::commit(path, msg).unwrap())
+            .collect::<Vec<_>>();
+
+        let (sender, _receiver) = unbounded();
+        let mut revlog = Revlog::new(
+            Arc::from(path),
+            false,
+            &Queue::default(),
+            &sender,
+            &SharedOptions::default(),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        let timeout = Duration::from_secs(10);
+        let show = |revlog: &mut Revlog| {
+            revlog.show().unwrap();
+            revlog.git_log.wait(timeout).unwrap();
+            revlog.update().unwrap();
+        };
+
+        show(&mut revlog);
+        revlog.list.select_entry(2);
+        assert_eq!(revlog.selected_commit(), Some(ids[1]));
+
+        // new commits on top move the selected one down
+        revlog.hide();
+        sync::commit(path, "e").unwrap();
+        show(&mut revlog);
+        assert_eq!(revlog.list.selection(), 3);
+        assert_eq!(revlog.selected_commit(), Some(ids[1]));
+        assert_eq!(revlog.list.selection_row(), 2);
+
+        // the commit is gone after a rewrite
+        revlog.hide();
+        git(path, &["reset", "-q", "--hard", &ids[0].to_string()]);
+        sync::commit(path, "f").unwrap();
+        let top = sync::commit(path, "g").unwrap();
+        show(&mut revlog);
+        assert_eq!(revlog.list.selection(), 0);
+        assert_eq!(revlog.selected_commit(), Some(top));
+    }
+
+    #[test]
+    fn test_author_counts() {
+        let commits: Vec<CommitInfo> =
+            ["bob", "alice", "bob", "carol", "alice", "bob"]
+                .iter()
+                .map(|author| CommitInfo {
+                    message: String::new(),
+                    time: 0,
+                    author: (*author).to_string(),
+                    id: CommitId::default(),
+                })
+                .collect();
+
+        assert_eq!(
+            author_counts(&commits),
+            vec![
+                (String::from("bob"), 3),
+                (String::from("alice"), 2),
+                (String::from("carol"), 1),
+            ]
+        );
+        assert_eq!(author_counts(&[]), Vec::new());
+
+        let msg = strings::log_author_summary_msg(
+            &SharedKeyConfig::default(),
+            &author_counts(&commits),
+            2,
+            false,
+        );
+        assert_eq!(
+            msg,
+            "6 matching commits by 3 authors:\n      3  bob\n      2  alice\n+1 more"
+        );
+    }
+
+    #[test]
+    fn test_filter_to_copy() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+        git(path, &["config", "user.name", "name"]);
+        git(path, &["config", "user.email", "email"]);
+        sync::commit(path, "fix parser").unwrap();
+
+        let (sender, _receiver) = unbounded();
+        let mut revlog = Revlog::new(
+            Arc::from(path),
+            false,
+            &Queue::default(),
+            &sender,
+            &SharedOptions::default(),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        assert_eq!(revlog.filter_to_copy(), None);
+
+        let typed = ":top 10  :m fix || (:a= bob)";
+        revlog.filter(typed).unwrap();
+        assert_eq!(revlog.filter_to_copy(), Some(typed));
+
+        revlog.toggle_relevance().unwrap();
+        assert_eq!(revlog.filter_to_copy(), Some(typed));
+
+        revlog.filter("  ").unwrap();
+        assert_eq!(revlog.filter_to_copy(), None);
+
+        revlog.filter(typed).unwrap();
+        revlog.set_filter(None).unwrap();
+        assert_eq!(revlog.filter_to_copy(), None);
+    }
+
+    #[test]
+    fn test_filter_and_count() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+        git(path, &["config", "user.name", "name"]);
+        git(path, &["config", "user.email", "email"]);
+
+        for msg in &["fix parser", "wip", "fix docs"] {
+            sync::commit(path, msg).unwrap();
+        }
+
+        let (sender, _receiver) = unbounded();
+        let mut revlog = Revlog::new(
+            Arc::from(path),
+            false,
+            &Queue::default(),
+            &sender,
+            &SharedOptions::default(),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        let timeout = Duration::from_secs(10);
+
+        assert_eq!(
+            revlog.filter_and_count(":m fix", timeout).unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            revlog
+                .filter_and_count(":!(fix) && wip", timeout)
+                .unwrap(),
+            Some(1)
+        );
+
+        // the oldest fix is beyond the 2 most recent commits
+        assert_eq!(
+            revlog
+                .filter_and_count(":top 2 :m fix", timeout)
+                .unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            revlog.filter_status().unwrap(),
+            Some(FilterStatus::Done { matches: 1 })
+        );
+        assert_eq!(revlog.git_log.scanned(), 2);
+
+        assert_eq!(
+            revlog.filter_and_count("", timeout).unwrap(),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_filter_line_range() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+        git(path, &["config", "user.name", "name"]);
+        git(path, &["config", "user.email", "email"]);
+
+        for (content, msg) in &[
+            ("a\nb\nc\n", "fix add"),
+            ("a\nB\nc\n", "fix b"),
+            ("a\nB\nC\n", "fix c"),
+        ] {
+            std::fs::write(td.path().join("foo.txt"), content)
+                .unwrap();
+            git(path, &["add", "foo.txt"]);
+            sync::commit(path, msg).unwrap();
+        }
+
+        let (sender, _receiver) = unbounded();
+        let mut revlog = Revlog::new(
+            Arc::from(path),
+            false,
+            &Queue::default(),
+            &sender,
+            &SharedOptions::default(),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        let timeout = Duration::from_secs(10);
+
+        assert_eq!(
+            revlog
+                .filter_and_count(":L 2,2:foo.txt", timeout)
+                .unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            revlog
+                .filter_and_count(":L 2,+2:foo.txt :m c", timeout)
+                .unwrap(),
+            Some(1)
+        );
+        assert_eq!(
+            revlog.filter_to_copy(),
+            Some(":L 2,+2:foo.txt :m c")
+        );
+
+        // the range applies after `:top`
+        assert_eq!(
+            revlog
+                .filter_and_count(":top 1 :L 1,1:foo.txt", timeout)
+                .unwrap(),
+            Some(0)
+        );
+    }
+
+    #[test]
+    fn test_search_beyond_loaded_slice() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+        git(path, &["config", "user.name", "name"]);
+        git(path, &["config", "user.email", "email"]);
+
+        let needle = sync::commit(path, "needle").unwrap();
+        for _ in 0..SLICE_SIZE + SEARCH_STEP {
+            sync::commit(path, "hay").unwrap();
+        }
+
+        let (sender, _receiver) = unbounded();
+        let mut revlog = Revlog::new(
+            Arc::from(path),
+            false,
+            &Queue::default(),
+            &sender,
+            &SharedOptions::default(),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        revlog.show().unwrap();
+        let count = revlog
+            .git_log
+            .wait(Duration::from_secs(10))
+            .unwrap()
+            .unwrap();
+
+        let run_search = |revlog: &mut Revlog| {
+            for _ in 0..count {
+                if revlog.search_scan.is_none() {
+                    break;
+                }
+                revlog.update().unwrap();
+            }
+        };
+
+        revlog.search(":m needle").unwrap();
+        run_search(&mut revlog);
+        assert_eq!(revlog.list.selection(), count - 1);
+        assert_eq!(revlog.selected_commit(), Some(needle));
+
+        revlog.search_next(true).unwrap();
+        run_search(&mut revlog);
+        assert_eq!(revlog.list.selection(), count - 1);
+
+        revlog.search(":m hay").unwrap();
+        revlog.search_next(false).unwrap();
+        run_search(&mut revlog);
+        assert_eq!(revlog.list.selection(), count - 2);
+        assert!(revlog.search_scan.is_none());
+    }
+
+    #[test]
+    fn test_reverse_order() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+        git(path, &["config", "user.name", "name"]);
+        git(path, &["config", "user.email", "email"]);
+
+        // newest first: e d c b a
+        let ids: Vec<CommitId> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|msg| sync::commit(path, msg).unwrap())
+            .collect();
+
+        let (sender, _receiver) = unbounded();
+        let mut revlog = Revlog::new(
+            Arc::from(path),
+            false,
+            &Queue::default(),
+            &sender,
+            &SharedOptions::default(),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        revlog.show().unwrap();
+        revlog.git_log.wait(Duration::from_secs(10)).unwrap();
+        revlog.update().unwrap();
+
+        revlog.list.select_entry(1);
+        assert_eq!(revlog.selected_commit(), Some(ids[3]));
+
+        revlog.toggle_reverse().unwrap();
+        assert_eq!(revlog.list.selection(), 3);
+        assert_eq!(revlog.selected_commit(), Some(ids[3]));
+        revlog.list.select_entry(0);
+        assert_eq!(revlog.selected_commit(), Some(ids[0]));
+
+        // the range is handed out oldest first like before
+        revlog
+            .list
+            .event(Event::Key(revlog.key_config.log_visual_mode))
+            .unwrap();
+        revlog.list.select_entry(2);
+        assert_eq!(revlog.selected_commits().unwrap(), ids[..3]);
+        revlog.list.clear_range();
+
+        // searching down the list finds newer commits
+        revlog.search(":m d").unwrap();
+        revlog.update().unwrap();
+        assert_eq!(revlog.selected_commit(), Some(ids[3]));
+        revlog.search(":m b").unwrap();
+        revlog.search_next(false).unwrap();
+        assert_eq!(revlog.selected_commit(), Some(ids[1]));
+
+        revlog.toggle_reverse().unwrap();
+        assert_eq!(revlog.list.selection(), 3);
+        assert_eq!(revlog.selected_commit(), Some(ids[1]));
+
+        // a filtered log flips just the same
+        revlog.filter(":m a || :m e").unwrap();
+        revlog.git_log.wait(Duration::from_secs(10)).unwrap();
+        revlog.update().unwrap();
+        revlog.toggle_reverse().unwrap();
+        revlog.list.select_entry(0);
+        assert_eq!(revlog.selected_commit(), Some(ids[0]));
+        revlog.list.select_entry(1);
+        assert_eq!(revlog.selected_commit(), Some(ids[4]));
+    }
+
+    #[test]
+    fn test_toggle_topo_order() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+        git(path, &["config", "user.name", "name"]);
+        git(path, &["config", "user.email", "email"]);
+        for msg in &["a", "b", "c"] {
+            sync::commit(path, msg).unwrap();
+        }
+
+        let (sender, _receiver) = unbounded();
+        let queue = Queue::default();
+        let mut revlog = Revlog::new(
+            Arc::from(path),
+            false,
+            &queue,
+            &sender,
+            &SharedOptions::default(),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        revlog.show().unwrap();
+        let wait = |revlog: &mut Revlog| {
+            revlog.git_log.wait(Duration::from_secs(10)).unwrap();
+            revlog.update().unwrap();
+            revlog.git_log.count().unwrap()
+        };
+
+        assert_eq!(wait(&mut revlog), 3);
+        revlog.list.select_entry(2);
+
+        revlog.toggle_topo_order().unwrap();
+        assert!(revlog.walk.contains(WalkModes::TOPO_ORDER));
+        assert_eq!(wait(&mut revlog), 3);
+        assert_eq!(revlog.list.selection(), 0);
+
+        revlog.toggle_topo_order().unwrap();
+        assert!(!revlog.walk.contains(WalkModes::TOPO_ORDER));
+        assert_eq!(wait(&mut revlog), 3);
+    }
+
+    #[test]
+    fn test_toggle_all_refs() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+        git(path, &["config", "user.name", "name"]);
+        git(path, &["config", "user.email", "email"]);
+        let base = sync::commit(path, "base").unwrap();
+        git(path, &["checkout", "-q", "-b", "feature"]);
+        let feature = sync::commit(path, "feature").unwrap();
+        git(
+            path,
+            &["checkout", "-q", "-b", "other", &base.to_string()],
+        );
+        let other = sync::commit(path, "other").unwrap();
+        git(path, &["tag", "v1", &feature.to_string()]);
+        git(path, &["checkout", "-q", "--detach", &base.to_string()]);
+
+        let (sender, _receiver) = unbounded();
+        let queue = Queue::default();
+        let mut revlog = Revlog::new(
+            Arc::from(path),
+            false,
+            &queue,
+            &sender,
+            &SharedOptions::default(),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        revlog.show().unwrap();
+        let wait = |revlog: &mut Revlog| {
+            revlog.git_log.wait(Duration::from_secs(10)).unwrap();
+            revlog.update().unwrap();
+            revlog.git_log.get_slice(0, 100).unwrap()
+        };
+
+        assert_eq!(wait(&mut revlog), vec![base]);
+
+        revlog.toggle_all_refs().unwrap();
+        let mut all = wait(&mut revlog);
+        all.sort();
+        let mut expected = vec![base, feature, other];
+        expected.sort();
+        assert_eq!(all, expected);
+        // the selection stays with the commit
+        assert_eq!(revlog.selected_commit(), Some(base));
+
+        // refs moving start the walk over
+        git(path, &["checkout", "-q", "other"]);
+        let newer = sync::commit(path, "newer").unwrap();
+        git(path, &["checkout", "-q", "--detach", &base.to_string()]);
+        revlog.update().unwrap();
+        assert!(wait(&mut revlog).contains(&newer));
+
+        revlog.toggle_all_refs().unwrap();
+        assert_eq!(wait(&mut revlog), vec![base]);
+    }
+
+    #[test]
+    fn test_log_window() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+        git(path, &["config", "user.name", "name"]);
+        git(path, &["config", "user.email", "email"]);
+
+        let ids: Vec<CommitId> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|msg| sync::commit(path, msg).unwrap())
+            .collect();
+        git(path, &["tag", "v1", "HEAD~3"]);
+
+        let (sender, _receiver) = unbounded();
+        let open = |window: &str, queue: &Queue| {
+            let mut revlog = Revlog::new(
+                Arc::from(path),
+                false,
+                queue,
+                &sender,
+                &SharedOptions::new(Options {
+                    log: LogOptions {
+                        window: Some(window.to_string()),
+                        ..LogOptions::default()
+                    },
+                    ..Options::default()
+                }),
+                SharedTheme::default(),
+                SharedKeyConfig::default(),
+            );
+            revlog.show().unwrap();
+            revlog
+        };
+        let wait = |revlog: &mut Revlog| {
+            revlog.git_log.wait(Duration::from_secs(10)).unwrap();
+            revlog.update().unwrap();
+            revlog.git_log.count().unwrap()
+        };
+
+        let queue = Queue::default();
+        let mut revlog = open("since v1", &queue);
+        assert_eq!(wait(&mut revlog), 3);
+        assert!(queue.borrow().is_empty());
+
+        // the filter only sees the window
+        revlog.filter(":m a || :m e").unwrap();
+        assert_eq!(wait(&mut revlog), 1);
+        assert_eq!(revlog.selected_commit(), Some(ids[4]));
+
+        revlog.cycle_window().unwrap();
+        assert_eq!(revlog.window.as_deref(), Some("last 1000"));
+        assert_eq!(wait(&mut revlog), 2);
+
+        revlog.set_window(None).unwrap();
+        revlog.filter("").unwrap();
+        assert_eq!(wait(&mut revlog), 5);
+
+        let mut revlog = open("since nope", &queue);
+        assert_eq!(revlog.window, None);
+        assert_eq!(wait(&mut revlog), 5);
+        assert_eq!(queue.borrow().len(), 1);
+    }
+
+    #[test]
+    fn test_sort_by_relevance() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+        git(path, &["config", "user.name", "name"]);
+        git(path, &["config", "user.email", "email"]);
+
+        let both = sync::commit(path, "fix parser").unwrap();
+        let fix = sync::commit(path, "fix docs").unwrap();
+        sync::commit(path, "wip").unwrap();
+
+        let (sender, _receiver) = unbounded();
+        let mut revlog = Revlog::new(
+            Arc::from(path),
+            false,
+            &Queue::default(),
+            &sender,
+            &SharedOptions::default(),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        revlog.show().unwrap();
+        let timeout = Duration::from_secs(10);
+
+        assert_eq!(
+            revlog
+                .filter_and_count("fix || parser", timeout)
+                .unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            revlog.git_log.get_slice(0, 2).unwrap(),
+            [fix, both]
+        );
+
+        revlog.toggle_relevance().unwrap();
+        assert_eq!(
+            revlog.git_log.get_slice(0, 2).unwrap(),
+            [both, fix]
+        );
+
+        revlog.toggle_relevance().unwrap();
+        revlog.git_log.wait(timeout).unwrap();
+        revlog.update().unwrap();
+        assert_eq!(
+            revlog.git_log.get_slice(0, 2).unwrap(),
+            [fix, both]
+        );
+    }
+
+    #[test]
+    fn test_selected_commits_marked() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+        git(path, &["config", "user.name", "name"]);
+        git(path, &["config", "user.email", "email"]);
+
+        let ids = ["a", "b", "c", "d"]
+            .iter()
+            .map(|msg| sync::commit(path, msg).unwrap())
+            .collect::<Vec<_>>();
+
+        let (sender, _receiver) = unbounded();
+        let mut revlog = Revlog::new(
+            Arc::from(path),
+            false,
+            &Queue::default(),
+            &sender,
+            &SharedOptions::default(),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        revlog.show().unwrap();
+        revlog.git_log.wait(Duration::from_secs(10)).unwrap();
+        revlog.update().unwrap();
+
+        let mark = Event::Key(revlog.key_config.log_mark_commit);
+        for position in &[1, 3, 0] {
+            revlog.list.select_entry(*position);
+            revlog.list.event(mark).unwrap();
+        }
+
+        // log order (oldest first) no matter the order of marking,
+        // the range is ignored while there are marks
+        revlog
+            .list
+            .event(Event::Key(revlog.key_config.log_visual_mode))
+            .unwrap();
+        assert!(revlog.list.range().is_some());
+        assert_eq!(
+            revlog.selected_commits().unwrap(),
+            vec![ids[0], ids[2], ids[3]]
+        );
+
+        revlog.list.clear_marked();
+        assert_eq!(revlog.selected_commits().unwrap(), vec![ids[3]]);
+    }
+
+    #[test]
+    fn test_select_head_on_show() {
+        let td = TempDir::new().unwrap();
+        let path = td.path().to_str().unwrap();
+        git(path, &["init", "-q"]);
+        git(path, &["config", "user.name", "name"]);
+        git(path, &["config", "user.email", "email"]);
+
+        sync::commit(path, "fix parser").unwrap();
+        let head = sync::commit(path, "fix docs").unwrap();
+
+        let (sender, _receiver) = unbounded();
+        let mut revlog = Revlog::new(
+            Arc::from(path),
+            false,
+            &Queue::default(),
+            &sender,
+            &SharedOptions::new(Options {
+                log: LogOptions {
+                    select_head: true,
+                    ..LogOptions::default()
+                },
+                ..Options::default()
+            }),
+            SharedTheme::default(),
+            SharedKeyConfig::default(),
+        );
+        revlog.show().unwrap();
+        let timeout = Duration::from_secs(10);
+
+        // relevance moves `HEAD` down to the second entry
+        revlog.filter_and_count("fix || parser", timeout).unwrap();
+        revlog.toggle_relevance().unwrap();
+        assert_eq!(revlog.git_log.position(head).unwrap(), Some(1));
+
+        revlog.list.select_entry(0);
+
+        revlog.hide();
+        revlog.show().unwrap();
+
+        assert_eq!(revlog.list.selection(), 1);
     }
 }
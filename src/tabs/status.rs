@@ -3,7 +3,8 @@ use crate::{
     components::{
         command_pump, event_pump, visibility_blocking,
         ChangesComponent, CommandBlocking, CommandInfo, Component,
-        DiffComponent, DrawableComponent, FileTreeItemKind,
+        CredComponent, CredState, DiffComponent, DrawableComponent,
+        FileTreeItemKind,
     },
     keys::SharedKeyConfig,
     queue::{InternalEvent, Queue, ResetItem},
@@ -13,13 +14,18 @@ use crate::{
 use anyhow::Result;
 use asyncgit::{
     cached,
-    sync::BranchCompare,
     sync::{self, status::StatusType},
-    AsyncDiff, AsyncNotification, AsyncStatus, DiffParams, DiffType,
-    StatusParams, CWD,
+    sync::{
+        cred::BasicAuthCredential, BranchCompare, FetchStats,
+        ProgressNotification, RemoteError, RemoteResult,
+        DEFAULT_REMOTE_NAME,
+    },
+    AsyncDiff, AsyncFetch, AsyncNotification, AsyncStatus,
+    DiffParams, DiffType, FetchRequest, RemoteOps, StatusParams, CWD,
 };
 use crossbeam_channel::Sender;
 use crossterm::event::Event;
+use std::cmp;
 use tui::{
     layout::{Alignment, Constraint, Direction, Layout},
     widgets::Paragraph,
@@ -50,6 +56,10 @@ pub struct Status {
     git_diff: AsyncDiff,
     git_status_workdir: AsyncStatus,
     git_status_stage: AsyncStatus,
+    git_fetch: AsyncFetch,
+    /// the fetch asking for credentials or the last one started
+    fetch_request: Option<FetchRequest>,
+    input_cred: CredComponent,
     git_branch_state: BranchCompare,
     git_branch_name: cached::BranchName,
     queue: Queue,
@@ -103,6 +113,7 @@ impl DrawableComponent for Status {
         self.index.draw(f, left_chunks[1])?;
         self.diff.draw(f, chunks[1])?;
         self.draw_branch_state(f, &left_chunks);
+        self.input_cred.draw(f, rect)?;
 
         Ok(())
     }
@@ -115,6 +126,7 @@ impl Status {
     pub fn new(
         queue: &Queue,
         sender: &Sender<AsyncNotification>,
+        remote_ops: &RemoteOps,
         theme: SharedTheme,
         key_config: SharedKeyConfig,
     ) -> Self {
@@ -141,13 +153,16 @@ impl Status {
             ),
             diff: DiffComponent::new(
                 queue.clone(),
-                theme,
+                theme.clone(),
                 key_config.clone(),
                 false,
             ),
+            input_cred: CredComponent::new(theme, key_config.clone()),
             git_diff: AsyncDiff::new(sender),
             git_status_workdir: AsyncStatus::new(sender.clone()),
             git_status_stage: AsyncStatus::new(sender.clone()),
+            git_fetch: AsyncFetch::new(sender, remote_ops),
+            fetch_request: None,
             git_action_executed: false,
             git_branch_state: BranchCompare::default(),
             git_branch_name: cached::BranchName::new(CWD),
@@ -161,8 +176,13 @@ impl Status {
         chunks: &[tui::layout::Rect],
     ) {
         if let Some(branch_name) = self.git_branch_name.last() {
+            let fetch_progress = self
+                .fetch_progress()
+                .map(|p| format!("{} ", p))
+                .unwrap_or_default();
             let w = Paragraph::new(format!(
-                "\u{2191}{} \u{2193}{} {{{}}}",
+                "{}\u{2191}{} \u{2193}{} {{{}}}",
+                fetch_progress,
                 self.git_branch_state.ahead,
                 self.git_branch_state.behind,
                 branch_name
@@ -270,6 +290,7 @@ impl Status {
         self.git_diff.is_pending()
             || self.git_status_stage.is_pending()
             || self.git_status_workdir.is_pending()
+            || self.git_fetch.is_pending().unwrap_or_default()
     }
 
     ///
@@ -280,6 +301,7 @@ impl Status {
         match ev {
             AsyncNotification::Diff => self.update_diff()?,
             AsyncNotification::Status => self.update_status()?,
+            AsyncNotification::Fetch => self.update_fetch()?,
             _ => (),
         }
 
@@ -378,26 +400,201 @@ impl Status {
         }
     }
 
-    fn fetch(&self) {
+    fn push_dry_run(&self) {
+        if let Some(branch) = self.git_branch_name.last() {
+            let branch = format!("refs/heads/{}", branch);
+
+            self.queue
+                .borrow_mut()
+                .push_back(InternalEvent::PushDryRun(branch));
+        }
+    }
+
+    fn fetch(&mut self) -> Result<()> {
+        if let Some(branch) = self.git_branch_name.last() {
+            self.start_fetch(FetchRequest {
+                remote: DEFAULT_REMOTE_NAME.to_string(),
+                refspec: branch,
+                basic_credential: None,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn fetch_upstream(&mut self) -> Result<()> {
         if let Some(branch) = self.git_branch_name.last() {
-            match sync::fetch_origin(CWD, branch.as_str()) {
+            match sync::get_branch_upstream(CWD, branch.as_str()) {
+                Ok(upstream) => {
+                    self.start_fetch(FetchRequest {
+                        remote: upstream.remote,
+                        refspec: upstream.merge,
+                        basic_credential: None,
+                    })?;
+                }
                 Err(e) => {
-                    self.queue.borrow_mut().push_back(
-                        InternalEvent::ShowErrorMsg(format!(
-                            "fetch error:\n{}",
-                            e
-                        )),
-                    );
+                    self.fetch_result(Err(RemoteError::from(e)))?;
                 }
-                Ok(bytes) => {
-                    self.queue.borrow_mut().push_back(
-                        InternalEvent::ShowErrorMsg(format!(
-                            "fetched:\n{} B",
-                            bytes
-                        )),
-                    );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// fetches with the remembered credentials or asks for them
+    /// first
+    fn start_fetch(&mut self, request: FetchRequest) -> Result<()> {
+        let state =
+            self.input_cred.fetch_credential(CWD, &request.remote)?;
+        self.fetch_request = Some(request);
+
+        match state {
+            CredState::Ready(cred) => self.fetch_with(cred),
+            CredState::Asking => Ok(()),
+        }
+    }
+
+    fn fetch_with(
+        &mut self,
+        cred: Option<BasicAuthCredential>,
+    ) -> Result<()> {
+        if let Some(request) = self.fetch_request.clone() {
+            self.git_fetch.request(FetchRequest {
+                basic_credential: cred,
+                ..request
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// progress notifications arrive while pending, the result
+    /// once the last running fetch is done
+    fn update_fetch(&mut self) -> Result<()> {
+        if !self.git_fetch.is_pending()? {
+            if let Some(res) = self.git_fetch.last_result()? {
+                self.fetch_result(res)?;
+            }
+            self.check_branch_state();
+        }
+
+        Ok(())
+    }
+
+    fn fetch_progress(&self) -> Option<String> {
+        if !self.git_fetch.is_pending().unwrap_or_default() {
+            return None;
+        }
+
+        if let Some(op) = self.git_fetch.waiting_for().ok().flatten()
+        {
+            return Some(strings::remote_op_waiting_msg(
+                &self.key_config,
+                op,
+            ));
+        }
+
+        let (state, current, total) =
+            match self.git_fetch.progress().ok().flatten() {
+                Some(ProgressNotification::Transfer {
+                    objects,
+                    total_objects,
+                }) => (
+                    strings::fetch_states_receiving(),
+                    objects,
+                    total_objects,
+                ),
+                Some(ProgressNotification::ResolvingDeltas {
+                    indexed,
+                    total,
+                }) => (
+                    strings::push_popup_states_resolving_deltas(),
+                    indexed,
+                    total,
+                ),
+                _ => {
+                    return Some(
+                        strings::fetch_states_preparing().to_string(),
+                    )
                 }
+            };
+
+        Some(format!(
+            "{} {}%",
+            state,
+            current * 100 / cmp::max(total, 1)
+        ))
+    }
+
+    fn fetch_result(
+        &mut self,
+        res: RemoteResult<FetchStats>,
+    ) -> Result<()> {
+        if matches!(res, Err(RemoteError::Auth))
+            && self.ask_again_for_fetch()?
+        {
+            self.queue.borrow_mut().push_back(
+                InternalEvent::ShowRemoteError {
+                    action: strings::fetch_action(),
+                    retry: self.key_config.enter,
+                    error: RemoteError::Auth,
+                },
+            );
+            return Ok(());
+        }
+
+        match res {
+            Err(e) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowRemoteError {
+                        action: strings::fetch_action(),
+                        retry: self.key_config.fetch,
+                        error: e,
+                    },
+                );
+            }
+            Ok(stats) => {
+                self.queue.borrow_mut().push_back(
+                    InternalEvent::ShowErrorMsg(format!(
+                        "fetched:\n{} objects ({} B)",
+                        stats.received_objects, stats.received_bytes
+                    )),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// fetches once the credentials asked for are typed in
+    fn cred_event(&mut self, ev: Event) -> Result<bool> {
+        if self.input_cred.event(ev)? {
+            return Ok(true);
+        }
+
+        if let Event::Key(e) = ev {
+            if e == self.key_config.enter
+                && self.input_cred.is_visible()
+                && self.input_cred.is_complete()
+            {
+                self.fetch_with(Some(
+                    self.input_cred.get_cred().clone(),
+                ))?;
+                self.input_cred.hide();
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// asks again for the credentials of the last fetch after
+    /// they were rejected, keeping the request
+    fn ask_again_for_fetch(&mut self) -> Result<bool> {
+        match &self.fetch_request {
+            Some(request) => {
+                self.input_cred.ask_again(CWD, &request.remote)
             }
+            None => Ok(false),
         }
     }
 
@@ -422,6 +619,10 @@ impl Component for Status {
         out: &mut Vec<CommandInfo>,
         force_all: bool,
     ) -> CommandBlocking {
+        if self.input_cred.is_visible() {
+            return self.input_cred.commands(out, force_all);
+        }
+
         if self.visible || force_all {
             command_pump(
                 out,
@@ -437,11 +638,47 @@ impl Component for Status {
                 true,
             ));
 
+            out.push(CommandInfo::new(
+                strings::commands::open_worktrees(&self.key_config),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::open_undo(&self.key_config),
+                true,
+                true,
+            ));
+
             out.push(CommandInfo::new(
                 strings::commands::status_push(&self.key_config),
                 self.can_push(),
                 true,
             ));
+
+            out.push(CommandInfo::new(
+                strings::commands::status_push_dry_run(
+                    &self.key_config,
+                ),
+                self.can_push(),
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::status_edit_push_url(
+                    &self.key_config,
+                ),
+                true,
+                true,
+            ));
+
+            out.push(CommandInfo::new(
+                strings::commands::status_fetch_upstream(
+                    &self.key_config,
+                ),
+                self.git_branch_name.last().is_some(),
+                true,
+            ));
         }
 
         {
@@ -502,6 +739,10 @@ impl Component for Status {
 
     fn event(&mut self, ev: crossterm::event::Event) -> Result<bool> {
         if self.visible {
+            if self.input_cred.is_visible() {
+                return self.cred_event(ev);
+            }
+
             if event_pump(ev, self.components_mut().as_mut_slice())? {
                 self.git_action_executed = true;
                 return Ok(true);
@@ -518,9 +759,7 @@ impl Component for Status {
                 {
                     if let Some((path, _)) = self.selected_path() {
                         self.queue.borrow_mut().push_back(
-                            InternalEvent::OpenExternalEditor(Some(
-                                path,
-                            )),
+                            InternalEvent::OpenExternalEditor(path),
                         );
                     }
                     Ok(true)
@@ -548,11 +787,34 @@ impl Component for Status {
                         .borrow_mut()
                         .push_back(InternalEvent::SelectBranch);
                     Ok(true)
+                } else if k == self.key_config.open_worktrees {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenWorktrees);
+                    Ok(true)
+                } else if k == self.key_config.open_undo {
+                    self.queue
+                        .borrow_mut()
+                        .push_back(InternalEvent::OpenUndo);
+                    Ok(true)
                 } else if k == self.key_config.push {
                     self.push();
                     Ok(true)
+                } else if k == self.key_config.push_dry_run {
+                    self.push_dry_run();
+                    Ok(true)
                 } else if k == self.key_config.fetch {
-                    self.fetch();
+                    self.fetch()?;
+                    Ok(true)
+                } else if k == self.key_config.fetch_upstream {
+                    self.fetch_upstream()?;
+                    Ok(true)
+                } else if k == self.key_config.edit_push_url {
+                    self.queue.borrow_mut().push_back(
+                        InternalEvent::EditPushUrl(
+                            DEFAULT_REMOTE_NAME.to_string(),
+                        ),
+                    );
                     Ok(true)
                 } else {
                     Ok(false)
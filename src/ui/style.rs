@@ -1,6 +1,6 @@
 use crate::get_app_config_path;
 use anyhow::Result;
-use asyncgit::{DiffLineType, StatusItemType};
+use asyncgit::{sync::Decoration, DiffLineType, StatusItemType};
 use ron::{
     de::from_bytes,
     ser::{to_string_pretty, PrettyConfig},
@@ -16,6 +16,16 @@ use tui::style::{Color, Modifier, Style};
 
 pub type SharedTheme = Rc<Theme>;
 
+/// colors `Theme::author` tells authors apart by
+const AUTHOR_COLORS: [Color; 6] = [
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::LightRed,
+];
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Theme {
     selected_tab: Color,
@@ -94,6 +104,34 @@ impl Theme {
         }
     }
 
+    /// marker of the commits in the log's range selection
+    pub fn log_range(&self, selected: bool) -> Style {
+        if selected {
+            self.text(true, true)
+        } else {
+            Style::default().fg(self.selection_bg)
+        }
+        .add_modifier(Modifier::BOLD)
+    }
+
+    /// badge of a commit that was reverted (or of a revert)
+    pub fn log_revert(
+        &self,
+        reverted: bool,
+        selected: bool,
+    ) -> Style {
+        self.apply_select(
+            Style::default()
+                .fg(if reverted {
+                    self.danger_fg
+                } else {
+                    self.commit_time
+                })
+                .add_modifier(Modifier::BOLD),
+            selected,
+        )
+    }
+
     pub fn tags(&self, selected: bool) -> Style {
         Style::default()
             .fg(self.selected_tab)
@@ -105,6 +143,25 @@ impl Theme {
             })
     }
 
+    /// colored like `git log --decorate`
+    pub fn decoration(
+        &self,
+        decoration: &Decoration,
+        selected: bool,
+    ) -> Style {
+        let fg = match decoration {
+            Decoration::Head(_) => self.commit_time,
+            Decoration::LocalBranch(_) => self.diff_line_add,
+            Decoration::RemoteBranch(_) => self.diff_line_delete,
+            Decoration::Tag(_) => return self.tags(selected),
+        };
+
+        self.apply_select(
+            Style::default().fg(fg).add_modifier(Modifier::BOLD),
+            selected,
+        )
+    }
+
     pub fn text(&self, enabled: bool, selected: bool) -> Style {
         match (enabled, selected) {
             (false, _) => Style::default().fg(self.disabled_fg),
@@ -188,6 +245,16 @@ impl Theme {
         Style::default().fg(self.danger_fg)
     }
 
+    /// part of a commit message line past its length limit
+    pub fn message_overlength(&self) -> Style {
+        Style::default().fg(self.selected_tab)
+    }
+
+    /// marks the length limit of commit message body lines
+    pub fn message_ruler(&self) -> Style {
+        Style::default().fg(self.disabled_fg)
+    }
+
     pub fn commandbar(&self, enabled: bool, line: usize) -> Style {
         if enabled {
             Style::default().fg(self.command_fg)
@@ -220,6 +287,21 @@ impl Theme {
         )
     }
 
+    /// the same color for the same author name every time
+    pub fn author(&self, name: &str, selected: bool) -> Style {
+        // FNV-1a, stable across runs unlike the std hasher
+        let hash = name.bytes().fold(0x811c_9dc5_u32, |hash, b| {
+            (hash ^ u32::from(b)).wrapping_mul(0x0100_0193)
+        });
+
+        self.apply_select(
+            Style::default()
+                .fg(AUTHOR_COLORS
+                    [hash as usize % AUTHOR_COLORS.len()]),
+            selected,
+        )
+    }
+
     fn save(&self) -> Result<()> {
         let theme_file = Self::get_theme_file()?;
         let mut file = File::create(theme_file)?;